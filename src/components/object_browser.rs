@@ -154,13 +154,14 @@ struct BundleListProps {
 fn BundleList(props: &BundleListProps) -> Html {
 	use crate::{
 		components::database::{use_query_all_typed, QueryAllArgs, QueryStatus},
-		page::characters::sheet::joined::editor::bundle_content,
+		page::characters::sheet::{joined::editor::bundle_content, CharacterHandle},
 		system::{
 			core::System,
 			dnd5e::{data::Bundle, DnD5e},
 		},
 	};
 
+	let state = use_context::<CharacterHandle>().unwrap();
 	let fetch_bundles = use_query_all_typed::<Bundle>(
 		true,
 		Some(QueryAllArgs::<Bundle> {
@@ -178,11 +179,12 @@ fn BundleList(props: &BundleListProps) -> Html {
 		QueryStatus::Empty | QueryStatus::Failed(_) => html!("No bundles available"),
 		QueryStatus::Success(bundles) => {
 			let mut htmls = Vec::new();
-			// TODO: Disable bundles whose requirements are not met
 			for bundle in bundles {
 				let collapse_id = format!("{}", bundle.id.ref_id());
+				let unmet_reasons = bundle.unmet_requirements(&state);
+				let unmet_requirements = (!unmet_reasons.is_empty()).then(|| unmet_reasons.join(" "));
 				htmls.push(html! {
-					<div class="section mb-1">
+					<div class={classes!("section", "mb-1", unmet_requirements.is_some().then_some("text-muted"))}>
 						<div class="header mb-1">
 							<button
 								role="button" class={"collapse_trigger arrow_left collapsed"}
@@ -195,8 +197,14 @@ fn BundleList(props: &BundleListProps) -> Html {
 								data_path={props.data_path.clone()}
 								id={bundle.id.unversioned()}
 								capacity={props.capacity}
+								disabled_reason={unmet_requirements.clone().map(AttrValue::from)}
 							/>
 						</div>
+						{unmet_requirements.map(|reason| html! {
+							<div class="mb-1" style="font-size: 0.8rem; color: var(--bs-warning);">
+								{"Requirements not met: "}{reason}
+							</div>
+						}).unwrap_or_default()}
 						<div class="collapse mb-2" id={collapse_id}>
 							<div class="card">
 								<div class="card-body px-2 py-1">
@@ -351,6 +359,9 @@ struct ObjectSelectorEntryButtonProps {
 	data_path: std::path::PathBuf,
 	id: crate::system::core::SourceId,
 	capacity: usize,
+	/// If provided, the object cannot be selected (though it can still be deselected) for this reason.
+	#[prop_or_default]
+	disabled_reason: Option<AttrValue>,
 }
 #[function_component]
 fn ObjectSelectorEntryButton(props: &ObjectSelectorEntryButtonProps) -> Html {
@@ -371,7 +382,11 @@ fn ObjectSelectorEntryButton(props: &ObjectSelectorEntryButtonProps) -> Html {
 	}
 
 	let is_selected = selected_idx.is_some();
-	let onclick = state.new_dispatch({
+	let label = match is_selected {
+		true => "Deselect Option",
+		false => "Select Option",
+	};
+	let onclick = state.new_dispatch(label, {
 		let data_path = props.data_path.clone();
 		move |evt: MouseEvent, persistent| {
 			evt.stop_propagation();
@@ -391,10 +406,11 @@ fn ObjectSelectorEntryButton(props: &ObjectSelectorEntryButtonProps) -> Html {
 	});
 
 	let mut classes = classes!("btn", "btn-xs", "select");
-	let disabled = !is_selected && !can_select_more;
+	let can_select = can_select_more && props.disabled_reason.is_none();
+	let disabled = !is_selected && !can_select;
 	classes.push(match is_selected {
 		true => "btn-outline-theme",
-		false => match can_select_more {
+		false => match can_select {
 			true => "btn-theme",
 			false => "btn-outline-secondary",
 		},
@@ -403,6 +419,7 @@ fn ObjectSelectorEntryButton(props: &ObjectSelectorEntryButtonProps) -> Html {
 	html! {
 		<button
 			type="button" class={classes}
+			title={props.disabled_reason.clone()}
 			{disabled} {onclick}
 		>
 			{match is_selected {
@@ -44,6 +44,10 @@ impl Database {
 		self.0.read_write::<Module>()
 	}
 
+	pub fn write_local_modules(&self) -> Result<Transaction, Error> {
+		self.0.read_write::<crate::storage::local::LocalModules>()
+	}
+
 	pub async fn clear(&self) -> Result<(), Error> {
 		use database::TransactionExt;
 		let transaction = self.write()?;
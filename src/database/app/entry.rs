@@ -22,6 +22,13 @@ pub struct Entry {
 	pub metadata: serde_json::Value,
 	pub kdl: String,
 	pub file_id: Option<String>,
+	/// If true, the kdl is a local draft which contains changes that have not yet been saved to storage.
+	#[serde(default)]
+	pub dirty: bool,
+	/// The kdl which was last synced with storage, kept while the entry is dirty
+	/// so local changes can be merged with any changes made to the file by someone else.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub base_kdl: Option<String>,
 }
 
 impl Record for Entry {
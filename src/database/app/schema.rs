@@ -4,6 +4,7 @@ use database::{MissingVersion, ObjectStoreExt, Record, Schema};
 /// Use with `Client::open`.
 pub enum SchemaVersion {
 	Version1 = 1,
+	Version2 = 2,
 }
 
 impl TryFrom<u32> for SchemaVersion {
@@ -12,6 +13,7 @@ impl TryFrom<u32> for SchemaVersion {
 	fn try_from(value: u32) -> Result<Self, Self::Error> {
 		match value {
 			1 => Ok(Self::Version1),
+			2 => Ok(Self::Version2),
 			_ => Err(MissingVersion(value)),
 		}
 	}
@@ -19,7 +21,7 @@ impl TryFrom<u32> for SchemaVersion {
 
 impl Schema for SchemaVersion {
 	fn latest() -> u32 {
-		Self::Version1 as u32
+		Self::Version2 as u32
 	}
 
 	fn apply(&self, database: &idb::Database, _transaction: Option<&idb::Transaction>) -> Result<(), idb::Error> {
@@ -51,6 +53,15 @@ impl Schema for SchemaVersion {
 					store.create_index_of::<SystemCategory>(None)?;
 				}
 			}
+			Self::Version2 => {
+				// Create local-only storage table
+				{
+					use crate::storage::local::LocalModules;
+					let mut params = idb::ObjectStoreParams::new();
+					params.key_path(Some(idb::KeyPath::new_single("id")));
+					database.create_object_store(LocalModules::store_id(), params)?;
+				}
+			}
 		}
 		Ok(())
 	}
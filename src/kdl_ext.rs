@@ -2,6 +2,8 @@ pub use kdlize::{error::*, ext::*, AsKdl, FromKdl, NodeBuilder};
 
 mod context;
 pub use context::*;
+mod merge;
+pub use merge::*;
 
 pub type NodeReader<'doc> = kdlize::NodeReader<'doc, NodeContext>;
 
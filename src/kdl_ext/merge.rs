@@ -0,0 +1,334 @@
+use kdl::{KdlDocument, KdlNode};
+use std::collections::HashSet;
+
+/// Sibling groups which are only ever appended to, identified by the name of their parent node and their own name.
+/// Both sides adding to one of these groups (e.g. journal entries recorded on two devices) is not a conflict;
+/// the additions from each side are combined and ordered by the node's first entry, which is its timestamp.
+static APPEND_ONLY: &[(&str, &str)] = &[("journal", "entry")];
+
+/// Which version of a conflicting node to keep when resolving a merge.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum MergeSide {
+	#[default]
+	Local,
+	Remote,
+}
+
+/// A set of sibling nodes (all with the same name) which were changed differently by the local and remote versions.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MergeConflict {
+	/// The names of the nodes which contain the conflicting nodes, starting at the root.
+	pub path: Vec<String>,
+	pub name: String,
+	pub base: Vec<KdlNode>,
+	pub local: Vec<KdlNode>,
+	pub remote: Vec<KdlNode>,
+}
+
+/// A three-way merge of two versions of a kdl node (local and remote) which were both changed from a common base.
+///
+/// Child nodes are grouped by name and compared against the base. Groups which were only changed
+/// on one side (or changed identically on both sides) are merged automatically. If both sides changed
+/// the same group, and each side has exactly one node in that group, the merge continues into that node's children.
+/// Append-only groups (such as a character's journal entries) combine the nodes added by both sides.
+/// Otherwise the group is a conflict, which must be resolved by picking one of the sides.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ThreeWayMerge {
+	base: KdlNode,
+	local: KdlNode,
+	remote: KdlNode,
+	conflicts: Vec<MergeConflict>,
+}
+
+impl ThreeWayMerge {
+	pub fn new(base: KdlNode, local: KdlNode, remote: KdlNode) -> Self {
+		let mut conflicts = Vec::new();
+		merge_group(
+			&[],
+			std::slice::from_ref(&base),
+			std::slice::from_ref(&local),
+			std::slice::from_ref(&remote),
+			&mut |conflict| {
+				conflicts.push(conflict);
+				MergeSide::Local
+			},
+		);
+		Self {
+			base,
+			local,
+			remote,
+			conflicts,
+		}
+	}
+
+	/// Parses the first node of each document and merges them.
+	pub fn from_documents(base: &str, local: &str, remote: &str) -> Result<Self, kdl::KdlError> {
+		let first_node = |content: &str| -> Result<KdlNode, kdl::KdlError> {
+			let document = content.parse::<KdlDocument>()?;
+			Ok(document.nodes().get(0).cloned().unwrap_or_else(|| KdlNode::new("")))
+		};
+		Ok(Self::new(first_node(base)?, first_node(local)?, first_node(remote)?))
+	}
+
+	pub fn conflicts(&self) -> &Vec<MergeConflict> {
+		&self.conflicts
+	}
+
+	/// Returns the merged node, where each conflict is resolved using the side at the same index in `choices`.
+	/// Conflicts without a choice keep the local version.
+	pub fn resolve(&self, choices: &[MergeSide]) -> KdlNode {
+		let mut choices = choices.iter();
+		let merged = merge_group(
+			&[],
+			std::slice::from_ref(&self.base),
+			std::slice::from_ref(&self.local),
+			std::slice::from_ref(&self.remote),
+			&mut |_conflict| choices.next().copied().unwrap_or_default(),
+		);
+		merged.into_iter().next().unwrap_or_else(|| self.local.clone())
+	}
+
+	/// Returns the merged node as the content of a document.
+	pub fn resolve_document(&self, choices: &[MergeSide]) -> String {
+		let mut document = KdlDocument::new();
+		document.nodes_mut().push(self.resolve(choices));
+		document.to_string()
+	}
+}
+
+fn as_text(nodes: &[KdlNode]) -> Vec<String> {
+	nodes.iter().map(|node| node.to_string().trim().to_owned()).collect()
+}
+
+fn merge_group(
+	path: &[String],
+	base: &[KdlNode],
+	local: &[KdlNode],
+	remote: &[KdlNode],
+	resolve: &mut dyn FnMut(MergeConflict) -> MergeSide,
+) -> Vec<KdlNode> {
+	let (base_text, local_text, remote_text) = (as_text(base), as_text(local), as_text(remote));
+	if local_text == remote_text || remote_text == base_text {
+		return local.to_vec();
+	}
+	if local_text == base_text {
+		return remote.to_vec();
+	}
+	let name = local.iter().chain(remote).next();
+	let name = name.map(|node| node.name().value().to_owned()).unwrap_or_default();
+	let parent = path.last().map(String::as_str).unwrap_or_default();
+	if APPEND_ONLY.contains(&(parent, name.as_str())) {
+		return merge_append_only(base, local, remote);
+	}
+	if let ([base], [local], [remote]) = (base, local, remote) {
+		if let Some(merged) = merge_node(path, base, local, remote, resolve) {
+			return vec![merged];
+		}
+	}
+	// An append-only group's parent which was added by both sides is merged as if the base had an empty one.
+	if let ([], [local], [remote]) = (base, local, remote) {
+		if APPEND_ONLY.iter().any(|(parent, _)| *parent == name) {
+			let mut base = KdlNode::new(name.as_str());
+			base.ensure_children();
+			if let Some(merged) = merge_node(path, &base, local, remote, resolve) {
+				return vec![merged];
+			}
+		}
+	}
+	let conflict = MergeConflict {
+		path: path.to_vec(),
+		name,
+		base: base.to_vec(),
+		local: local.to_vec(),
+		remote: remote.to_vec(),
+	};
+	match resolve(conflict) {
+		MergeSide::Local => local.to_vec(),
+		MergeSide::Remote => remote.to_vec(),
+	}
+}
+
+/// Combines the nodes added by each side to an append-only group, ordered by their timestamps.
+/// Nodes in the base which either side removed (such as the oldest journal entries being forgotten) stay removed.
+fn merge_append_only(base: &[KdlNode], local: &[KdlNode], remote: &[KdlNode]) -> Vec<KdlNode> {
+	let (base_text, local_text, remote_text) = (as_text(base), as_text(local), as_text(remote));
+	let (base_set, local_set, remote_set) = (
+		base_text.iter().collect::<HashSet<_>>(),
+		local_text.iter().collect::<HashSet<_>>(),
+		remote_text.iter().collect::<HashSet<_>>(),
+	);
+	let mut merged = Vec::new();
+	for (node, text) in local.iter().zip(&local_text) {
+		if remote_set.contains(text) || !base_set.contains(text) {
+			merged.push(node.clone());
+		}
+	}
+	for (node, text) in remote.iter().zip(&remote_text) {
+		if !local_set.contains(text) && !base_set.contains(text) {
+			merged.push(node.clone());
+		}
+	}
+	merged.sort_by_key(|node| node.entries().first().and_then(|entry| entry.value().as_i64()));
+	merged
+}
+
+/// Merges the children of a node which was changed by both sides.
+/// Returns None if the node has no children, or if both sides changed the node's own entries.
+fn merge_node(
+	path: &[String],
+	base: &KdlNode,
+	local: &KdlNode,
+	remote: &KdlNode,
+	resolve: &mut dyn FnMut(MergeConflict) -> MergeSide,
+) -> Option<KdlNode> {
+	let (Some(base_children), Some(local_children), Some(remote_children)) =
+		(base.children(), local.children(), remote.children())
+	else {
+		return None;
+	};
+	let entries = |node: &KdlNode| node.entries().iter().map(ToString::to_string).collect::<Vec<_>>();
+	let (base_entries, local_entries, remote_entries) = (entries(base), entries(local), entries(remote));
+	let mut merged = if local_entries == remote_entries || remote_entries == base_entries {
+		local.clone()
+	} else if local_entries == base_entries {
+		remote.clone()
+	} else {
+		return None;
+	};
+
+	let mut path = path.to_vec();
+	path.push(local.name().value().to_owned());
+	let children = merge_documents(&path, base_children, local_children, remote_children, resolve);
+	*merged.ensure_children().nodes_mut() = children;
+	Some(merged)
+}
+
+fn merge_documents(
+	path: &[String],
+	base: &KdlDocument,
+	local: &KdlDocument,
+	remote: &KdlDocument,
+	resolve: &mut dyn FnMut(MergeConflict) -> MergeSide,
+) -> Vec<KdlNode> {
+	// Groups are ordered by their first appearance in the local document, followed by any only in the remote.
+	let mut names = Vec::<&str>::new();
+	for node in local.nodes().iter().chain(remote.nodes()).chain(base.nodes()) {
+		let name = node.name().value();
+		if !names.contains(&name) {
+			names.push(name);
+		}
+	}
+	let group = |document: &KdlDocument, name: &str| {
+		let nodes = document.nodes().iter();
+		nodes
+			.filter(|node| node.name().value() == name)
+			.cloned()
+			.collect::<Vec<_>>()
+	};
+	let mut merged = Vec::new();
+	for name in names {
+		let (base, local, remote) = (group(base, name), group(local, name), group(remote, name));
+		merged.extend(merge_group(path, &base, &local, &remote, resolve));
+	}
+	merged
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	static BASE: &str = "
+character {
+	hit_points {
+		current 10
+		temp 0
+	}
+	item \"Rope\"
+	item \"Torch\"
+	inspiration false
+}";
+
+	fn merged_text(merge: &ThreeWayMerge, choices: &[MergeSide]) -> String {
+		merge.resolve(choices).to_string().trim().to_owned()
+	}
+
+	#[test]
+	fn non_overlapping() -> anyhow::Result<()> {
+		let local = BASE.replace("current 10", "current 4");
+		let remote = BASE.replace("\titem \"Torch\"\n", "\titem \"Torch\"\n\titem \"Lantern\"\n");
+		let merge = ThreeWayMerge::from_documents(BASE, &local, &remote)?;
+		assert_eq!(merge.conflicts(), &vec![]);
+		let expected = local.replace("\titem \"Torch\"\n", "\titem \"Torch\"\n\titem \"Lantern\"\n");
+		assert_eq!(merged_text(&merge, &[]), expected.trim());
+		Ok(())
+	}
+
+	#[test]
+	fn nested_children() -> anyhow::Result<()> {
+		let local = BASE.replace("current 10", "current 4");
+		let remote = BASE.replace("temp 0", "temp 5");
+		let merge = ThreeWayMerge::from_documents(BASE, &local, &remote)?;
+		assert_eq!(merge.conflicts(), &vec![]);
+		let expected = BASE.replace("current 10", "current 4").replace("temp 0", "temp 5");
+		assert_eq!(merged_text(&merge, &[]), expected.trim());
+		Ok(())
+	}
+
+	#[test]
+	fn journal_entries() -> anyhow::Result<()> {
+		let base = BASE.replace(
+			"\tinspiration false\n",
+			"\tinspiration false\n\tjournal {\n\t\tentry 100 \"Long Rest\"\n\t}\n",
+		);
+		let local = base.replace("current 10", "current 4").replace(
+			"\t\tentry 100 \"Long Rest\"\n",
+			"\t\tentry 100 \"Long Rest\"\n\t\tentry 300 \"Take Damage\"\n",
+		);
+		let remote = base.replace("inspiration false", "inspiration true").replace(
+			"\t\tentry 100 \"Long Rest\"\n",
+			"\t\tentry 100 \"Long Rest\"\n\t\tentry 200 \"Toggle Inspiration\"\n",
+		);
+		let merge = ThreeWayMerge::from_documents(&base, &local, &remote)?;
+		assert_eq!(merge.conflicts(), &vec![]);
+		let expected = base
+			.replace("current 10", "current 4")
+			.replace("inspiration false", "inspiration true")
+			.replace(
+				"\t\tentry 100 \"Long Rest\"\n",
+				"\t\tentry 100 \"Long Rest\"\n\t\tentry 200 \"Toggle Inspiration\"\n\t\tentry 300 \"Take Damage\"\n",
+			);
+		assert_eq!(merged_text(&merge, &[]), expected.trim());
+
+		// Both sides starting a journal is also merged, rather than a conflict.
+		let local = BASE.replace(
+			"\tinspiration false\n",
+			"\tinspiration false\n\tjournal {\n\t\tentry 300 \"Take Damage\"\n\t}\n",
+		);
+		let remote = BASE.replace(
+			"\tinspiration false\n",
+			"\tinspiration false\n\tjournal {\n\t\tentry 200 \"Toggle Inspiration\"\n\t}\n",
+		);
+		let merge = ThreeWayMerge::from_documents(BASE, &local, &remote)?;
+		assert_eq!(merge.conflicts(), &vec![]);
+		let expected = BASE.replace(
+			"\tinspiration false\n",
+			"\tinspiration false\n\tjournal {\n\t\tentry 200 \"Toggle Inspiration\"\n\t\tentry 300 \"Take Damage\"\n\t}\n",
+		);
+		assert_eq!(merged_text(&merge, &[]), expected.trim());
+		Ok(())
+	}
+
+	#[test]
+	fn conflict() -> anyhow::Result<()> {
+		let local = BASE.replace("inspiration false", "inspiration true");
+		let remote = BASE.replace("inspiration false", "inspiration null");
+		let merge = ThreeWayMerge::from_documents(BASE, &local, &remote)?;
+		assert_eq!(merge.conflicts().len(), 1);
+		let conflict = &merge.conflicts()[0];
+		assert_eq!(conflict.path, vec!["character".to_owned()]);
+		assert_eq!(conflict.name, "inspiration");
+		assert_eq!(merged_text(&merge, &[MergeSide::Local]), local.trim());
+		assert_eq!(merged_text(&merge, &[MergeSide::Remote]), remote.trim());
+		Ok(())
+	}
+}
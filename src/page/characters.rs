@@ -2,6 +2,7 @@ use super::app;
 use crate::{
 	components::{modal, Spinner},
 	database::Database,
+	storage::{ModuleQuery, PutFileArgs, RemoveFileArgs},
 	system::{
 		core::{ModuleId, SourceId, System},
 		dnd5e::{
@@ -138,30 +139,22 @@ pub fn CharacterLanding() -> Html {
 	let navigator = use_navigator().unwrap();
 	let task_dispatch = use_context::<crate::task::Dispatch>().unwrap();
 	let modal_dispatcher = use_context::<modal::Context>().unwrap();
+	let database = use_context::<Database>().unwrap();
 
 	let on_create = Callback::from({
 		let auth_status = auth_status.clone();
 		let navigator = navigator.clone();
 		let task_dispatch = task_dispatch.clone();
+		let database = database.clone();
 		move |_| {
-			let Some(client) = crate::storage::get(&*auth_status) else {
-				log::debug!("no storage client");
-				return;
-			};
+			let client = crate::storage::get(&*auth_status, &database);
 			let navigator = navigator.clone();
 			task_dispatch.spawn("Prepare Character", None, async move {
-				let search_params = github::SearchRepositoriesParams {
-					query: github::Query::default()
-						.keyed("user", "@me")
-						.keyed("repo", crate::storage::USER_HOMEBREW_REPO_NAME)
-						.keyed("in", "name"),
-					page_size: 1,
-				};
-				let (_, mut repositories) = client.search_repositories(search_params).await;
-				let Some(homebrew_repo) = repositories.pop() else {
+				let search = client.search_modules(ModuleQuery::Homebrew).await?;
+				let Some(homebrew) = search.modules.into_iter().next() else {
 					return Ok(());
 				};
-				let module_id = (&homebrew_repo).into();
+				let module_id = homebrew.id;
 
 				let system = DnD5e::id();
 				let source_id_unversioned = SourceId {
@@ -362,28 +355,18 @@ fn ModalCreate() -> Html {
 			if action_in_progress.value() {
 				return;
 			}
-			let Some(client) = crate::storage::get(&*auth_status) else {
-				log::debug!("no storage client");
-				return;
-			};
+			let client = crate::storage::get(&*auth_status, &database);
 			let id_path = Path::new("character").join(format!("{}.kdl", filename.as_str()));
 			let database = database.clone();
 			let close_modal = close_modal.clone();
 			let signal = task_dispatch.spawn("Create Character File", None, async move {
-				let search_params = github::SearchRepositoriesParams {
-					query: github::Query::default()
-						.keyed("user", "@me")
-						.keyed("repo", crate::storage::USER_HOMEBREW_REPO_NAME)
-						.keyed("in", "name"),
-					page_size: 1,
-				};
-				let (_, mut repositories) = client.search_repositories(search_params).await;
-				let Some(homebrew_repo) = repositories.pop() else {
+				let search = client.search_modules(ModuleQuery::Homebrew).await?;
+				let Some(homebrew) = search.modules.into_iter().next() else {
 					return Ok(());
 				};
-				let module_id = ModuleId::from(&homebrew_repo);
+				let module_id = homebrew.id.clone();
 
-				// NOTE: Cannot continue if our local version is not the latest version in storage (github).
+				// NOTE: Cannot continue if our local version is not the latest version in storage.
 				// We need to ensure that all files from the local ddb module are on the correct version,
 				// so we aren't accidentally ahead for some files and not for others.
 				// e.g. creating a new file without having latest means our module either
@@ -393,7 +376,7 @@ fn ModalCreate() -> Html {
 					Ok(Some(local_module)) => local_module.version,
 					_ => return Ok(()),
 				};
-				if local_module_version != homebrew_repo.version {
+				if local_module_version != homebrew.version {
 					return Ok(());
 				}
 
@@ -434,16 +417,14 @@ fn ModalCreate() -> Html {
 					}
 				};
 
-				let args = github::repos::contents::update::Args {
-					repo_org: &homebrew_repo.owner,
-					repo_name: &homebrew_repo.name,
-					path_in_repo: &path_in_repo,
-					commit_message: &message,
+				let args = PutFileArgs {
+					module: &homebrew.id,
+					path: &path_in_repo,
+					message: &message,
 					content: &content,
 					file_id: None,
-					branch: None,
 				};
-				let response = client.create_or_update_file(args).await?;
+				let response = client.put_file(args).await?;
 				let updated_version = response.version;
 
 				let record = crate::database::Entry {
@@ -455,6 +436,8 @@ fn ModalCreate() -> Html {
 					metadata,
 					kdl: content.clone(),
 					file_id: Some(response.file_id),
+					dirty: false,
+					base_kdl: None,
 				};
 				if let Err(err) = database
 					.mutate(move |transaction| {
@@ -536,22 +519,18 @@ fn ModalDelete(ModalDeleteProps { id, file_id, on_click }: &ModalDeleteProps) ->
 				return;
 			}
 
-			let Some(ModuleId::Github {
-				user_org: repo_org,
-				repository: repo_name,
-			}) = id.module.clone()
-			else {
+			let Some(module_id) = id.module.clone() else {
 				return;
 			};
 			let Some(system) = &id.system else {
 				return;
 			};
 			let path_in_repo = Path::new(system.as_str()).join(&id.path);
-			let module_id_str = id.module.as_ref().unwrap().to_string();
+			let module_id_str = module_id.to_string();
 
 			let message = "Delete character";
 			let file_id = file_id.clone();
-			let Some(client) = crate::storage::get(&*auth_status) else {
+			let Some(client) = crate::storage::get_for(&module_id, &*auth_status, &database) else {
 				log::debug!("no storage client");
 				return;
 			};
@@ -560,15 +539,13 @@ fn ModalDelete(ModalDeleteProps { id, file_id, on_click }: &ModalDeleteProps) ->
 			let close_modal = close_modal.clone();
 			let on_success = on_success.clone();
 			let signal = task_dispatch.spawn("Delete Character File", None, async move {
-				let args = github::repos::contents::delete::Args {
-					repo_org: repo_org.as_str(),
-					repo_name: repo_name.as_str(),
-					path_in_repo: path_in_repo.as_path(),
-					commit_message: &message,
+				let args = RemoveFileArgs {
+					module: &module_id,
+					path: path_in_repo.as_path(),
+					message: &message,
 					file_id: &file_id,
-					branch: None,
 				};
-				let updated_version = client.delete_file(args).await?;
+				let updated_version = client.remove_file(args).await?;
 
 				if let Err(err) = database
 					.mutate(move |transaction| {
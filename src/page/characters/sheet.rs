@@ -7,6 +7,10 @@ use yew::prelude::*;
 
 mod handle;
 pub use handle::*;
+mod history;
+pub use history::*;
+mod save;
+pub use save::*;
 pub mod joined;
 pub mod paged;
 
@@ -43,6 +47,7 @@ fn use_update_character_modules(character: &CharacterHandle) {
 pub fn Sheet(props: &GeneralProp<SourceId>) -> Html {
 	let character = use_character(props.value.clone());
 	use_update_character_modules(&character);
+	use_history_shortcuts(&character);
 
 	let autosync_channel = use_context::<autosync::Channel>().unwrap();
 	crate::components::hook::use_document_visibility({
@@ -1,13 +1,17 @@
+use super::{History, SaveConflict, SaveOutcome};
 use crate::{
 	database::{Database, FetchError},
 	system::{
 		self,
 		core::SourceId,
-		dnd5e::data::character::{Character, DefaultsBlock, ObjectCacheProvider, Persistent},
+		dnd5e::data::character::{
+			Character, CombatState, DefaultsBlock, JournalEntry, ObjectCacheProvider, Persistent,
+		},
 	},
 	task,
 };
 use std::{
+	cell::RefCell,
 	rc::Rc,
 	sync::{atomic::AtomicBool, Mutex},
 };
@@ -20,6 +24,9 @@ pub fn use_character(id: SourceId) -> CharacterHandle {
 	let task_dispatch = use_context::<task::Dispatch>().unwrap();
 
 	let state = use_state(|| CharacterState::default());
+	let autosave = use_mut_ref(|| AutosaveState::default());
+	let save_conflict = use_state_eq(|| None::<SaveConflict>);
+	let history = use_mut_ref(|| History::default());
 	let handle = CharacterHandle {
 		database,
 		system_depot,
@@ -27,6 +34,9 @@ pub fn use_character(id: SourceId) -> CharacterHandle {
 		state,
 		is_recompiling: Rc::new(AtomicBool::new(false)),
 		pending_mutations: Rc::new(Mutex::new(Vec::new())),
+		autosave,
+		save_conflict,
+		history,
 	};
 
 	// Character Initialization
@@ -57,11 +67,14 @@ pub struct CharacterHandle {
 	task_dispatch: task::Dispatch,
 	state: UseStateHandle<CharacterState>,
 	is_recompiling: Rc<AtomicBool>,
-	pending_mutations: Rc<Mutex<Vec<FnMutator>>>,
+	pending_mutations: Rc<Mutex<Vec<PendingMutation>>>,
+	autosave: Rc<RefCell<AutosaveState>>,
+	save_conflict: UseStateHandle<Option<SaveConflict>>,
+	history: Rc<RefCell<History>>,
 }
 impl PartialEq for CharacterHandle {
 	fn eq(&self, other: &Self) -> bool {
-		self.state == other.state
+		self.state == other.state && self.save_conflict == other.save_conflict
 	}
 }
 impl std::ops::Deref for CharacterHandle {
@@ -92,6 +105,47 @@ impl CharacterHandle {
 		self.state.set(CharacterState::Unloaded);
 	}
 
+	/// Returns the conflicting changes found the last time the character was saved, if they haven't been resolved yet.
+	pub fn save_conflict(&self) -> Option<&SaveConflict> {
+		self.save_conflict.as_ref()
+	}
+
+	pub fn apply_save_outcome(&self, outcome: SaveOutcome) {
+		match outcome {
+			SaveOutcome::Saved => {
+				self.save_conflict.set(None);
+			}
+			SaveOutcome::Merged => {
+				self.save_conflict.set(None);
+				self.load_merged();
+			}
+			SaveOutcome::Conflict(conflict) => {
+				self.save_conflict.set(Some(conflict));
+			}
+		}
+	}
+
+	/// Replaces the character's data with the data saved in the database, which includes the changes merged
+	/// from storage. Unlike reloading the character, this keeps the undo history and the combat situation.
+	fn load_merged(&self) {
+		let Some(id) = self.state.as_loaded().map(|character| character.id().unversioned()) else {
+			return;
+		};
+		let handle = self.clone();
+		wasm_bindgen_futures::spawn_local(async move {
+			let entry = handle
+				.database
+				.get_typed_entry::<Persistent>(id.clone(), handle.system_depot.clone(), None);
+			match entry.await {
+				Ok(Some(persistent)) => handle.push_pending(PendingMutation::Replace(persistent)),
+				Ok(None) => {
+					log::error!(target: "character", "Merged character {:?} is not in the database", id.to_string())
+				}
+				Err(err) => log::error!(target: "character", "Failed to load merged character data: {err:?}"),
+			}
+		});
+	}
+
 	fn load_with(&self, id: SourceId) {
 		wasm_bindgen_futures::spawn_local({
 			let handle = self.clone();
@@ -136,6 +190,8 @@ impl CharacterHandle {
 					log::warn!(target: "character", "Encountered error updating cached character objects: {err:?}");
 				}
 				log::info!(target: "character", "Finished loading {:?}", id_str);
+				// Changes made before the character was (re)loaded can't be restored on top of the loaded data.
+				handle.history.borrow_mut().clear();
 				handle.state.set(CharacterState::Loaded(character));
 				handle.set_recompiling(false);
 				handle.process_pending_mutations();
@@ -156,6 +212,14 @@ pub enum MutatorImpact {
 	Recompile,
 }
 
+/// Tracks the pending autosave for a character, which is shared across renders so that
+/// each change restarts the same timer (batching many changes into a single save).
+#[derive(Default)]
+struct AutosaveState {
+	timer: Option<gloo_timers::callback::Timeout>,
+	in_progress: bool,
+}
+
 #[derive(PartialEq, Default)]
 enum CharacterState {
 	#[default]
@@ -163,6 +227,13 @@ enum CharacterState {
 	Loaded(Character),
 }
 impl CharacterState {
+	fn as_loaded(&self) -> Option<&Character> {
+		match self {
+			Self::Loaded(character) => Some(character),
+			Self::Unloaded => None,
+		}
+	}
+
 	fn value(&self) -> &Character {
 		match self {
 			Self::Loaded(character) => character,
@@ -172,6 +243,50 @@ impl CharacterState {
 }
 
 type FnMutator = Box<dyn FnOnce(&mut Persistent) -> MutatorImpact + 'static>;
+type FnCombatUpdate = Box<dyn FnOnce(&mut CombatState) + 'static>;
+
+/// The current time in seconds since the unix epoch, for timestamping journal entries.
+fn journal_timestamp() -> i64 {
+	(js_sys::Date::now() / 1000.0) as i64
+}
+
+/// The mutations applied since the last change which was recorded in the character's history.
+/// These are recorded as a single change, so that a batch only needs to be compared to the prior state once.
+struct MutationBatch {
+	before: Persistent,
+	labels: Vec<AttrValue>,
+	requires_recompile: bool,
+}
+impl MutationBatch {
+	fn new(before: Persistent) -> Self {
+		Self {
+			before,
+			labels: Vec::new(),
+			requires_recompile: false,
+		}
+	}
+
+	fn push(&mut self, label: AttrValue, impact: &MutatorImpact) {
+		if !self.labels.contains(&label) {
+			self.labels.push(label);
+		}
+		self.requires_recompile |= matches!(impact, MutatorImpact::Recompile);
+	}
+
+	/// The label of the batch, combined from the labels of each mutation.
+	fn label(&self) -> AttrValue {
+		let labels = self.labels.iter().map(AttrValue::as_str).collect::<Vec<_>>();
+		AttrValue::from(labels.join(", "))
+	}
+}
+enum PendingMutation {
+	Apply(AttrValue, FnMutator),
+	Undo,
+	Redo,
+	Combat(FnCombatUpdate),
+	/// Replaces the character's data with data which has already been saved, such as the result of merging a save.
+	Replace(Persistent),
+}
 impl CharacterHandle {
 	fn process_pending_mutations(&self) {
 		let pending = {
@@ -190,15 +305,64 @@ impl CharacterHandle {
 			CharacterState::Loaded(character) => character.clone(),
 		};
 
+		let mut batch = None::<MutationBatch>;
+		let mut changed = false;
+		let mut combat_changed = false;
+		let mut replaced = false;
 		let mut requires_recompile = false;
-		for mutator in pending {
-			match mutator(character.persistent_mut()) {
+		for pending in pending {
+			let impact = match pending {
+				PendingMutation::Apply(label, mutator) => {
+					let batch = batch.get_or_insert_with(|| MutationBatch::new(character.persistent().clone()));
+					let impact = mutator(character.persistent_mut());
+					batch.push(label, &impact);
+					impact
+				}
+				PendingMutation::Undo => {
+					// Changes queued before the undo must be in the history, so they are what gets undone.
+					changed |= self.record_batch(batch.take(), &mut character);
+					let impact = self.restore_from_history(&mut character, false);
+					changed |= impact.is_some();
+					impact.unwrap_or(MutatorImpact::None)
+				}
+				PendingMutation::Redo => {
+					changed |= self.record_batch(batch.take(), &mut character);
+					let impact = self.restore_from_history(&mut character, true);
+					changed |= impact.is_some();
+					impact.unwrap_or(MutatorImpact::None)
+				}
+				PendingMutation::Combat(update) => {
+					// The combat situation is transient, so it isn't part of the history or journal, and isn't saved.
+					let before = character.combat().clone();
+					update(character.combat_mut());
+					if *character.combat() == before {
+						continue;
+					}
+					combat_changed = true;
+					MutatorImpact::Recompile
+				}
+				PendingMutation::Replace(persistent) => {
+					// Changes queued before the replacement are kept in the history, so they can still be undone.
+					changed |= self.record_batch(batch.take(), &mut character);
+					*character.persistent_mut() = persistent;
+					replaced = true;
+					MutatorImpact::Recompile
+				}
+			};
+			match impact {
 				MutatorImpact::None => {}
 				MutatorImpact::Recompile => {
 					requires_recompile = true;
 				}
 			}
 		}
+		changed |= self.record_batch(batch.take(), &mut character);
+		if !changed && !combat_changed && !replaced {
+			return;
+		}
+		if changed {
+			self.write_draft(character.persistent().clone());
+		}
 		if !requires_recompile {
 			self.state.set(CharacterState::Loaded(character));
 			return;
@@ -227,29 +391,165 @@ impl CharacterHandle {
 		});
 	}
 
-	pub fn dispatch<F>(&self, mutator: F)
-	where
-		F: FnOnce(&mut Persistent) -> MutatorImpact + 'static,
-	{
+	/// Records the changes made by a batch of mutations in the character's history and journal.
+	/// The character is only compared against its state before the batch once, no matter how many mutations
+	/// were applied. Returns false if the batch didn't change the character.
+	fn record_batch(&self, batch: Option<MutationBatch>, character: &mut Character) -> bool {
+		let Some(batch) = batch else {
+			return false;
+		};
+		if *character.persistent() == batch.before {
+			return false;
+		}
+		let label = batch.label();
+		let details = character.persistent().describe_changes_since(&batch.before);
+		character.persistent_mut().journal.push(JournalEntry {
+			timestamp: journal_timestamp(),
+			description: label.to_string(),
+			details,
+		});
+		let impact = match batch.requires_recompile {
+			true => MutatorImpact::Recompile,
+			false => MutatorImpact::None,
+		};
+		self.history.borrow_mut().push(label, batch.before, &impact);
+		true
+	}
+
+	/// Undoes (or redoes) the most recent change in the history, and records doing so in the character's journal.
+	/// Returns None if there was no change to undo (or redo).
+	fn restore_from_history(&self, character: &mut Character, redo: bool) -> Option<MutatorImpact> {
+		let mut history = self.history.borrow_mut();
+		let before = character.persistent().clone();
+		let (action, label) = match redo {
+			false => ("Undo", history.undo_label().cloned()?),
+			true => ("Redo", history.redo_label().cloned()?),
+		};
+		let impact = match redo {
+			false => history.undo(character.persistent_mut())?,
+			true => history.redo(character.persistent_mut())?,
+		};
+		let details = character.persistent().describe_changes_since(&before);
+		character.persistent_mut().journal.push(JournalEntry {
+			timestamp: journal_timestamp(),
+			description: format!("{action} {label}"),
+			details,
+		});
+		Some(impact)
+	}
+
+	/// Writes the character as a local draft in the database, so changes aren't lost if the page is closed,
+	/// and then schedules an autosave to push the draft to storage.
+	fn write_draft(&self, persistent: Persistent) {
+		if !persistent.id.has_path() {
+			return;
+		}
+		let handle = self.clone();
+		wasm_bindgen_futures::spawn_local(async move {
+			match super::write_draft(&handle.database, persistent).await {
+				Ok(true) => handle.schedule_autosave(),
+				Ok(false) => {}
+				Err(err) => log::error!(target: "character", "Failed to write character draft: {err:?}"),
+			}
+		});
+	}
+
+	fn schedule_autosave(&self) {
+		let Some(delay) = super::autosave_delay() else {
+			return;
+		};
+		let handle = self.clone();
+		let timer = gloo_timers::callback::Timeout::new(delay * 1000, move || handle.autosave());
+		// Replacing the timer cancels any autosave which was already scheduled.
+		self.autosave.borrow_mut().timer = Some(timer);
+	}
+
+	fn autosave(&self) {
+		if self.autosave.borrow().in_progress {
+			// Changes made while the previous save is in progress will be saved once it finishes.
+			self.schedule_autosave();
+			return;
+		}
+		self.autosave.borrow_mut().in_progress = true;
+		let Some(id) = self.state.as_loaded().map(|character| character.id().clone()) else {
+			self.autosave.borrow_mut().in_progress = false;
+			return;
+		};
+		let database = self.database.clone();
+		let system_depot = self.system_depot.clone();
+		let handle = self.clone();
+		self.task_dispatch.spawn("Autosave Character", None, async move {
+			let result = super::autosave(database, system_depot, id).await;
+			handle.autosave.borrow_mut().in_progress = false;
+			handle.apply_save_outcome(result?);
+			Ok(()) as anyhow::Result<()>
+		});
+	}
+
+	fn push_pending(&self, mutation: PendingMutation) {
 		{
 			let mut pending_mutations = self.pending_mutations.lock().unwrap();
-			pending_mutations.push(Box::new(mutator));
+			pending_mutations.push(mutation);
 		}
 		if !self.is_recompiling() {
 			self.process_pending_mutations();
 		}
 	}
 
-	pub fn new_dispatch<I, F>(&self, mutator: F) -> Callback<I>
+	/// Applies a change to the character. The label describes the change in both the undo history and the journal.
+	pub fn dispatch<F>(&self, label: impl Into<AttrValue>, mutator: F)
+	where
+		F: FnOnce(&mut Persistent) -> MutatorImpact + 'static,
+	{
+		self.push_pending(PendingMutation::Apply(label.into(), Box::new(mutator)));
+	}
+
+	/// Returns a callback which applies a change to the character, described by the label like in `dispatch`.
+	pub fn new_dispatch<I, F>(&self, label: impl Into<AttrValue>, mutator: F) -> Callback<I>
 	where
 		I: 'static,
 		F: Fn(I, &mut Persistent) -> MutatorImpact + 'static,
 	{
 		let handle = self.clone();
+		let label = label.into();
 		let mutator = std::rc::Rc::new(mutator);
 		Callback::from(move |input: I| {
 			let mutator = mutator.clone();
-			handle.dispatch(move |persistent| (*mutator)(input, persistent));
+			handle.dispatch(label.clone(), move |persistent| (*mutator)(input, persistent));
 		})
 	}
+
+	/// Changes the character's current combat situation (cover, raised shield, and temporary effects),
+	/// and recompiles the character to apply the bonuses it grants.
+	/// This state only lives as long as the character sheet is open, and is not part of the undo history.
+	pub fn update_combat<F>(&self, update: F)
+	where
+		F: FnOnce(&mut CombatState) + 'static,
+	{
+		self.push_pending(PendingMutation::Combat(Box::new(update)));
+	}
+
+	/// Reverts the most recent change to the character, if there is one.
+	pub fn undo(&self) {
+		if self.history.borrow().undo_label().is_none() {
+			return;
+		}
+		self.push_pending(PendingMutation::Undo);
+	}
+
+	/// Reapplies the most recently undone change to the character, if there is one.
+	pub fn redo(&self) {
+		if self.history.borrow().redo_label().is_none() {
+			return;
+		}
+		self.push_pending(PendingMutation::Redo);
+	}
+
+	pub fn undo_label(&self) -> Option<AttrValue> {
+		self.history.borrow().undo_label().cloned()
+	}
+
+	pub fn redo_label(&self) -> Option<AttrValue> {
+		self.history.borrow().redo_label().cloned()
+	}
 }
@@ -0,0 +1,257 @@
+use super::{CharacterHandle, MutatorImpact};
+use crate::system::dnd5e::data::character::Persistent;
+use std::collections::VecDeque;
+use wasm_bindgen::JsCast;
+use yew::prelude::*;
+
+/// The number of changes which can be undone before the oldest are forgotten.
+static HISTORY_CAPACITY: usize = 50;
+
+/// The state of the character before (or after) a change, which can be restored to undo (or redo) that change.
+/// Snapshots never include the character's journal, so undoing a change doesn't erase the record of it.
+#[derive(Clone, PartialEq, Debug)]
+struct Snapshot {
+	label: AttrValue,
+	persistent: Persistent,
+	/// If the change required the character to be recompiled, then so does restoring this snapshot.
+	requires_recompile: bool,
+}
+
+/// A bounded record of the changes made to a character, which can be undone and redone.
+#[derive(Clone, PartialEq, Debug)]
+pub struct History {
+	undo: VecDeque<Snapshot>,
+	redo: Vec<Snapshot>,
+	capacity: usize,
+}
+
+impl Default for History {
+	fn default() -> Self {
+		Self::with_capacity(HISTORY_CAPACITY)
+	}
+}
+
+impl History {
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self {
+			undo: VecDeque::with_capacity(capacity),
+			redo: Vec::new(),
+			capacity,
+		}
+	}
+
+	pub fn clear(&mut self) {
+		self.undo.clear();
+		self.redo.clear();
+	}
+
+	/// Records a change which was made to the character, given the state of the character before the change.
+	/// Any changes which had been undone can no longer be redone.
+	pub fn push(&mut self, label: AttrValue, mut before: Persistent, impact: &MutatorImpact) {
+		before.journal = Default::default();
+		self.redo.clear();
+		self.undo.push_back(Snapshot {
+			label,
+			persistent: before,
+			requires_recompile: matches!(impact, MutatorImpact::Recompile),
+		});
+		while self.undo.len() > self.capacity {
+			self.undo.pop_front();
+		}
+	}
+
+	/// The label of the change which would be reverted by `undo`.
+	pub fn undo_label(&self) -> Option<&AttrValue> {
+		self.undo.back().map(|snapshot| &snapshot.label)
+	}
+
+	/// The label of the change which would be reapplied by `redo`.
+	pub fn redo_label(&self) -> Option<&AttrValue> {
+		self.redo.last().map(|snapshot| &snapshot.label)
+	}
+
+	/// Reverts the most recent change, returning None if there is nothing to undo.
+	pub fn undo(&mut self, persistent: &mut Persistent) -> Option<MutatorImpact> {
+		let snapshot = self.undo.pop_back()?;
+		let (inverse, impact) = Self::restore(snapshot, persistent);
+		self.redo.push(inverse);
+		Some(impact)
+	}
+
+	/// Reapplies the most recently undone change, returning None if there is nothing to redo.
+	pub fn redo(&mut self, persistent: &mut Persistent) -> Option<MutatorImpact> {
+		let snapshot = self.redo.pop()?;
+		let (inverse, impact) = Self::restore(snapshot, persistent);
+		self.undo.push_back(inverse);
+		Some(impact)
+	}
+
+	fn restore(snapshot: Snapshot, persistent: &mut Persistent) -> (Snapshot, MutatorImpact) {
+		let Snapshot {
+			label,
+			persistent: restored,
+			requires_recompile,
+		} = snapshot;
+		let journal = std::mem::take(&mut persistent.journal);
+		let replaced = std::mem::replace(persistent, restored);
+		persistent.journal = journal;
+		let inverse = Snapshot {
+			label,
+			persistent: replaced,
+			requires_recompile,
+		};
+		let impact = match requires_recompile {
+			true => MutatorImpact::Recompile,
+			false => MutatorImpact::None,
+		};
+		(inverse, impact)
+	}
+}
+
+/// Undoes or redoes changes to the character when ctrl+z, ctrl+shift+z, or ctrl+y are pressed.
+/// Shortcuts pressed while typing in a text field are left to the browser.
+#[hook]
+pub fn use_history_shortcuts(character: &CharacterHandle) {
+	let character = character.clone();
+	crate::components::hook::use_document_event("keydown", move |evt: web_sys::KeyboardEvent| {
+		if !(evt.ctrl_key() || evt.meta_key()) || !character.is_loaded() {
+			return;
+		}
+		if let Some(target) = evt.target() {
+			let is_text_field = target.dyn_ref::<web_sys::HtmlInputElement>().is_some()
+				|| target.dyn_ref::<web_sys::HtmlTextAreaElement>().is_some();
+			if is_text_field {
+				return;
+			}
+		}
+		match (evt.key().to_lowercase().as_str(), evt.shift_key()) {
+			("z", false) => character.undo(),
+			("z", true) | ("y", false) => character.redo(),
+			_ => return,
+		}
+		evt.prevent_default();
+	});
+}
+
+#[function_component]
+pub fn HistoryButtons() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let undo_title = state.undo_label().map(|label| format!("Undo {label} (Ctrl+Z)"));
+	let redo_title = state.redo_label().map(|label| format!("Redo {label} (Ctrl+Y)"));
+	let undo = Callback::from({
+		let state = state.clone();
+		move |_: MouseEvent| state.undo()
+	});
+	let redo = Callback::from({
+		let state = state.clone();
+		move |_: MouseEvent| state.redo()
+	});
+	html! {
+		<div class="btn-group btn-group-xs mx-2" role="group">
+			<button
+				type="button" class="btn btn-outline-theme btn-xs"
+				disabled={undo_title.is_none()} onclick={undo} title={undo_title.clone()}
+			>
+				<i class="bi bi-arrow-counterclockwise" />
+			</button>
+			<button
+				type="button" class="btn btn-outline-theme btn-xs"
+				disabled={redo_title.is_none()} onclick={redo} title={redo_title.clone()}
+			>
+				<i class="bi bi-arrow-clockwise" />
+			</button>
+		</div>
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::character::JournalEntry;
+
+	fn persistent(current: u32) -> Persistent {
+		let mut persistent = Persistent::default();
+		persistent.hit_points.current = current;
+		persistent
+	}
+
+	fn change(history: &mut History, persistent: &mut Persistent, label: &'static str, current: u32) {
+		history.push(label.into(), persistent.clone(), &MutatorImpact::None);
+		persistent.hit_points.current = current;
+	}
+
+	#[test]
+	fn undo_redo() {
+		let mut history = History::default();
+		let mut state = persistent(10);
+		change(&mut history, &mut state, "Damage", 4);
+		change(&mut history, &mut state, "Heal", 7);
+		assert_eq!(history.undo_label().map(AttrValue::as_str), Some("Heal"));
+
+		assert!(history.undo(&mut state).is_some());
+		assert_eq!(state, persistent(4));
+		assert!(history.undo(&mut state).is_some());
+		assert_eq!(state, persistent(10));
+		assert!(history.undo(&mut state).is_none());
+		assert_eq!(history.redo_label().map(AttrValue::as_str), Some("Damage"));
+
+		assert!(history.redo(&mut state).is_some());
+		assert_eq!(state, persistent(4));
+		assert_eq!(history.undo_label().map(AttrValue::as_str), Some("Damage"));
+		assert_eq!(history.redo_label().map(AttrValue::as_str), Some("Heal"));
+	}
+
+	#[test]
+	fn journal_is_kept() {
+		let mut history = History::default();
+		let mut state = persistent(10);
+		change(&mut history, &mut state, "Damage", 4);
+		state.journal.push(JournalEntry {
+			timestamp: 0,
+			description: "Damage".into(),
+			details: vec![],
+		});
+		let journal = state.journal.clone();
+		assert!(history.undo(&mut state).is_some());
+		assert_eq!(state.hit_points.current, 10);
+		assert_eq!(state.journal, journal);
+		assert!(history.redo(&mut state).is_some());
+		assert_eq!(state.hit_points.current, 4);
+		assert_eq!(state.journal, journal);
+	}
+
+	#[test]
+	fn change_clears_redo() {
+		let mut history = History::default();
+		let mut state = persistent(10);
+		change(&mut history, &mut state, "Damage", 4);
+		history.undo(&mut state);
+		change(&mut history, &mut state, "Heal", 12);
+		assert_eq!(history.redo_label(), None);
+		assert!(history.redo(&mut state).is_none());
+		assert_eq!(state, persistent(12));
+	}
+
+	#[test]
+	fn bounded() {
+		let mut history = History::with_capacity(2);
+		let mut state = persistent(0);
+		for current in 1..=3 {
+			change(&mut history, &mut state, "Heal", current);
+		}
+		assert!(history.undo(&mut state).is_some());
+		assert!(history.undo(&mut state).is_some());
+		assert!(history.undo(&mut state).is_none());
+		assert_eq!(state, persistent(1));
+	}
+
+	#[test]
+	fn recompile_impact() {
+		let mut history = History::default();
+		let mut state = persistent(10);
+		history.push("Add Class".into(), state.clone(), &MutatorImpact::Recompile);
+		state.hit_points.current = 5;
+		assert!(matches!(history.undo(&mut state), Some(MutatorImpact::Recompile)));
+		assert!(matches!(history.redo(&mut state), Some(MutatorImpact::Recompile)));
+	}
+}
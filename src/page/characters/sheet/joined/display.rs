@@ -1,15 +1,18 @@
 use crate::{
 	auth,
-	components::{use_media_query, Nav, NavDisplay, TabContent},
+	components::{context_menu, use_media_query, Nav, NavDisplay, TabContent},
 	database::{Database, Entry},
-	page::characters::sheet::{CharacterHandle, ViewProps},
+	page::characters::sheet::{
+		character_file_content, AutosaveSelect, CharacterHandle, HistoryButtons, SaveCharacter, SaveConflictModal,
+		ViewProps,
+	},
 	system::{
 		self,
 		core::{ModuleId, SourceId},
 		dnd5e::{
 			components::{
-				ability, panel, rest, ArmorClass, ConditionsCard, DefensesCard, HitPointMgmtCard, InitiativeBonus,
-				Inspiration, ProfBonus, Proficiencies, SpeedAndSenses,
+				ability, level_up, panel, rest, ArmorClass, ConditionsCard, DefensesCard, HitPointMgmtCard,
+				InitiativeBonus, Inspiration, ProfBonus, Proficiencies, SpeedAndSenses,
 			},
 			data::{character::Persistent, Ability},
 			SystemComponent,
@@ -41,7 +44,11 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 		let database = database.clone();
 		let id = state.id().clone();
 		move |_| {
-			let Some(client) = crate::storage::get(&*auth_status) else {
+			let Some(module_id) = &id.module else {
+				log::debug!("character has no module");
+				return;
+			};
+			let Some(client) = crate::storage::get_for(module_id, &*auth_status, &database) else {
 				log::debug!("no storage client");
 				return;
 			};
@@ -58,14 +65,14 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 				};
 
 				let SourceId {
-					module: Some(ModuleId::Github { user_org, repository }),
+					module: Some(module_id),
 					system,
 					path,
 					version,
 					..
 				} = &id
 				else {
-					log::error!("non-github source id");
+					log::error!("missing module in source id");
 					return Ok(());
 				};
 				let Some(version) = version.clone() else {
@@ -77,17 +84,14 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 					return Ok(());
 				};
 				let path_in_repo = std::path::Path::new(&system).join(&path);
-				let user_org = user_org.clone();
-				let repository = repository.clone();
 
-				let args = github::repos::contents::get::Args {
-					owner: user_org.as_str(),
-					repo: repository.as_str(),
+				let args = crate::storage::GetFileArgs {
+					module: module_id,
 					path: path_in_repo.as_path(),
 					version: version.as_str(),
 				};
 				let content = client
-					.get_file_content(args)
+					.get_file(args)
 					.await
 					.with_context(|| format!("Failed to fetch content from storage"))?;
 
@@ -106,6 +110,8 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 
 				entry.kdl = content;
 				entry.metadata = metadata;
+				// fetching from storage discards any local draft
+				entry.dirty = false;
 
 				log::debug!("Successfully force-fetched {:?}", id.to_string());
 
@@ -137,9 +143,15 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 		let task_dispatch = task_dispatch.clone();
 		let database = database.clone();
 		let navigator = navigator.clone();
+		let system_depot = system_depot.clone();
+		let state = state.clone();
 		let id = state.id().unversioned();
 		move |_| {
-			let Some(client) = crate::storage::get(&*auth_status) else {
+			let Some(module_id) = &id.module else {
+				log::debug!("character has no module");
+				return;
+			};
+			let Some(client) = crate::storage::get_for(module_id, &*auth_status, &database) else {
 				log::debug!("no storage client");
 				return;
 			};
@@ -156,24 +168,11 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 				}
 			};
 
-			let SourceId {
-				module: Some(ModuleId::Github { user_org, repository }),
-				..
-			} = &id
-			else {
-				log::debug!("non-github source id");
-				return;
-			};
-			let path_in_repo = match &id.system {
-				None => id.path.clone(),
-				Some(system) => std::path::Path::new(&system).join(&id.path),
-			};
-			let id_str = id.to_string();
 			let route = crate::page::characters::Route::sheet(&id);
-			let empty_entry = match is_new {
+			let new_entry = match is_new {
 				false => None,
 				true => Some(Entry {
-					id: id_str.clone(),
+					id: id.to_string(),
 					module: id.module.as_ref().map(ModuleId::to_string).unwrap(),
 					system: id.system.clone().unwrap(),
 					category: Persistent::id().into(),
@@ -181,75 +180,25 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 					metadata: Default::default(),
 					kdl: Default::default(),
 					file_id: None,
+					dirty: false,
+					base_kdl: None,
 				}),
 			};
-			let message = format!("Manually save character");
-			let metadata = state.persistent().clone().to_metadata();
-			let content = {
-				let doc = state.export_as_kdl();
-				let doc = doc.to_string();
-				let doc = doc.replace("\\r", "");
-				let doc = doc.replace("\\n", "\n");
-				let doc = doc.replace("\\t", "\t");
-				let doc = doc.replace("    ", "\t");
-				doc
+			let save = SaveCharacter {
+				database: database.clone(),
+				system_depot: system_depot.clone(),
+				client,
+				id,
+				content: character_file_content(state.persistent()),
+				metadata: state.persistent().clone().to_metadata(),
+				message: format!("Manually save character"),
+				new_entry,
 			};
-			let repo_org = user_org.clone();
-			let repo_name = repository.clone();
-			let database = database.clone();
 			let navigator = navigator.clone();
+			let state = state.clone();
 			task_dispatch.spawn("Update File", None, async move {
-				let mut entry = match empty_entry {
-					Some(entry) => entry,
-					None => match database.get::<Entry>(id_str).await? {
-						Some(entry) => entry,
-						None => {
-							log::debug!("missing entry");
-							return Ok(());
-						}
-					},
-				};
-				let args = github::repos::contents::update::Args {
-					repo_org: &repo_org,
-					repo_name: &repo_name,
-					path_in_repo: &path_in_repo,
-					commit_message: &message,
-					content: &content,
-					file_id: entry.file_id.as_ref().map(String::as_str),
-					branch: None,
-				};
-				log::debug!("executing update character request {args:?}");
-				let response = client.create_or_update_file(args).await?;
-				log::debug!("finished update character request {response:?}");
-
-				let module_version = response.version;
-				// put the updated content in the database for the persistent character segment
-				entry.kdl = content;
-				entry.metadata = metadata;
-				// with the updated module version
-				entry.version = Some(module_version.clone());
-				// and updated storage sha/file id (because it changes every time a change is saved on a file)
-				entry.file_id = Some(response.file_id);
-				// Commit the module version and entry changes to database
-				database
-					.mutate(move |transaction| {
-						use crate::database::{Entry, Module};
-						use database::{ObjectStoreExt, TransactionExt};
-						Box::pin(async move {
-							let module_store = transaction.object_store_of::<Module>()?;
-							let entry_store = transaction.object_store_of::<Entry>()?;
-
-							let module_req = module_store.get_record::<Module>(entry.module.clone());
-							let mut module = module_req.await?.unwrap();
-							module.version = module_version;
-							module_store.put_record(&module).await?;
-
-							entry_store.put_record(&entry).await?;
-
-							Ok(())
-						})
-					})
-					.await?;
+				let outcome = save.run().await?;
+				state.apply_save_outcome(outcome);
 
 				if is_new {
 					navigator.push(&route);
@@ -260,6 +209,38 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 		}
 	});
 
+	let open_conflicts = context_menu::use_control_action({
+		|_: web_sys::MouseEvent, _context| {
+			context_menu::Action::open_root("Save Conflicts", html!(<SaveConflictModal />))
+		}
+	});
+	let conflicts_btn = match state.save_conflict() {
+		None => html!(),
+		Some(conflict) => {
+			let count = conflict.merge.conflicts().len();
+			html! {
+				<button class="btn btn-danger btn-xs mx-2" onclick={open_conflicts}>
+					{format!("{count} save conflict{}", if count == 1 { "" } else { "s" })}
+				</button>
+			}
+		}
+	};
+
+	let unmet_requirements = match state.unmet_requirements().is_empty() {
+		true => html!(),
+		false => {
+			let iter = state.unmet_requirements().iter();
+			let iter = iter.map(|unmet| format!("{}: {}", unmet.path.display(), unmet.reason));
+			let tooltip = iter.collect::<Vec<_>>().join("\n");
+			let count = state.unmet_requirements().len();
+			html! {
+				<span class="me-2" style="color: var(--bs-warning);" title={tooltip}>
+					{format!("{count} unmet requirement{}", if count == 1 { "" } else { "s" })}
+				</span>
+			}
+		}
+	};
+
 	let is_large_page = use_media_query("(min-width: 1400px)");
 	let above_panels_content = html! {<>
 		<div class="row m-0" style="--bs-gutter-x: 0;">
@@ -298,13 +279,19 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 				<Header />
 				<div class="ms-auto d-flex flex-column justify-content-center">
 					<div class="d-flex align-items-center">
+						<panel::LastRoll />
+						<level_up::Button />
 						<rest::Button value={crate::system::dnd5e::data::Rest::Short} />
 						<rest::Button value={crate::system::dnd5e::data::Rest::Long} />
 						<a class="glyph forge" style="margin-right: 0.3rem;" onclick={swap_view.reform(|_| ())} />
 					</div>
 					<div class="d-flex align-items-center mt-2">
 						<div class="ms-auto" />
+						{unmet_requirements}
+						{conflicts_btn}
 						{fetch_btn}
+						<HistoryButtons />
+						<AutosaveSelect />
 						<button class="btn btn-success btn-xs mx-2" onclick={save_to_storage}>{"Save"}</button>
 					</div>
 				</div>
@@ -363,6 +350,12 @@ pub fn Display(ViewProps { swap_view }: &ViewProps) -> Html {
 								<TabContent id="description" title={html! {{"Description"}}}>
 									<panel::Description />
 								</TabContent>
+								<TabContent id="rolls" title={html! {{"Rolls"}}}>
+									<panel::RollLog />
+								</TabContent>
+								<TabContent id="journal" title={html! {{"Journal"}}}>
+									<panel::JournalLog />
+								</TabContent>
 							</Nav>
 						</div>
 					</div>
@@ -1,4 +1,4 @@
-use crate::page::characters::sheet::CharacterHandle;
+use crate::{page::characters::sheet::CharacterHandle, system::dnd5e::data::character::LevelProgression};
 use itertools::Itertools;
 use yew::prelude::*;
 
@@ -83,6 +83,13 @@ pub fn Header() -> Html {
 		.map(|class| html!(format!("{} {}", class.name, class.current_level)));
 	let classes = Itertools::intersperse(classes, html!(" / ")).collect::<Vec<_>>();
 
+	let experience = match state.persistent().settings.level_progression {
+		LevelProgression::Milestone => None,
+		LevelProgression::Experience => Some(html! {
+			<div class="group experience">{format!("Experience: {} XP", state.persistent().experience)}</div>
+		}),
+	};
+
 	html! {
 		<div class="sheet-header">
 			{name}
@@ -90,6 +97,7 @@ pub fn Header() -> Html {
 			{lineage_upbringing}
 			{background.unwrap_or_default()}
 			<div class="level">{format!("Character Level ({total_level}): ")}{classes}</div>
+			{experience.unwrap_or_default()}
 		</div>
 	}
 }
@@ -2,7 +2,7 @@ use crate::{
 	page::characters::sheet::{CharacterHandle, MutatorImpact},
 	system::dnd5e::{
 		components::{ability, validate_uint_only},
-		data::{character::Persistent, Ability},
+		data::Ability,
 	},
 	utility::InputExt,
 };
@@ -49,11 +49,11 @@ fn AbilityScoreInput(AbilityScoreInputProps { ability }: &AbilityScoreInputProps
 			let Some(value) = evt.input_value_t::<u32>() else {
 				return;
 			};
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Set Ability Score", move |persistent| {
 				persistent.ability_scores[ability] = value;
 				// only actually need ability_score_finalize to execute
 				MutatorImpact::Recompile
-			}));
+			});
 		}
 	});
 	html! {
@@ -139,11 +139,11 @@ fn GenerationSection() -> Html {
 		let scores = scores.clone();
 		move |_| {
 			let scores = (*scores).clone();
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Set Ability Scores", move |persistent| {
 				persistent.ability_scores = scores;
 				// only actually need ability_score_finalize to execute
 				MutatorImpact::Recompile
-			}));
+			});
 		}
 	});
 	use_effect_with((*method).clone(), {
@@ -5,10 +5,10 @@ use crate::{
 	},
 	page::characters::sheet::{joined::editor::mutator_list, CharacterHandle, MutatorImpact},
 	system::dnd5e::{
-		data::{character::Persistent, roll::Die, Class, Level},
+		data::{roll::Die, Class, Level},
 		DnD5e,
 	},
-	utility::InputExt,
+	utility::{Evaluator, InputExt},
 };
 use convert_case::{Case, Casing};
 use std::{collections::HashSet, sync::Arc};
@@ -106,10 +106,10 @@ fn ClassBrowser(ClassBrowserProps { on_added }: &ClassBrowserProps) -> Html {
 			let update = update.clone();
 			move |mut class_to_add: Class| {
 				class_to_add.current_level = 1;
-				state.dispatch(Box::new(move |persistent: &mut Persistent| {
+				state.dispatch("Add Class", move |persistent| {
 					persistent.add_class(class_to_add);
 					MutatorImpact::Recompile
-				}));
+				});
 				on_added.emit(());
 				update.force_update();
 			}
@@ -122,6 +122,7 @@ fn ClassBrowser(ClassBrowserProps { on_added }: &ClassBrowserProps) -> Html {
 		QueryStatus::Success(classes) => html! {<>
 			{classes.iter().map(|class| {
 				let id = class.name.to_case(Case::Snake);
+				let unmet_requirements = multiclass_blockers(&state, class);
 				html! {
 					<div class="accordion-item">
 						<h2 class="accordion-header">
@@ -131,9 +132,18 @@ fn ClassBrowser(ClassBrowserProps { on_added }: &ClassBrowserProps) -> Html {
 						</h2>
 						<div {id} class="accordion-collapse collapse" data-bs-parent={"#all-entries"}>
 							<div class="accordion-body">
+								{(!unmet_requirements.is_empty()).then(|| html! {
+									<div class="my-1" style="color: var(--bs-warning);">
+										{"Multiclassing requirements not met:"}
+										<ul class="mb-0">
+											{unmet_requirements.iter().map(|reason| html!(<li>{reason}</li>)).collect::<Vec<_>>()}
+										</ul>
+									</div>
+								}).unwrap_or_default()}
 								<button
 									type="button" class="btn btn-success my-1 w-100"
 									data-bs-toggle="collapse" data-bs-target="#classBrowser"
+									disabled={!unmet_requirements.is_empty()}
 									onclick={on_add_class.reform({
 										let class_id = class.id.unversioned();
 										move |_: MouseEvent| class_id.clone()
@@ -156,37 +166,49 @@ fn ClassBrowser(ClassBrowserProps { on_added }: &ClassBrowserProps) -> Html {
 	}
 }
 
+/// Multiclassing requires that the character meet the prerequisites of both the new class
+/// and every class they already have. Returns the reason for each prerequisite which is not met.
+fn multiclass_blockers(state: &CharacterHandle, class: &Class) -> Vec<String> {
+	let existing = &state.persistent().classes;
+	if existing.is_empty() {
+		return Vec::new();
+	}
+	let iter = std::iter::once(class).chain(existing.iter());
+	iter.filter_map(|class| class.evaluate_multiclass_requirement(state).err())
+		.collect()
+}
+
 #[function_component]
 fn ActiveClassList() -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
 	let onclick_add = Callback::from({
 		let state = state.clone();
 		move |idx: usize| {
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Level Up", move |persistent| {
 				let Some(class) = persistent.classes.get_mut(idx) else {
 					return MutatorImpact::None;
 				};
 				class.current_level += 1;
 				// TODO: Only recompile on exit
 				MutatorImpact::Recompile
-			}));
+			});
 		}
 	});
 
 	let remove_class = Callback::from({
 		let state = state.clone();
 		move |idx| {
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Remove Class", move |persistent| {
 				let _ = persistent.classes.remove(idx);
 				// TODO: Only recompile on exit
 				MutatorImpact::Recompile
-			}));
+			});
 		}
 	});
 	let onclick_remove = Callback::from({
 		let state = state.clone();
 		move |idx: usize| {
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Level Down", move |persistent| {
 				let remove_class = {
 					let Some(class) = persistent.classes.get_mut(idx) else {
 						return MutatorImpact::None;
@@ -199,7 +221,7 @@ fn ActiveClassList() -> Html {
 				}
 				// TODO: Only recompile on exit
 				MutatorImpact::Recompile
-			}));
+			});
 		}
 	});
 	html! {<>
@@ -248,6 +270,15 @@ fn class_body(value: &Class, state: Option<&CharacterHandle>) -> Html {
 			{value.hit_die.to_string()}
 		</span>
 		{mutator_list(&value.mutators, state)}
+		{value.multiclass_requirement.as_ref().and_then(|req| req.description()).map(|desc| html! {
+			<div>{"Multiclassing Prerequisite: "}{desc}</div>
+		}).unwrap_or_default()}
+		{(!value.multiclass_mutators.is_empty()).then(|| html! {
+			<div class="mt-1">
+				<strong>{"When Multiclassing"}</strong>
+				{mutator_list(&value.multiclass_mutators, state)}
+			</div>
+		}).unwrap_or_default()}
 	</>}
 }
 
@@ -375,7 +406,7 @@ fn LevelHitPoints(LevelHitPointsProps { data_path, die }: &LevelHitPointsProps)
 		classes.push("missing-value");
 	}
 	let hp_path_dst = hp_path.clone();
-	let onchange = state.new_dispatch(move |evt: web_sys::Event, persistent| {
+	let onchange = state.new_dispatch("Select Hit Points", move |evt: web_sys::Event, persistent| {
 		persistent.set_selected(&hp_path_dst, evt.select_value());
 		MutatorImpact::Recompile // TODO: this can be delayed till editor is closed i think
 	});
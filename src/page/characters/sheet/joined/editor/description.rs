@@ -2,7 +2,7 @@ use crate::{
 	page::characters::sheet::{CharacterHandle, MutatorImpact},
 	system::dnd5e::{
 		components::{validate_uint_only, GeneralProp},
-		data::character::{Persistent, PersonalityKind},
+		data::character::PersonalityKind,
 	},
 	utility::InputExt,
 };
@@ -61,21 +61,21 @@ fn SizeForm() -> Html {
 	);
 	let weight_label = format!("{}kg", ((weight as f32) * 0.45359237).round() as u32);
 
-	let set_height = state.new_dispatch(|evt: web_sys::Event, persistent| {
+	let set_height = state.new_dispatch("Set Height", |evt: web_sys::Event, persistent| {
 		let Some(value) = evt.input_value_t::<u32>() else {
 			return MutatorImpact::None;
 		};
 		persistent.description.height = value;
 		MutatorImpact::None
 	});
-	let set_weight = state.new_dispatch(|evt: web_sys::Event, persistent| {
+	let set_weight = state.new_dispatch("Set Weight", |evt: web_sys::Event, persistent| {
 		let Some(value) = evt.input_value_t::<u32>() else {
 			return MutatorImpact::None;
 		};
 		persistent.description.weight = value;
 		MutatorImpact::None
 	});
-	let roll_size = state.new_dispatch(move |_, persistent| {
+	let roll_size = state.new_dispatch("Roll Height and Weight", move |_, persistent| {
 		let mut rng = rand::thread_rng();
 		let (height, weight) = formula.get_random(&mut rng);
 		persistent.description.height = height;
@@ -158,7 +158,7 @@ fn PersonalitySection() -> Html {
 fn PersonalityCard(GeneralProp { value }: &GeneralProp<PersonalityKind>) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
 	let personality_kind = *value;
-	let add_item = state.new_dispatch(move |value, persistent| {
+	let add_item = state.new_dispatch("Add Personality", move |value, persistent| {
 		persistent.description.personality[personality_kind].push(value);
 		MutatorImpact::None
 	});
@@ -183,20 +183,23 @@ fn PersonalityCard(GeneralProp { value }: &GeneralProp<PersonalityKind>) -> Html
 	};
 	let selected_values = {
 		let add_custom = add_item.reform(|_| String::new());
-		let delete_item = state.new_dispatch(move |idx: usize, persistent| {
+		let delete_item = state.new_dispatch("Remove Personality", move |idx: usize, persistent| {
 			persistent.description.personality[personality_kind].remove(idx);
 			MutatorImpact::None
 		});
-		let update_item = state.new_dispatch(move |(idx, evt): (usize, web_sys::Event), persistent| {
-			let Some(value) = evt.input_value() else {
-				return MutatorImpact::None;
-			};
-			let Some(target) = persistent.description.personality[personality_kind].get_mut(idx) else {
-				return MutatorImpact::None;
-			};
-			*target = value.trim().to_owned();
-			MutatorImpact::None
-		});
+		let update_item = state.new_dispatch(
+			"Edit Personality",
+			move |(idx, evt): (usize, web_sys::Event), persistent| {
+				let Some(value) = evt.input_value() else {
+					return MutatorImpact::None;
+				};
+				let Some(target) = persistent.description.personality[personality_kind].get_mut(idx) else {
+					return MutatorImpact::None;
+				};
+				*target = value.trim().to_owned();
+				MutatorImpact::None
+			},
+		);
 		let selected_traits = &state.persistent().description.personality[*value];
 		html! {
 			<div class="mb-3">
@@ -282,10 +285,10 @@ pub fn AppearanceSection() -> Html {
 			let Some(value) = evt.input_value() else {
 				return;
 			};
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Set Appearance", move |persistent| {
 				persistent.description.appearance = value;
 				MutatorImpact::None
-			}));
+			});
 		}
 	});
 	html! {
@@ -1,8 +1,14 @@
 use crate::{
 	page::characters::sheet::{CharacterHandle, MutatorImpact},
-	system::dnd5e::data::character::Persistent,
+	system::dnd5e::data::{
+		character::{ExperienceTable, LevelProgression},
+		Rest, RestVariant,
+	},
 	utility::InputExt,
 };
+use enumset::EnumSet;
+use itertools::Itertools;
+use std::str::FromStr;
 use yew::prelude::*;
 
 #[function_component]
@@ -51,10 +57,10 @@ fn NameEditor() -> Html {
 			let Some(value) = evt.input_value() else {
 				return;
 			};
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Rename Character", move |persistent| {
 				persistent.description.name = value;
 				MutatorImpact::None
-			}));
+			});
 		}
 	});
 	html! {<>
@@ -83,7 +89,7 @@ fn PronounEditor() -> Html {
 			let is_checkbox = input.type_() == "checkbox";
 			let is_checked = input.checked();
 			let value = input.value();
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Set Pronouns", move |persistent| {
 				match (is_checkbox, is_checked) {
 					(true, true) => {
 						persistent.description.pronouns.insert(value);
@@ -96,7 +102,7 @@ fn PronounEditor() -> Html {
 					}
 				}
 				MutatorImpact::None
-			}));
+			});
 		}
 	});
 	html! {
@@ -130,9 +136,133 @@ fn SettingsEditor() -> Html {
 	html! {<>
 		<h4>{"Settings"}</h4>
 		<AutoExchangeSwitch />
+		<VariantEncumbranceSwitch />
+		<LevelProgressionEditor />
+		<RestVariantEditor />
 	</>}
 }
 
+#[function_component]
+fn RestVariantEditor() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let rest_variant = state.persistent().settings.rest_variant;
+	let onchange = Callback::from({
+		let state = state.clone();
+		move |evt: web_sys::Event| {
+			let Some(value) = evt.select_value() else {
+				return;
+			};
+			let Ok(variant) = RestVariant::from_str(&value) else {
+				return;
+			};
+			state.dispatch("Change Rest Variant", move |persistent| {
+				persistent.settings.rest_variant = variant;
+				MutatorImpact::None
+			});
+		}
+	});
+	html! {
+		<div class="my-2">
+			<div class="input-group">
+				<span class="input-group-text"><strong>{"Resting"}</strong></span>
+				<select class="form-select" {onchange}>
+					{EnumSet::<RestVariant>::all().into_iter().map(|variant| html! {
+						<option value={variant.to_string()} selected={variant == rest_variant}>
+							{variant.display_name()}
+						</option>
+					}).collect::<Vec<_>>()}
+				</select>
+			</div>
+			<div class="form-text">
+				{format!(
+					"Short rests take {} and long rests take {}.",
+					rest_variant.duration(Rest::Short),
+					rest_variant.duration(Rest::Long),
+				)}
+				{(rest_variant.charge_resets(Rest::Short).contains(&Rest::Long)).then(|| {
+					" Items which regain charges daily do so after every short rest."
+				})}
+			</div>
+		</div>
+	}
+}
+
+#[function_component]
+fn LevelProgressionEditor() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let settings = &state.persistent().settings;
+	let on_progression_changed = Callback::from({
+		let state = state.clone();
+		move |evt: web_sys::Event| {
+			let Some(value) = evt.select_value() else {
+				return;
+			};
+			let Ok(progression) = LevelProgression::from_str(&value) else {
+				return;
+			};
+			state.dispatch("Change Level Progression", move |persistent| {
+				persistent.settings.level_progression = progression;
+				MutatorImpact::None
+			});
+		}
+	});
+	let on_table_changed = Callback::from({
+		let state = state.clone();
+		move |evt: web_sys::Event| {
+			let Some(value) = evt.input_value() else {
+				return;
+			};
+			let thresholds = value.split(',').map(str::trim).filter(|value| !value.is_empty());
+			let Ok(thresholds) = thresholds.map(u32::from_str).collect::<Result<Vec<_>, _>>() else {
+				return;
+			};
+			state.dispatch("Change Experience Table", move |persistent| {
+				persistent.settings.experience_table = match thresholds.is_empty() {
+					true => ExperienceTable::default(),
+					false => thresholds.into(),
+				};
+				MutatorImpact::None
+			});
+		}
+	});
+	let table_str = settings.experience_table.thresholds().iter().join(", ");
+	html! {
+		<div class="my-2">
+			<div class="input-group">
+				<span class="input-group-text"><strong>{"Leveling"}</strong></span>
+				<select class="form-select" onchange={on_progression_changed}>
+					{EnumSet::<LevelProgression>::all().into_iter().map(|progression| html! {
+						<option
+							value={progression.to_string()}
+							selected={progression == settings.level_progression}
+						>
+							{progression.to_string()}
+						</option>
+					}).collect::<Vec<_>>()}
+				</select>
+			</div>
+			<div class="form-text text-block">
+				{"Milestone: You can level up whenever your game master says you have reached a new level.
+				Experience: You level up when you have gained enough experience points."}
+			</div>
+			{(settings.level_progression == LevelProgression::Experience).then(|| html! {
+				<div class="mt-2">
+					<label for="experience_table" class="form-label">{"Experience per Level"}</label>
+					<input
+						id="experience_table" class="form-control" type="text"
+						value={table_str}
+						onchange={on_table_changed}
+					/>
+					<div class="form-text">
+						{"The experience points required to reach each level, separated by commas. \
+						Clear the field to use the standard table."}
+					</div>
+				</div>
+			}).unwrap_or_default()}
+		</div>
+	}
+}
+
 #[function_component]
 pub fn AutoExchangeSwitch() -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
@@ -142,10 +272,10 @@ pub fn AutoExchangeSwitch() -> Html {
 			let Some(value) = evt.input_checked() else {
 				return;
 			};
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Toggle Currency Auto-Exchange", move |persistent| {
 				persistent.settings.currency_auto_exchange = value;
 				MutatorImpact::None
-			}));
+			});
 		}
 	});
 	html! {
@@ -169,3 +299,40 @@ pub fn AutoExchangeSwitch() -> Html {
 		</div>
 	}
 }
+
+#[function_component]
+fn VariantEncumbranceSwitch() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let onchange = Callback::from({
+		let state = state.clone();
+		move |evt: web_sys::Event| {
+			let Some(value) = evt.input_checked() else {
+				return;
+			};
+			state.dispatch("Toggle Variant Encumbrance", move |persistent| {
+				persistent.settings.variant_encumbrance = value;
+				MutatorImpact::Recompile
+			});
+		}
+	});
+	html! {
+		<div class="form-check form-switch">
+			<input
+				class="form-check-input"
+				type="checkbox" role="switch" id="variant_encumbrance"
+				aria-describedby="variant_encumbrance-help"
+				onchange={onchange}
+				checked={state.persistent().settings.variant_encumbrance}
+			/>
+			<label class="form-check-label" for="variant_encumbrance">
+				<strong>{"Encumbrance: "}</strong>
+				{"Variant Rules"}
+			</label>
+			<div id="variant_encumbrance-help" class="form-text text-block">
+				{"If enabled, carrying more than 5 times your Strength score reduces your speed by 10 feet, \
+				and carrying more than 10 times your Strength score reduces your speed by 20 feet \
+				and gives you disadvantage on Strength, Dexterity, and Constitution checks, attack rolls, and saving throws."}
+			</div>
+		</div>
+	}
+}
@@ -11,7 +11,7 @@ use crate::{
 		core::System,
 		dnd5e::{
 			components::GeneralProp,
-			data::{bundle::BundleRequirement, character::Persistent, description, Bundle, Feature},
+			data::{bundle::BundleRequirement, description, Bundle, Feature},
 			DnD5e,
 		},
 	},
@@ -217,18 +217,15 @@ fn selected_bundle(
 	};
 
 	let reqs_desc = {
-		let reqs = bundle
-			.requirements
-			.iter()
-			.map(|req| match req {
-				BundleRequirement::Bundle { category, name } => format!("{category}: {name}"),
-				BundleRequirement::Ability(ability, score) => {
-					format!("{} >= {score}", ability.long_name())
-				}
-			})
-			.collect::<Vec<_>>();
+		let reqs = bundle.requirements.iter().map(ToString::to_string).collect::<Vec<_>>();
 		(!reqs.is_empty()).then(|| format!(" (requires: [{}])", reqs.join(", ")))
 	};
+	let unmet_requirements = {
+		let path_to_bundle = std::path::Path::new(&bundle.name);
+		let iter = state.unmet_requirements().iter();
+		let iter = iter.filter(|unmet| unmet.path == path_to_bundle);
+		iter.map(|unmet| html!(<li>{&unmet.reason}</li>)).collect::<Vec<_>>()
+	};
 	let title = reqs_desc
 		.map(|desc| format!("{}{desc}", bundle.name))
 		.unwrap_or_else(|| bundle.name.clone());
@@ -240,12 +237,18 @@ fn selected_bundle(
 			kind={ContentItemKind::Remove {
 				disable_selection: dependents.map(|desc| format!("Cannot remove, depended on by: {desc}").into()),
 			}}
-			on_click={state.new_dispatch(move |_, persistent| {
+			on_click={state.new_dispatch(format!("Remove {}", bundle.category), move |_, persistent| {
 				log::debug!("remove bundle {idx}");
 				persistent.bundles.remove(idx);
 				MutatorImpact::Recompile // TODO: Only do this when returning to sheet view
 			})}
 		>
+			{(!unmet_requirements.is_empty()).then(|| html! {
+				<div class="mb-2" style="color: var(--bs-warning);">
+					{"Requirements not met:"}
+					<ul class="mb-0">{unmet_requirements}</ul>
+				</div>
+			}).unwrap_or_default()}
 			<div class="text-block">
 				{description(&bundle.description, false, true)}
 			</div>
@@ -259,7 +262,7 @@ fn AvailableBundle(GeneralProp { value: bundle }: &GeneralProp<Bundle>) -> Html
 	let state = use_context::<CharacterHandle>().unwrap();
 	let on_select = use_typed_fetch_callback(
 		"Add Bundle".into(),
-		state.new_dispatch(|bundle: Bundle, persistent| {
+		state.new_dispatch(format!("Add {}", bundle.category), |bundle: Bundle, persistent| {
 			persistent.bundles.push(bundle);
 			MutatorImpact::Recompile // TODO: Only do this when returning to sheet view
 		}),
@@ -272,40 +275,11 @@ fn AvailableBundle(GeneralProp { value: bundle }: &GeneralProp<Bundle>) -> Html
 		.filter(|selected| selected.id == bundle.id)
 		.count();
 	let mut title = bundle.name.clone();
-	let mut requirements_met = true;
 	if !bundle.requirements.is_empty() {
-		let reqs = bundle
-			.requirements
-			.iter()
-			.map(|req| match req {
-				BundleRequirement::Bundle { category, name } => format!("{category}: {name}"),
-				BundleRequirement::Ability(ability, score) => {
-					format!("{} >= {score}", ability.long_name())
-				}
-			})
-			.collect::<Vec<_>>();
+		let reqs = bundle.requirements.iter().map(ToString::to_string).collect::<Vec<_>>();
 		title = format!("{title} (requires: [{}])", reqs.join(", "));
-		for req in &bundle.requirements {
-			match req {
-				BundleRequirement::Bundle { category, name } => {
-					let mut iter_bundles = state.persistent().bundles.iter();
-					let passed = iter_bundles
-						.find(|selected| &selected.category == category && &selected.name == name)
-						.is_some();
-					if !passed {
-						requirements_met = false;
-						break;
-					}
-				}
-				BundleRequirement::Ability(ability, score) => {
-					if state.ability_scores().get(*ability).score().0 < *score {
-						requirements_met = false;
-						break;
-					}
-				}
-			}
-		}
 	}
+	let unmet_reasons = bundle.unmet_requirements(&state);
 	let bundle_id = bundle.name.to_case(Case::Kebab).replace("(", "").replace(")", "");
 	html! {
 		<ContentItem
@@ -315,7 +289,8 @@ fn AvailableBundle(GeneralProp { value: bundle }: &GeneralProp<Bundle>) -> Html
 			kind={ContentItemKind::Add {
 				amount_selected,
 				selection_limit: bundle.limit,
-				disable_selection: (!requirements_met).then(|| "Requirements not met".into()),
+				disable_selection: (!unmet_reasons.is_empty())
+					.then(|| format!("Requirements not met: {}", unmet_reasons.join(" ")).into()),
 			}}
 			on_click={on_select.reform({
 				let source_id = bundle.id.unversioned();
@@ -594,7 +569,7 @@ fn SelectorField(
 		let state = state.clone();
 		move |value| {
 			let data_path = data_path.clone();
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Select Option", move |persistent| {
 				match value {
 					None => {
 						persistent.selected_values.remove(&data_path);
@@ -604,7 +579,7 @@ fn SelectorField(
 					}
 				}
 				MutatorImpact::Recompile // TODO: Only do this when returning to sheet view
-			}));
+			});
 		}
 	});
 
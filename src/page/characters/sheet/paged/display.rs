@@ -32,6 +32,8 @@ enum Page {
 	Spells,
 	Inventory,
 	Description,
+	Rolls,
+	Journal,
 }
 impl Page {
 	fn display_name(&self) -> &'static str {
@@ -42,6 +44,8 @@ impl Page {
 			Self::Spells => "Spells",
 			Self::Inventory => "Inventory",
 			Self::Description => "Description",
+			Self::Rolls => "Roll Log",
+			Self::Journal => "Journal",
 		}
 	}
 
@@ -53,6 +57,8 @@ impl Page {
 			Self::Spells => html!(<crate::system::dnd5e::components::panel::Spells />),
 			Self::Inventory => html!(<crate::system::dnd5e::components::panel::Inventory />),
 			Self::Description => html!(<crate::system::dnd5e::components::panel::Description />),
+			Self::Rolls => html!(<crate::system::dnd5e::components::panel::RollLog />),
+			Self::Journal => html!(<crate::system::dnd5e::components::panel::JournalLog />),
 		}
 	}
 }
@@ -65,6 +71,8 @@ impl ToString for Page {
 			Self::Spells => "Spells",
 			Self::Inventory => "Inventory",
 			Self::Description => "Description",
+			Self::Rolls => "Rolls",
+			Self::Journal => "Journal",
 		}
 		.into()
 	}
@@ -80,6 +88,8 @@ impl FromStr for Page {
 			"Spells" => Ok(Self::Spells),
 			"Inventory" => Ok(Self::Inventory),
 			"Description" => Ok(Self::Description),
+			"Rolls" => Ok(Self::Rolls),
+			"Journal" => Ok(Self::Journal),
 			v => Err(NotInList(
 				v.into(),
 				vec![
@@ -89,6 +99,8 @@ impl FromStr for Page {
 					"Spells",
 					"Inventory",
 					"Description",
+					"Rolls",
+					"Journal",
 				],
 			)),
 		}
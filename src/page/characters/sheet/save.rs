@@ -0,0 +1,467 @@
+use super::CharacterHandle;
+use crate::{
+	components::context_menu,
+	database::{Database, Entry},
+	kdl_ext::{MergeSide, ThreeWayMerge},
+	storage::{ChangedFileStatus, CompareArgs, GetFileArgs, PutFileArgs, Storage},
+	system::{
+		self,
+		core::SourceId,
+		dnd5e::{data::character::Persistent, SystemComponent},
+	},
+	utility::InputExt,
+	GeneralError,
+};
+use gloo_storage::Storage as _;
+use std::path::{Path, PathBuf};
+use yew::prelude::*;
+
+static AUTOSAVE_DELAY_KEY: &str = "autosave_delay";
+/// The options for how long (in seconds) to wait after the most recent change before saving, where None disables autosave.
+static AUTOSAVE_DELAY_OPTIONS: [Option<u32>; 5] = [None, Some(5), Some(30), Some(60), Some(300)];
+static DEFAULT_AUTOSAVE_DELAY: Option<u32> = Some(30);
+
+/// Returns the number of seconds to wait after the most recent change to a character before automatically saving it.
+/// If None, characters are only saved to storage when the user presses the save button.
+pub fn autosave_delay() -> Option<u32> {
+	gloo_storage::LocalStorage::get::<Option<u32>>(AUTOSAVE_DELAY_KEY).unwrap_or(DEFAULT_AUTOSAVE_DELAY)
+}
+
+pub fn set_autosave_delay(delay: Option<u32>) {
+	if let Err(err) = gloo_storage::LocalStorage::set(AUTOSAVE_DELAY_KEY, delay) {
+		log::error!(target: "character", "Failed to save autosave delay: {err:?}");
+	}
+}
+
+/// Returns the contents of the file a character is saved as in storage.
+pub fn character_file_content(persistent: &Persistent) -> String {
+	let doc = persistent.export_as_kdl();
+	let doc = doc.to_string();
+	let doc = doc.replace("\\r", "");
+	let doc = doc.replace("\\n", "\n");
+	let doc = doc.replace("\\t", "\t");
+	let doc = doc.replace("    ", "\t");
+	doc
+}
+
+/// Writes the character's current data into its database entry as a local draft,
+/// marking the entry as dirty until it is saved to storage. Characters which have never been saved
+/// don't have an entry, and so don't have drafts. Returns true if a draft was written.
+pub async fn write_draft(database: &Database, persistent: Persistent) -> Result<bool, database::Error> {
+	let id_str = persistent.id.unversioned().to_string();
+	let content = character_file_content(&persistent);
+	let metadata = persistent.to_metadata();
+	let written = std::rc::Rc::new(std::cell::Cell::new(false));
+	database
+		.mutate({
+			let written = written.clone();
+			move |transaction| {
+				use database::{ObjectStoreExt, TransactionExt};
+				Box::pin(async move {
+					// The entry is read in the same transaction it is written in, so that a save which is committed
+					// at the same time can't have its file id and version replaced with the ones read before it.
+					let entry_store = transaction.object_store_of::<Entry>()?;
+					let Some(mut entry) = entry_store.get_record::<Entry>(id_str).await? else {
+						return Ok(());
+					};
+					if entry.kdl == content {
+						return Ok(());
+					}
+					// the content last synced with storage is kept as the base for merging in case someone else changes the file
+					if !entry.dirty {
+						entry.base_kdl = Some(std::mem::take(&mut entry.kdl));
+					}
+					entry.kdl = content;
+					entry.metadata = metadata;
+					entry.dirty = true;
+					entry_store.put_record(&entry).await?;
+					written.set(true);
+					Ok(())
+				})
+			}
+		})
+		.await?;
+	Ok(written.get())
+}
+
+/// Saves the local draft of a character to storage, if the character has unsaved changes.
+pub async fn autosave(database: Database, system_depot: system::Depot, id: SourceId) -> anyhow::Result<SaveOutcome> {
+	let id = id.unversioned();
+	let Some(module_id) = &id.module else {
+		return Ok(SaveOutcome::Saved);
+	};
+	let Some(entry) = database.get::<Entry>(id.to_string()).await? else {
+		return Ok(SaveOutcome::Saved);
+	};
+	if !entry.dirty {
+		return Ok(SaveOutcome::Saved);
+	}
+	let auth_status = yewdux::dispatch::get::<crate::auth::Status>();
+	let Some(client) = crate::storage::get_for(module_id, &*auth_status, &database) else {
+		log::debug!(target: "character", "No storage client available to autosave {:?}", id.to_string());
+		return Ok(SaveOutcome::Saved);
+	};
+	log::debug!(target: "character", "Autosaving {:?}", id.to_string());
+	let save = SaveCharacter {
+		database,
+		system_depot,
+		client,
+		id,
+		content: entry.kdl,
+		metadata: entry.metadata,
+		message: "Autosave character".into(),
+		new_entry: None,
+	};
+	save.run().await
+}
+
+/// Saves a character's data as a file in storage, and updates its database entry and module version to match.
+pub struct SaveCharacter {
+	pub database: Database,
+	pub system_depot: system::Depot,
+	pub client: Storage,
+	/// The unversioned id of the character, which must have a path.
+	pub id: SourceId,
+	pub content: String,
+	pub metadata: serde_json::Value,
+	pub message: String,
+	/// The entry to save into, if the character is not yet in the database.
+	pub new_entry: Option<Entry>,
+}
+
+pub enum SaveOutcome {
+	/// The content was saved as-is.
+	Saved,
+	/// The file was changed in storage since it was last synced, and those changes were merged
+	/// with the local changes before saving. The character's data should be replaced to pick up the remote changes.
+	Merged,
+	/// The file was changed in storage in ways which conflict with the local changes.
+	/// Nothing was saved, and the conflicts must be resolved before the character can be saved.
+	Conflict(SaveConflict),
+}
+
+/// The local and remote changes to a character file which could not be merged automatically.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SaveConflict {
+	pub id: SourceId,
+	pub message: String,
+	pub merge: ThreeWayMerge,
+	/// The id of the file in storage which contains the remote changes.
+	pub remote_file_id: String,
+}
+
+/// A change made to a file in storage by someone else.
+struct RemoteChange {
+	file_id: String,
+	content: String,
+}
+
+impl SaveCharacter {
+	fn path_in_repo(&self) -> PathBuf {
+		match &self.id.system {
+			None => self.id.path.clone(),
+			Some(system) => Path::new(&system).join(&self.id.path),
+		}
+	}
+
+	async fn get_entry(&mut self) -> anyhow::Result<Option<Entry>> {
+		match self.new_entry.take() {
+			Some(entry) => Ok(Some(entry)),
+			None => Ok(self.database.get::<Entry>(self.id.to_string()).await?),
+		}
+	}
+
+	pub async fn run(mut self) -> anyhow::Result<SaveOutcome> {
+		let Some(entry) = self.get_entry().await? else {
+			log::debug!("missing entry");
+			return Ok(SaveOutcome::Saved);
+		};
+
+		let Some(remote) = self.find_remote_change(&entry).await? else {
+			let file_id = entry.file_id.clone();
+			self.commit(entry, file_id).await?;
+			return Ok(SaveOutcome::Saved);
+		};
+
+		// Someone else saved the file since it was last synced, so the local and remote changes need to be merged.
+		let base = entry.base_kdl.as_ref().unwrap_or(&entry.kdl);
+		let merge = ThreeWayMerge::from_documents(base, &self.content, &remote.content)?;
+		if !merge.conflicts().is_empty() {
+			log::debug!(target: "character", "Found {} save conflicts in {:?}", merge.conflicts().len(), self.id.to_string());
+			return Ok(SaveOutcome::Conflict(SaveConflict {
+				id: self.id,
+				message: self.message,
+				merge,
+				remote_file_id: remote.file_id,
+			}));
+		}
+		self.content = merge.resolve_document(&[]);
+		self.metadata = self.parse_metadata()?;
+		self.commit(entry, Some(remote.file_id)).await?;
+		Ok(SaveOutcome::Merged)
+	}
+
+	/// Saves the result of resolving a save conflict, replacing the remote changes.
+	pub async fn resolve(mut self, remote_file_id: String) -> anyhow::Result<()> {
+		let Some(entry) = self.get_entry().await? else {
+			log::debug!("missing entry");
+			return Ok(());
+		};
+		self.metadata = self.parse_metadata()?;
+		self.commit(entry, Some(remote_file_id)).await
+	}
+
+	fn parse_metadata(&self) -> anyhow::Result<serde_json::Value> {
+		let Some(system_reg) = self.id.system.as_ref().and_then(|system| self.system_depot.get(system)) else {
+			return Err(GeneralError(format!("Missing system registration for {:?}", self.id.to_string())).into());
+		};
+		let document = self.content.parse::<kdl::KdlDocument>()?;
+		let Some(node) = document.nodes().get(0) else {
+			return Err(GeneralError(format!("Character data is empty")).into());
+		};
+		system_reg.parse_metadata(node, &self.id)
+	}
+
+	/// Checks if the file has been changed in storage since the entry was last synced, by comparing the
+	/// module version the entry was synced at with the latest version of the module.
+	async fn find_remote_change(&self, entry: &Entry) -> anyhow::Result<Option<RemoteChange>> {
+		let (Some(module_id), Some(synced_version), Some(synced_file_id)) =
+			(&self.id.module, &entry.version, &entry.file_id)
+		else {
+			return Ok(None);
+		};
+		let latest_version = self.client.get_module_version(module_id).await?;
+		if &latest_version == synced_version {
+			return Ok(None);
+		}
+		let path_in_repo = self.path_in_repo();
+		let args = CompareArgs {
+			module: module_id,
+			old_version: synced_version,
+			new_version: &latest_version,
+		};
+		let changed_files = self.client.list_changed_files(args).await?;
+		let changed_file = changed_files
+			.into_iter()
+			.find(|file| Path::new(&file.path) == path_in_repo);
+		let Some(changed_file) = changed_file else {
+			return Ok(None);
+		};
+		if &changed_file.file_id == synced_file_id || changed_file.status == ChangedFileStatus::Removed {
+			return Ok(None);
+		}
+		let args = GetFileArgs {
+			module: module_id,
+			path: &path_in_repo,
+			version: &latest_version,
+		};
+		let content = self.client.get_file(args).await?;
+		Ok(Some(RemoteChange {
+			file_id: changed_file.file_id,
+			content,
+		}))
+	}
+
+	async fn commit(self, mut entry: Entry, file_id: Option<String>) -> anyhow::Result<()> {
+		let Some(module_id) = &self.id.module else {
+			log::debug!("character has no module");
+			return Ok(());
+		};
+		let path_in_repo = self.path_in_repo();
+		let args = PutFileArgs {
+			module: module_id,
+			path: &path_in_repo,
+			message: &self.message,
+			content: &self.content,
+			file_id: file_id.as_ref().map(String::as_str),
+		};
+		let response = self.client.put_file(args).await?;
+
+		let module_version = response.version;
+		// put the updated content in the database for the persistent character segment
+		entry.kdl = self.content;
+		entry.metadata = self.metadata;
+		entry.dirty = false;
+		entry.base_kdl = None;
+		// with the updated module version
+		entry.version = Some(module_version.clone());
+		// and updated storage sha/file id (because it changes every time a change is saved on a file)
+		entry.file_id = Some(response.file_id);
+		// Commit the module version and entry changes to database
+		self.database
+			.mutate(move |transaction| {
+				use crate::database::Module;
+				use database::{ObjectStoreExt, TransactionExt};
+				Box::pin(async move {
+					let module_store = transaction.object_store_of::<Module>()?;
+					let entry_store = transaction.object_store_of::<Entry>()?;
+
+					let module_req = module_store.get_record::<Module>(entry.module.clone());
+					let mut module = module_req.await?.unwrap();
+					module.version = module_version;
+					module_store.put_record(&module).await?;
+
+					// A newer draft may have been written while the file was being saved,
+					// in which case it is kept (and remains dirty) until the next save.
+					if let Some(current) = entry_store.get_record::<Entry>(entry.id.clone()).await? {
+						if current.dirty && current.kdl != entry.kdl {
+							entry.base_kdl = Some(std::mem::replace(&mut entry.kdl, current.kdl));
+							entry.metadata = current.metadata;
+							entry.dirty = true;
+						}
+					}
+					entry_store.put_record(&entry).await?;
+
+					Ok(())
+				})
+			})
+			.await?;
+
+		Ok(())
+	}
+}
+
+#[function_component]
+pub fn AutosaveSelect() -> Html {
+	let delay = use_state_eq(autosave_delay);
+	let onchange = Callback::from({
+		let delay = delay.clone();
+		move |evt: web_sys::Event| {
+			let Some(value) = evt.select_value() else {
+				return;
+			};
+			let value = value.parse::<u32>().ok();
+			set_autosave_delay(value);
+			delay.set(value);
+		}
+	});
+	html! {
+		<select class="form-select form-select-sm w-auto mx-2" title="Autosave" {onchange}>
+			{AUTOSAVE_DELAY_OPTIONS.iter().map(|option| {
+				let (value, text) = match option {
+					None => (String::new(), "Autosave Off".to_owned()),
+					Some(seconds) if *seconds < 60 => (seconds.to_string(), format!("Autosave after {seconds}s")),
+					Some(seconds) => (seconds.to_string(), format!("Autosave after {}m", seconds / 60)),
+				};
+				html! {
+					<option {value} selected={*delay == *option}>{text}</option>
+				}
+			}).collect::<Vec<_>>()}
+		</select>
+	}
+}
+
+#[function_component]
+pub fn SaveConflictModal() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let database = use_context::<Database>().unwrap();
+	let system_depot = use_context::<system::Depot>().unwrap();
+	let task_dispatch = use_context::<crate::task::Dispatch>().unwrap();
+	let close_modal = context_menu::use_close_fn::<()>();
+	let conflict_count = state.save_conflict().map(|conflict| conflict.merge.conflicts().len());
+	let choices = use_state_eq(|| vec![MergeSide::Local; conflict_count.unwrap_or_default()]);
+	let Some(conflict) = state.save_conflict() else {
+		return html!("There are no conflicts to resolve.");
+	};
+
+	let set_choice = Callback::from({
+		let choices = choices.clone();
+		move |(idx, side): (usize, MergeSide)| {
+			let mut next = (*choices).clone();
+			if idx >= next.len() {
+				next.resize(idx + 1, MergeSide::Local);
+			}
+			next[idx] = side;
+			choices.set(next);
+		}
+	});
+	let resolve = Callback::from({
+		let state = state.clone();
+		let conflict = conflict.clone();
+		let choices = choices.clone();
+		move |_: MouseEvent| {
+			let Some(module_id) = &conflict.id.module else {
+				return;
+			};
+			let auth_status = yewdux::dispatch::get::<crate::auth::Status>();
+			let Some(client) = crate::storage::get_for(module_id, &*auth_status, &database) else {
+				log::debug!("no storage client");
+				return;
+			};
+			let save = SaveCharacter {
+				database: database.clone(),
+				system_depot: system_depot.clone(),
+				client,
+				id: conflict.id.clone(),
+				content: conflict.merge.resolve_document(&choices),
+				metadata: Default::default(),
+				message: conflict.message.clone(),
+				new_entry: None,
+			};
+			let remote_file_id = conflict.remote_file_id.clone();
+			let state = state.clone();
+			task_dispatch.spawn("Resolve Save Conflict", None, async move {
+				save.resolve(remote_file_id).await?;
+				state.apply_save_outcome(SaveOutcome::Merged);
+				Ok(()) as anyhow::Result<()>
+			});
+			close_modal.emit(());
+		}
+	});
+
+	let as_text = |nodes: &Vec<kdl::KdlNode>| match nodes.is_empty() {
+		true => "(removed)".to_owned(),
+		false => nodes
+			.iter()
+			.map(|node| node.to_string().trim().to_owned())
+			.collect::<Vec<_>>()
+			.join("\n"),
+	};
+	let sections = conflict
+		.merge
+		.conflicts()
+		.iter()
+		.enumerate()
+		.map(|(idx, merge_conflict)| {
+			let mut path = merge_conflict.path.clone();
+			path.push(merge_conflict.name.clone());
+			let choice = choices.get(idx).copied().unwrap_or_default();
+			let option = |side: MergeSide, title: &'static str, nodes: &Vec<kdl::KdlNode>| {
+				let id = format!("conflict-{idx}-{side:?}");
+				html! {
+					<div class="col">
+						<div class="form-check">
+							<input
+								class="form-check-input" type="radio" id={id.clone()} name={format!("conflict-{idx}")}
+								checked={choice == side}
+								onchange={set_choice.reform(move |_: web_sys::Event| (idx, side))}
+							/>
+							<label class="form-check-label" for={id}>{title}</label>
+						</div>
+						<pre class="border rounded p-1 m-0" style="font-size: 0.75rem; white-space: pre-wrap;">{as_text(nodes)}</pre>
+					</div>
+				}
+			};
+			html! {
+				<div class="mb-3">
+					<h6>{path.join(" > ")}</h6>
+					<div class="row">
+						{option(MergeSide::Local, "Keep Your Changes", &merge_conflict.local)}
+						{option(MergeSide::Remote, "Keep Their Changes", &merge_conflict.remote)}
+					</div>
+				</div>
+			}
+		});
+
+	html! {
+		<div>
+			<div class="text-block mb-3">
+				{"This character was saved somewhere else since you last synced it. \
+				Changes which didn't overlap have been merged, but the following were changed in both places. \
+				Pick which version of each to keep, and the result will be saved."}
+			</div>
+			{sections.collect::<Vec<_>>()}
+			<button type="button" class="btn btn-success" onclick={resolve}>{"Save Resolution"}</button>
+		</div>
+	}
+}
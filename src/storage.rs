@@ -1,22 +1,44 @@
+use crate::{database::Database, system::core::ModuleId};
+
 pub mod autosync;
+mod backend;
+pub use backend::*;
 
 pub static USER_HOMEBREW_REPO_NAME: &str = "integro-homebrew";
 pub static MODULE_TOPIC: &str = "integro-tabletop-module";
 static APP_USER_AGENT: &str = concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
 
-pub fn get(status: &crate::auth::Status) -> Option<github::GithubClient> {
+/// Returns the storage for the logged in user,
+/// or the local-only storage if the user is not logged in to any remote storage.
+pub fn get(status: &crate::auth::Status, database: &Database) -> Storage {
+	match get_remote(status) {
+		Some(storage) => storage,
+		None => get_local(database),
+	}
+}
+
+/// Returns the storage which contains a specific module (if that storage is available).
+pub fn get_for(module: &ModuleId, status: &crate::auth::Status, database: &Database) -> Option<Storage> {
+	match module {
+		ModuleId::Local { .. } => Some(get_local(database)),
+		ModuleId::Github { .. } => get_remote(status),
+	}
+}
+
+pub fn get_local(database: &Database) -> Storage {
+	Storage::new(LocalBackend::new(database.clone()))
+}
+
+pub fn get_remote(status: &crate::auth::Status) -> Option<Storage> {
 	use crate::auth::*;
 	use std::str::FromStr;
-	let Status::Successful {
-		oauth_id,
-		token,
-	} = status else {
+	let Status::Successful { oauth_id, token } = status else {
 		return None;
 	};
 	let Ok(oauth) = OAuthProvider::from_str(oauth_id) else {
 		return None;
 	};
 	match oauth {
-		OAuthProvider::Github => github::GithubClient::new(token, APP_USER_AGENT).ok(),
+		OAuthProvider::Github => github::GithubClient::new(token, APP_USER_AGENT).ok().map(Storage::new),
 	}
 }
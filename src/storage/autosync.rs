@@ -1,6 +1,6 @@
 use crate::{
 	database::{Database, Module},
-	storage::USER_HOMEBREW_REPO_NAME,
+	storage::ChangedFileStatus,
 	system::{
 		self,
 		core::{ModuleId, SourceId},
@@ -166,7 +166,7 @@ enum StorageSyncError {
 	#[error(transparent)]
 	Database(#[from] database::Error),
 	#[error(transparent)]
-	StorageError(#[from] github::Error),
+	StorageError(#[from] crate::storage::Error),
 }
 
 #[function_component]
@@ -239,10 +239,7 @@ async fn process_request(
 	status: &Status,
 ) -> Result<(), StorageSyncError> {
 	let auth_status = yewdux::dispatch::get::<crate::auth::Status>();
-	let Some(storage) = crate::storage::get(&*auth_status) else {
-		log::error!(target: "autosync", "No storage available, cannot progess request {req:?}");
-		return Ok(());
-	};
+	let storage = crate::storage::get(&*auth_status, database);
 
 	let mut scan_storage_for_modules = false;
 	let mut modules = BTreeMap::new();
@@ -296,27 +293,29 @@ async fn process_request(
 	}
 
 	status.push_stage("Checking authentiation", None);
-	let (viewer, repo_owners) = {
+	let (homebrew_id, repo_owners) = {
 		let mut query_module_owners = QueryModuleOwners {
 			status: status.clone(),
 			client: storage.clone(),
-			user: None,
-			found_homebrew: false,
+			homebrew: None,
 		};
 		let owners = query_module_owners.run().await?;
 
-		// If the homebrew repo was not found when querying who the user is,
+		// If the homebrew module was not found when querying who the user is,
 		// then we need to generate one, since this is where their user data is stored
 		// and is the default location for any creations.
-		if !query_module_owners.found_homebrew {
-			let generate_homebrew = GenerateHomebrew {
-				status: status.clone(),
-				client: storage.clone(),
-			};
-			generate_homebrew.run().await?;
-		}
+		let homebrew_id = match query_module_owners.homebrew.take() {
+			Some(homebrew_id) => homebrew_id,
+			None => {
+				let generate_homebrew = GenerateHomebrew {
+					status: status.clone(),
+					client: storage.clone(),
+				};
+				generate_homebrew.run().await?
+			}
+		};
 
-		(query_module_owners.user.take(), owners)
+		(homebrew_id, owners)
 	};
 	status.pop_stage();
 
@@ -330,20 +329,35 @@ async fn process_request(
 		};
 		let repositories = scan_for_modules.run().await?;
 		for repository in repositories {
-			remote_repositories.insert((&repository).into(), repository);
+			remote_repositories.insert(repository.id.clone(), repository);
 		}
 		status.pop_stage();
 	} else {
 		status.push_stage("Checking for module updates", None);
-		let module_names = modules_to_fetch.iter().map(ModuleId::to_string).collect();
-		let mut find_modules = FindModules {
-			status: status.clone(),
-			client: storage.clone(),
-			names: module_names,
-		};
-		let repositories = find_modules.run().await?;
-		for repository in repositories {
-			remote_repositories.insert((&repository).into(), repository);
+		// Each module can only be found in the storage it lives in (local or remote).
+		let (local_ids, remote_ids) = modules_to_fetch
+			.iter()
+			.cloned()
+			.partition::<Vec<_>, _>(|id| matches!(id, ModuleId::Local { .. }));
+		let local_storage = Some(crate::storage::get_local(database));
+		let remote_storage = crate::storage::get_remote(&*auth_status);
+		for (client, ids) in [(local_storage, local_ids), (remote_storage, remote_ids)] {
+			if ids.is_empty() {
+				continue;
+			}
+			let Some(client) = client else {
+				log::warn!(target: "autosync", "Cannot check for updates to {ids:?}, their storage is not available");
+				continue;
+			};
+			let mut find_modules = FindModules {
+				status: status.clone(),
+				client,
+				ids,
+			};
+			let repositories = find_modules.run().await?;
+			for repository in repositories {
+				remote_repositories.insert(repository.id.clone(), repository);
+			}
 		}
 		status.pop_stage();
 	}
@@ -382,16 +396,11 @@ async fn process_request(
 		status.pop_stage();
 	}
 
-	if let Some(viewer) = &viewer {
-		let homebrew_id = ModuleId::Github {
-			user_org: viewer.clone(),
-			repository: USER_HOMEBREW_REPO_NAME.to_owned(),
-		};
-		modules_to_uninstall.remove(&homebrew_id);
-		if let Some(module) = modules.get(&homebrew_id) {
-			if !module.installed {
-				modules_to_install.insert(homebrew_id);
-			}
+	// The homebrew module is always installed.
+	modules_to_uninstall.remove(&homebrew_id);
+	if let Some(module) = modules.get(&homebrew_id) {
+		if !module.installed {
+			modules_to_install.insert(homebrew_id);
 		}
 	}
 
@@ -431,6 +440,7 @@ async fn process_request(
 	if !modules_to_install.is_empty() {
 		struct ModuleUpdate {
 			module_id: ModuleId,
+			client: crate::storage::Storage,
 			files: Vec<ModuleFileUpdate>,
 		}
 
@@ -441,11 +451,14 @@ async fn process_request(
 		for module_id in modules_to_install {
 			status.increment_progress();
 
-			let ModuleId::Github { user_org, repository } = &module_id else {
-				// ERROR: Invalid module id to scan
+			let Some(module) = modules.get_mut(&module_id) else {
 				continue;
 			};
-			let Some(module) = modules.get_mut(&module_id) else {
+
+			// Modules are read from the storage they live in, which may not be the active storage
+			// (e.g. local modules while logged in to a remote storage).
+			let Some(client) = crate::storage::get_for(&module_id, &*auth_status, database) else {
+				log::warn!(target: "autosync", "Skipping {module_id:?}, its storage is not available");
 				continue;
 			};
 
@@ -455,9 +468,8 @@ async fn process_request(
 
 				let scan = ScanRepository {
 					status: status.clone(),
-					client: storage.clone(),
-					owner: user_org.clone(),
-					name: repository.clone(),
+					client: client.clone(),
+					module: module_id.clone(),
 					tree_id: None,
 				};
 				let files = scan.run().await?;
@@ -465,26 +477,33 @@ async fn process_request(
 					.into_iter()
 					.map(|file| ModuleFileUpdate {
 						file,
-						status: github::ChangedFileStatus::Added,
+						status: ChangedFileStatus::Added,
 					})
 					.collect();
 
-				module_updates.push(ModuleUpdate { module_id, files });
+				module_updates.push(ModuleUpdate {
+					module_id,
+					client,
+					files,
+				});
 			}
 			// For module updates, ask repo for changed files since current version.
 			else if module.version != module.remote_version {
 				let scan = FindFileUpdates {
 					status: status.clone(),
-					client: storage.clone(),
-					owner: user_org.clone(),
-					name: repository.clone(),
+					client: client.clone(),
+					module: module_id.clone(),
 					old_version: module.version.clone(),
 					new_version: module.remote_version.clone(),
 				};
 				module.version = module.remote_version.clone();
 
 				let files = scan.run().await?;
-				module_updates.push(ModuleUpdate { module_id, files });
+				module_updates.push(ModuleUpdate {
+					module_id,
+					client,
+					files,
+				});
 			}
 		}
 		status.pop_stage(); // Gathering Updates
@@ -492,7 +511,12 @@ async fn process_request(
 		// For all files to fetch, across all modules, fetch each file and update progress.
 		// Iterate per module so updates can be committed to database as each is fetched.
 		status.push_stage("Downloading Modules", Some(module_updates.len()));
-		for ModuleUpdate { module_id, files } in module_updates {
+		for ModuleUpdate {
+			module_id,
+			client,
+			files,
+		} in module_updates
+		{
 			use crate::database::Entry;
 			use database::{ObjectStoreExt, TransactionExt};
 
@@ -504,7 +528,7 @@ async fn process_request(
 
 			let download = DownloadFileUpdates {
 				status: status.clone(),
-				client: storage.clone(),
+				client,
 				system_depot: system_depot.clone(),
 				module_id: module.id.clone(),
 				version: module.remote_version.clone(),
@@ -521,6 +545,12 @@ async fn process_request(
 
 			let entry_store = transaction.object_store_of::<Entry>()?;
 			for record in entries {
+				// Local drafts which haven't been saved to storage yet take precedence over downloaded updates.
+				let existing = entry_store.get_record::<Entry>(record.id.clone()).await?;
+				if existing.map(|entry| entry.dirty).unwrap_or_default() {
+					log::debug!(target: "autosync", "Skipping update to {:?}, which has a local draft", record.id);
+					continue;
+				}
 				entry_store.put_record(&record).await?;
 			}
 			// Delete entries by module and file-id
@@ -532,6 +562,11 @@ async fn process_request(
 					let Some(file_id) = &entry.file_id else {
 						continue;
 					};
+					// Local drafts are kept, even if their file was removed from storage, so unsaved changes aren't lost.
+					if entry.dirty {
+						log::debug!(target: "autosync", "Skipping removal of {:?}, which has a local draft", entry.id);
+						continue;
+					}
 					if removed_file_ids.contains(file_id) {
 						entry_ids_to_remove.push(entry.id.clone());
 					}
@@ -560,12 +595,12 @@ pub struct ModuleFile {
 	pub system: String,
 	// The path within the module of the file (including game system root).
 	pub path_in_repo: String,
-	// The file-id sha in the github repo.
+	// The id of the file's content in storage (e.g. the file sha in a github repo).
 	pub file_id: String,
 }
 pub struct ModuleFileUpdate {
 	pub file: ModuleFile,
-	pub status: github::ChangedFileStatus,
+	pub status: ChangedFileStatus,
 }
 
 impl ModuleFile {
@@ -1,12 +1,14 @@
 use crate::{
-	storage::autosync::{ModuleFile, ModuleFileUpdate},
+	storage::{
+		autosync::{ModuleFile, ModuleFileUpdate},
+		ChangedFileStatus, Error, GetFileArgs, Storage,
+	},
 	system::{
 		self,
 		core::{ModuleId, SourceId},
 	},
 };
 use anyhow::Context;
-use github::{repos, ChangedFileStatus, Error, GithubClient};
 use std::{
 	collections::HashSet,
 	path::{Path, PathBuf},
@@ -14,7 +16,7 @@ use std::{
 
 pub struct DownloadFileUpdates {
 	pub status: super::Status,
-	pub client: GithubClient,
+	pub client: Storage,
 	pub system_depot: system::Depot,
 
 	pub module_id: ModuleId,
@@ -23,12 +25,10 @@ pub struct DownloadFileUpdates {
 }
 impl DownloadFileUpdates {
 	pub async fn run(mut self) -> Result<(Vec<crate::database::Entry>, HashSet<String>), Error> {
-		let ModuleId::Github { user_org, repository } = &self.module_id else {
-			// ERROR: Invalid module id
-			return Ok((Vec::new(), HashSet::new()));
-		};
-		self.status
-			.push_stage(format!("Downloading {user_org}/{repository}"), Some(self.files.len()));
+		self.status.push_stage(
+			format!("Downloading {}", self.module_id.to_string()),
+			Some(self.files.len()),
+		);
 		let mut entries = Vec::with_capacity(self.files.len());
 		let mut removed_file_ids = HashSet::new();
 		let files = self.files.drain(..).collect::<Vec<_>>();
@@ -44,9 +44,8 @@ impl DownloadFileUpdates {
 
 			self.status.increment_progress();
 
-			let args = repos::contents::get::Args {
-				owner: user_org.as_str(),
-				repo: repository.as_str(),
+			let args = GetFileArgs {
+				module: &self.module_id,
 				path: Path::new(path_in_repo.as_str()),
 				version: self.version.as_str(),
 			};
@@ -56,10 +55,9 @@ impl DownloadFileUpdates {
 				| ChangedFileStatus::Renamed
 				| ChangedFileStatus::Copied
 				| ChangedFileStatus::Changed => {
-					let content = self.client.get_file_content(args).await?;
+					let content = self.client.get_file(args).await?;
 					let parsed_entries = self.parse_content(system, path_in_repo, file_id, content);
-					let parsed_entries =
-						parsed_entries.map_err(|err| Error::InvalidResponse(format!("{err:?}").into()))?;
+					let parsed_entries = parsed_entries.map_err(|err| Error::InvalidResponse(format!("{err:?}")))?;
 					entries.extend(parsed_entries);
 				}
 				ChangedFileStatus::Removed => {
@@ -83,7 +81,9 @@ impl DownloadFileUpdates {
 			return Ok(Vec::new());
 		};
 
-		let document = content.parse::<kdl::KdlDocument>().with_context(|| format!("Failed to parse content: {content:?}"))?;
+		let document = content
+			.parse::<kdl::KdlDocument>()
+			.with_context(|| format!("Failed to parse content: {content:?}"))?;
 		let path_in_system = match file_path.strip_prefix(&format!("{system}/")) {
 			Some(systemless) => PathBuf::from(systemless),
 			None => PathBuf::from(&file_path),
@@ -108,6 +108,8 @@ impl DownloadFileUpdates {
 				metadata,
 				kdl: node.to_string(),
 				file_id: Some(file_id.clone()),
+				dirty: false,
+				base_kdl: None,
 			};
 			entries.push(record);
 		}
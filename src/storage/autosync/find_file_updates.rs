@@ -1,26 +1,29 @@
-use crate::storage::autosync::{ModuleFile, ModuleFileUpdate};
-use github::{repos, GithubClient};
+use crate::{
+	storage::{
+		autosync::{ModuleFile, ModuleFileUpdate},
+		CompareArgs, Error, Storage,
+	},
+	system::core::ModuleId,
+};
 
 pub struct FindFileUpdates {
 	pub status: super::Status,
-	pub client: GithubClient,
+	pub client: Storage,
 
-	pub owner: String,
-	pub name: String,
+	pub module: ModuleId,
 	pub old_version: String,
 	pub new_version: String,
 }
 impl FindFileUpdates {
-	pub async fn run(self) -> Result<Vec<ModuleFileUpdate>, github::Error> {
+	pub async fn run(self) -> Result<Vec<ModuleFileUpdate>, Error> {
 		// Getting the files changed for this upgrade
-		let args = repos::compare::Args {
-			owner: self.owner.as_str(),
-			repo: self.name.as_str(),
-			commit_start: self.old_version.as_str(),
-			commit_end: self.new_version.as_str(),
+		let args = CompareArgs {
+			module: &self.module,
+			old_version: self.old_version.as_str(),
+			new_version: self.new_version.as_str(),
 		};
 
-		let changed_file_paths = self.client.get_files_changed(args).await?;
+		let changed_file_paths = self.client.list_changed_files(args).await?;
 		let mut files = Vec::with_capacity(changed_file_paths.len());
 		for changed_file in changed_file_paths {
 			let path_in_repo = std::path::Path::new(&changed_file.path);
@@ -1,24 +1,22 @@
-use crate::storage::MODULE_TOPIC;
-use github::{GithubClient, Query, RepositoryMetadata, SearchRepositoriesParams};
+use crate::{
+	storage::{Error, ModuleMetadata, ModuleQuery, Storage},
+	system::core::ModuleId,
+};
 
 pub struct FindModules {
 	pub status: super::Status,
-	pub client: GithubClient,
-	pub names: Vec<String>,
+	pub client: Storage,
+	pub ids: Vec<ModuleId>,
 }
 impl FindModules {
-	pub async fn run(&mut self) -> Result<Vec<RepositoryMetadata>, github::Error> {
+	pub async fn run(&mut self) -> Result<Vec<ModuleMetadata>, Error> {
 		self.status.push_stage("Fetching info on specific modules", None);
 
-		let iter_reponames = self.names.iter().cloned();
-		let query = iter_reponames.fold(Query::default(), |query, name| query.keyed("repo", name));
-		let query = query.keyed("topic", MODULE_TOPIC);
-
-		let search_params = SearchRepositoriesParams { query, page_size: 25 };
-		let (_, repositories) = self.client.search_repositories(search_params).await;
+		let query = ModuleQuery::Modules(self.ids.clone());
+		let search = self.client.search_modules(query).await?;
 
 		self.status.pop_stage();
 
-		Ok(repositories)
+		Ok(search.modules)
 	}
 }
@@ -1,28 +1,22 @@
-use github::{GithubClient, Query};
+use crate::{
+	storage::{Error, ModuleQuery, Storage},
+	system::core::ModuleId,
+};
 
-// Query github for the logged in user and all organizations they have access to.
+// Query storage for the logged in user and all organizations they have access to.
 pub struct QueryModuleOwners {
 	pub status: super::Status,
-	pub client: GithubClient,
-	pub user: Option<String>,
-	pub found_homebrew: bool,
+	pub client: Storage,
+	pub homebrew: Option<ModuleId>,
 }
 impl QueryModuleOwners {
-	pub async fn run(&mut self) -> Result<Vec<String>, github::Error> {
+	pub async fn run(&mut self) -> Result<Vec<String>, Error> {
 		self.status.push_stage("Finding module owners", None);
-		let search_params = github::SearchRepositoriesParams {
-			query: Query::default()
-				.keyed("user", "@me")
-				.value(crate::storage::USER_HOMEBREW_REPO_NAME)
-				.keyed("in", "name"),
-			page_size: 1,
-		};
-		let (user, repositories) = self.client.search_repositories(search_params).await;
-		self.user = Some(user.clone());
-		self.found_homebrew = !repositories.is_empty();
+		let search = self.client.search_modules(ModuleQuery::Homebrew).await?;
+		self.homebrew = search.modules.into_iter().next().map(|metadata| metadata.id);
 
-		let mut owners = self.client.find_orgs().await;
-		owners.push(user);
+		let mut owners = self.client.find_organizations().await?;
+		owners.push(search.viewer);
 
 		self.status.pop_stage();
 		Ok(owners)
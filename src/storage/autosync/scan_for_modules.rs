@@ -1,21 +1,14 @@
-use crate::storage::MODULE_TOPIC;
-use github::{GithubClient, Query, RepositoryMetadata, SearchRepositoriesParams};
+use crate::storage::{Error, ModuleMetadata, ModuleQuery, Storage};
 
 pub struct ScanForModules {
 	pub status: super::Status,
-	pub client: GithubClient,
+	pub client: Storage,
 	pub owners: Vec<String>,
 }
 impl ScanForModules {
-	pub async fn run(self) -> Result<Vec<RepositoryMetadata>, github::Error> {
-		// Query github for all modules with the topic MODULE_TOPIC which are owned by the provided owners (user or organization).
-
-		let iter_owners = self.owners.into_iter();
-		let query = iter_owners.fold(Query::default(), |query, owner| query.keyed("user", owner));
-		let query = query.keyed("topic", MODULE_TOPIC);
-
-		let search_params = SearchRepositoriesParams { query, page_size: 25 };
-		let (_, repositories) = self.client.search_repositories(search_params).await;
-		Ok(repositories)
+	pub async fn run(self) -> Result<Vec<ModuleMetadata>, Error> {
+		// Query storage for all modules which are owned by the provided owners (user or organization).
+		let search = self.client.search_modules(ModuleQuery::Owners(self.owners)).await?;
+		Ok(search.modules)
 	}
 }
@@ -1,32 +1,30 @@
-use crate::storage::{autosync::ModuleFile, MODULE_TOPIC};
-use github::{repos, Error, GithubClient, Query, SearchRepositoriesParams};
+use crate::{
+	storage::{autosync::ModuleFile, Error, ModuleQuery, Storage, TreeArgs},
+	system::core::ModuleId,
+};
 use std::{collections::VecDeque, path::PathBuf};
 
 pub struct ScanRepository {
 	pub status: super::Status,
-	pub client: GithubClient,
+	pub client: Storage,
 
-	pub owner: String,
-	pub name: String,
+	pub module: ModuleId,
 	pub tree_id: Option<String>,
 }
 impl ScanRepository {
 	pub async fn run(self) -> Result<Vec<ModuleFile>, Error> {
-		let repo_id = format!("{}/{}", self.owner, self.name);
 		let mut tree_count = 1;
-		self.status.push_stage(format!("Scanning {repo_id}"), Some(tree_count));
+		self.status
+			.push_stage(format!("Scanning {}", self.module.to_string()), Some(tree_count));
 
 		let tree_id = match self.tree_id {
 			Some(id) => id,
 			None => {
-				let search_params = SearchRepositoriesParams {
-					query: Query::default().keyed("repo", &repo_id).keyed("topic", MODULE_TOPIC),
-					page_size: 1,
-				};
-				let (_, repositories) = self.client.search_repositories(search_params).await;
+				let query = ModuleQuery::Modules(vec![self.module.clone()]);
+				let search = self.client.search_modules(query).await?;
 
-				let Some(metadata) = repositories.into_iter().next() else {
-					return Err(Error::InvalidResponse(format!("Empty repository metadata").into()));
+				let Some(metadata) = search.modules.into_iter().next() else {
+					return Err(Error::InvalidResponse(format!("Empty module metadata")));
 				};
 				metadata.tree_id
 			}
@@ -35,13 +33,12 @@ impl ScanRepository {
 		let mut tree_ids = VecDeque::from([(PathBuf::new(), tree_id)]);
 		let mut files = Vec::new();
 		while let Some((tree_path, tree_id)) = tree_ids.pop_front() {
-			let args = repos::tree::Args {
-				owner: self.owner.as_str(),
-				repo: self.name.as_str(),
+			let args = TreeArgs {
+				module: &self.module,
 				tree_id: tree_id.as_str(),
 			};
 			self.status.increment_progress();
-			for entry in self.client.get_tree(args).await? {
+			for entry in self.client.list_tree(args).await? {
 				let full_path = tree_path.join(&entry.path);
 				// if the entry is a directory, put it in the queue to be scanned
 				if entry.is_tree {
@@ -0,0 +1,175 @@
+use crate::system::core::ModuleId;
+use futures_util::future::LocalBoxFuture;
+use std::{path::Path, rc::Rc};
+
+mod github;
+pub mod local;
+pub use local::LocalBackend;
+
+/// A storage location for modules (and their content files), such as a remote github account or
+/// a local-only store in the browser. All of the reading and writing of module files is done through this api,
+/// so the rest of the application does not need to know where the content actually lives.
+pub trait StorageBackend {
+	/// Search storage for modules which match the query.
+	fn search_modules(&self, query: ModuleQuery) -> LocalBoxFuture<'_, Result<ModuleSearch, Error>>;
+
+	/// Returns the names of any owners (other than the viewer) which the viewer has access to
+	/// (e.g. organizations the user belongs to).
+	fn find_organizations(&self) -> LocalBoxFuture<'_, Result<Vec<String>, Error>>;
+
+	/// Creates a new module, owned by the viewer, with the provided name.
+	fn create_module<'a>(&'a self, name: &'a str) -> LocalBoxFuture<'a, Result<ModuleId, Error>>;
+
+	/// Returns the latest version of a module.
+	fn get_module_version<'a>(&'a self, module: &'a ModuleId) -> LocalBoxFuture<'a, Result<String, Error>>;
+
+	/// Lists the entries (files and subtrees) in a specific tree of a module.
+	fn list_tree<'a>(&'a self, args: TreeArgs<'a>) -> LocalBoxFuture<'a, Result<Vec<TreeEntry>, Error>>;
+
+	/// Lists all of the files which changed in a module between two versions.
+	fn list_changed_files<'a>(&'a self, args: CompareArgs<'a>) -> LocalBoxFuture<'a, Result<Vec<ChangedFile>, Error>>;
+
+	/// Returns the text content of a file in a module.
+	fn get_file<'a>(&'a self, args: GetFileArgs<'a>) -> LocalBoxFuture<'a, Result<String, Error>>;
+
+	/// Creates a file (if `file_id` is None) or updates an existing file.
+	fn put_file<'a>(&'a self, args: PutFileArgs<'a>) -> LocalBoxFuture<'a, Result<FileVersion, Error>>;
+
+	/// Removes a file from a module, returning the new version of the module.
+	fn remove_file<'a>(&'a self, args: RemoveFileArgs<'a>) -> LocalBoxFuture<'a, Result<String, Error>>;
+}
+
+/// A shareable handle to some `StorageBackend`.
+#[derive(Clone)]
+pub struct Storage(Rc<dyn StorageBackend + 'static>);
+impl Storage {
+	pub fn new(backend: impl StorageBackend + 'static) -> Self {
+		Self(Rc::new(backend))
+	}
+}
+impl std::ops::Deref for Storage {
+	type Target = dyn StorageBackend + 'static;
+
+	fn deref(&self) -> &Self::Target {
+		&*self.0
+	}
+}
+
+#[derive(thiserror::Error, Debug, Clone)]
+pub enum Error {
+	#[error(transparent)]
+	Github(#[from] ::github::Error),
+	#[error(transparent)]
+	Database(#[from] database::Error),
+	#[error("Module {0:?} is not supported by this storage.")]
+	UnsupportedModule(ModuleId),
+	#[error("Module {0:?} does not exist in storage.")]
+	MissingModule(ModuleId),
+	#[error("File {1:?} does not exist in module {0:?}.")]
+	MissingFile(ModuleId, String),
+	#[error("File {1:?} in module {0:?} has been changed by someone else.")]
+	FileConflict(ModuleId, String),
+	#[error("Invalid response from storage: {0}")]
+	InvalidResponse(String),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ModuleQuery {
+	/// The homebrew module of the viewer (the user whose content is stored in this storage).
+	Homebrew,
+	/// All modules owned by any of the provided owners.
+	Owners(Vec<String>),
+	/// The specific modules requested.
+	Modules(Vec<ModuleId>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModuleSearch {
+	/// The name of the user who is viewing the storage.
+	pub viewer: String,
+	pub modules: Vec<ModuleMetadata>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ModuleMetadata {
+	pub id: ModuleId,
+	/// The latest version of the module.
+	pub version: String,
+	/// The id of the root tree of the module.
+	pub tree_id: String,
+	/// The names of the top-level trees in the module (the game systems it has content for).
+	pub root_trees: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TreeArgs<'a> {
+	pub module: &'a ModuleId,
+	pub tree_id: &'a str,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeEntry {
+	/// The path of the entry relative to the tree it is in.
+	pub path: String,
+	/// The tree id (if the entry is a tree) or the file id (if the entry is a file).
+	pub id: String,
+	pub is_tree: bool,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct CompareArgs<'a> {
+	pub module: &'a ModuleId,
+	pub old_version: &'a str,
+	pub new_version: &'a str,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChangedFile {
+	pub path: String,
+	pub file_id: String,
+	pub status: ChangedFileStatus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ChangedFileStatus {
+	Added,
+	Modified,
+	Removed,
+	Renamed,
+	Copied,
+	Changed,
+	Unchanged,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct GetFileArgs<'a> {
+	pub module: &'a ModuleId,
+	pub path: &'a Path,
+	pub version: &'a str,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct PutFileArgs<'a> {
+	pub module: &'a ModuleId,
+	pub path: &'a Path,
+	pub message: &'a str,
+	pub content: &'a str,
+	/// The id of the file being replaced, or None if the file is new.
+	pub file_id: Option<&'a str>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileVersion {
+	/// The version of the module after the change.
+	pub version: String,
+	/// The id of the changed file.
+	pub file_id: String,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct RemoveFileArgs<'a> {
+	pub module: &'a ModuleId,
+	pub path: &'a Path,
+	pub message: &'a str,
+	pub file_id: &'a str,
+}
@@ -0,0 +1,212 @@
+use super::{
+	ChangedFile, ChangedFileStatus, CompareArgs, Error, FileVersion, GetFileArgs, ModuleMetadata, ModuleQuery,
+	ModuleSearch, PutFileArgs, RemoveFileArgs, StorageBackend, TreeArgs, TreeEntry,
+};
+use crate::{
+	storage::{MODULE_TOPIC, USER_HOMEBREW_REPO_NAME},
+	system::core::ModuleId,
+};
+use ::github::{repos, GithubClient, Query, RepositoryMetadata, SearchRepositoriesParams};
+use futures_util::future::LocalBoxFuture;
+
+// Github modules are repositories, identified by their owner (user or organization) and repository name.
+fn repository(module: &ModuleId) -> Result<(&str, &str), Error> {
+	match module {
+		ModuleId::Github { user_org, repository } => Ok((user_org.as_str(), repository.as_str())),
+		_ => Err(Error::UnsupportedModule(module.clone())),
+	}
+}
+
+impl From<&RepositoryMetadata> for ModuleMetadata {
+	fn from(value: &RepositoryMetadata) -> Self {
+		Self {
+			id: ModuleId::from(value),
+			version: value.version.clone(),
+			tree_id: value.tree_id.clone(),
+			root_trees: value.root_trees.iter().cloned().collect(),
+		}
+	}
+}
+
+impl From<::github::ChangedFileStatus> for ChangedFileStatus {
+	fn from(value: ::github::ChangedFileStatus) -> Self {
+		use ::github::ChangedFileStatus as Github;
+		match value {
+			Github::Added => Self::Added,
+			Github::Modified => Self::Modified,
+			Github::Removed => Self::Removed,
+			Github::Renamed => Self::Renamed,
+			Github::Copied => Self::Copied,
+			Github::Changed => Self::Changed,
+			Github::Unchanged => Self::Unchanged,
+		}
+	}
+}
+
+impl StorageBackend for GithubClient {
+	fn search_modules(&self, query: ModuleQuery) -> LocalBoxFuture<'_, Result<ModuleSearch, Error>> {
+		Box::pin(async move {
+			let (query, page_size) = match query {
+				ModuleQuery::Homebrew => {
+					let query = Query::default()
+						.keyed("user", "@me")
+						.value(USER_HOMEBREW_REPO_NAME)
+						.keyed("in", "name");
+					(query, 1)
+				}
+				ModuleQuery::Owners(owners) => {
+					let iter_owners = owners.into_iter();
+					let query = iter_owners.fold(Query::default(), |query, owner| query.keyed("user", owner));
+					(query.keyed("topic", MODULE_TOPIC), 25)
+				}
+				ModuleQuery::Modules(ids) => {
+					let names = ids.iter().filter(|id| repository(id).is_ok()).map(ModuleId::to_string);
+					let names = names.collect::<Vec<_>>();
+					// Without any repositories, the query would match every public module,
+					// so skip the request entirely (the viewer is not known without making a request).
+					if names.is_empty() {
+						return Ok(ModuleSearch {
+							viewer: String::default(),
+							modules: Vec::new(),
+						});
+					}
+					let query = names
+						.into_iter()
+						.fold(Query::default(), |query, name| query.keyed("repo", name));
+					(query.keyed("topic", MODULE_TOPIC), 25)
+				}
+			};
+			let search_params = SearchRepositoriesParams { query, page_size };
+			let (viewer, repositories) = self.search_repositories(search_params).await;
+			let modules = repositories.iter().map(ModuleMetadata::from).collect();
+			Ok(ModuleSearch { viewer, modules })
+		})
+	}
+
+	fn find_organizations(&self) -> LocalBoxFuture<'_, Result<Vec<String>, Error>> {
+		Box::pin(async move { Ok(self.find_orgs().await) })
+	}
+
+	// https://docs.github.com/en/rest/repos/repos?apiVersion=2022-11-28#create-a-repository-for-the-authenticated-user
+	fn create_module<'a>(&'a self, name: &'a str) -> LocalBoxFuture<'a, Result<ModuleId, Error>> {
+		Box::pin(async move {
+			let create_repo = repos::create::Args {
+				org: None,
+				name,
+				private: true,
+			};
+			let owner = self.create_repo(create_repo).await?;
+
+			let set_topics = repos::set_topics::Args {
+				owner: owner.as_str(),
+				repo: name,
+				topics: vec![MODULE_TOPIC.to_owned()],
+			};
+			self.set_repo_topics(set_topics).await?;
+
+			Ok(ModuleId::Github {
+				user_org: owner,
+				repository: name.to_owned(),
+			})
+		})
+	}
+
+	fn get_module_version<'a>(&'a self, module: &'a ModuleId) -> LocalBoxFuture<'a, Result<String, Error>> {
+		Box::pin(async move {
+			let search = self.search_modules(ModuleQuery::Modules(vec![module.clone()])).await?;
+			let metadata = search.modules.into_iter().find(|metadata| &metadata.id == module);
+			let Some(metadata) = metadata else {
+				return Err(Error::MissingModule(module.clone()));
+			};
+			Ok(metadata.version)
+		})
+	}
+
+	fn list_tree<'a>(&'a self, args: TreeArgs<'a>) -> LocalBoxFuture<'a, Result<Vec<TreeEntry>, Error>> {
+		Box::pin(async move {
+			let (owner, repo) = repository(args.module)?;
+			let args = repos::tree::Args {
+				owner,
+				repo,
+				tree_id: args.tree_id,
+			};
+			let entries = self.get_tree(args).await?;
+			let entries = entries.into_iter().map(|entry| TreeEntry {
+				path: entry.path,
+				id: entry.id,
+				is_tree: entry.is_tree,
+			});
+			Ok(entries.collect())
+		})
+	}
+
+	fn list_changed_files<'a>(&'a self, args: CompareArgs<'a>) -> LocalBoxFuture<'a, Result<Vec<ChangedFile>, Error>> {
+		Box::pin(async move {
+			let (owner, repo) = repository(args.module)?;
+			let args = repos::compare::Args {
+				owner,
+				repo,
+				commit_start: args.old_version,
+				commit_end: args.new_version,
+			};
+			let changed_files = self.get_files_changed(args).await?;
+			let changed_files = changed_files.into_iter().map(|changed_file| ChangedFile {
+				path: changed_file.path,
+				file_id: changed_file.file_id,
+				status: changed_file.status.into(),
+			});
+			Ok(changed_files.collect())
+		})
+	}
+
+	fn get_file<'a>(&'a self, args: GetFileArgs<'a>) -> LocalBoxFuture<'a, Result<String, Error>> {
+		Box::pin(async move {
+			let (owner, repo) = repository(args.module)?;
+			let args = repos::contents::get::Args {
+				owner,
+				repo,
+				path: args.path,
+				version: args.version,
+			};
+			Ok(self.get_file_content(args).await?)
+		})
+	}
+
+	// https://docs.github.com/en/rest/repos/contents?apiVersion=2022-11-28#create-or-update-file-contents
+	fn put_file<'a>(&'a self, args: PutFileArgs<'a>) -> LocalBoxFuture<'a, Result<FileVersion, Error>> {
+		Box::pin(async move {
+			let (repo_org, repo_name) = repository(args.module)?;
+			let args = repos::contents::update::Args {
+				repo_org,
+				repo_name,
+				path_in_repo: args.path,
+				commit_message: args.message,
+				content: args.content,
+				file_id: args.file_id,
+				branch: None,
+			};
+			log::debug!(target: "storage", "executing update file request {args:?}");
+			let response = self.create_or_update_file(args).await?;
+			log::debug!(target: "storage", "finished update file request {response:?}");
+			Ok(FileVersion {
+				version: response.version,
+				file_id: response.file_id,
+			})
+		})
+	}
+
+	fn remove_file<'a>(&'a self, args: RemoveFileArgs<'a>) -> LocalBoxFuture<'a, Result<String, Error>> {
+		Box::pin(async move {
+			let (repo_org, repo_name) = repository(args.module)?;
+			let args = repos::contents::delete::Args {
+				repo_org,
+				repo_name,
+				path_in_repo: args.path,
+				commit_message: args.message,
+				file_id: args.file_id,
+				branch: None,
+			};
+			Ok(self.delete_file(args).await?)
+		})
+	}
+}
@@ -0,0 +1,544 @@
+use super::{
+	ChangedFile, ChangedFileStatus, CompareArgs, Error, FileVersion, GetFileArgs, ModuleMetadata, ModuleQuery,
+	ModuleSearch, PutFileArgs, RemoveFileArgs, StorageBackend, TreeArgs, TreeEntry,
+};
+use crate::{database::Database, storage::USER_HOMEBREW_REPO_NAME, system::core::ModuleId};
+use database::Record;
+use futures_util::future::LocalBoxFuture;
+use serde::{Deserialize, Serialize};
+use std::{
+	cell::RefCell,
+	collections::{BTreeMap, BTreeSet},
+	path::Path,
+	rc::Rc,
+};
+
+static VIEWER: &str = "local";
+static RECORD_ID: &str = "local";
+
+/// Storage which never leaves the device. Modules are saved to the client database (or only kept in memory,
+/// if there is no database) so that characters and homebrew can be used without a remote storage account.
+///
+/// Each change to a module increments its version, and the module keeps a history of which files
+/// changed in each version, so that the database can be updated in the same way as remote modules.
+#[derive(Clone, Default)]
+pub struct LocalBackend {
+	database: Option<Database>,
+	memory: Rc<RefCell<LocalModules>>,
+}
+
+/// The database record containing all of the local modules.
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+pub struct LocalModules {
+	pub id: String,
+	pub modules: BTreeMap<String, LocalModule>,
+}
+impl Record for LocalModules {
+	fn store_id() -> &'static str {
+		"local_modules"
+	}
+}
+
+#[derive(Clone, PartialEq, Default, Debug, Serialize, Deserialize)]
+pub struct LocalModule {
+	pub version: u64,
+	/// The content of each file, keyed by its path in the module.
+	pub files: BTreeMap<String, LocalFile>,
+	pub history: Vec<LocalChange>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct LocalFile {
+	pub file_id: String,
+	pub content: String,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct LocalChange {
+	pub version: u64,
+	pub path: String,
+	pub file_id: String,
+	pub removed: bool,
+	pub message: String,
+}
+
+impl LocalBackend {
+	/// Creates a backend which stores its modules in the client database.
+	pub fn new(database: Database) -> Self {
+		Self {
+			database: Some(database),
+			memory: Default::default(),
+		}
+	}
+
+	/// Creates a backend which only stores its modules in memory (which are lost when the backend is dropped).
+	pub fn in_memory() -> Self {
+		Self::default()
+	}
+
+	async fn load(&self) -> Result<LocalModules, Error> {
+		match &self.database {
+			None => Ok(self.memory.borrow().clone()),
+			Some(database) => Ok(database.get::<LocalModules>(RECORD_ID).await?.unwrap_or_default()),
+		}
+	}
+
+	/// Applies a change to the local modules, saving the result.
+	/// When backed by the database, the read and write happen in the same transaction,
+	/// so concurrent changes to different files cannot overwrite each other.
+	async fn modify<T>(&self, perform: impl FnOnce(&mut LocalModules) -> Result<T, Error>) -> Result<T, Error> {
+		match &self.database {
+			None => perform(&mut *self.memory.borrow_mut()),
+			Some(database) => {
+				use database::{ObjectStoreExt, TransactionExt};
+				let transaction = database.write_local_modules()?;
+				let store = transaction.object_store_of::<LocalModules>()?;
+				let mut modules = store
+					.get_record::<LocalModules>(RECORD_ID.to_owned())
+					.await?
+					.unwrap_or_default();
+				modules.id = RECORD_ID.to_owned();
+				let output = perform(&mut modules)?;
+				store.put_record(&modules).await?;
+				transaction.commit().await.map_err(database::Error::from)?;
+				Ok(output)
+			}
+		}
+	}
+}
+
+fn module_name(module: &ModuleId) -> Result<&str, Error> {
+	match module {
+		ModuleId::Local { name } => Ok(name.as_str()),
+		_ => Err(Error::UnsupportedModule(module.clone())),
+	}
+}
+
+fn path_str(path: &Path) -> String {
+	path.display().to_string().replace("\\", "/")
+}
+
+fn parse_version(version: &str) -> Result<u64, Error> {
+	version
+		.parse::<u64>()
+		.map_err(|_| Error::InvalidResponse(format!("{version:?} is not a local module version")))
+}
+
+fn new_file_id() -> String {
+	uuid::Uuid::new_v4().as_hyphenated().to_string()
+}
+
+impl LocalModules {
+	fn get(&self, id: &ModuleId) -> Result<&LocalModule, Error> {
+		let name = module_name(id)?;
+		self.modules.get(name).ok_or_else(|| Error::MissingModule(id.clone()))
+	}
+
+	fn get_mut(&mut self, id: &ModuleId) -> Result<&mut LocalModule, Error> {
+		let name = module_name(id)?;
+		self.modules
+			.get_mut(name)
+			.ok_or_else(|| Error::MissingModule(id.clone()))
+	}
+
+	fn metadata(&self, name: &str) -> Option<ModuleMetadata> {
+		let module = self.modules.get(name)?;
+		let root_trees = module
+			.files
+			.keys()
+			.filter_map(|path| path.split_once('/').map(|(root, _)| root));
+		let root_trees = root_trees.map(str::to_owned).collect::<BTreeSet<_>>();
+		Some(ModuleMetadata {
+			id: ModuleId::Local { name: name.to_owned() },
+			version: module.version.to_string(),
+			tree_id: String::default(),
+			root_trees: root_trees.into_iter().collect(),
+		})
+	}
+}
+
+impl LocalModule {
+	fn commit(&mut self, path: String, file_id: String, removed: bool, message: &str) {
+		self.version += 1;
+		self.history.push(LocalChange {
+			version: self.version,
+			path,
+			file_id,
+			removed,
+			message: message.to_owned(),
+		});
+	}
+}
+
+impl StorageBackend for LocalBackend {
+	fn search_modules(&self, query: ModuleQuery) -> LocalBoxFuture<'_, Result<ModuleSearch, Error>> {
+		Box::pin(async move {
+			let local = self.load().await?;
+			let names = match query {
+				ModuleQuery::Homebrew => vec![USER_HOMEBREW_REPO_NAME.to_owned()],
+				// All local modules are owned by the local viewer.
+				ModuleQuery::Owners(_) => local.modules.keys().cloned().collect(),
+				ModuleQuery::Modules(ids) => ids
+					.iter()
+					.filter_map(|id| module_name(id).ok())
+					.map(str::to_owned)
+					.collect(),
+			};
+			let modules = names.iter().filter_map(|name| local.metadata(name)).collect();
+			Ok(ModuleSearch {
+				viewer: VIEWER.to_owned(),
+				modules,
+			})
+		})
+	}
+
+	fn find_organizations(&self) -> LocalBoxFuture<'_, Result<Vec<String>, Error>> {
+		Box::pin(async move { Ok(Vec::new()) })
+	}
+
+	fn create_module<'a>(&'a self, name: &'a str) -> LocalBoxFuture<'a, Result<ModuleId, Error>> {
+		Box::pin(async move {
+			self.modify(|local| {
+				local.modules.entry(name.to_owned()).or_default();
+				Ok(())
+			})
+			.await?;
+			Ok(ModuleId::Local { name: name.to_owned() })
+		})
+	}
+
+	fn get_module_version<'a>(&'a self, module: &'a ModuleId) -> LocalBoxFuture<'a, Result<String, Error>> {
+		Box::pin(async move {
+			let local = self.load().await?;
+			Ok(local.get(module)?.version.to_string())
+		})
+	}
+
+	fn list_tree<'a>(&'a self, args: TreeArgs<'a>) -> LocalBoxFuture<'a, Result<Vec<TreeEntry>, Error>> {
+		Box::pin(async move {
+			let local = self.load().await?;
+			let module = local.get(args.module)?;
+			// Trees are identified by their path in the module, where the root tree is the empty path.
+			let prefix = match args.tree_id.is_empty() {
+				true => String::default(),
+				false => format!("{}/", args.tree_id),
+			};
+			let mut trees = BTreeSet::new();
+			let mut entries = Vec::new();
+			for (path, file) in &module.files {
+				let Some(relative) = path.strip_prefix(&prefix) else {
+					continue;
+				};
+				match relative.split_once('/') {
+					Some((tree_name, _)) => {
+						trees.insert(tree_name.to_owned());
+					}
+					None => entries.push(TreeEntry {
+						path: relative.to_owned(),
+						id: file.file_id.clone(),
+						is_tree: false,
+					}),
+				}
+			}
+			for tree_name in trees {
+				entries.push(TreeEntry {
+					id: format!("{prefix}{tree_name}"),
+					path: tree_name,
+					is_tree: true,
+				});
+			}
+			Ok(entries)
+		})
+	}
+
+	fn list_changed_files<'a>(&'a self, args: CompareArgs<'a>) -> LocalBoxFuture<'a, Result<Vec<ChangedFile>, Error>> {
+		Box::pin(async move {
+			let local = self.load().await?;
+			let module = local.get(args.module)?;
+			let old_version = parse_version(args.old_version)?;
+			let new_version = parse_version(args.new_version)?;
+
+			// A file exists at some version if the last change to it (at or before that version) was not a removal.
+			let existed_at = |path: &str, version: u64| {
+				let iter_history = module.history.iter();
+				let prior = iter_history.filter(|change| change.version <= version && change.path == path);
+				prior.last().map(|change| !change.removed).unwrap_or(false)
+			};
+
+			// Collapse the history into the net change for each file between the two versions.
+			let mut latest_changes = BTreeMap::<&str, &LocalChange>::new();
+			let iter_history = module.history.iter();
+			let iter_history =
+				iter_history.filter(|change| change.version > old_version && change.version <= new_version);
+			for change in iter_history {
+				latest_changes.insert(change.path.as_str(), change);
+			}
+
+			let mut changed_files = Vec::with_capacity(latest_changes.len());
+			for (path, change) in latest_changes {
+				let status = match (existed_at(path, old_version), change.removed) {
+					// Added and removed within the range, so there is no net change.
+					(false, true) => continue,
+					(false, false) => ChangedFileStatus::Added,
+					(true, false) => ChangedFileStatus::Modified,
+					(true, true) => ChangedFileStatus::Removed,
+				};
+				changed_files.push(ChangedFile {
+					path: path.to_owned(),
+					file_id: change.file_id.clone(),
+					status,
+				});
+			}
+			Ok(changed_files)
+		})
+	}
+
+	fn get_file<'a>(&'a self, args: GetFileArgs<'a>) -> LocalBoxFuture<'a, Result<String, Error>> {
+		Box::pin(async move {
+			// Local modules only keep the latest content of each file, so the version is not relevant.
+			let local = self.load().await?;
+			let module = local.get(args.module)?;
+			let path = path_str(args.path);
+			match module.files.get(&path) {
+				Some(file) => Ok(file.content.clone()),
+				None => Err(Error::MissingFile(args.module.clone(), path)),
+			}
+		})
+	}
+
+	fn put_file<'a>(&'a self, args: PutFileArgs<'a>) -> LocalBoxFuture<'a, Result<FileVersion, Error>> {
+		Box::pin(async move {
+			self.modify(|local| {
+				let module = local.get_mut(args.module)?;
+				let path = path_str(args.path);
+				let existing_id = module.files.get(&path).map(|file| file.file_id.as_str());
+				if existing_id.is_some() && existing_id != args.file_id {
+					return Err(Error::FileConflict(args.module.clone(), path));
+				}
+
+				let file_id = new_file_id();
+				module.files.insert(
+					path.clone(),
+					LocalFile {
+						file_id: file_id.clone(),
+						content: args.content.to_owned(),
+					},
+				);
+				module.commit(path, file_id.clone(), false, args.message);
+				let version = module.version.to_string();
+				Ok(FileVersion { version, file_id })
+			})
+			.await
+		})
+	}
+
+	fn remove_file<'a>(&'a self, args: RemoveFileArgs<'a>) -> LocalBoxFuture<'a, Result<String, Error>> {
+		Box::pin(async move {
+			self.modify(|local| {
+				let module = local.get_mut(args.module)?;
+				let path = path_str(args.path);
+				match module.files.get(&path) {
+					None => return Err(Error::MissingFile(args.module.clone(), path)),
+					Some(file) if file.file_id != args.file_id => {
+						return Err(Error::FileConflict(args.module.clone(), path));
+					}
+					Some(_) => {}
+				}
+
+				module.files.remove(&path);
+				module.commit(path, args.file_id.to_owned(), true, args.message);
+				Ok(module.version.to_string())
+			})
+			.await
+		})
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use futures::executor::block_on;
+
+	fn put(storage: &LocalBackend, module: &ModuleId, path: &str, content: &str, file_id: Option<&str>) -> FileVersion {
+		let args = PutFileArgs {
+			module,
+			path: Path::new(path),
+			message: "test",
+			content,
+			file_id,
+		};
+		block_on(storage.put_file(args)).expect("failed to put file")
+	}
+
+	#[test]
+	fn create_module() -> Result<(), Error> {
+		let storage = LocalBackend::in_memory();
+		let search = block_on(storage.search_modules(ModuleQuery::Homebrew))?;
+		assert_eq!(search.viewer, VIEWER);
+		assert!(search.modules.is_empty());
+
+		let module = block_on(storage.create_module(USER_HOMEBREW_REPO_NAME))?;
+		let search = block_on(storage.search_modules(ModuleQuery::Homebrew))?;
+		assert_eq!(search.modules.len(), 1);
+		assert_eq!(search.modules[0].id, module);
+		assert_eq!(block_on(storage.get_module_version(&module))?, "0");
+		Ok(())
+	}
+
+	#[test]
+	fn put_get_file() -> Result<(), Error> {
+		let storage = LocalBackend::in_memory();
+		let module = block_on(storage.create_module("homebrew"))?;
+
+		let created = put(&storage, &module, "dnd5e/character/bob.kdl", "character", None);
+		assert_eq!(created.version, "1");
+		let args = GetFileArgs {
+			module: &module,
+			path: Path::new("dnd5e/character/bob.kdl"),
+			version: &created.version,
+		};
+		assert_eq!(block_on(storage.get_file(args))?, "character");
+
+		let updated = put(
+			&storage,
+			&module,
+			"dnd5e/character/bob.kdl",
+			"updated",
+			Some(&created.file_id),
+		);
+		assert_eq!(updated.version, "2");
+		assert_ne!(updated.file_id, created.file_id);
+		assert_eq!(block_on(storage.get_file(args))?, "updated");
+		Ok(())
+	}
+
+	#[test]
+	fn put_stale_file() -> Result<(), Error> {
+		let storage = LocalBackend::in_memory();
+		let module = block_on(storage.create_module("homebrew"))?;
+		let created = put(&storage, &module, "dnd5e/item.kdl", "a", None);
+		let _updated = put(&storage, &module, "dnd5e/item.kdl", "b", Some(&created.file_id));
+
+		let args = PutFileArgs {
+			module: &module,
+			path: Path::new("dnd5e/item.kdl"),
+			message: "test",
+			content: "c",
+			file_id: Some(&created.file_id),
+		};
+		let result = block_on(storage.put_file(args));
+		assert!(matches!(result, Err(Error::FileConflict(_, _))));
+		Ok(())
+	}
+
+	/// Only the in-memory path is covered here. The database path keeps concurrent puts from overwriting
+	/// each other by reading and writing in one IndexedDB transaction (see `LocalBackend::modify`),
+	/// but IndexedDB only exists in a browser, and these tests run natively without a wasm test runner.
+	#[test]
+	fn concurrent_puts() -> Result<(), Error> {
+		let storage = LocalBackend::in_memory();
+		let module = block_on(storage.create_module("homebrew"))?;
+		let put_args = |path: &'static str| PutFileArgs {
+			module: &module,
+			path: Path::new(path),
+			message: "test",
+			content: path,
+			file_id: None,
+		};
+		let (a, b) = block_on(futures::future::join(
+			storage.put_file(put_args("dnd5e/a.kdl")),
+			storage.put_file(put_args("dnd5e/b.kdl")),
+		));
+		assert!(a.is_ok() && b.is_ok());
+		assert_eq!(block_on(storage.get_module_version(&module))?, "2");
+		let system = block_on(storage.list_tree(TreeArgs {
+			module: &module,
+			tree_id: "dnd5e",
+		}))?;
+		assert_eq!(system.len(), 2);
+		Ok(())
+	}
+
+	#[test]
+	fn list_tree() -> Result<(), Error> {
+		let storage = LocalBackend::in_memory();
+		let module = block_on(storage.create_module("homebrew"))?;
+		let spell = put(&storage, &module, "dnd5e/spell.kdl", "spell", None);
+		let _character = put(&storage, &module, "dnd5e/character/bob.kdl", "character", None);
+
+		let search = block_on(storage.search_modules(ModuleQuery::Modules(vec![module.clone()])))?;
+		assert_eq!(search.modules[0].root_trees, vec!["dnd5e".to_owned()]);
+
+		let root = block_on(storage.list_tree(TreeArgs {
+			module: &module,
+			tree_id: &search.modules[0].tree_id,
+		}))?;
+		let expected_root = vec![TreeEntry {
+			path: "dnd5e".into(),
+			id: "dnd5e".into(),
+			is_tree: true,
+		}];
+		assert_eq!(root, expected_root);
+
+		let system = block_on(storage.list_tree(TreeArgs {
+			module: &module,
+			tree_id: "dnd5e",
+		}))?;
+		let expected_system = vec![
+			TreeEntry {
+				path: "spell.kdl".into(),
+				id: spell.file_id,
+				is_tree: false,
+			},
+			TreeEntry {
+				path: "character".into(),
+				id: "dnd5e/character".into(),
+				is_tree: true,
+			},
+		];
+		assert_eq!(system, expected_system);
+		Ok(())
+	}
+
+	#[test]
+	fn changed_files() -> Result<(), Error> {
+		let storage = LocalBackend::in_memory();
+		let module = block_on(storage.create_module("homebrew"))?;
+		let a = put(&storage, &module, "dnd5e/a.kdl", "a", None);
+		let b = put(&storage, &module, "dnd5e/b.kdl", "b", None);
+		let a2 = put(&storage, &module, "dnd5e/a.kdl", "a2", Some(&a.file_id));
+		let removed_version = block_on(storage.remove_file(RemoveFileArgs {
+			module: &module,
+			path: Path::new("dnd5e/b.kdl"),
+			message: "test",
+			file_id: &b.file_id,
+		}))?;
+		let c = put(&storage, &module, "dnd5e/c.kdl", "c", None);
+
+		let changes = block_on(storage.list_changed_files(CompareArgs {
+			module: &module,
+			old_version: &b.version,
+			new_version: &c.version,
+		}))?;
+		let expected = vec![
+			ChangedFile {
+				path: "dnd5e/a.kdl".into(),
+				file_id: a2.file_id,
+				status: ChangedFileStatus::Modified,
+			},
+			ChangedFile {
+				path: "dnd5e/b.kdl".into(),
+				file_id: b.file_id,
+				status: ChangedFileStatus::Removed,
+			},
+			ChangedFile {
+				path: "dnd5e/c.kdl".into(),
+				file_id: c.file_id,
+				status: ChangedFileStatus::Added,
+			},
+		];
+		assert_eq!(changes, expected);
+		assert_eq!(removed_version, "4");
+		Ok(())
+	}
+}
@@ -91,12 +91,15 @@ pub fn node_registry() -> NodeRegistry {
 
 	registry.register_mutator::<AbilityScoreChange>();
 	registry.register_mutator::<AddArmorClassFormula>();
+	registry.register_mutator::<AddCarryingSize>();
 	registry.register_mutator::<AddDefense>();
 	registry.register_mutator::<AddLifeExpectancy>();
 	registry.register_mutator::<AddToActionBudget>();
+	registry.register_mutator::<Exhaustion>();
 	registry.register_mutator::<AddSize>();
 	registry.register_mutator::<SuggestedPersonality>();
 	registry.register_mutator::<AddMaxHitPoints>();
+	registry.register_mutator::<AddMaxAttunement>();
 	registry.register_mutator::<AddModifier>();
 	registry.register_mutator::<AddProficiency>();
 	registry.register_mutator::<Sense>();
@@ -113,14 +116,23 @@ pub fn node_registry() -> NodeRegistry {
 
 	registry.register_evaluator::<GetAbilityModifier>();
 	registry.register_evaluator::<GetProficiencyBonus>();
+	registry.register_evaluator::<GetExhaustion>();
 	registry.register_evaluator::<GetHitPoints>();
 	registry.register_evaluator::<GetLevelInt>();
 	registry.register_evaluator::<GetLevelStr>();
+	registry.register_evaluator::<HasAbilityScore>();
 	registry.register_evaluator::<HasArmorEquipped>();
 	registry.register_evaluator::<HasAttack>();
 	registry.register_evaluator::<HasCondition>();
 	registry.register_evaluator::<IsProficientWith>();
 	registry.register_evaluator::<Math>();
+	registry.register_evaluator::<AllCriteria>();
+	registry.register_evaluator::<AllBool>();
+	registry.register_evaluator::<AnyCriteria>();
+	registry.register_evaluator::<AnyBool>();
+	registry.register_evaluator::<NotCriteria>();
+	registry.register_evaluator::<NotBool>();
+	registry.register_evaluator::<Compare>();
 
 	registry
 }
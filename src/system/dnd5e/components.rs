@@ -3,6 +3,9 @@ pub mod ability;
 mod armor_class;
 pub use armor_class::*;
 
+mod concentration;
+pub use concentration::*;
+
 mod condition;
 pub use condition::*;
 
@@ -22,6 +25,8 @@ pub mod panel;
 mod prof_bonus;
 pub use prof_bonus::*;
 
+pub mod level_up;
+
 mod initiative_bonus;
 pub use initiative_bonus::*;
 
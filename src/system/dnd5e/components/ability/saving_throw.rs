@@ -3,7 +3,7 @@ use crate::{
 	components::context_menu,
 	page::characters::sheet::CharacterHandle,
 	system::dnd5e::{
-		components::glyph,
+		components::{glyph, roll},
 		data::{character::ModifierMapItem, roll::Modifier, Ability},
 	},
 };
@@ -44,11 +44,15 @@ pub struct SavingThrowProps {
 pub fn SavingThrow(SavingThrowProps { ability, abbreviated }: &SavingThrowProps) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
 	let proficiency = state.saving_throws().get_prof(*ability);
-	let modifier = state.ability_modifier(*ability, Some(*proficiency.value()));
+	let modifier = state.saving_throw_modifier(*ability);
 	let mod_sign = match modifier >= 0 {
 		true => "+",
 		false => "-",
 	};
+	let roll_save = roll::use_roller().callback({
+		let ability = *ability;
+		move |state, rand| Some(state.roll_saving_throw(ability, rand))
+	});
 	html! {<tr>
 		<Tooltip tag={"td"} classes={"text-center"} use_html={true} content={abbreviated.then(|| {
 			crate::data::as_feature_paths_html(proficiency.sources().iter().map(|(path, _)| path))
@@ -60,14 +64,20 @@ pub fn SavingThrow(SavingThrowProps { ability, abbreviated }: &SavingThrowProps)
 			false => ability.long_name().to_owned(),
 		}}</td>
 		<td class="text-center">
-			<span style="font-weight: 700; color: var(--theme-roll-modifier);">
-				{mod_sign}{modifier.abs()}
-			</span>
+			<roll::Rollable onclick={roll_save}>
+				<span style="font-weight: 700; color: var(--theme-roll-modifier);">
+					{mod_sign}{modifier.abs()}
+				</span>
+			</roll::Rollable>
 		</td>
 		{(!abbreviated).then(|| html! {<td>
 			{proficiency.sources().iter().filter_map(|(path, _)| {
 				crate::data::as_feature_path_text(path)
 			}).map(|text| html! {<div>{text}</div>}).collect::<Vec<_>>()}
+			{state.saving_throws().bonuses(*ability).iter().map(|(bonus, source)| {
+				let source = crate::data::as_feature_path_text(source).unwrap_or_default();
+				html! {<div>{format!("{bonus:+} from {source}")}</div>}
+			}).collect::<Vec<_>>()}
 		</td>}).unwrap_or_default()}
 	</tr>}
 }
@@ -125,7 +135,7 @@ pub fn SavingThrowModifiers(
 	}: &ModifiersProps,
 ) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
-	let modifiers = state
+	let mut modifiers = state
 		.saving_throws()
 		.iter_modifiers()
 		.map(|(ability, modifier, item)| {
@@ -140,6 +150,13 @@ pub fn SavingThrowModifiers(
 			}
 		})
 		.collect::<Vec<_>>();
+	for ability in EnumSet::<Ability>::all() {
+		for (bonus, source) in state.saving_throws().bonuses(ability) {
+			let source = crate::data::as_feature_path_text(source).unwrap_or_default();
+			let name = ability.abbreviated_name().to_uppercase();
+			modifiers.push(html!(<div>{format!("{bonus:+} on {name} ({source})")}</div>));
+		}
+	}
 	let content = match (modifiers.is_empty(), *show_none_label) {
 		(false, _) => html! {<>{modifiers}</>},
 		(true, false) => html!(),
@@ -66,7 +66,7 @@ pub fn Score(ScoreProps { ability }: &ScoreProps) -> Html {
 		},
 		mobile::Kind::Mobile => {
 			let saving_throw_prof = state.saving_throws().get_prof(*ability);
-			let saving_throw_modifier = state.ability_modifier(*ability, Some(*saving_throw_prof.value()));
+			let saving_throw_modifier = state.saving_throw_modifier(*ability);
 
 			html! {
 				<div class="p-1 text-center" {onclick}>
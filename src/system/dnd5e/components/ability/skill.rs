@@ -3,7 +3,7 @@ use crate::{
 	components::context_menu,
 	page::characters::sheet::CharacterHandle,
 	system::dnd5e::{
-		components::glyph,
+		components::{glyph, roll},
 		data::{Ability, Skill},
 	},
 };
@@ -199,6 +199,11 @@ fn Row(
 		}
 	}).collect::<Vec<_>>();
 
+	let roll_check = roll::use_roller().callback({
+		let skill = *skill;
+		move |state, rand| Some(state.roll_skill_check(skill, rand))
+	});
+
 	let mut table_data = vec![
 		html! {
 			<Tooltip tag={"td"} classes={"text-center"} content={prof_tooltip} use_html={true}>
@@ -211,7 +216,11 @@ fn Row(
 				{roll_modifiers}
 			</div>
 		</td> },
-		html! { <td class="text-center">{if modifier >= 0 { "+" } else { "-" }}{modifier.abs()}</td> },
+		html! { <td class="text-center">
+			<roll::Rollable onclick={roll_check.clone()}>
+				{if modifier >= 0 { "+" } else { "-" }}{modifier.abs()}
+			</roll::Rollable>
+		</td> },
 		html! { <td class="text-center">{passive}</td> },
 	];
 	if let Some(idx) = ability_name_col {
@@ -249,6 +258,10 @@ fn SkillModal(SkillModalProps { skill }: &SkillModalProps) -> Html {
 
 	let proficiency = state.skills().proficiency(*skill);
 	let bonus = state.ability_modifier(skill.ability(), Some(*proficiency.value()));
+	let roll_check = roll::use_roller().callback({
+		let skill = *skill;
+		move |state, rand| Some(state.roll_skill_check(skill, rand))
+	});
 
 	let prof_table = match proficiency.sources().is_empty() {
 		true => html! {},
@@ -321,7 +334,11 @@ fn SkillModal(SkillModalProps { skill }: &SkillModalProps) -> Html {
 	html! {<>
 		<div class="text-center fs-5" style="width: 100%; margin-bottom: 10px;">
 			<span>{"Bonus:"}</span>
-			<span style="margin-left: 5px;">{match bonus >= 0 { true => "+", false => "-", }}{bonus.abs()}</span>
+			<span style="margin-left: 5px;">
+				<roll::Rollable onclick={roll_check}>
+					{match bonus >= 0 { true => "+", false => "-", }}{bonus.abs()}
+				</roll::Rollable>
+			</span>
 		</div>
 		{prof_table}
 		{roll_modifiers_table}
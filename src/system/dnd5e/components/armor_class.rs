@@ -1,7 +1,11 @@
 use crate::{
 	components::{context_menu, AnnotatedNumber, AnnotatedNumberCard},
 	page::characters::sheet::CharacterHandle,
-	system::dnd5e::data::ArmorClassFormula,
+	system::dnd5e::data::{
+		character::{CombatState, Cover, TemporaryEffect},
+		ArmorClassFormula,
+	},
+	utility::InputExt,
 };
 use yew::prelude::*;
 
@@ -11,7 +15,20 @@ Things that contribute to your AC include the armor you wear, the shield you car
 and your Dexterity modifier. Not all characters wear armor or carry shields, however. \
 Without armor or a shield, your character's AC equals 10 + their Dexterity modifier.";
 
-// TODO: Rules text for Cover (half, 3/4, and full cover)
+static COVER_TEXT: &'static str = "\
+Walls, trees, creatures, and other obstacles can provide cover during combat, making a target more difficult to harm. \
+A target can benefit from cover only when an attack or other effect originates on the opposite side of the cover. \
+There are three degrees of cover. If a target is behind multiple sources of cover, only the most protective degree \
+of cover applies; the degrees aren't added together.
+
+A target with half cover has a +2 bonus to AC and Dexterity saving throws. A target has half cover if an obstacle \
+blocks at least half of its body.
+
+A target with three-quarters cover has a +5 bonus to AC and Dexterity saving throws. A target has three-quarters \
+cover if about three-quarters of it is covered by an obstacle.
+
+A target with total cover can't be targeted directly by an attack or a spell, although some spells can reach such \
+a target by including it in an area of effect. A target has total cover if it is completely concealed by an obstacle.";
 
 #[function_component]
 pub fn ArmorClass() -> Html {
@@ -19,9 +36,12 @@ pub fn ArmorClass() -> Html {
 	let on_click = context_menu::use_control_action({
 		|_, _context| context_menu::Action::open_root(format!("Armor Class"), html!(<Modal />))
 	});
+	let base = state.armor_class().evaluate(&*state);
+	let situational = state.armor_class().evaluate_situational(&*state);
+	let suffix = (situational != base).then(|| AttrValue::from(format!("/{situational}")));
 	html! {
 		<AnnotatedNumberCard header={"Armor"} footer={"Class"} {on_click}>
-			<AnnotatedNumber value={state.armor_class().evaluate(&*state)} />
+			<AnnotatedNumber value={base} {suffix} />
 		</AnnotatedNumberCard>
 	}
 }
@@ -123,8 +143,209 @@ fn Modal() -> Html {
 	html! {<>
 		{formula_table}
 		{bonuses_table}
+		<CombatSection />
 		<div class="text-block">
 			{TEXT}
 		</div>
+		<div class="text-block mt-2">
+			<h6>{"Cover"}</h6>
+			{COVER_TEXT}
+		</div>
 	</>}
 }
+
+#[function_component]
+fn CombatSection() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let combat = state.combat();
+	let base = state.armor_class().evaluate(&*state);
+	let situational = state.armor_class().evaluate_situational(&*state);
+	let raisable_shield = CombatState::raisable_shield(state.inventory());
+
+	let set_cover = Callback::from({
+		let state = state.clone();
+		move |evt: web_sys::Event| {
+			let cover = evt.select_value_t::<Cover>();
+			state.update_combat(move |combat| combat.cover = cover);
+		}
+	});
+	let set_shield_raised = Callback::from({
+		let state = state.clone();
+		move |evt: web_sys::Event| {
+			let Some(raised) = evt.input_checked() else {
+				return;
+			};
+			state.update_combat(move |combat| combat.shield_raised = raised);
+		}
+	});
+	let remove_effect = Callback::from({
+		let state = state.clone();
+		move |idx: usize| {
+			state.update_combat(move |combat| {
+				if idx < combat.effects.len() {
+					combat.effects.remove(idx);
+				}
+			});
+		}
+	});
+	let next_round = Callback::from({
+		let state = state.clone();
+		move |_: MouseEvent| state.update_combat(CombatState::advance_round)
+	});
+	let end_combat = Callback::from({
+		let state = state.clone();
+		move |_: MouseEvent| state.update_combat(|combat| *combat = CombatState::default())
+	});
+
+	let effect_name = use_state(|| String::new());
+	let effect_ac = use_state(|| 2i32);
+	let effect_rounds = use_state(|| None::<u32>);
+	let add_effect = Callback::from({
+		let state = state.clone();
+		let effect_name = effect_name.clone();
+		let effect_ac = effect_ac.clone();
+		let effect_rounds = effect_rounds.clone();
+		move |_: MouseEvent| {
+			if effect_name.is_empty() {
+				return;
+			}
+			let effect = TemporaryEffect {
+				name: (*effect_name).clone(),
+				armor_class: *effect_ac,
+				rounds: *effect_rounds,
+			};
+			state.update_combat(move |combat| combat.effects.push(effect));
+			effect_name.set(String::new());
+		}
+	});
+
+	let bonus_rows = state.armor_class().iter_combat_bonuses().map(|(bonus, source)| {
+		html! {<tr>
+			<td class="text-center">{format!("{bonus:+}")}</td>
+			<td>{crate::data::as_feature_path_text(source).unwrap_or_default()}</td>
+		</tr>}
+	});
+	let bonus_rows = bonus_rows.collect::<Vec<_>>();
+
+	html! {
+		<div class="border-bottom-theme-muted mb-2 pb-2">
+			<div class="d-flex align-items-center mb-2">
+				<h5 class="flex-grow-1 m-0">{"In Combat: "}{situational}</h5>
+				<button type="button" class="btn btn-xs btn-outline-theme me-1" onclick={next_round}>{"Next Round"}</button>
+				<button type="button" class="btn btn-xs btn-outline-theme" onclick={end_combat}>{"End Combat"}</button>
+			</div>
+			{(situational != base).then(|| html! {
+				<div class="text-body-secondary mb-1">{format!("Base armor class is {base}.")}</div>
+			}).unwrap_or_default()}
+			<div class="d-flex align-items-center mb-1">
+				<span class="me-2">{"Cover"}</span>
+				<select class="form-select form-select-sm w-auto" onchange={set_cover}>
+					<option value="" selected={combat.cover.is_none()}>{"None"}</option>
+					{[Cover::Half, Cover::ThreeQuarters, Cover::Full].into_iter().map(|cover| html! {
+						<option value={cover.to_string()} selected={combat.cover == Some(cover)}>
+							{cover.display_name()}
+						</option>
+					}).collect::<Vec<_>>()}
+				</select>
+			</div>
+			{(combat.cover == Some(Cover::Full)).then(|| html! {
+				<div class="text-body-secondary mb-1">{"You can't be targeted directly by attacks or spells."}</div>
+			}).unwrap_or_default()}
+			<div class="form-check mb-1">
+				<input
+					class="form-check-input" type="checkbox" id="shield-raised"
+					checked={combat.shield_raised}
+					disabled={raisable_shield.is_none()}
+					onchange={set_shield_raised}
+				/>
+				<label class="form-check-label" for="shield-raised">
+					{match raisable_shield {
+						Some((bonus, name)) => format!("Raise {name} ({bonus:+})"),
+						None => "Raise Shield (no unequipped shield in inventory)".into(),
+					}}
+				</label>
+			</div>
+			<table class="table table-compact table-striped m-0 mb-1">
+				<thead>
+					<tr class="text-center" style="color: var(--bs-heading-color);">
+						<th scope="col">{"Effect"}</th>
+						<th scope="col">{"AC"}</th>
+						<th scope="col">{"Rounds Left"}</th>
+						<th scope="col"></th>
+					</tr>
+				</thead>
+				<tbody>
+					{combat.effects.iter().enumerate().map(|(idx, effect)| html! {
+						<tr>
+							<td>{&effect.name}</td>
+							<td class="text-center">{format!("{:+}", effect.armor_class)}</td>
+							<td class="text-center">{effect.rounds.map(|rounds| rounds.to_string()).unwrap_or_else(|| "--".into())}</td>
+							<td class="text-center">
+								<button
+									type="button" class="btn btn-xs btn-outline-danger"
+									onclick={remove_effect.reform(move |_: MouseEvent| idx)}
+								>
+									<i class="bi bi-trash" />
+								</button>
+							</td>
+						</tr>
+					}).collect::<Vec<_>>()}
+					<tr>
+						<td>
+							<input
+								type="text" class="form-control form-control-sm" placeholder="Shield of Faith"
+								value={(*effect_name).clone()}
+								onchange={Callback::from({
+									let effect_name = effect_name.clone();
+									move |evt: web_sys::Event| {
+										effect_name.set(evt.input_value().unwrap_or_default());
+									}
+								})}
+							/>
+						</td>
+						<td>
+							<input
+								type="number" class="form-control form-control-sm text-center"
+								value={effect_ac.to_string()}
+								onchange={Callback::from({
+									let effect_ac = effect_ac.clone();
+									move |evt: web_sys::Event| {
+										effect_ac.set(evt.input_value_t::<i32>().unwrap_or_default());
+									}
+								})}
+							/>
+						</td>
+						<td>
+							<input
+								type="number" class="form-control form-control-sm text-center" min="1" placeholder="--"
+								value={effect_rounds.map(|rounds| rounds.to_string()).unwrap_or_default()}
+								onchange={Callback::from({
+									let effect_rounds = effect_rounds.clone();
+									move |evt: web_sys::Event| {
+										effect_rounds.set(evt.input_value_t::<u32>().filter(|rounds| *rounds > 0));
+									}
+								})}
+							/>
+						</td>
+						<td class="text-center">
+							<button type="button" class="btn btn-xs btn-outline-theme" onclick={add_effect}>
+								{"Add"}
+							</button>
+						</td>
+					</tr>
+				</tbody>
+			</table>
+			{(!bonus_rows.is_empty()).then(|| html! {
+				<table class="table table-compact table-striped m-0">
+					<thead>
+						<tr class="text-center" style="color: var(--bs-heading-color);">
+							<th scope="col">{"Bonus"}</th>
+							<th scope="col">{"Source"}</th>
+						</tr>
+					</thead>
+					<tbody>{bonus_rows}</tbody>
+				</table>
+			}).unwrap_or_default()}
+		</div>
+	}
+}
@@ -0,0 +1,171 @@
+use crate::{
+	components::context_menu,
+	page::characters::sheet::{CharacterHandle, MutatorImpact},
+	system::dnd5e::{
+		components::{
+			glyph::Glyph,
+			roll::{Outcome, RollHistory},
+		},
+		data::{roll::RollOutcome, Ability},
+	},
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+static TEXT_CONCENTRATION: &str = "\
+Normal activity, such as moving and attacking, doesn't interfere with concentration. \
+Whenever you take damage while you are concentrating on a spell, you must make a Constitution \
+saving throw to maintain your concentration. The DC equals 10 or half the damage you take, \
+whichever number is higher. If you take damage from multiple sources, such as an arrow and a \
+dragon's breath, you make a separate saving throw for each source of damage.";
+
+/// Returns a callback which prompts the user to make a concentration saving throw at each of the provided DCs
+/// (one for each source of damage taken).
+#[hook]
+pub fn use_concentration_check() -> Callback<Vec<u32>> {
+	context_menu::use_control_action(|dcs: Vec<u32>, _context| {
+		context_menu::Action::open_root("Concentration Check", html!(<ConcentrationCheck {dcs} />))
+	})
+}
+
+/// Shows the spell the character is concentrating on (if any), with a button to end concentration.
+#[function_component]
+pub fn ConcentrationStatus() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let end_concentration = state.new_dispatch("End Concentration", |_: MouseEvent, persistent| {
+		persistent.end_concentration();
+		MutatorImpact::None
+	});
+	let Some(concentration) = &state.persistent().concentration else {
+		return Html::default();
+	};
+	html! {
+		<div class="concentration-status d-flex align-items-center">
+			<Glyph tag="div" classes={"concentration me-1"} />
+			<span>{"Concentrating on "}<strong>{&concentration.spell_name}</strong></span>
+			{concentration.caster.as_ref().map(|caster| html! {
+				<span class="text-body-secondary ms-1">{format!("({caster})")}</span>
+			}).unwrap_or_default()}
+			<button class="btn btn-outline-theme btn-xs ms-auto" onclick={end_concentration}>
+				{"End"}
+			</button>
+		</div>
+	}
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct ConcentrationCheckProps {
+	pub dcs: Vec<u32>,
+}
+
+#[function_component]
+pub fn ConcentrationCheck(ConcentrationCheckProps { dcs }: &ConcentrationCheckProps) -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let history = use_dispatch::<RollHistory>();
+	// The saves which have been rolled so far, in the same order as the DCs.
+	let outcomes = use_state_eq(|| Vec::<RollOutcome>::new());
+	let close = context_menu::use_close_fn::<()>();
+
+	let mut saves = outcomes.iter().zip(dcs);
+	let maintained = saves.all(|(outcome, dc)| outcome.total() >= *dc as i32);
+	// Once a save is failed, concentration is lost and there are no further saves to make.
+	let next_dc = maintained.then(|| dcs.get(outcomes.len()).copied()).flatten();
+
+	let roll_save = Callback::from({
+		let state = state.clone();
+		let outcomes = outcomes.clone();
+		move |_: MouseEvent| {
+			let Some(dc) = next_dc else {
+				return;
+			};
+			let rolled = state.roll_saving_throw(Ability::Constitution, &mut rand::thread_rng());
+			if rolled.total() < dc as i32 {
+				state.dispatch("Lose Concentration", |persistent| {
+					persistent.end_concentration();
+					MutatorImpact::None
+				});
+			}
+			let id = state.id().unversioned();
+			history.reduce_mut({
+				let rolled = rolled.clone();
+				move |history| history.push(id, rolled)
+			});
+			let mut rolled_outcomes = (*outcomes).clone();
+			rolled_outcomes.push(rolled);
+			outcomes.set(rolled_outcomes);
+		}
+	});
+	let end_concentration = state.new_dispatch("End Concentration", {
+		let close = close.clone();
+		move |_: MouseEvent, persistent| {
+			persistent.end_concentration();
+			close.emit(());
+			MutatorImpact::None
+		}
+	});
+
+	let results = outcomes.iter().zip(dcs).map(|(outcome, dc)| {
+		html! {
+			<div class="my-2">
+				<div class="text-center text-body-secondary">{format!("DC {dc}")}</div>
+				<Outcome value={outcome.clone()} />
+			</div>
+		}
+	});
+	let results = results.collect::<Vec<_>>();
+	let result = (!outcomes.is_empty() && (!maintained || next_dc.is_none())).then(|| {
+		html! {
+			<div class={classes!("text-center", "mb-2", maintained.then_some("theme-healing"), (!maintained).then_some("theme-damage"))}>
+				{match maintained {
+					true => "Concentration maintained.",
+					false => "Concentration lost.",
+				}}
+			</div>
+		}
+	});
+
+	let spell_name = state
+		.persistent()
+		.concentration
+		.as_ref()
+		.map(|concentration| concentration.spell_name.clone());
+	html! {
+		<div class="concentration-check">
+			<div class="text-center mb-2">
+				{match &spell_name {
+					Some(spell_name) => html! {<>
+						{"You took damage while concentrating on "}<strong>{spell_name}</strong>{". "}
+						{match dcs.as_slice() {
+							[dc] => format!("Make a DC {dc} Constitution saving throw to maintain concentration."),
+							dcs => format!(
+								"Make a Constitution saving throw for each source of damage (DC {}) to maintain concentration.",
+								dcs.iter().map(u32::to_string).collect::<Vec<_>>().join(", ")
+							),
+						}}
+					</>},
+					None if !outcomes.is_empty() => html!(),
+					None => html!("You are not concentrating on a spell."),
+				}}
+			</div>
+			{results}
+			{result.unwrap_or_default()}
+			<div class="d-flex justify-content-center">
+				{(spell_name.is_some() && next_dc.is_some()).then(|| html! {<>
+					<button class="btn btn-theme btn-sm mx-1" onclick={roll_save}>
+						{match next_dc {
+							Some(dc) if dcs.len() > 1 => format!("Roll Constitution Save (DC {dc})"),
+							_ => "Roll Constitution Save".into(),
+						}}
+					</button>
+					<button class="btn btn-outline-theme btn-sm mx-1" onclick={end_concentration}>
+						{"End Concentration"}
+					</button>
+				</>}).unwrap_or_default()}
+				<button class="btn btn-outline-theme btn-sm mx-1" onclick={close.reform(|_: MouseEvent| ())}>
+					{"Close"}
+				</button>
+			</div>
+			<div class="text-block mt-3">{TEXT_CONCENTRATION}</div>
+		</div>
+	}
+}
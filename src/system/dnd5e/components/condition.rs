@@ -11,7 +11,8 @@ use crate::{
 	system::{
 		core::SourceId,
 		dnd5e::{
-			data::{character::Persistent, Condition, Indirect},
+			data::{Condition, Indirect},
+			mutator::{Exhaustion, MAX_EXHAUSTION_LEVEL},
 			DnD5e,
 		},
 	},
@@ -40,6 +41,9 @@ pub fn ConditionsCard() -> Html {
 	for condition in state.persistent().conditions.iter() {
 		insert_condition_tag(&mut condition_names, condition);
 	}
+	if state.persistent().exhaustion > 0 {
+		condition_names.push(format!("Exhaustion {}", state.persistent().exhaustion));
+	}
 	html! {
 		<div class="card m-1" style="height: 80px;" {onclick}>
 			<div class="card-body text-center" style="padding: 5px 5px;">
@@ -76,10 +80,10 @@ fn Modal() -> Html {
 		);
 		let add_condition_by_id = use_typed_fetch_callback(
 			"Add Condition".into(),
-			state.new_dispatch(Box::new(move |condition: Condition, persistent: &mut Persistent| {
+			state.new_dispatch("Add Condition", move |condition: Condition, persistent| {
 				persistent.conditions.insert(condition);
 				MutatorImpact::Recompile
-			})),
+			}),
 		);
 		let on_add_condition = Callback::from(move |evt: web_sys::Event| {
 			let Some(value) = evt.select_value() else {
@@ -137,15 +141,16 @@ fn Modal() -> Html {
 	let on_remove_condition = Callback::from({
 		let state = state.clone();
 		move |key| {
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Remove Condition", move |persistent| {
 				persistent.conditions.remove(&key);
 				MutatorImpact::Recompile
-			}));
+			});
 		}
 	});
 
 	html! {<>
 		{add_condition_section}
+		<ExhaustionSection />
 		<div>
 			{state.persistent().conditions.iter_keyed().map(|(key, condition)| {
 				let on_remove = on_remove_condition.reform({
@@ -176,6 +181,48 @@ fn Modal() -> Html {
 	</>}
 }
 
+#[function_component]
+fn ExhaustionSection() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let level = state.persistent().exhaustion;
+	let set_level_delta = Callback::from({
+		let state = state.clone();
+		move |delta: i32| {
+			state.dispatch("Change Exhaustion", move |persistent| {
+				let level = (persistent.exhaustion as i32 + delta).clamp(0, MAX_EXHAUSTION_LEVEL as i32);
+				persistent.exhaustion = level as u8;
+				MutatorImpact::Recompile
+			});
+		}
+	});
+	let effects = Exhaustion::level_effects(level).collect::<Vec<_>>();
+	html! {
+		<div class="mb-3">
+			<div class="d-flex align-items-center">
+				<h5 class="mb-0 me-3">{"Exhaustion"}</h5>
+				<span class="deltaform d-flex align-items-center">
+					<button
+						type="button" class="btn btn-theme sub"
+						onclick={set_level_delta.reform(|_| -1)} disabled={level == 0}
+					/>
+					<span class="amount">{format!("Level {level} / {MAX_EXHAUSTION_LEVEL}")}</span>
+					<button
+						type="button" class="btn btn-theme add"
+						onclick={set_level_delta.reform(|_| 1)} disabled={level >= MAX_EXHAUSTION_LEVEL}
+					/>
+				</span>
+			</div>
+			{match effects.is_empty() {
+				true => html!(<span>{"You are not exhausted."}</span>),
+				false => html!(<ul class="mb-0">
+					{effects.into_iter().map(|effect| html!(<li>{effect}</li>)).collect::<Vec<_>>()}
+				</ul>),
+			}}
+			<span class="text-block">{"Finishing a long rest reduces your exhaustion level by 1."}</span>
+		</div>
+	}
+}
+
 #[function_component]
 fn ConditionBody(GeneralProp { value: condition }: &GeneralProp<Rc<Condition>>) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
@@ -156,12 +156,12 @@ fn Modal(WalletContainerProps { id }: &WalletContainerProps) -> Html {
 					adjustments.set(Wallet::default());
 					wallet
 				};
-				state.dispatch(Box::new(move |persistent: &mut Persistent| {
+				state.dispatch("Add Currency", move |persistent| {
 					if let Some(target) = get_wallet_mut(persistent, &id) {
 						*target += adjustments;
 					}
 					MutatorImpact::None
-				}));
+				});
 			}
 		});
 		let onclick_remove = Callback::from({
@@ -177,14 +177,14 @@ fn Modal(WalletContainerProps { id }: &WalletContainerProps) -> Html {
 					adjustments.set(Wallet::default());
 					wallet
 				};
-				state.dispatch(Box::new(move |persistent: &mut Persistent| {
+				state.dispatch("Spend Currency", move |persistent| {
 					let Some(target) = get_wallet_mut(persistent, &id) else {
 						return MutatorImpact::None;
 					};
 					assert!(target.contains(&adjustments, auto_exchange));
 					target.remove(adjustments, auto_exchange);
 					MutatorImpact::None
-				}));
+				});
 			}
 		});
 		let onclick_clear = Callback::from({
@@ -204,13 +204,13 @@ fn Modal(WalletContainerProps { id }: &WalletContainerProps) -> Html {
 				if !auto_exchange {
 					return;
 				}
-				state.dispatch(Box::new(move |persistent: &mut Persistent| {
+				state.dispatch("Exchange Currency", move |persistent| {
 					let Some(target) = get_wallet_mut(persistent, &id) else {
 						return MutatorImpact::None;
 					};
 					target.normalize();
 					MutatorImpact::None
-				}));
+				});
 			}
 		});
 		html! {
@@ -3,11 +3,15 @@ use crate::{
 	page::characters::sheet::CharacterHandle,
 	page::characters::sheet::MutatorImpact,
 	system::dnd5e::{
-		components::UseCounterDelta,
-		data::character::{HitPoint, Persistent},
+		components::{glyph, use_concentration_check, UseCounterDelta},
+		data::{
+			character::{DamageInstance, DamageOutcome, HitPoint},
+			DamageType,
+		},
 	},
 	utility::InputExt,
 };
+use enumset::EnumSet;
 use std::{cmp::Ordering, path::PathBuf};
 use yew::prelude::*;
 
@@ -118,9 +122,10 @@ pub fn HitPointMgmtCard() -> Html {
 	html! {
 		<div class="card m-1 hit-points">
 			<div class="card-body">
-				{match current_hp > 0 {
-					true => html! { <HitPointsBody {on_open_modal} /> },
-					false => html! { <DeathSavesBody {on_open_modal} /> },
+				{match (state.is_dead(), current_hp > 0) {
+					(true, _) => html! { <DeadBody {on_open_modal} /> },
+					(false, true) => html! { <HitPointsBody {on_open_modal} /> },
+					(false, false) => html! { <DeathSavesBody {on_open_modal} /> },
 				}}
 			</div>
 		</div>
@@ -155,26 +160,38 @@ fn HitPointsBody(BodyProps { on_open_modal }: &BodyProps) -> Html {
 		}
 	});
 	let max_hp = state.get_hp(HitPoint::Max);
-	let onclick_heal = state.new_dispatch({
+	let onclick_heal = Callback::from({
+		let state = state.clone();
 		let take_hp_input = take_hp_input.clone();
-		move |evt: MouseEvent, character| {
+		move |evt: MouseEvent| {
 			evt.stop_propagation();
 			let Some(amt) = take_hp_input.emit(()) else {
-				return MutatorImpact::None;
+				return;
 			};
-			*character.hit_points_mut() += (amt as i32, max_hp);
-			MutatorImpact::None
+			state.dispatch("Heal", move |character| {
+				*character.hit_points_mut() += (amt as i32, max_hp);
+				MutatorImpact::None
+			});
 		}
 	});
-	let onclick_dmg = state.new_dispatch({
+	let concentration_check = use_concentration_check();
+	let onclick_dmg = Callback::from({
+		let state = state.clone();
 		let take_hp_input = take_hp_input.clone();
-		move |evt: MouseEvent, character| {
+		move |evt: MouseEvent| {
 			evt.stop_propagation();
 			let Some(amt) = take_hp_input.emit(()) else {
-				return MutatorImpact::None;
+				return;
 			};
-			*character.hit_points_mut() += (-1 * (amt as i32), max_hp);
-			MutatorImpact::None
+			let delta = -1 * (amt as i32);
+			let dc = state.persistent().hit_point_delta_concentration_dc(delta, max_hp);
+			state.dispatch("Take Damage", move |character| {
+				character.apply_hit_point_delta(delta, max_hp);
+				MutatorImpact::None
+			});
+			if let Some(dc) = dc {
+				concentration_check.emit(vec![dc]);
+			}
 		}
 	});
 
@@ -223,6 +240,16 @@ fn HitPointsBody(BodyProps { on_open_modal }: &BodyProps) -> Html {
 	}
 }
 
+#[function_component]
+fn DeadBody(BodyProps { on_open_modal }: &BodyProps) -> Html {
+	html! {
+		<div class="death-saves" onclick={on_open_modal.clone()}>
+			<h5 class="text-center" style="font-size: 0.8rem; color: var(--bs-card-title-color); margin: 0 0 2px 0;">{"Hit Points"}</h5>
+			<div class="text-center" style="font-size: 1.5rem; color: var(--bs-danger);">{"DEAD"}</div>
+		</div>
+	}
+}
+
 #[function_component]
 fn DeathSavesBody(BodyProps { on_open_modal }: &BodyProps) -> Html {
 	html! {
@@ -267,6 +294,8 @@ fn Modal() -> Html {
 		<span class="hr my-3" />
 		<ModalSectionApplyChangeForm />
 		<span class="hr my-3" />
+		<ModalSectionTakeDamage />
+		<span class="hr my-3" />
 		<ModalSectionHitDice />
 		<span class="hr my-3" />
 		<ModalSectionInfo />
@@ -306,10 +335,10 @@ fn ModalSectionCurrentStats() -> Html {
 			let Some(value) = evt.input_value_t::<u32>() else {
 				return;
 			};
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Set Temp HP", move |persistent| {
 				persistent.hit_points_mut().temp = value;
 				MutatorImpact::None
-			}));
+			});
 		}
 	});
 	html! {
@@ -391,12 +420,21 @@ fn ModalSectionApplyChangeForm() -> Html {
 			delta.set(delta.saturating_sub(1));
 		}
 	});
-	let apply_delta = state.new_dispatch({
+	let concentration_check = use_concentration_check();
+	let apply_delta = Callback::from({
+		let state = state.clone();
 		let delta = delta.clone();
-		move |_: MouseEvent, character| {
-			*character.hit_points_mut() += (*delta, max_hp);
+		move |_: MouseEvent| {
+			let amount = *delta;
+			let dc = state.persistent().hit_point_delta_concentration_dc(amount, max_hp);
+			state.dispatch("Adjust Hit Points", move |character| {
+				character.apply_hit_point_delta(amount, max_hp);
+				MutatorImpact::None
+			});
+			if let Some(dc) = dc {
+				concentration_check.emit(vec![dc]);
+			}
 			delta.set(0);
-			MutatorImpact::None
 		}
 	});
 	let clear_delta = Callback::from({
@@ -498,6 +536,242 @@ fn ModalSectionApplyChangeForm() -> Html {
 	}
 }
 
+#[function_component]
+fn ModalSectionTakeDamage() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let instances = use_state_eq(|| {
+		vec![DamageInstance {
+			amount: 0,
+			damage_type: DamageType::default(),
+		}]
+	});
+	let critical = use_state_eq(|| false);
+	let intake = state.evaluate_damage(&instances, *critical);
+
+	let set_amount = Callback::from({
+		let instances = instances.clone();
+		move |(idx, evt): (usize, web_sys::Event)| {
+			let Some(amount) = evt.input_value_t::<u32>() else {
+				return;
+			};
+			let mut next = (*instances).clone();
+			if let Some(instance) = next.get_mut(idx) {
+				instance.amount = amount;
+			}
+			instances.set(next);
+		}
+	});
+	let set_damage_type = Callback::from({
+		let instances = instances.clone();
+		move |(idx, evt): (usize, web_sys::Event)| {
+			let Some(damage_type) = evt.select_value_t::<DamageType>() else {
+				return;
+			};
+			let mut next = (*instances).clone();
+			if let Some(instance) = next.get_mut(idx) {
+				instance.damage_type = damage_type;
+			}
+			instances.set(next);
+		}
+	});
+	let remove_instance = Callback::from({
+		let instances = instances.clone();
+		move |idx: usize| {
+			let mut next = (*instances).clone();
+			if idx < next.len() {
+				next.remove(idx);
+			}
+			instances.set(next);
+		}
+	});
+	let add_instance = Callback::from({
+		let instances = instances.clone();
+		move |_: MouseEvent| {
+			let mut next = (*instances).clone();
+			next.push(DamageInstance {
+				amount: 0,
+				damage_type: DamageType::default(),
+			});
+			instances.set(next);
+		}
+	});
+	let set_critical = Callback::from({
+		let critical = critical.clone();
+		move |evt: web_sys::Event| {
+			critical.set(evt.input_checked().unwrap_or_default());
+		}
+	});
+	let concentration_check = use_concentration_check();
+	let take_damage = Callback::from({
+		let state = state.clone();
+		let intake = intake.clone();
+		let instances = instances.clone();
+		let critical = critical.clone();
+		move |_: MouseEvent| {
+			let dcs = intake.concentration_save_dcs(state.persistent().concentration.is_some());
+			state.dispatch("Take Damage", {
+				let intake = intake.clone();
+				move |persistent| {
+					persistent.apply_damage(&intake);
+					MutatorImpact::None
+				}
+			});
+			if !dcs.is_empty() {
+				concentration_check.emit(dcs);
+			}
+			instances.set(vec![DamageInstance {
+				amount: 0,
+				damage_type: DamageType::default(),
+			}]);
+			critical.set(false);
+		}
+	});
+
+	let rows = intake.steps.iter().enumerate().map(|(idx, step)| {
+		let defenses = step.defenses.iter().map(|(defense, source)| {
+			html! {
+				<div>
+					<glyph::Defense value={*defense} />
+					{crate::data::as_feature_path_text(source).unwrap_or_default()}
+				</div>
+			}
+		});
+		let conditional = step.conditional.iter().map(|(defense, context)| {
+			html! {
+				<div class="text-body-secondary">
+					<glyph::Defense value={*defense} />
+					{format!("{} from {context} (not applied)", defense.to_string())}
+				</div>
+			}
+		});
+		html! {
+			<tr>
+				<td>
+					<input
+						type="number" class="form-control form-control-sm text-center" min="0"
+						value={step.instance.amount.to_string()}
+						onkeydown={validate_uint_only()}
+						onchange={set_amount.reform(move |evt| (idx, evt))}
+					/>
+				</td>
+				<td>
+					<select class="form-select form-select-sm" onchange={set_damage_type.reform(move |evt| (idx, evt))}>
+						{EnumSet::<DamageType>::all().into_iter().map(|damage_type| html! {
+							<option value={damage_type.to_string()} selected={damage_type == step.instance.damage_type}>
+								{damage_type.display_name()}
+							</option>
+						}).collect::<Vec<_>>()}
+					</select>
+				</td>
+				<td>
+					{defenses.collect::<Vec<_>>()}
+					{conditional.collect::<Vec<_>>()}
+				</td>
+				<td class="text-center">{step.amount}</td>
+				<td class="text-center">
+					<button
+						type="button" class="btn btn-xs btn-outline-danger"
+						onclick={remove_instance.reform(move |_: MouseEvent| idx)}
+					>
+						<i class="bi bi-trash" />
+					</button>
+				</td>
+			</tr>
+		}
+	});
+
+	let mut summary = Vec::new();
+	if intake.damage.temp_absorbed > 0 {
+		summary.push(format!(
+			"{} absorbed by temporary hit points.",
+			intake.damage.temp_absorbed
+		));
+	}
+	if intake.damage.hp_lost > 0 {
+		summary.push(format!("{} taken from current hit points.", intake.damage.hp_lost));
+	}
+	let outcome = match intake.damage.outcome {
+		DamageOutcome::Conscious => None,
+		DamageOutcome::Unconscious => Some("You drop to 0 hit points and fall unconscious."),
+		DamageOutcome::DeathSaveFailures(1) => Some("You suffer a death saving throw failure."),
+		DamageOutcome::DeathSaveFailures(_) => Some("You suffer two death saving throw failures."),
+		DamageOutcome::InstantDeath => {
+			Some("The remaining damage equals or exceeds your hit point maximum. You die instantly.")
+		}
+	};
+	let concentration_dcs = intake.concentration_save_dcs(state.persistent().concentration.is_some());
+	let outcome = match (outcome, intake.is_dead()) {
+		(Some(outcome), true) if intake.damage.outcome != DamageOutcome::InstantDeath => {
+			Some(format!("{outcome} With three failures, you die."))
+		}
+		(outcome, _) => outcome.map(str::to_owned),
+	};
+
+	html! {
+		<div>
+			<h4>{"Take Damage"}</h4>
+			<table class="table table-compact table-striped m-0 mb-1">
+				<thead>
+					<tr class="text-center" style="color: var(--bs-heading-color);">
+						<th scope="col" style="width: 80px;">{"Amount"}</th>
+						<th scope="col">{"Type"}</th>
+						<th scope="col">{"Defenses"}</th>
+						<th scope="col">{"Taken"}</th>
+						<th scope="col"></th>
+					</tr>
+				</thead>
+				<tbody>
+					{rows.collect::<Vec<_>>()}
+				</tbody>
+			</table>
+			<div class="d-flex align-items-center mb-2">
+				<button type="button" class="btn btn-xs btn-outline-theme me-3" onclick={add_instance}>
+					{"Add Damage Type"}
+				</button>
+				<div class="form-check m-0">
+					<input
+						class="form-check-input" type="checkbox" id="damage-critical"
+						checked={*critical}
+						onchange={set_critical}
+					/>
+					<label class="form-check-label" for="damage-critical">{"Critical Hit"}</label>
+				</div>
+			</div>
+			<div class="d-flex align-items-center">
+				<div class="flex-grow-1">
+					<div>
+						<strong>{"Total Damage: "}</strong>
+						{intake.total}
+						{" "}
+						<span class="text-body-secondary">
+							{format!("(HP {} → {}, Temp {} → {})",
+								state.get_hp(HitPoint::Current), intake.hit_points.current,
+								state.get_hp(HitPoint::Temp), intake.hit_points.temp,
+							)}
+						</span>
+					</div>
+					{summary.into_iter().map(|line| html! { <div>{line}</div> }).collect::<Vec<_>>()}
+					{outcome.map(|outcome| html! {
+						<div class="theme-damage">{outcome}</div>
+					}).unwrap_or_default()}
+					{(!concentration_dcs.is_empty()).then(|| html! {
+						<div>
+							{"You must make a Constitution saving throw to maintain concentration for each source of damage "}
+							{format!("(DC {}).", concentration_dcs.iter().map(u32::to_string).collect::<Vec<_>>().join(", "))}
+						</div>
+					}).unwrap_or_default()}
+				</div>
+				<button
+					type="button"
+					class="m-2 btn btn-theme"
+					disabled={intake.total == 0}
+					onclick={take_damage}
+				>{"Take Damage"}</button>
+			</div>
+		</div>
+	}
+}
+
 #[function_component]
 fn ModalSectionHitDice() -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
@@ -507,7 +781,7 @@ fn ModalSectionHitDice() -> Html {
 		and force the usage of hit dice outside of rests. These inputs will not change your hit points, \
 		just the number of hit dice you have available until your next Long Rest.";
 
-	let apply_delta = state.new_dispatch(|(data_path, delta): (PathBuf, i32), persistent| {
+	let apply_delta = state.new_dispatch("Use Hit Dice", |(data_path, delta): (PathBuf, i32), persistent| {
 		let prev_value = persistent.get_first_selection_at::<u32>(&data_path);
 		let prev_value = prev_value.map(Result::ok).flatten().unwrap_or_default();
 		let new_value = ((prev_value as i32) + delta).max(0) as u32;
@@ -724,7 +998,7 @@ fn DeathSaveBoxes(DeathSaveBoxesProps { class_name }: &DeathSaveBoxesProps) -> H
 	let mut classes = classes!("form-check-input");
 	classes.push(class_name.as_str().to_owned());
 
-	let onchange = state.new_dispatch({
+	let onchange = state.new_dispatch("Mark Death Save", {
 		let class_name = class_name.clone();
 		move |evt: web_sys::Event, persistent| {
 			let Some(checked) = evt.input_checked() else {
@@ -1,7 +1,10 @@
 use crate::{
 	components::{context_menu, AnnotatedNumber, AnnotatedNumberCard},
 	page::characters::sheet::CharacterHandle,
-	system::dnd5e::data::Ability,
+	system::dnd5e::{
+		components::{glyph, roll},
+		data::{proficiency, Ability},
+	},
 };
 use yew::prelude::*;
 
@@ -24,20 +27,7 @@ pub fn InitiativeBonus() -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
 	let value = state.initiative_bonus();
 	let on_click = context_menu::use_control_action({
-		move |_, _context| {
-			context_menu::Action::open_root(
-				format!("Initiative Bonus"),
-				html! {<>
-					<div class="text-center fs-5" style="width: 100%; margin-bottom: 10px;">
-						<span>{Ability::Dexterity.long_name()}{":"}</span>
-						<span style="margin-left: 5px;">{match value >= 0 { true => "+", false => "-", }}{value.abs()}</span>
-					</div>
-					<div class="text-block">
-						{TEXT}
-					</div>
-				</>},
-			)
-		}
+		move |_, _context| context_menu::Action::open_root(format!("Initiative Bonus"), html!(<Modal />))
 	});
 	html! {
 		<AnnotatedNumberCard header={"Initiative"} footer={"Bonus"} {on_click}>
@@ -45,3 +35,101 @@ pub fn InitiativeBonus() -> Html {
 		</AnnotatedNumberCard>
 	}
 }
+
+#[function_component]
+fn Modal() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let initiative = state.initiative();
+	let bonus = state.initiative_bonus();
+	let roll_initiative = roll::use_roller().callback(|state, rand| Some(state.roll_initiative(rand)));
+
+	let proficiency = initiative.proficiency();
+	let prof_bonus = *proficiency.value() * state.proficiency_bonus();
+	let mut bonuses = vec![(
+		state.ability_modifier(Ability::Dexterity, None),
+		format!("{} modifier", Ability::Dexterity.long_name()),
+	)];
+	if *proficiency.value() != proficiency::Level::None {
+		let sources = proficiency.sources().iter();
+		let sources = sources.filter_map(|(path, _level)| crate::data::as_feature_path_text(path));
+		let sources = sources.collect::<Vec<_>>().join(", ");
+		bonuses.push((
+			prof_bonus,
+			format!("{} ({sources})", proficiency.value().as_display_name()),
+		));
+	}
+	for (bonus, source) in initiative.bonuses() {
+		bonuses.push((*bonus, crate::data::as_feature_path_text(source).unwrap_or_default()));
+	}
+	let bonus_table = html! {<div style="margin-bottom: 10px;">
+		<table class="table table-compact table-striped m-0">
+			<thead>
+				<tr class="text-center" style="color: var(--bs-heading-color);">
+					<th scope="col" style="width: 100px;">{"Bonus"}</th>
+					<th scope="col">{"Source"}</th>
+				</tr>
+			</thead>
+			<tbody>
+				{bonuses.into_iter().map(|(bonus, source)| html! {
+					<tr>
+						<td class="text-center">{format!("{bonus:+}")}</td>
+						<td>{source}</td>
+					</tr>
+				}).collect::<Vec<_>>()}
+			</tbody>
+		</table>
+	</div>};
+
+	let roll_modifiers = {
+		let dex_modifiers = state.skills().ability_modifiers(Ability::Dexterity).iter_all();
+		let mut entries = dex_modifiers
+			.chain(initiative.modifiers().iter_all())
+			.collect::<Vec<_>>();
+		entries.sort_by_key(|(modifier, _)| *modifier);
+		entries
+	};
+	let roll_modifiers_table = match roll_modifiers.is_empty() {
+		true => html! {},
+		false => html! {<div style="margin-bottom: 10px;">
+			<table class="table table-compact table-striped m-0">
+				<thead>
+					<tr class="text-center" style="color: var(--bs-heading-color);">
+						<th scope="col" style="width: 150px;">{"Modifier"}</th>
+						<th scope="col">{"Target"}</th>
+						<th scope="col">{"Source"}</th>
+					</tr>
+				</thead>
+				<tbody>
+					{roll_modifiers.into_iter().map(|(modifier, item)| html! {
+						<tr>
+							<td class="d-flex">
+								<span aria-label={format!("{modifier:?}")} style="margin-left: 2px; display: block; height: 16px; width: 16px; vertical-align: middle; margin-top: -2px;">
+									<glyph::RollModifier value={modifier} />
+								</span>
+								<span class="flex-grow-1 text-center" style="margin-left: 5px;">{modifier.display_name()}</span>
+							</td>
+							<td class="text-center">{item.context.clone().unwrap_or_else(|| "--".into())}</td>
+							<td>{crate::data::as_feature_path_text(&item.source).unwrap_or_default()}</td>
+						</tr>
+					}).collect::<Vec<_>>()}
+				</tbody>
+			</table>
+		</div>},
+	};
+
+	html! {<>
+		<div class="text-center fs-5" style="width: 100%; margin-bottom: 10px;">
+			<span>{"Bonus:"}</span>
+			<span style="margin-left: 5px;">
+				<roll::Rollable onclick={roll_initiative}>
+					{match bonus >= 0 { true => "+", false => "-", }}{bonus.abs()}
+				</roll::Rollable>
+			</span>
+		</div>
+		{bonus_table}
+		{roll_modifiers_table}
+		<div class="text-block">
+			{TEXT}
+		</div>
+	</>}
+}
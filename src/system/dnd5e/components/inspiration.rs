@@ -7,7 +7,7 @@ use yew::prelude::*;
 #[function_component]
 pub fn Inspiration() -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
-	let onclick = state.new_dispatch(|_, character| {
+	let onclick = state.new_dispatch("Toggle Inspiration", |_, character| {
 		character.inspiration = !character.inspiration;
 		MutatorImpact::None
 	});
@@ -0,0 +1,399 @@
+use crate::{
+	components::{
+		context_menu,
+		database::{use_query_all_typed, use_typed_fetch_callback_tuple, QueryAllArgs, QueryStatus},
+		Spinner,
+	},
+	page::characters::sheet::{joined::editor::mutator_list, CharacterHandle, MutatorImpact},
+	system::{
+		core::SourceId,
+		dnd5e::{
+			components::validate_uint_only,
+			data::{
+				character::{LevelProgression, Persistent},
+				Class,
+			},
+			DnD5e,
+		},
+	},
+	utility::{InputExt, MutatorGroup},
+};
+use std::{collections::HashSet, path::Path, str::FromStr, sync::Arc};
+use yew::prelude::*;
+
+#[function_component]
+pub fn Button() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let onclick = context_menu::use_control_action({
+		move |_: web_sys::MouseEvent, _context| context_menu::Action::open_root("Level Up", html!(<Modal />))
+	});
+
+	let can_level_up = state.persistent().available_level_ups() > 0;
+	let (classes, text) = match (state.persistent().settings.level_progression, can_level_up) {
+		(LevelProgression::Milestone, _) => ("btn-outline-theme", "Level Up"),
+		(LevelProgression::Experience, true) => ("btn-success", "Level Up!"),
+		(LevelProgression::Experience, false) => ("btn-outline-theme", "Experience"),
+	};
+	html! {
+		<button class={classes!("btn", "btn-sm", "me-3", classes)} {onclick}>
+			{text}
+		</button>
+	}
+}
+
+#[function_component]
+fn Modal() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let experience_section = match state.persistent().settings.level_progression {
+		LevelProgression::Milestone => Html::default(),
+		LevelProgression::Experience => html!(<ExperienceSection />),
+	};
+	let level_up_section = match state.persistent().available_level_ups() {
+		0 => html!(<div class="text-center">{"You do not have enough experience to gain a level."}</div>),
+		_ => html!(<Walkthrough />),
+	};
+	html! {<>
+		{experience_section}
+		{level_up_section}
+	</>}
+}
+
+#[function_component]
+fn ExperienceSection() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let amount = use_state_eq(|| 0u32);
+
+	let persistent = state.persistent();
+	let next_level = persistent.level(None) + 1;
+	let next_threshold = persistent.settings.experience_table.threshold(next_level);
+	let progress = match next_threshold {
+		None => format!("{} XP", persistent.experience),
+		Some(threshold) => format!("{} / {threshold} XP (for level {next_level})", persistent.experience),
+	};
+
+	let set_amount = Callback::from({
+		let amount = amount.clone();
+		move |evt: web_sys::Event| {
+			let Some(value) = evt.input_value_t::<u32>() else {
+				return;
+			};
+			amount.set(value);
+		}
+	});
+	let add_experience = Callback::from({
+		let state = state.clone();
+		let amount = amount.clone();
+		move |_| {
+			let gained = *amount;
+			state.dispatch("Gain Experience", move |persistent: &mut Persistent| {
+				persistent.experience = persistent.experience.saturating_add(gained);
+				MutatorImpact::None
+			});
+			amount.set(0);
+		}
+	});
+
+	html! {
+		<div class="mb-3">
+			<h4>{"Experience"}</h4>
+			<div class="text-center mb-2">{progress}</div>
+			<div class="input-group">
+				<span class="input-group-text">{"Gain Experience"}</span>
+				<input
+					type="number" class="form-control text-center"
+					min="0"
+					value={amount.to_string()}
+					onkeydown={validate_uint_only()}
+					onchange={set_amount}
+				/>
+				<button class="btn btn-success" disabled={*amount == 0} onclick={add_experience}>
+					{"Add"}
+				</button>
+			</div>
+		</div>
+	}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum ClassChoice {
+	/// Level up a class that the character already has (by index in `Persistent::classes`).
+	Existing(usize),
+	/// Multiclass into a new class.
+	New(SourceId),
+}
+
+impl ClassChoice {
+	fn as_value(&self) -> String {
+		match self {
+			Self::Existing(idx) => format!("existing:{idx}"),
+			Self::New(id) => format!("new:{id}"),
+		}
+	}
+
+	fn from_value(value: &str) -> Option<Self> {
+		match value.split_once(':') {
+			Some(("existing", idx)) => idx.parse::<usize>().ok().map(Self::Existing),
+			Some(("new", id)) => SourceId::from_str(id).ok().map(Self::New),
+			_ => None,
+		}
+	}
+}
+
+#[function_component]
+fn Walkthrough() -> Html {
+	use crate::system::core::System;
+	let state = use_context::<CharacterHandle>().unwrap();
+	let close_modal = context_menu::use_close_fn::<()>();
+	let choice = use_state_eq(|| None::<ClassChoice>);
+	let hit_points = use_state_eq(|| None::<u32>);
+
+	let query_args = QueryAllArgs::<Class> {
+		system: DnD5e::id().into(),
+		adjust_listings: Some(Arc::new({
+			let iter_classes = state.persistent().classes.iter();
+			let iter_ids = iter_classes.map(|class| class.id.unversioned());
+			let existing_class_ids = iter_ids.collect::<HashSet<_>>();
+			move |mut listings| {
+				listings.retain(|class| !existing_class_ids.contains(&class.id.unversioned()));
+				listings.sort_by(|a, b| a.name.cmp(&b.name));
+				listings
+			}
+		})),
+		..Default::default()
+	};
+	let classes_handle = use_query_all_typed::<Class>(true, Some(query_args));
+
+	let add_new_class = use_typed_fetch_callback_tuple::<Class, u32>(
+		"Add Class".into(),
+		Callback::from({
+			let state = state.clone();
+			move |(mut class, hit_points): (Class, u32)| {
+				class.current_level = 1;
+				class.set_data_path(Path::new(""));
+				let hp_path = class.levels.first().map(|level| level.hit_points.get_data_path());
+				state.dispatch("Add Class", move |persistent: &mut Persistent| {
+					if let Some(hp_path) = hp_path.flatten() {
+						persistent.set_selected(hp_path, Some(hit_points.to_string()));
+					}
+					persistent.add_class(class);
+					MutatorImpact::Recompile
+				});
+			}
+		}),
+	);
+
+	let on_choice_changed = Callback::from({
+		let choice = choice.clone();
+		let hit_points = hit_points.clone();
+		move |evt: web_sys::Event| {
+			let Some(value) = evt.select_value() else {
+				return;
+			};
+			choice.set(ClassChoice::from_value(&value));
+			hit_points.set(None);
+		}
+	});
+
+	// The class which will gain a level, with its current level already incremented.
+	let chosen_class = match &*choice {
+		None => None,
+		Some(ClassChoice::Existing(idx)) => state.persistent().classes.get(*idx).cloned().map(|mut class| {
+			class.current_level += 1;
+			class
+		}),
+		Some(ClassChoice::New(id)) => match classes_handle.status() {
+			QueryStatus::Success(classes) => {
+				let class = classes.iter().find(|class| class.id.unversioned() == *id);
+				class.cloned().map(|mut class| {
+					class.current_level = 1;
+					class
+				})
+			}
+			_ => None,
+		},
+	};
+	// Ensure the selectors for the new level have data paths, so they can be filled in before the level is gained.
+	if let Some(class) = &chosen_class {
+		class.set_data_path(Path::new(""));
+	}
+
+	let mut class_options = Vec::new();
+	for (idx, class) in state.persistent().classes.iter().enumerate() {
+		let value = ClassChoice::Existing(idx);
+		class_options.push(html! {
+			<option value={value.as_value()} selected={Some(&value) == (*choice).as_ref()}>
+				{format!("{} ({} → {})", class.name, class.current_level, class.current_level + 1)}
+			</option>
+		});
+	}
+	let new_class_options = match classes_handle.status() {
+		QueryStatus::Pending => html!(<option disabled={true}>{"Loading classes..."}</option>),
+		QueryStatus::Empty | QueryStatus::Failed(_) => Html::default(),
+		QueryStatus::Success(classes) => {
+			let label = match state.persistent().classes.is_empty() {
+				true => "Classes",
+				false => "Multiclass",
+			};
+			html! {
+				<optgroup {label}>
+					{classes.iter().map(|class| {
+						let value = ClassChoice::New(class.id.unversioned());
+						html! {
+							<option value={value.as_value()} selected={Some(&value) == (*choice).as_ref()}>
+								{class.name.clone()}
+							</option>
+						}
+					}).collect::<Vec<_>>()}
+				</optgroup>
+			}
+		}
+	};
+
+	// At first level, characters always gain the maximum value of their hit die.
+	let is_first_level = state.persistent().level(None) == 0;
+	let hit_points_gained = match (&chosen_class, is_first_level) {
+		(Some(class), true) => Some(class.hit_die.value()),
+		_ => *hit_points,
+	};
+	let hit_points_section = match &chosen_class {
+		None => Html::default(),
+		Some(class) if is_first_level => {
+			html! {
+				<div class="mt-3">
+					<h5>{"Hit Points"}</h5>
+					<span>{format!(
+						"At 1st level, you gain hit points equal to the maximum roll of your hit die ({}).",
+						class.hit_die.value()
+					)}</span>
+				</div>
+			}
+		}
+		Some(class) => {
+			let die = class.hit_die;
+			let average = die.value() / 2 + 1;
+			let roll_hp = Callback::from({
+				let hit_points = hit_points.clone();
+				move |_| {
+					let mut rng = rand::thread_rng();
+					hit_points.set(Some(die.roll(&mut rng, 1)));
+				}
+			});
+			let take_average = Callback::from({
+				let hit_points = hit_points.clone();
+				move |_| hit_points.set(Some(average))
+			});
+			let select_hp = Callback::from({
+				let hit_points = hit_points.clone();
+				move |evt: web_sys::Event| {
+					let value = evt.select_value().map(|value| value.parse::<u32>().ok()).flatten();
+					hit_points.set(value);
+				}
+			});
+			html! {
+				<div class="mt-3">
+					<h5>{"Hit Points"}</h5>
+					<span>{format!(
+						"Roll your hit die ({die}) or take the average ({average}). \
+						Your constitution modifier is added to your hit point maximum automatically."
+					)}</span>
+					<div class="d-flex justify-content-center align-items-center mt-2">
+						<button class="btn btn-outline-theme btn-sm me-2" onclick={roll_hp}>{format!("Roll {die}")}</button>
+						<button class="btn btn-outline-theme btn-sm me-3" onclick={take_average}>
+							{format!("Take Average ({average})")}
+						</button>
+						<span class="glyph heart me-1" />
+						<select class="form-select hit-points py-0 w-auto" onchange={select_hp}>
+							<option selected={hit_points.is_none()}></option>
+							{(1..=die.value()).map(|value| html! {
+								<option value={value.to_string()} selected={*hit_points == Some(value)}>
+									{value}
+								</option>
+							}).collect::<Vec<_>>()}
+						</select>
+					</div>
+				</div>
+			}
+		}
+	};
+
+	let features_section = match &chosen_class {
+		None => Html::default(),
+		Some(class) => {
+			let level = class.levels.get(class.current_level - 1);
+			html! {
+				<div class="mt-3">
+					<h5>{format!("{} Level {}", class.name, class.current_level)}</h5>
+					{match level {
+						Some(level) if !level.is_empty() => mutator_list(&level.mutators, Some(&state)),
+						_ => html!("There are no features to configure at this level."),
+					}}
+				</div>
+			}
+		}
+	};
+
+	let on_confirm = Callback::from({
+		let state = state.clone();
+		let choice = (*choice).clone();
+		let hit_points = hit_points_gained;
+		let hp_path = chosen_class.as_ref().map(|class| {
+			let level = class.levels.get(class.current_level - 1);
+			level.map(|level| level.hit_points.get_data_path()).flatten()
+		});
+		let add_new_class = add_new_class.clone();
+		let close_modal = close_modal.clone();
+		move |_| {
+			let (Some(choice), Some(hit_points)) = (choice.clone(), hit_points) else {
+				return;
+			};
+			match choice {
+				ClassChoice::Existing(idx) => {
+					let hp_path = hp_path.clone().flatten();
+					state.dispatch("Level Up", move |persistent: &mut Persistent| {
+						let Some(class) = persistent.classes.get_mut(idx) else {
+							return MutatorImpact::None;
+						};
+						class.current_level += 1;
+						if let Some(hp_path) = hp_path {
+							persistent.set_selected(hp_path, Some(hit_points.to_string()));
+						}
+						MutatorImpact::Recompile
+					});
+				}
+				ClassChoice::New(id) => {
+					add_new_class.emit((id, hit_points));
+				}
+			}
+			close_modal.emit(());
+		}
+	});
+
+	html! {
+		<div>
+			<h4>{"Level Up"}</h4>
+			<div class="input-group">
+				<span class="input-group-text">{"Class"}</span>
+				<select class="form-select" onchange={on_choice_changed}>
+					<option value="" selected={choice.is_none()}>{"Pick a Class..."}</option>
+					{class_options}
+					{new_class_options}
+				</select>
+			</div>
+			{match classes_handle.status() {
+				QueryStatus::Pending if matches!(*choice, Some(ClassChoice::New(_))) => html!(<Spinner />),
+				_ => Html::default(),
+			}}
+			{hit_points_section}
+			{features_section}
+			<div class="d-flex justify-content-center mt-3">
+				<button
+					class="btn btn-success"
+					disabled={chosen_class.is_none() || hit_points_gained.is_none()}
+					onclick={on_confirm}
+				>
+					{"Gain Level"}
+				</button>
+			</div>
+		</div>
+	}
+}
@@ -1,9 +1,6 @@
 use crate::{
-	components::stop_propagation,
-	page::characters::sheet::CharacterHandle,
-	page::characters::sheet::MutatorImpact,
-	system::dnd5e::data::{action::LimitedUses, character::Persistent},
-	utility::InputExt,
+	components::stop_propagation, page::characters::sheet::CharacterHandle, page::characters::sheet::MutatorImpact,
+	system::dnd5e::data::action::LimitedUses, utility::InputExt,
 };
 use std::sync::Arc;
 use yew::prelude::*;
@@ -21,18 +18,31 @@ impl<'parent> UsesCounter<'parent> {
 			return html! {};
 		}
 
-		let consume_delta = match self.limited_uses.get_uses_path(&self.state) {
-			None => Callback::default(),
-			Some(data_path) => Callback::from({
+		let item_charges = self.limited_uses.get_item_charges(&self.state);
+		let item_id = item_charges.map(|entry| entry.id_path.clone());
+		let consume_delta = match (self.limited_uses.get_uses_path(&self.state), item_id) {
+			(None, None) => Callback::default(),
+			(None, Some(item_id)) => Callback::from({
+				let state = self.state.clone();
+				move |delta: i32| {
+					let new_uses = consumed_uses.saturating_add(delta).max(0) as usize;
+					let item_id = item_id.clone();
+					state.dispatch("Use Item Charges", move |persistent| {
+						persistent.inventory.set_consumed_charges(&item_id, new_uses);
+						MutatorImpact::None
+					});
+				}
+			}),
+			(Some(data_path), _) => Callback::from({
 				let state = self.state.clone();
 				let uses_path = Arc::new(data_path);
 				move |delta: i32| {
 					let new_uses = consumed_uses.saturating_add(delta).max(0) as u32;
 					let uses_path = uses_path.clone();
-					state.dispatch(Box::new(move |persistent: &mut Persistent| {
+					state.dispatch("Use Feature", move |persistent| {
 						persistent.set_selected_value(uses_path.as_path(), new_uses.to_string());
 						MutatorImpact::None
-					}));
+					});
 				}
 			}),
 		};
@@ -63,7 +73,10 @@ impl<'parent> UsesCounter<'parent> {
 					<div class="d-flex align-items-center mt-1">
 						<span class="source-path-sm">{crate::data::as_feature_path_text(resource)}</span>
 						<span class="ms-2" style="font-size: 10px;">
-							{format!("{} / {max_uses} uses remaining{reset_desc}", max_uses - consumed_uses)}
+							{format!("{} / {max_uses} {} remaining{reset_desc}", max_uses - consumed_uses, match item_charges.is_some() {
+								true => "charges",
+								false => "uses",
+							})}
 						</span>
 					</div>
 				</div>
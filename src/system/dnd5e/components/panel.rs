@@ -10,5 +10,11 @@ pub use features::*;
 mod inventory;
 pub use inventory::*;
 
+mod journal;
+pub use journal::*;
+
+mod roll_log;
+pub use roll_log::*;
+
 mod spells;
 pub use spells::*;
@@ -10,10 +10,10 @@ use crate::{
 	system::{
 		core::SourceId,
 		dnd5e::{
-			components::UsesCounter,
+			components::{roll, UsesCounter},
 			data::{
 				action::{ActivationKind, AttackCheckKind, AttackKindValue},
-				character::{ActionBudgetKind, Persistent},
+				character::ActionBudgetKind,
 				AreaOfEffect, Condition, DamageRoll, Feature, IndirectCondition,
 			},
 		},
@@ -65,6 +65,7 @@ impl ActionTag {
 #[function_component]
 pub fn Actions() -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
+	let roller = roll::use_roller();
 	let selected_tags = use_state(|| EnumSet::<ActionTag>::all());
 
 	let context_menu = use_context::<context_menu::Control>().unwrap();
@@ -165,6 +166,16 @@ pub fn Actions() -> Html {
 							return None;
 						};
 						let onclick = open_feature_details.reform(move |_| feature_path.clone());
+						let roll_attack = roller.callback({
+							let name = feature.name.clone();
+							let attack = attack.clone();
+							move |state, rand| state.roll_attack(&name, &attack, rand)
+						});
+						let roll_damage = roller.callback({
+							let name = feature.name.clone();
+							let attack = attack.clone();
+							move |state, rand| state.roll_damage(&name, &attack, rand)
+						});
 
 						let (_check_ability, atk_bonus, dmg_bonus) = attack.evaluate_bonuses(&*state);
 						Some(html! {
@@ -177,10 +188,12 @@ pub fn Actions() -> Html {
 								}}</td>
 								<td class="text-center">{{
 									match attack.check {
-										AttackCheckKind::AttackRoll {..} => html!{<>
-											{match atk_bonus >= 0 { true => "+", false => "-" }}
-											{atk_bonus.abs()}
-										</>},
+										AttackCheckKind::AttackRoll {..} => html!{
+											<roll::Rollable onclick={roll_attack}>
+												{match atk_bonus >= 0 { true => "+", false => "-" }}
+												{atk_bonus.abs()}
+											</roll::Rollable>
+										},
 										AttackCheckKind::SavingThrow { save_ability, ..} => html!{<>
 											{save_ability.abbreviated_name()}
 											<br />
@@ -199,11 +212,14 @@ pub fn Actions() -> Html {
 												None => None,
 												Some(roll_value) => Some(roll_value.evaluate(&state).to_string()),
 											};
-											match (roll_str, bonus) {
+											let damage = match (roll_str, bonus) {
 												(None, bonus) => html! {{bonus.max(0)}},
 												(Some(roll), 0) => html! {{roll}},
 												(Some(roll), 1..=i32::MAX) => html! {<>{roll}{" + "}{bonus}</>},
 												(Some(roll), i32::MIN..=-1) => html! {<>{roll}{" - "}{bonus.abs()}</>},
+											};
+											html! {
+												<roll::Rollable onclick={roll_damage}>{damage}</roll::Rollable>
 											}
 										}
 										None => html! {},
@@ -481,13 +497,13 @@ fn ActionOverview(ActionProps { entry }: &ActionProps) -> Html {
 						move |evt: MouseEvent| {
 							evt.stop_propagation();
 							let conditions_to_apply = conditions_to_apply.clone();
-							state.dispatch(Box::new(move |persistent: &mut Persistent| {
+							state.dispatch("Apply Conditions", move |persistent| {
 								// TODO: Applying a condition should include the path to the feature which caused it (if it was not manually added)
 								for condition in &*conditions_to_apply {
 									persistent.conditions.insert(condition.clone());
 								}
 								MutatorImpact::Recompile
-							}));
+							});
 						}
 					});
 
@@ -581,6 +597,7 @@ struct ModalProps {
 #[function_component]
 fn Modal(ModalProps { path }: &ModalProps) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
+	let roller = roll::use_roller();
 	let fetch_indirect_conditions = use_query_typed::<Condition>();
 	let indirect_condition_ids = use_state_eq(|| Vec::new());
 	use_effect_with(indirect_condition_ids.clone(), {
@@ -668,13 +685,18 @@ fn Modal(ModalProps { path }: &ModalProps) -> Html {
 			match &attack.check {
 				AttackCheckKind::AttackRoll { ability: _, proficient } => {
 					let use_prof = proficient.evaluate(&*state);
+					let roll_attack = roller.callback({
+						let name = feature.name.clone();
+						let attack = attack.clone();
+						move |state, rand| state.roll_attack(&name, &attack, rand)
+					});
 					attack_sections.push(html! {
 						<div class="property">
 							<strong>{"To Hit:"}</strong>
-							<span>
+							<roll::Rollable onclick={roll_attack}>
 								{match atk_bonus >= 0 { true => "+", false => "-" }}
 								{atk_bonus.abs()}
-							</span>
+							</roll::Rollable>
 							<span style="color: var(--bs-gray-600);">
 								{" ("}
 								{&check_ability_mod_str}
@@ -766,12 +788,17 @@ fn Modal(ModalProps { path }: &ModalProps) -> Html {
 						</span>
 					}
 				});
+				let roll_damage = roller.callback({
+					let name = feature.name.clone();
+					let attack = attack.clone();
+					move |state, rand| state.roll_damage(&name, &attack, rand)
+				});
 				attack_sections.push(html! {
 					<div class="property">
 						<strong>{"Damage:"}</strong>
-						<span>
+						<roll::Rollable onclick={roll_damage}>
 							{concat_roll_bonus(&roll_str, bonus)}{format!(" {}", damage_type.display_name())}
-						</span>
+						</roll::Rollable>
 						{suffix_info.unwrap_or_default()}
 					</div>
 				});
@@ -1,6 +1,5 @@
 use crate::{
 	page::characters::sheet::{joined::pronouns, CharacterHandle, MutatorImpact},
-	system::dnd5e::data::character::Persistent,
 	utility::InputExt,
 };
 use yew::prelude::*;
@@ -159,10 +158,10 @@ pub fn AppearanceEditor() -> Html {
 			let Some(value) = evt.input_value() else {
 				return;
 			};
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			state.dispatch("Set Appearance", move |persistent| {
 				persistent.description.appearance = value;
 				MutatorImpact::None
-			}));
+			});
 		}
 	});
 
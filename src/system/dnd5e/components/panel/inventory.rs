@@ -12,7 +12,7 @@ use crate::{
 		dnd5e::{
 			components::{WalletInline, WalletInlineButton},
 			data::{
-				character::{IndirectItem, Persistent, StartingEquipment},
+				character::{EncumbranceLevel, IndirectItem, Persistent, StartingEquipment},
 				currency::Wallet,
 				item::{self, container::item::AsItem, Item},
 				Indirect,
@@ -84,6 +84,39 @@ pub fn Inventory() -> Html {
 		};
 	}
 
+	let attunement = {
+		let attuned_count = state.inventory().attuned_count();
+		let max_attunement = state.max_attunement().value();
+		let has_attunable_items = state
+			.inventory()
+			.entries()
+			.any(|entry| entry.item.requires_attunement());
+		(has_attunable_items || attuned_count > 0).then(|| {
+			html! {
+				<div class="attunement mt-1 ms-2">
+					<strong>{"Attuned Items:"}</strong>
+					<span class="ms-1">{format!("{attuned_count} / {max_attunement}")}</span>
+				</div>
+			}
+		})
+	};
+
+	let encumbrance = {
+		let encumbrance = state.encumbrance();
+		let level = encumbrance.level(state.persistent().settings.variant_encumbrance);
+		html! {
+			<div class="encumbrance mt-1 ms-2">
+				<strong>{"Carrying:"}</strong>
+				<span class="ms-1">
+					{format!("{:.1} / {:.0} lb.", encumbrance.carried_weight(), encumbrance.capacity())}
+				</span>
+				{(level != EncumbranceLevel::Unencumbered).then(|| html! {
+					<span class="badge text-bg-danger ms-2">{level.display_name()}</span>
+				}).unwrap_or_default()}
+			</div>
+		}
+	};
+
 	// TODO: Implement search-inventory functionality
 	// TODO: tag buttons to browse item containers
 	let containers = state
@@ -95,6 +128,8 @@ pub fn Inventory() -> Html {
 	html! {
 		<div class="panel inventory">
 			{search_header}
+			{attunement.unwrap_or_default()}
+			{encumbrance}
 			<div class="sections">
 				<ContainerSection container_id={None} />
 				{containers}
@@ -145,9 +180,9 @@ fn ContainerSection(ContainerSectionProps { container_id }: &ContainerSectionPro
 				(!container.wallet().is_empty()).then(|| html! { <WalletInlineButton id={container_id.clone()} /> });
 			rows = container
 				.iter_by_name()
-				.map(|(item_id, item)| {
+				.map(|(item_id, entry)| {
 					html! {
-						<ItemRow id_path={vec![container_id.clone(), item_id.clone()]} item={item.clone()} />
+						<ItemRow id_path={vec![container_id.clone(), item_id.clone()]} item={entry.item.clone()} />
 					}
 				})
 				.collect::<Vec<_>>();
@@ -354,7 +389,7 @@ fn BrowseStartingEquipment() -> Html {
 				}
 				// finally, actually add the wallet and items to the inventory,
 				// and then close the details
-				state.dispatch(move |persistent| {
+				state.dispatch("Add Starting Equipment", move |persistent| {
 					*persistent.inventory.wallet_mut() += selected_equipment.wallet;
 					for item in selected_equipment.items {
 						persistent.inventory.insert(item);
@@ -488,7 +523,7 @@ fn Group(props: &GroupProps) -> Html {
 			let key = selection_path.clone();
 			let value = idx.to_string();
 			let selected_options = selected_options.clone();
-			state.dispatch(move |persistent| {
+			state.dispatch("Select Starting Equipment", move |persistent| {
 				match (should_be_selected, pick_max) {
 					(false, _) => {
 						persistent.remove_selected_value(&key, value);
@@ -698,7 +733,7 @@ fn SelectItem(
 			};
 			let next_value = (!value.is_empty()).then(move || value);
 			let key = key.clone();
-			state.dispatch(move |persistent| {
+			state.dispatch("Select Starting Equipment", move |persistent| {
 				persistent.set_selected(key, next_value);
 				MutatorImpact::None
 			});
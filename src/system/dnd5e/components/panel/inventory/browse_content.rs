@@ -15,7 +15,6 @@ use crate::{
 				validate_uint_only, GeneralProp, WalletInline,
 			},
 			data::{
-				character::Persistent,
 				currency::Wallet,
 				item::{self, Item},
 			},
@@ -176,12 +175,10 @@ fn BrowsedItemCard(props: &GeneralProp<ItemLocation>) -> Html {
 
 	let add_item = use_typed_fetch_callback_tuple::<Item, Option<Vec<Uuid>>>(
 		"Add Item".into(),
-		state.new_dispatch(Box::new({
-			move |(item, container_id), persistent: &mut Persistent| {
-				persistent.inventory.insert_to(item, &container_id);
-				MutatorImpact::None
-			}
-		})),
+		state.new_dispatch("Add Item", move |(item, container_id), persistent| {
+			persistent.inventory.insert_to(item, &container_id);
+			MutatorImpact::None
+		}),
 	);
 	let add_item = add_item.reform({
 		let id = item.id.unversioned();
@@ -248,26 +245,24 @@ fn AddItemActions(AddItemActionsProps { id, batch_size, worth }: &AddItemActions
 
 	let add_items = use_typed_fetch_callback_tuple::<Item, AddItemArgs>(
 		"Add Items".into(),
-		state.new_dispatch(Box::new({
-			move |args: (Item, AddItemArgs), persistent: &mut Persistent| {
-				let (mut item, (amount, cost, container_id)) = args;
-				let items = if let item::Kind::Simple { count } = &mut item.kind {
-					*count *= amount;
-					vec![item]
-				} else {
-					let mut items = Vec::with_capacity(amount as usize);
-					items.resize(amount as usize, item);
-					items
-				};
-				if !cost.is_empty() {
-					persistent.inventory.wallet_mut().remove(cost, auto_exchange);
-				}
-				for item in items {
-					persistent.inventory.insert_to(item, &container_id);
-				}
-				MutatorImpact::None
+		state.new_dispatch("Add Items", move |args: (Item, AddItemArgs), persistent| {
+			let (mut item, (amount, cost, container_id)) = args;
+			let items = if let item::Kind::Simple { count } = &mut item.kind {
+				*count *= amount;
+				vec![item]
+			} else {
+				let mut items = Vec::with_capacity(amount as usize);
+				items.resize(amount as usize, item);
+				items
+			};
+			if !cost.is_empty() {
+				persistent.inventory.wallet_mut().remove(cost, auto_exchange);
 			}
-		})),
+			for item in items {
+				persistent.inventory.insert_to(item, &container_id);
+			}
+			MutatorImpact::None
+		}),
 	);
 	let add_items = add_items.reform({
 		let id = id.clone();
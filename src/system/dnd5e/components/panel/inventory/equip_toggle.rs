@@ -1,6 +1,6 @@
 use crate::{
 	bootstrap::components::Tooltip, components::stop_propagation, page::characters::sheet::CharacterHandle,
-	page::characters::sheet::MutatorImpact, system::dnd5e::data::character::Persistent, utility::InputExt,
+	page::characters::sheet::MutatorImpact, utility::InputExt,
 };
 use uuid::Uuid;
 use yew::prelude::*;
@@ -34,10 +34,14 @@ pub fn ItemRowEquipBox(
 			let Some(should_be_equipped) = evt.input_checked() else {
 				return;
 			};
-			state.dispatch(Box::new(move |persistent: &mut Persistent| {
+			let label = match should_be_equipped {
+				true => "Equip Item",
+				false => "Unequip Item",
+			};
+			state.dispatch(label, move |persistent| {
 				persistent.inventory.set_equipped(&id, should_be_equipped);
 				MutatorImpact::Recompile
-			}));
+			});
 		}
 	});
 
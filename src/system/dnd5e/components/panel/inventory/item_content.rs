@@ -97,6 +97,10 @@ pub struct ItemBodyProps {
 	pub is_equipped: bool,
 	#[prop_or_default]
 	pub set_equipped: Option<Callback<bool>>,
+	#[prop_or_default]
+	pub is_attuned: bool,
+	#[prop_or_default]
+	pub set_attuned: Option<Callback<bool>>,
 }
 #[function_component]
 pub fn ItemInfo(props: &ItemBodyProps) -> Html {
@@ -384,11 +388,48 @@ pub fn ItemInfo(props: &ItemBodyProps) -> Html {
 					</div>
 				});
 			}
-			if let Some(_attunement) = &equipment.attunement {
-				// TODO: Display attunement
-				// (if mutable) (un)attune button: disabled when all slots filled and not currently attuned
-				// mutators & criteria applied when attuned
-				// warning if attuned and not currently equipped
+			if let Some(attunement) = &equipment.attunement {
+				let attuned_count = state.inventory().attuned_count();
+				let max_attunement = state.max_attunement().value();
+				let slots_full = !props.is_attuned && attuned_count >= max_attunement;
+				let attune_button = props.set_attuned.clone().map(|set_attuned| {
+					let is_attuned = props.is_attuned;
+					let onclick = set_attuned.reform(move |_: MouseEvent| !is_attuned);
+					html! {
+						<button
+							class={classes!("btn", "btn-xs", match is_attuned {
+								true => "btn-outline-theme",
+								false => "btn-theme",
+							})}
+							{onclick}
+							disabled={slots_full}
+						>
+							{match is_attuned {
+								true => "End Attunement",
+								false => "Attune",
+							}}
+						</button>
+					}
+				});
+				equip_sections.push(html! {
+					<div class="border-bottom-theme-muted">
+						<div class="d-flex align-items-center">
+							<strong class="me-2">{"Requires Attunement"}</strong>
+							<span class="me-2">{format!("({attuned_count}/{max_attunement} attuned)")}</span>
+							{attune_button.unwrap_or_default()}
+						</div>
+						{slots_full.then(|| html! {
+							<div class="text-body-secondary">
+								{"You are already attuned to the maximum number of items. \
+								End your attunement with another item to attune to this one."}
+							</div>
+						}).unwrap_or_default()}
+						{(!attunement.mutators.is_empty()).then(|| html! {<>
+							<div>{"You gain the following benefits while attuned to this item:"}</div>
+							{mutator_list(&attunement.mutators, None::<&CharacterHandle>)}
+						</>}).unwrap_or_default()}
+					</div>
+				});
 			}
 			sections.push(html! {
 				<div>
@@ -742,7 +783,7 @@ fn ContainedSpellsSection(props: &ContainedSpellsSectionProps) -> Html {
 	let rank_min = spell_container.capacity.rank_min.unwrap_or(0);
 	let rank_max = spell_container.capacity.rank_max.unwrap_or(MAX_SPELL_RANK);
 
-	let remove_from_container = state.new_dispatch({
+	let remove_from_container = state.new_dispatch("Remove Spell", {
 		let id_path = id_path.clone();
 		move |spell_idx: usize, persistent: &mut Persistent| {
 			let Some(item) = get_inventory_item_mut(persistent, &id_path) else {
@@ -771,7 +812,7 @@ fn ContainedSpellsSection(props: &ContainedSpellsSectionProps) -> Html {
 		spell_container.spells.get_mut(spell_idx)
 	}
 
-	let select_rank = state.new_dispatch({
+	let select_rank = state.new_dispatch("Set Spell Rank", {
 		let id_path = id_path.clone();
 		move |(spell_idx, desired_rank): (usize, Option<u8>), persistent: &mut Persistent| {
 			let Some(contained) = get_container_spell(persistent, &id_path, spell_idx) else {
@@ -782,7 +823,7 @@ fn ContainedSpellsSection(props: &ContainedSpellsSectionProps) -> Html {
 			return MutatorImpact::Recompile;
 		}
 	});
-	let select_atk_bonus = state.new_dispatch({
+	let select_atk_bonus = state.new_dispatch("Set Spell Attack Bonus", {
 		let id_path = id_path.clone();
 		move |(spell_idx, desired_bonus): (usize, Option<i32>), persistent: &mut Persistent| {
 			let Some(contained) = get_container_spell(persistent, &id_path, spell_idx) else {
@@ -793,7 +834,7 @@ fn ContainedSpellsSection(props: &ContainedSpellsSectionProps) -> Html {
 			return MutatorImpact::Recompile;
 		}
 	});
-	let select_save_dc = state.new_dispatch({
+	let select_save_dc = state.new_dispatch("Set Spell Save DC", {
 		let id_path = id_path.clone();
 		move |(spell_idx, desired_dc): (usize, Option<u8>), persistent: &mut Persistent| {
 			let Some(contained) = get_container_spell(persistent, &id_path, spell_idx) else {
@@ -836,6 +877,7 @@ fn ContainedSpellsSection(props: &ContainedSpellsSectionProps) -> Html {
 					rank,
 					save_dc,
 					attack_bonus,
+					..
 				} = contained;
 				let spell = match spell {
 					Indirect::Id(id) => match spells_by_id.get(&*id.minimal()) {
@@ -1194,7 +1236,7 @@ fn SpellListContainerAction(
 		let spell_id = spell_id.clone();
 		move |evt: MouseEvent| {
 			evt.stop_propagation();
-			state.dispatch({
+			state.dispatch("Add Spell", {
 				let container_id = container_id.clone();
 				let spell_id = spell_id.clone();
 				move |persistent: &mut Persistent| {
@@ -1209,6 +1251,7 @@ fn SpellListContainerAction(
 						rank: None,
 						save_dc: None,
 						attack_bonus: None,
+						charges: None,
 					});
 					// TODO: only recompile character when the modal is dismissed
 					return MutatorImpact::Recompile;
@@ -74,14 +74,17 @@ pub fn ItemModal(InventoryItemProps { id_path }: &InventoryItemProps) -> Html {
 	// 		Or maybe this just uses some `inheiret` property and allows user to
 	// 		override any property after copying from some source id.
 
-	let on_delete = state.new_dispatch({
+	let on_delete = state.new_dispatch("Delete Item", {
 		let id_path = id_path.clone();
 		let close_modal = close_modal.clone();
 		move |_: MouseEvent, persistent| {
-			let equipped = id_path.len() == 1 && persistent.inventory.is_equipped(&id_path[0]);
+			let has_mutators = id_path.len() == 1 && {
+				let id = &id_path[0];
+				persistent.inventory.is_equipped(id) || persistent.inventory.is_attuned(id)
+			};
 			let _item = persistent.inventory.remove_at_path(&id_path);
 			close_modal.emit(());
-			match equipped {
+			match has_mutators {
 				true => MutatorImpact::Recompile,
 				false => MutatorImpact::None,
 			}
@@ -95,7 +98,7 @@ pub fn ItemModal(InventoryItemProps { id_path }: &InventoryItemProps) -> Html {
 	};
 	match &item.kind {
 		item::Kind::Simple { .. } => {
-			item_props.on_quantity_changed = Some(state.new_dispatch({
+			item_props.on_quantity_changed = Some(state.new_dispatch("Change Quantity", {
 				let id_path = id_path.clone();
 				move |amt, persistent| {
 					if let Some(item) = persistent.inventory.get_mut_at_path(&id_path) {
@@ -110,13 +113,26 @@ pub fn ItemModal(InventoryItemProps { id_path }: &InventoryItemProps) -> Html {
 		item::Kind::Equipment(_equipment) => {
 			if id_path.len() == 1 {
 				item_props.is_equipped = state.inventory().is_equipped(&id_path[0]);
-				item_props.set_equipped = Some(state.new_dispatch({
+				item_props.set_equipped = Some(state.new_dispatch("Equip Item", {
 					let id: Uuid = id_path[0].clone();
 					move |should_be_equipped, persistent| {
 						persistent.inventory.set_equipped(&id, should_be_equipped);
 						MutatorImpact::Recompile
 					}
 				}));
+				item_props.is_attuned = state.inventory().is_attuned(&id_path[0]);
+				item_props.set_attuned = Some(state.new_dispatch("Attune Item", {
+					let id: Uuid = id_path[0].clone();
+					let max_attunement = state.max_attunement().value();
+					move |should_be_attuned, persistent| match persistent.inventory.set_attuned(
+						&id,
+						should_be_attuned,
+						max_attunement,
+					) {
+						true => MutatorImpact::Recompile,
+						false => MutatorImpact::None,
+					}
+				}));
 			}
 		}
 	}
@@ -135,14 +151,19 @@ pub fn ItemModal(InventoryItemProps { id_path }: &InventoryItemProps) -> Html {
 					n => Some(id_path[0..(n-1)].to_vec()),
 				},
 			}}
-			on_click={state.new_dispatch({
+			on_click={state.new_dispatch("Move Item", {
 				let close_modal = close_modal.clone();
 				let id_path = id_path.clone();
 				move |dst_id: Option<Vec<Uuid>>, persistent| {
-					let Some(item) = persistent.inventory.remove_at_path(&id_path) else { return MutatorImpact::None; };
-					persistent.inventory.insert_to(item, &dst_id);
+					let was_equipped = id_path.len() == 1 && persistent.inventory.is_equipped(&id_path[0]);
+					if persistent.inventory.move_to(&id_path, &dst_id).is_none() {
+						return MutatorImpact::None;
+					}
 					close_modal.emit(());
-					MutatorImpact::None
+					match was_equipped {
+						true => MutatorImpact::Recompile,
+						false => MutatorImpact::None,
+					}
 				}
 			})}
 		/>
@@ -0,0 +1,102 @@
+use crate::{
+	page::characters::sheet::CharacterHandle,
+	system::dnd5e::data::character::{journal_to_markdown, JournalSession},
+	utility::InputExt,
+};
+use time::UtcOffset;
+use yew::prelude::*;
+
+/// The timezone offset of the browser, used to group journal entries by the local day they were made.
+fn local_offset() -> UtcOffset {
+	// getTimezoneOffset is the number of minutes to add to local time to get UTC, so the sign is flipped.
+	let minutes = js_sys::Date::new_0().get_timezone_offset() as i32;
+	UtcOffset::from_whole_seconds(-minutes * 60).unwrap_or(UtcOffset::UTC)
+}
+
+#[function_component]
+pub fn JournalLog() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let selected_session = use_state_eq(|| None::<String>);
+	let offset = local_offset();
+
+	let journal = &state.persistent().journal;
+	if journal.is_empty() {
+		return html! {
+			<div class="panel journal empty">
+				<div class="text-center">
+					{"No journal entries yet. Every change made to the character (and every undo or redo) is recorded here."}
+				</div>
+			</div>
+		};
+	}
+
+	let sessions = journal.sessions(offset);
+	let session_options = sessions.iter().rev().map(|session| {
+		let date = session.date.to_string();
+		html! {
+			<option value={date.clone()} selected={selected_session.as_ref() == Some(&date)}>
+				{format!("{date} ({} entries)", session.entries.len())}
+			</option>
+		}
+	});
+	let onchange = Callback::from({
+		let selected_session = selected_session.clone();
+		move |evt: web_sys::Event| {
+			let value = evt.input_value().filter(|value| !value.is_empty());
+			selected_session.set(value);
+		}
+	});
+	let sessions = match &*selected_session {
+		None => sessions,
+		Some(date) => sessions
+			.into_iter()
+			.filter(|session| session.date.to_string() == *date)
+			.collect(),
+	};
+
+	let name = &state.persistent().description.name;
+	let markdown = journal_to_markdown(name, &sessions, offset);
+	let export_url = format!(
+		"data:text/markdown;charset=utf-8,{}",
+		String::from(js_sys::encode_uri_component(&markdown))
+	);
+	let export_filename = match &*selected_session {
+		None => format!("{name} Session Log.md"),
+		Some(date) => format!("{name} Session Log {date}.md"),
+	};
+
+	html! {
+		<div class="panel journal">
+			<div class="d-flex align-items-center mb-2">
+				<select class="form-select form-select-sm w-auto" {onchange}>
+					<option value="" selected={selected_session.is_none()}>{"All Sessions"}</option>
+					{session_options.collect::<Vec<_>>()}
+				</select>
+				<a class="btn btn-outline-theme btn-xs ms-auto" href={export_url} download={export_filename}>
+					<i class="bi bi-download me-1" />
+					{"Export Markdown"}
+				</a>
+			</div>
+			{sessions.iter().rev().map(|session| session_html(session, offset)).collect::<Vec<_>>()}
+		</div>
+	}
+}
+
+fn session_html(session: &JournalSession<'_>, offset: UtcOffset) -> Html {
+	html! {
+		<div class="mb-2">
+			<h6 class="border-bottom-theme-muted">{session.date.to_string()}</h6>
+			{session.entries.iter().rev().map(|entry| html! {
+				<div class="d-flex" style="font-size: 0.8rem;">
+					<span class="text-body-secondary me-2">{entry.time_of_day(offset)}</span>
+					<div>
+						<div>{&entry.description}</div>
+						{(!entry.details.is_empty()).then(|| html! {
+							<div class="text-body-secondary">{entry.details.join(", ")}</div>
+						}).unwrap_or_default()}
+					</div>
+				</div>
+			}).collect::<Vec<_>>()}
+		</div>
+	}
+}
@@ -0,0 +1,68 @@
+use crate::{
+	components::context_menu,
+	page::characters::sheet::CharacterHandle,
+	system::dnd5e::components::roll::{Outcome, RollHistory},
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+#[function_component]
+pub fn RollLog() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let (history, dispatch) = use_store::<RollHistory>();
+	let id = state.id().unversioned();
+
+	let clear = dispatch.reduce_mut_callback({
+		let id = id.clone();
+		move |history| history.clear(&id)
+	});
+
+	let rolls = history.get(&id);
+	if rolls.is_empty() {
+		return html! {
+			<div class="panel roll-log empty">
+				<div class="text-center">
+					{"No rolls yet. Click a skill, saving throw, attack, or damage value to roll it."}
+				</div>
+			</div>
+		};
+	}
+
+	html! {
+		<div class="panel roll-log">
+			<div class="d-flex align-items-center mb-1">
+				<span class="text-body-secondary" style="font-size: 12px;">
+					{format!("{} rolls this session", rolls.len())}
+				</span>
+				<button class="btn btn-outline-theme btn-xs ms-auto" onclick={clear.reform(|_: MouseEvent| ())}>
+					{"Clear"}
+				</button>
+			</div>
+			<div class="rolls">
+				{rolls.iter().rev().map(|outcome| html! {
+					<Outcome value={outcome.clone()} />
+				}).collect::<Vec<_>>()}
+			</div>
+		</div>
+	}
+}
+
+/// The most recent roll for the character, which opens the full roll log when clicked.
+#[function_component]
+pub fn LastRoll() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let history = use_store_value::<RollHistory>();
+	let onclick = context_menu::use_control_action({
+		|_, _context| context_menu::Action::open_root("Roll Log", html!(<RollLog />))
+	});
+	let Some(outcome) = history.get(&state.id().unversioned()).last() else {
+		return html!();
+	};
+	html! {
+		<div class="last-roll me-2" {onclick}>
+			<i class="bi bi-dice-5 me-1" />
+			<span>{&outcome.label}{": "}</span>
+			<strong>{outcome.total()}</strong>
+		</div>
+	}
+}
@@ -1,5 +1,5 @@
 use crate::{
-	components::{context_menu, database::use_typed_fetch_callback, stop_propagation, Spinner},
+	components::{context_menu, database::use_typed_fetch_callback, stop_propagation, IndirectFetch, Spinner},
 	database::{Criteria, Database},
 	page::characters::sheet::joined::editor::{CollapsableCard, DescriptionSection},
 	page::characters::sheet::CharacterHandle,
@@ -8,15 +8,15 @@ use crate::{
 		self,
 		core::{ModuleId, SourceId},
 		dnd5e::{
-			components::{glyph::Glyph, panel::get_inventory_item_mut},
+			components::{glyph::Glyph, panel::get_inventory_item_mut, roll, ConcentrationStatus},
 			data::{
 				character::{
-					spellcasting::{AbilityOrStat, CasterKind, CastingMethod, RitualCapability, SpellEntry},
-					MAX_SPELL_RANK,
+					spellcasting::{CasterKind, CastingMethod, RitualCapability, SpellEntry},
+					Concentration, SpellCast, MAX_SPELL_RANK,
 				},
-				proficiency,
-				spell::{self, CastingDuration, DurationKind},
-				AreaOfEffect, Indirect, Spell,
+				roll::RollBonus,
+				spell::{self, CastingDuration, DurationKind, MaterialCost},
+				AreaOfEffect, Condition, Indirect, Spell,
 			},
 			DnD5e,
 		},
@@ -26,7 +26,11 @@ use crate::{
 use convert_case::{Case, Casing};
 use futures_util::StreamExt;
 use itertools::Itertools;
-use std::{collections::BTreeMap, path::Path};
+use std::{
+	collections::BTreeMap,
+	path::{Path, PathBuf},
+	rc::Rc,
+};
 use yew::prelude::*;
 
 fn rank_suffix(rank: u8) -> &'static str {
@@ -66,15 +70,21 @@ pub fn Spells() -> Html {
 		let (names, modifier, atk_bonus, save_dc) = state
 			.spellcasting()
 			.iter_casters()
+			.sorted_by(|a, b| a.name().cmp(b.name()))
 			.map(|caster| {
 				let name = caster.name().clone();
 				let modifier = state.ability_modifier(caster.ability, None);
-				let atk_bonus = state.ability_modifier(caster.ability, Some(proficiency::Level::Full));
-				let save_dc = 8 + atk_bonus;
-				(name, modifier, atk_bonus, save_dc)
+				let atk_bonus = state.spellcasting().attack_bonus(&name, &state).map(|(total, _)| total);
+				let save_dc = state.spellcasting().save_dc(&name, &state).map(|(total, _)| total);
+				(
+					name,
+					modifier,
+					atk_bonus.unwrap_or_default(),
+					save_dc.unwrap_or_default(),
+				)
 			})
 			.unzip_n_vec();
-		let names = names.into_iter().sorted().map(|caster_id| {
+		let names = names.into_iter().map(|caster_id| {
 			html! {
 				<ManageCasterButton {caster_id} />
 			}
@@ -107,6 +117,7 @@ pub fn Spells() -> Html {
 	html! {
 		<div class="panel spells">
 			{feature_stats.unwrap_or_default()}
+			<ConcentrationStatus />
 
 			<div class="input-group search my-2">
 				<span class="input-group-text"><i class="bi bi-search"/></span>
@@ -431,7 +442,7 @@ fn spell_section<'c>(state: &'c CharacterHandle, rank: u8, section_props: Sectio
 	);
 
 	let slots = section_props.slot_count.as_ref().map(|(consumed, count)| {
-		let toggle_slot = state.new_dispatch({
+		let toggle_slot = state.new_dispatch("Spell Slot", {
 			let consumed_slots = *consumed;
 			move |evt: web_sys::Event, persistent| {
 				let Some(consume_slot) = evt.input_checked() else {
@@ -469,7 +480,6 @@ fn spell_section<'c>(state: &'c CharacterHandle, rank: u8, section_props: Sectio
 					spell_row(SpellRowProps {
 						state,
 						section_rank: rank,
-						slots: section_props.slot_count,
 						section_spell,
 					})
 				}).collect::<Vec<_>>()}
@@ -490,14 +500,12 @@ fn spell_section<'c>(state: &'c CharacterHandle, rank: u8, section_props: Sectio
 struct SpellRowProps<'c> {
 	state: &'c CharacterHandle,
 	section_rank: u8,
-	slots: Option<(usize, usize)>,
 	section_spell: SectionSpell<'c>,
 }
 fn spell_row<'c>(props: SpellRowProps<'c>) -> Html {
 	let SpellRowProps {
 		state,
 		section_rank,
-		slots,
 		section_spell: SectionSpell { spell, entry, location },
 	} = props;
 
@@ -514,36 +522,17 @@ fn spell_row<'c>(props: SpellRowProps<'c>) -> Html {
 				let slot = UseSpell::Slot {
 					spell_rank: spell.rank,
 					slot_rank: section_rank,
-					slots: slots.clone(),
 				};
 				(slot, None)
 			}
 			CastingMethod::LimitedUses(limited_uses) => {
-				let data_path = limited_uses.get_uses_path(state);
 				let max_uses = limited_uses.get_max_uses(state) as u32;
 				let uses_consumed = limited_uses.get_uses_consumed(state);
-				let kind = UseSpell::Usage(Callback::from(move |state: CharacterHandle| {
-					let onclick = match &data_path {
-						None => Callback::default(),
-						Some(path) => state.new_dispatch({
-							let uses_consumed = uses_consumed;
-							let key = path.clone();
-							move |evt: MouseEvent, persistent| {
-								evt.stop_propagation();
-								let uses_consumed = uses_consumed + 1;
-								persistent.set_selected_value(&key, uses_consumed.to_string());
-								MutatorImpact::None
-							}
-						}),
-					};
-					let uses_remaining = max_uses.saturating_sub(uses_consumed);
-					html! {
-						<button class="btn btn-theme btn-xs px-1" {onclick} disabled={uses_consumed >= max_uses}>
-							{"Use"}
-							<span class="ms-1 d-none" style="font-size: 9px; color: var(--bs-gray-600);">{format!("({uses_remaining}/{max_uses})")}</span>
-						</button>
-					}
-				}));
+				let kind = UseSpell::LimitedUses {
+					data_path: limited_uses.get_uses_path(state),
+					uses_consumed,
+					max_uses,
+				};
 				let text = html! {
 					<span class="ms-1">
 						{format!(
@@ -561,44 +550,26 @@ fn spell_row<'c>(props: SpellRowProps<'c>) -> Html {
 				item_id,
 				consume_spell,
 				consume_item,
+				charges,
 			} => {
-				let use_spell = match *consume_spell {
-					false => UseSpell::AtWill,
-					true => UseSpell::Usage(Callback::from({
-						let item_id = item_id.clone();
-						let spell_id = spell.id.unversioned();
-						let consume_item = *consume_item;
-						move |state: CharacterHandle| {
-							let onclick = state.new_dispatch({
-								let item_id = item_id.clone();
-								let spell_id = spell_id.clone();
-								move |evt: MouseEvent, persistent| {
-									evt.stop_propagation();
-									let container_is_empty = {
-										let Some(item) = get_inventory_item_mut(persistent, &item_id) else {
-											return MutatorImpact::None;
-										};
-										let Some(spell_container) = &mut item.spells else {
-											return MutatorImpact::None;
-										};
-										spell_container.remove(&spell_id);
-										spell_container.spells.is_empty()
-									};
-									if consume_item && container_is_empty {
-										persistent.inventory.remove_at_path(&item_id);
-									}
-									MutatorImpact::Recompile
-								}
-							});
-							html! {
-								<button class="btn btn-theme btn-xs px-1" {onclick}>
-									{"Use"}
-								</button>
-							}
-						}
-					})),
+				let entry = state.inventory().get_entry_at_path(item_id);
+				let charges_remaining = entry.map(|entry| entry.charges_remaining()).unwrap_or_default();
+				let use_spell = match (*consume_spell, charges) {
+					(false, None) => UseSpell::AtWill,
+					_ => UseSpell::FromContainer {
+						item_id: item_id.clone(),
+						consume_spell: *consume_spell,
+						consume_item: *consume_item,
+						charge_cost: *charges,
+						charges_remaining,
+					},
 				};
-				(use_spell, None)
+				let text = charges.map(|cost| {
+					html! {
+						<span class="ms-1">{format!("({cost} of {charges_remaining} charges)")}</span>
+					}
+				});
+				(use_spell, text)
 			}
 			CastingMethod::Cast {
 				can_use_slots: false, ..
@@ -606,14 +577,21 @@ fn spell_row<'c>(props: SpellRowProps<'c>) -> Html {
 		},
 	};
 
+	let concentration = spell.duration.concentration.then(|| Concentration {
+		spell_id: spell.id.unversioned(),
+		spell_name: spell.name.clone(),
+		caster: entry.classified_as.clone(),
+	});
+
 	// TODO: tooltip for casting time duration
 	// TODO: Tooltips for ritual & concentration icons
 	let use_ritual_only = use_kind == UseSpell::RitualOnly;
+	let cast_location = location.clone();
 	html! {
 		<SpellModalRowRoot {location}>
 			<div class="spell-row">
 				<div class="cast-button" onclick={stop_propagation()}>
-					<UseSpellButton kind={use_kind} />
+					<UseSpellButton location={cast_location} kind={use_kind} {concentration} />
 				</div>
 				<div class="name-and-source">
 					{spell_name_and_icons(&state, spell, Some(entry), use_ritual_only)}
@@ -625,13 +603,10 @@ fn spell_row<'c>(props: SpellRowProps<'c>) -> Html {
 	}
 }
 
-pub fn spell_name_and_icons(
-	state: &CharacterHandle,
-	spell: &Spell,
-	entry: Option<&SpellEntry>,
-	ritual_only: bool,
-) -> Html {
-	let can_ritual_cast = spell.casting_time.ritual && {
+/// Returns true if the spell can be cast as a ritual, either because it is only available as a ritual
+/// or because the caster which selected the spell is able to ritual cast its selected spells.
+fn can_ritual_cast(state: &CharacterHandle, spell: &Spell, entry: Option<&SpellEntry>, ritual_only: bool) -> bool {
+	spell.casting_time.ritual && {
 		ritual_only || {
 			let classified = entry.as_ref().map(|entry| entry.classified_as.as_ref()).flatten();
 			let caster = classified.map(|id| state.spellcasting().get_caster(id)).flatten();
@@ -639,7 +614,16 @@ pub fn spell_name_and_icons(
 			let ritual_cast_selected = ritual_casting.map(|ritual| ritual.selected_spells).unwrap_or_default();
 			ritual_cast_selected
 		}
-	};
+	}
+}
+
+pub fn spell_name_and_icons(
+	state: &CharacterHandle,
+	spell: &Spell,
+	entry: Option<&SpellEntry>,
+	ritual_only: bool,
+) -> Html {
+	let can_ritual_cast = can_ritual_cast(state, spell, entry, ritual_only);
 	html! {
 		<div class="name-row">
 			{&spell.name}
@@ -676,14 +660,8 @@ pub fn spell_overview_info(
 		.map(|entry| entry.range.as_ref())
 		.flatten()
 		.unwrap_or(&spell.range);
-	let attack_bonus = entry.map(|entry| entry.attack_bonus).unwrap_or(AbilityOrStat::Stat(0));
-	let save_dc = entry.map(|entry| entry.save_dc).unwrap_or(AbilityOrStat::Stat(0));
 	let cast_at_rank = override_rank.or(entry.map(|entry| entry.rank).flatten());
-	let damage_modifier = entry
-		.map(|entry| entry.damage_ability)
-		.flatten()
-		.map(|ability| state.ability_modifier(ability, Some(proficiency::Level::Full)))
-		.unwrap_or_default();
+	let cast = state.evaluate_spell_cast(spell, entry, cast_at_rank.unwrap_or(spell.rank));
 	html! {
 		<div class="attributes">
 			<div class="attribute-row">
@@ -723,65 +701,7 @@ pub fn spell_overview_info(
 				</span>
 			</div>
 			<div class="attribute-row">
-				{match &spell.check {
-					None => html!(),
-					Some(spell::Check::AttackRoll(_atk_kind)) => {
-						let modifier = match attack_bonus {
-							AbilityOrStat::Stat(modifier) => modifier,
-							AbilityOrStat::Ability(ability) => state.ability_modifier(ability, Some(proficiency::Level::Full)),
-						};
-						html! {<span class="attribute atk-roll">
-							<span class="label">{"Atk Roll:"}</span>
-							{format!("{modifier:+}")}
-						</span>}
-					}
-					Some(spell::Check::SavingThrow(ability, fixed_dc)) => {
-						let abb_name = ability.abbreviated_name().to_case(Case::Upper);
-						let dc = match save_dc {
-							AbilityOrStat::Stat(dc) => dc as i32,
-							AbilityOrStat::Ability(ability) => match fixed_dc {
-								Some(dc) => *dc as i32,
-								None => {
-									let modifier = state.ability_modifier(ability, Some(proficiency::Level::Full));
-									8 + modifier
-								}
-							},
-						};
-						html! {<span class="attribute save-dc">
-							<span class="label">{"Save DC:"}</span>
-							{format!("{abb_name} {dc}")}
-						</span>}
-					}
-				}}
-				{match &spell.damage {
-					None => html!(),
-					Some(damage) => {
-						let upcast_amt = cast_at_rank.map(|rank| rank - spell.rank).unwrap_or(0);
-						let (mut roll_set, mut bonus) = damage.evaluate(&*state, damage_modifier, upcast_amt as u32);
-						for (bonus_dmg_roll, _source) in state.attack_bonuses().get_spell_damage(&spell) {
-							roll_set.push(*bonus_dmg_roll);
-						}
-						bonus += roll_set.take_flat_bonus() as i32;
-						let mut spans = roll_set.rolls().into_iter().enumerate().map(|(idx, roll)| {
-							html! {
-								<span>
-									{(idx != 0).then(|| html!("+")).unwrap_or_default()}
-									{roll.to_string()}
-								</span>
-							}
-						}).collect::<Vec<_>>();
-						if bonus != 0 {
-							spans.push(html! {
-								<span>{format!("{bonus:+}")}</span>
-							});
-						}
-						// TODO: DamageType glyph `damage.damage_type`
-						html! {<span class="attribute damage">
-							<span class="label">{"Damage:"}</span>
-							{spans}
-						</span>}
-					}
-				}}
+				{spell_cast_attributes(&cast, CastRolls::default())}
 				{match &spell.area_of_effect {
 					None => html!(),
 					Some(area_of_effect) => html! {
@@ -802,6 +722,57 @@ pub fn spell_overview_info(
 	}
 }
 
+/// Callbacks which roll the values of a spell cast when they are clicked.
+#[derive(Default)]
+struct CastRolls {
+	attack: Option<Callback<MouseEvent>>,
+	damage: Option<Callback<MouseEvent>>,
+	healing: Option<Callback<MouseEvent>>,
+	temp_hit_points: Option<Callback<MouseEvent>>,
+}
+
+/// The attack roll, save DC, damage, and healing attributes of a spell cast.
+/// When roll callbacks are provided, the corresponding values can be clicked to roll them.
+fn spell_cast_attributes(cast: &SpellCast, rolls: CastRolls) -> Html {
+	let rollable = |value: Html, onclick: Option<Callback<MouseEvent>>| match onclick {
+		None => value,
+		Some(onclick) => html!(<roll::Rollable {onclick}>{value}</roll::Rollable>),
+	};
+	html! {<>
+		{cast.attack_bonus.map(|modifier| html! {
+			<span class="attribute atk-roll">
+				<span class="label">{"Atk Roll:"}</span>
+				{rollable(html!(format!("{modifier:+}")), rolls.attack)}
+			</span>
+		}).unwrap_or_default()}
+		{cast.save_dc.map(|(ability, dc)| html! {
+			<span class="attribute save-dc">
+				<span class="label">{"Save DC:"}</span>
+				{format!("{} {dc}", ability.abbreviated_name().to_case(Case::Upper))}
+			</span>
+		}).unwrap_or_default()}
+		{cast.damage.as_ref().map(|(damage, _damage_type)| html! {
+			// TODO: DamageType glyph
+			<span class="attribute damage">
+				<span class="label">{"Damage:"}</span>
+				{rollable(html!(<span>{damage.to_string()}</span>), rolls.damage)}
+			</span>
+		}).unwrap_or_default()}
+		{cast.healing.as_ref().map(|healing| html! {
+			<span class="attribute healing">
+				<span class="label">{"Healing:"}</span>
+				{rollable(html!(<span>{healing.to_string()}</span>), rolls.healing)}
+			</span>
+		}).unwrap_or_default()}
+		{cast.temp_hit_points.as_ref().map(|temp_hit_points| html! {
+			<span class="attribute temp-hp">
+				<span class="label">{"Temp HP:"}</span>
+				{rollable(html!(<span>{temp_hit_points.to_string()}</span>), rolls.temp_hit_points)}
+			</span>
+		}).unwrap_or_default()}
+	</>}
+}
+
 #[function_component]
 fn SpellModalRowRoot(SpellModalProps { location, children }: &SpellModalProps) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
@@ -853,6 +824,26 @@ fn SpellModal(SpellModalProps { location, children: _ }: &SpellModalProps) -> Ht
 	</>}
 }
 
+fn caster_stat_breakdown(title: &'static str, total: String, breakdown: Vec<RollBonus>) -> Html {
+	html! {
+		<table class="table table-compact table-striped m-0">
+			<thead>
+				<tr class="text-center" style="color: var(--bs-heading-color);">
+					<th scope="col" colspan="2">{title}{": "}{total}</th>
+				</tr>
+			</thead>
+			<tbody>
+				{breakdown.into_iter().map(|RollBonus { value, source }| html! {
+					<tr>
+						<td class="text-center" style="width: 60px;">{format!("{value:+}")}</td>
+						<td>{source}</td>
+					</tr>
+				}).collect::<Vec<_>>()}
+			</tbody>
+		</table>
+	}
+}
+
 #[function_component]
 fn ManageCasterModal(CasterNameProps { caster_id }: &CasterNameProps) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
@@ -904,12 +895,32 @@ fn ManageCasterModal(CasterNameProps { caster_id }: &CasterNameProps) -> Html {
 		max_spells,
 	};
 
-	// TODO: Display modifier/atk bonus/save dc and how they are calculated for this caster.
+	let attack_bonus = state.spellcasting().attack_bonus(caster.name(), &state);
+	let save_dc = state.spellcasting().save_dc(caster.name(), &state);
+
 	// TODO: Display restriction info for the caster's spell list.
 	// TODO: Display rules for when spells can be selected or swapped out.
 	html! {<>
 		<div class="details spells browse">
 			<div>
+				<CollapsableCard
+					id={"caster-stats"}
+					header_content={{html! { {"Spell Attack & Save DC"} }}}
+					body_classes={"caster-stats"}
+				>
+					<div class="row">
+						<div class="col">
+							{attack_bonus.map(|(total, breakdown)| {
+								caster_stat_breakdown("Spell Attack", format!("{total:+}"), breakdown)
+							}).unwrap_or_default()}
+						</div>
+						<div class="col">
+							{save_dc.map(|(total, breakdown)| {
+								caster_stat_breakdown("Save DC", format!("{total}"), breakdown)
+							}).unwrap_or_default()}
+						</div>
+					</div>
+				</CollapsableCard>
 				<CollapsableCard
 					id={"selected-spells"}
 					header_content={{html! { {"Selected Spells"} }}}
@@ -1060,13 +1071,13 @@ fn SpellListAction(
 		"Select Spell".into(),
 		Callback::from({
 			let caster_id = info.id.clone();
-			state.new_dispatch(move |spell: Spell, persistent| {
+			state.new_dispatch("Select Spell", move |spell: Spell, persistent| {
 				persistent.selected_spells.insert(&caster_id, spell);
 				MutatorImpact::None // TODO: maybe recompile when spells are added because of bonuses to spell attacks and other mutators?
 			})
 		}),
 	);
-	let deselect_spell = state.new_dispatch({
+	let deselect_spell = state.new_dispatch("Remove Spell", {
 		let caster_id = info.id.clone();
 		move |spell_id: SourceId, persistent| {
 			persistent.selected_spells.remove(&caster_id, &spell_id);
@@ -1220,6 +1231,23 @@ fn spell_content(spell: &Spell, entry: Option<&SpellEntry>, state: &CharacterHan
 			{spell.duration.concentration.then(|| html!(" (requires concentration)")).unwrap_or_default()}
 		</div>
 	});
+	if !spell.conditions.is_empty() {
+		sections.push(html! {
+			<div class="property d-inline-flex">
+				<strong>{"Conditions:"}</strong>
+				<Tags>
+					{spell.conditions.iter().map(|condition| html! {
+						<IndirectFetch<Condition>
+							indirect={condition.clone()}
+							to_inner={Callback::from(|condition: Rc<Condition>| html! {
+								<Tag>{condition.name.clone()}</Tag>
+							})}
+						/>
+					}).collect::<Vec<_>>()}
+				</Tags>
+			</div>
+		});
+	}
 	if !spell.tags.is_empty() {
 		sections.push(html! {
 			<div class="property d-inline-flex">
@@ -1260,12 +1288,26 @@ fn spell_content(spell: &Spell, entry: Option<&SpellEntry>, state: &CharacterHan
 	if spell.components.somatic {
 		component_items.push(html!("Somatic"));
 	}
-	for (material, consumed) in &spell.components.materials {
+	let materials_worth = spell.components.materials_worth();
+	let can_afford_materials = state.inventory().wallet().contains(&materials_worth, true);
+	for material in &spell.components.materials {
+		let missing = match &material.cost {
+			None => None,
+			Some(MaterialCost::Item(id)) => state
+				.inventory()
+				.find_by_source(id)
+				.is_none()
+				.then_some("not in inventory"),
+			Some(MaterialCost::Worth(_)) => (!can_afford_materials).then_some("not enough currency"),
+		};
 		component_items.push(html! {
 			<span>
 				{"Material: "}
-				{material}
-				{consumed.then(|| " (consumed)").unwrap_or_default()}
+				{&material.description}
+				{material.consumed.then(|| " (consumed)").unwrap_or_default()}
+				{missing.map(|reason| html! {
+					<span class="text-warning-emphasis">{format!(" ({reason})")}</span>
+				}).unwrap_or_default()}
 			</span>
 		});
 	}
@@ -1280,19 +1322,7 @@ fn spell_content(spell: &Spell, entry: Option<&SpellEntry>, state: &CharacterHan
 
 	let desc = {
 		let (atk_bonus, save_dc) = entry
-			.map(|entry| {
-				let atk_bonus = match entry.attack_bonus {
-					AbilityOrStat::Stat(stat) => stat,
-					AbilityOrStat::Ability(ability) => state.ability_modifier(ability, Some(proficiency::Level::Full)),
-				};
-				let save_dc = match entry.save_dc {
-					AbilityOrStat::Stat(stat) => stat as i32,
-					AbilityOrStat::Ability(ability) => {
-						8 + state.ability_modifier(ability, Some(proficiency::Level::Full))
-					}
-				};
-				(atk_bonus, save_dc)
-			})
+			.map(|entry| (state.spell_attack_bonus(entry), state.spell_save_dc(entry)))
 			.unwrap_or((0, 0));
 		let caster_args = std::collections::HashMap::from([
 			("{CasterAtk}".into(), format!("{atk_bonus:+}")),
@@ -1487,7 +1517,11 @@ pub fn AvailableSpellList(props: &AvailableSpellListProps) -> Html {
 
 #[derive(Clone, PartialEq, Properties)]
 struct UseSpellButtonProps {
+	location: SpellLocation,
 	kind: UseSpell,
+	/// The concentration to begin when the spell is cast, if the spell requires concentration.
+	#[prop_or_default]
+	concentration: Option<Concentration>,
 }
 #[derive(Clone, PartialEq)]
 enum UseSpell {
@@ -1496,51 +1530,81 @@ enum UseSpell {
 	Slot {
 		spell_rank: u8,
 		slot_rank: u8,
-		slots: Option<(usize, usize)>,
 	},
-	Usage(Callback<CharacterHandle, Html>),
+	LimitedUses {
+		data_path: Option<PathBuf>,
+		uses_consumed: u32,
+		max_uses: u32,
+	},
+	FromContainer {
+		item_id: Vec<uuid::Uuid>,
+		consume_spell: bool,
+		consume_item: bool,
+		/// The number of the item's charges which are spent to cast the spell.
+		charge_cost: Option<usize>,
+		charges_remaining: usize,
+	},
 }
 #[function_component]
-fn UseSpellButton(UseSpellButtonProps { kind }: &UseSpellButtonProps) -> Html {
+fn UseSpellButton(
+	UseSpellButtonProps {
+		location,
+		kind,
+		concentration,
+	}: &UseSpellButtonProps,
+) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
+	let open_cast_modal = context_menu::use_control_action(|(name, props): (String, CastSpellModalProps), _context| {
+		context_menu::Action::open_root(format!("Cast {name}"), html!(<CastSpellModal ..props />))
+	});
+	let Some((spell, entry)) = location.get(&state) else {
+		return Html::default();
+	};
+	let onclick = open_cast_modal.reform({
+		let name = spell.name.clone();
+		let props = CastSpellModalProps {
+			location: location.clone(),
+			kind: kind.clone(),
+			concentration: concentration.clone(),
+		};
+		move |evt: MouseEvent| {
+			evt.stop_propagation();
+			(name.clone(), props.clone())
+		}
+	});
+	let missing_materials = missing_materials(&state, spell, kind);
+	let has_materials = missing_materials.is_empty();
+	let title = (!has_materials).then(|| missing_materials.join(" "));
+	let text_classes = match has_materials {
+		true => classes!("text-center"),
+		false => classes!("text-center", "text-warning-emphasis"),
+	};
 	match kind {
 		UseSpell::AtWill => html! {
-			<div class="text-center" style="font-size: 9px; font-weight: 700; color: var(--bs-gray-600);">
+			<div class={text_classes} style="font-size: 9px; font-weight: 700; cursor: pointer;" {title} {onclick}>
 				{"AT"}<br />{"WILL"}
 			</div>
 		},
 		UseSpell::RitualOnly => html! {
-			<div class="text-center" style="font-size: 9px; font-weight: 700; color: var(--bs-gray-600);">
+			<div class={text_classes} style="font-size: 9px; font-weight: 700; cursor: pointer;" {title} {onclick}>
 				{"RITUAL"}<br />{"ONLY"}
 			</div>
 		},
-		UseSpell::Slot {
-			spell_rank,
-			slot_rank,
-			slots,
-		} => {
-			let can_cast = slots.as_ref().map(|(consumed, max)| consumed < max).unwrap_or(false);
-			let onclick = state.new_dispatch({
-				let consumed_slots = slots.as_ref().map(|(consumed, _max)| *consumed).unwrap_or(0);
-				let slot_rank = *slot_rank;
-				move |evt: MouseEvent, persistent| {
-					evt.stop_propagation();
-					if can_cast {
-						let data_path = persistent.selected_spells.consumed_slots_path(slot_rank);
-						persistent.set_selected_value(&data_path, (consumed_slots + 1).to_string());
-					}
-					MutatorImpact::None
-				}
-			});
-			// TODO: Revisit when spell panel rows are more fleshed out. This upcast rank span thing is not
-			// very well formated/displayed, esp with such a small text size.
-			let upcast_span = (slot_rank > spell_rank).then(|| html! {
-				<span class="d-flex position-absolute" style="left: 1px; right: 0; top: -8px;">
-					<span style="align-items: flex-start; background-color: #1c9aef; border: 1px solid hsla(0,0%,100%,.5); border-radius: 2px; color: #fff; display: flex; font-size: 8px; line-height: 1; padding: 1px 3px;">
-						{*spell_rank}
-						<span style="font-size: 6px;">{rank_suffix(*spell_rank)}</span>
+		UseSpell::Slot { spell_rank, slot_rank } => {
+			let has_slot = state
+				.slots_for_rank(*slot_rank)
+				.iter()
+				.any(|slots| slots.remaining() > 0);
+			let can_cast = (has_slot || can_ritual_cast(&state, spell, Some(entry), false)) && has_materials;
+			let upcast_span = (slot_rank > spell_rank).then(|| {
+				html! {
+					<span class="upcast-rank">
+						<span class="rank">
+							{*spell_rank}
+							<span class="suffix">{rank_suffix(*spell_rank)}</span>
+						</span>
 					</span>
-				</span>
+				}
 			});
 			let mut btn_classes = classes!("btn", "btn-xs", "px-1");
 			btn_classes.push(match can_cast {
@@ -1548,7 +1612,7 @@ fn UseSpellButton(UseSpellButtonProps { kind }: &UseSpellButtonProps) -> Html {
 				false => classes!("btn-outline-theme", "disabled"),
 			});
 			html! {
-				<button class={btn_classes} {onclick}>
+				<button class={btn_classes} {title} {onclick}>
 					<div class="position-relative">
 						{upcast_span.unwrap_or_default()}
 						{"Cast"}
@@ -1556,6 +1620,415 @@ fn UseSpellButton(UseSpellButtonProps { kind }: &UseSpellButtonProps) -> Html {
 				</button>
 			}
 		}
-		UseSpell::Usage(html_constructor) => html_constructor.emit(state.clone()),
+		UseSpell::LimitedUses {
+			uses_consumed,
+			max_uses,
+			..
+		} => {
+			let uses_remaining = max_uses.saturating_sub(*uses_consumed);
+			html! {
+				<button class="btn btn-theme btn-xs px-1" {title} {onclick} disabled={uses_consumed >= max_uses || !has_materials}>
+					{"Use"}
+					<span class="ms-1 d-none" style="font-size: 9px; color: var(--bs-gray-600);">{format!("({uses_remaining}/{max_uses})")}</span>
+				</button>
+			}
+		}
+		UseSpell::FromContainer {
+			charge_cost,
+			charges_remaining,
+			..
+		} => {
+			let has_charges = charge_cost.map(|cost| cost <= *charges_remaining).unwrap_or(true);
+			html! {
+				<button class="btn btn-theme btn-xs px-1" {onclick} disabled={!has_charges}>
+					{"Use"}
+				</button>
+			}
+		}
+	}
+}
+
+/// Returns the reasons the character can't provide the costly material components of the spell.
+/// Spells cast from an item (e.g. a spell scroll or wand) don't require material components.
+fn missing_materials(state: &CharacterHandle, spell: &Spell, kind: &UseSpell) -> Vec<String> {
+	match kind {
+		UseSpell::FromContainer { .. } => Vec::new(),
+		_ => state.persistent().missing_materials(spell),
+	}
+}
+
+#[derive(Clone, PartialEq, Properties)]
+struct CastSpellModalProps {
+	location: SpellLocation,
+	kind: UseSpell,
+	concentration: Option<Concentration>,
+}
+/// The resource a spell is being cast with.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum CastWith {
+	/// A spell slot of the provided rank.
+	Slot(u8),
+	Ritual,
+	/// The casting method of the spell's entry (at will, limited uses, or an item).
+	Entry,
+}
+#[function_component]
+fn CastSpellModal(
+	CastSpellModalProps {
+		location,
+		kind,
+		concentration,
+	}: &CastSpellModalProps,
+) -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let roller = roll::use_roller();
+	let close = context_menu::use_close_fn::<()>();
+
+	let spell_and_entry = location.get(&state);
+	let slots = match (kind, spell_and_entry) {
+		(UseSpell::Slot { .. }, Some((spell, _entry))) => state.slots_for_rank(spell.rank),
+		_ => Vec::new(),
+	};
+	let can_ritual = spell_and_entry
+		.map(|(spell, entry)| can_ritual_cast(&state, spell, Some(entry), *kind == UseSpell::RitualOnly))
+		.unwrap_or_default();
+	let cast_with = use_state_eq({
+		let initial = match kind {
+			UseSpell::RitualOnly => CastWith::Ritual,
+			UseSpell::Slot { slot_rank, .. } => {
+				let mut available = slots.iter().filter(|slots| slots.remaining() > 0);
+				let preferred = available.clone().find(|slots| slots.rank >= *slot_rank);
+				match preferred.or_else(|| available.next()) {
+					Some(slots) => CastWith::Slot(slots.rank),
+					None if can_ritual => CastWith::Ritual,
+					None => CastWith::Slot(*slot_rank),
+				}
+			}
+			_ => CastWith::Entry,
+		};
+		move || initial
+	});
+	let target_self = use_state_eq({
+		let range = spell_and_entry.map(|(spell, entry)| entry.range.as_ref().unwrap_or(&spell.range));
+		let only_self = matches!(range, Some(spell::Range::OnlySelf));
+		move || only_self
+	});
+	let Some((spell, entry)) = spell_and_entry else {
+		return Html::default();
+	};
+
+	let rank = match *cast_with {
+		CastWith::Slot(rank) => rank,
+		CastWith::Ritual | CastWith::Entry => entry.rank.unwrap_or(spell.rank),
+	};
+	let selected_slots = slots.iter().find(|slots| CastWith::Slot(slots.rank) == *cast_with);
+	let can_cast = match (kind, *cast_with) {
+		(_, CastWith::Ritual) => true,
+		(_, CastWith::Slot(_)) => selected_slots.map(|slots| slots.remaining() > 0).unwrap_or_default(),
+		(
+			UseSpell::LimitedUses {
+				uses_consumed,
+				max_uses,
+				..
+			},
+			_,
+		) => uses_consumed < max_uses,
+		(
+			UseSpell::FromContainer {
+				charge_cost: Some(cost),
+				charges_remaining,
+				..
+			},
+			_,
+		) => cost <= charges_remaining,
+		_ => true,
+	};
+	let missing_materials = missing_materials(&state, spell, kind);
+	let can_cast = can_cast && missing_materials.is_empty();
+	let requires_materials = !matches!(kind, UseSpell::FromContainer { .. });
+	let consumed_materials = spell.components.materials.iter().filter(|material| material.consumed);
+	let consumed_materials = consumed_materials.filter(|material| material.cost.is_some());
+	let consumed_materials = requires_materials.then(|| consumed_materials.collect::<Vec<_>>());
+	let materials_info = html! {<>
+		{missing_materials.iter().map(|reason| html! {
+			<div class="text-center text-warning-emphasis mb-2">{reason}</div>
+		}).collect::<Vec<_>>()}
+		{consumed_materials.unwrap_or_default().into_iter().map(|material| html! {
+			<div class="text-center mb-2">
+				{format!("Casting this spell consumes {}.", material.description)}
+			</div>
+		}).collect::<Vec<_>>()}
+	</>};
+
+	let cast = state.evaluate_spell_cast(spell, Some(entry), rank);
+	let roll_attack = cast.attack_bonus.is_some().then(|| {
+		roller.callback({
+			let spell = spell.clone();
+			let cast = cast.clone();
+			move |character, rand| character.roll_spell_attack(&spell, &cast, rand)
+		})
+	});
+	let roll_damage = cast.damage.is_some().then(|| {
+		roller.callback({
+			let spell = spell.clone();
+			let cast = cast.clone();
+			move |character, rand| character.roll_spell_damage(&spell, &cast, rand)
+		})
+	});
+	let roll_healing = cast.healing.is_some().then(|| {
+		roller.callback({
+			let spell = spell.clone();
+			let cast = cast.clone();
+			move |character, rand| character.roll_spell_healing(&spell, &cast, rand)
+		})
+	});
+	let roll_temp_hit_points = cast.temp_hit_points.is_some().then(|| {
+		roller.callback({
+			let spell = spell.clone();
+			let cast = cast.clone();
+			move |character, rand| character.roll_spell_temp_hit_points(&spell, &cast, rand)
+		})
+	});
+
+	let has_self_effects = cast.healing.is_some() || cast.temp_hit_points.is_some();
+	let toggle_target_self = Callback::from({
+		let target_self = target_self.clone();
+		move |evt: web_sys::Event| {
+			let Some(checked) = evt.input_checked() else {
+				return;
+			};
+			target_self.set(checked);
+		}
+	});
+	let target_self_toggle = has_self_effects.then(|| {
+		html! {
+			<div class="form-check d-flex justify-content-center mb-2">
+				<input
+					class="form-check-input me-2" type="checkbox" id="cast-spell-target-self"
+					checked={*target_self} onchange={toggle_target_self}
+				/>
+				<label class="form-check-label" for="cast-spell-target-self">
+					{"Cast on yourself (apply healing and temporary hit points)"}
+				</label>
+			</div>
+		}
+	});
+
+	let select_cast_with = Callback::from({
+		let cast_with = cast_with.clone();
+		move |evt: web_sys::Event| {
+			cast_with.set(match evt.select_value_t::<u8>() {
+				Some(rank) => CastWith::Slot(rank),
+				None => CastWith::Ritual,
+			});
+		}
+	});
+	let cast_with_select = match kind {
+		UseSpell::Slot { .. } => html! {
+			<div class="d-flex align-items-center justify-content-center mb-2">
+				<span class="me-2">{"Cast using"}</span>
+				<select class="form-select form-select-sm w-auto" onchange={select_cast_with}>
+					{slots.iter().map(|slots| html! {
+						<option
+							value={slots.rank.to_string()}
+							selected={*cast_with == CastWith::Slot(slots.rank)}
+							disabled={slots.remaining() == 0}
+						>
+							{format!(
+								"{}{} Rank Slot ({}/{})",
+								slots.rank, rank_suffix(slots.rank), slots.remaining(), slots.total
+							)}
+						</option>
+					}).collect::<Vec<_>>()}
+					{can_ritual.then(|| html! {
+						<option value="ritual" selected={*cast_with == CastWith::Ritual}>{"Ritual (no slot)"}</option>
+					}).unwrap_or_default()}
+				</select>
+			</div>
+		},
+		UseSpell::AtWill => html!(<div class="text-center mb-2">{"This spell can be cast at will."}</div>),
+		UseSpell::RitualOnly => html!(<div class="text-center mb-2">{"This spell can only be cast as a ritual."}</div>),
+		UseSpell::LimitedUses {
+			uses_consumed,
+			max_uses,
+			..
+		} => html! {
+			<div class="text-center mb-2">
+				{format!("{} of {max_uses} uses remaining.", max_uses.saturating_sub(*uses_consumed))}
+			</div>
+		},
+		UseSpell::FromContainer {
+			consume_spell,
+			consume_item,
+			charge_cost,
+			charges_remaining,
+			..
+		} => html! {
+			<div class="text-center mb-2">
+				{charge_cost.map(|cost| html! {
+					<div>{format!("Casting this spell spends {cost} of the item's {charges_remaining} remaining charges.")}</div>
+				}).unwrap_or_default()}
+				{consume_spell.then(|| html! {
+					<div>
+						{"Casting this spell removes it from the item"}
+						{consume_item.then_some(", and the item is destroyed when it has no spells left").unwrap_or_default()}
+						{"."}
+					</div>
+				}).unwrap_or_default()}
+			</div>
+		},
+	};
+
+	let current_concentration = state.persistent().concentration.as_ref();
+	let ends_concentration = concentration.as_ref().zip(current_concentration);
+	let ends_concentration = ends_concentration.filter(|(started, current)| started != current);
+	let concentration_warning = ends_concentration.map(|(_started, current)| {
+		html! {
+			<div class="text-center text-warning-emphasis mb-2">
+				{"You can only concentrate on one spell at a time. Casting this spell will end your concentration on "}
+				<strong>{&current.spell_name}</strong>
+				{"."}
+			</div>
+		}
+	});
+
+	let on_cast = Callback::from({
+		let state = state.clone();
+		let label = AttrValue::from(format!("Cast {}", spell.name));
+		let kind = kind.clone();
+		let cast_with = *cast_with;
+		let slot_total = selected_slots.map(|slots| slots.total).unwrap_or_default();
+		let spell_id = spell.id.unversioned();
+		let concentration = concentration.clone();
+		let close = close.clone();
+		let self_effects = (has_self_effects && *target_self).then(|| (spell.clone(), cast.clone()));
+		let max_hp = state.max_hit_points().value();
+		let roller = roller.clone();
+		let material_spell = requires_materials.then(|| spell.clone());
+		move |_: MouseEvent| {
+			if !can_cast {
+				return;
+			}
+			// Effects on the caster are rolled before dispatching, so the mutation only applies fixed amounts.
+			// The rolls are recorded by the mutation itself, once the cast is known to have gone through.
+			let mut healing = None;
+			let mut temp_hit_points = None;
+			if let Some((spell, cast)) = &self_effects {
+				let mut rand = rand::thread_rng();
+				healing = state.roll_spell_healing(spell, cast, &mut rand);
+				temp_hit_points = state.roll_spell_temp_hit_points(spell, cast, &mut rand);
+			}
+			let roller = roller.clone();
+			let kind = kind.clone();
+			let spell_id = spell_id.clone();
+			let concentration = concentration.clone();
+			let material_spell = material_spell.clone();
+			state.dispatch(label.clone(), move |persistent| {
+				let mut impact = MutatorImpact::None;
+				if let Some(spell) = &material_spell {
+					if !persistent.missing_materials(spell).is_empty() {
+						return MutatorImpact::None;
+					}
+				}
+				match (&kind, cast_with) {
+					(_, CastWith::Ritual) => {}
+					(_, CastWith::Slot(rank)) => {
+						if !persistent.spend_spell_slot(rank, slot_total) {
+							return MutatorImpact::None;
+						}
+					}
+					(
+						UseSpell::LimitedUses {
+							data_path: Some(data_path),
+							uses_consumed,
+							..
+						},
+						CastWith::Entry,
+					) => {
+						persistent.set_selected_value(data_path, (uses_consumed + 1).to_string());
+					}
+					(
+						UseSpell::FromContainer {
+							item_id,
+							consume_spell,
+							consume_item,
+							charge_cost,
+							..
+						},
+						CastWith::Entry,
+					) => {
+						if let Some(cost) = charge_cost {
+							if !persistent.inventory.consume_charges(item_id, *cost) {
+								return MutatorImpact::None;
+							}
+						}
+						if *consume_spell {
+							let container_is_empty = {
+								let Some(item) = get_inventory_item_mut(persistent, item_id) else {
+									return MutatorImpact::None;
+								};
+								let Some(spell_container) = &mut item.spells else {
+									return MutatorImpact::None;
+								};
+								spell_container.remove(&spell_id);
+								spell_container.spells.is_empty()
+							};
+							if *consume_item && container_is_empty {
+								persistent.inventory.remove_at_path(item_id);
+							}
+							impact = MutatorImpact::Recompile;
+						}
+					}
+					_ => {}
+				}
+				if let Some(spell) = &material_spell {
+					persistent.consume_materials(spell);
+					let mut consumed = spell.components.materials.iter().filter(|material| material.consumed);
+					if consumed.any(|material| matches!(material.cost, Some(MaterialCost::Item(_)))) {
+						impact = MutatorImpact::Recompile;
+					}
+				}
+				if let Some(outcome) = healing {
+					*persistent.hit_points_mut() += (outcome.total(), max_hp);
+					roller.record(outcome);
+				}
+				if let Some(outcome) = temp_hit_points {
+					persistent.hit_points_mut().grant_temp_hp(outcome.total().max(0) as u32);
+					roller.record(outcome);
+				}
+				if let Some(concentration) = concentration {
+					persistent.begin_concentration(concentration);
+				}
+				impact
+			});
+			close.emit(());
+		}
+	});
+
+	html! {
+		<div class="cast-spell">
+			{cast_with_select}
+			<div class="attributes mb-2">
+				<div class="attribute-row justify-content-center">
+					{spell_cast_attributes(&cast, CastRolls {
+						attack: roll_attack,
+						damage: roll_damage,
+						healing: roll_healing,
+						temp_hit_points: roll_temp_hit_points,
+					})}
+				</div>
+			</div>
+			{target_self_toggle.unwrap_or_default()}
+			{materials_info}
+			{concentration_warning.unwrap_or_default()}
+			<div class="d-flex justify-content-center">
+				<button class="btn btn-theme btn-sm mx-1" onclick={on_cast} disabled={!can_cast}>
+					{"Cast"}
+				</button>
+				<button class="btn btn-outline-theme btn-sm mx-1" onclick={close.reform(|_: MouseEvent| ())}>
+					{"Close"}
+				</button>
+			</div>
+		</div>
 	}
 }
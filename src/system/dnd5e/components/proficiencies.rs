@@ -2,8 +2,13 @@ use crate::{
 	bootstrap::components::Tooltip,
 	components::context_menu,
 	page::characters::sheet::CharacterHandle,
-	system::dnd5e::data::{AttributedValueMap, WeaponProficiency},
+	system::dnd5e::{
+		components::{glyph, roll},
+		data::{Ability, AttributedValueMap, WeaponProficiency},
+	},
+	utility::InputExt,
 };
+use enumset::EnumSet;
 use yew::prelude::*;
 
 #[function_component]
@@ -22,7 +27,7 @@ pub fn Proficiencies() -> Html {
 						format!("{}{}", value.to_string(), context.as_ref().map(|s| format!(" ({s})")).unwrap_or_default())
 					})}
 					{make_proficiencies_section_long("Weapons", &proficiencies.weapons, WeaponProficiency::display_name)}
-					{make_proficiencies_section_long("Tools", &proficiencies.tools, String::to_string)}
+					<ToolsSection />
 				</>},
 			)
 		}
@@ -36,7 +41,10 @@ pub fn Proficiencies() -> Html {
 					format!("{}{}", value.to_string(), context.as_ref().map(|s| format!(" ({s})")).unwrap_or_default())
 				})}
 				{make_proficiencies_section("Weapons", &proficiencies.weapons, WeaponProficiency::display_name)}
-				{make_proficiencies_section("Tools", &proficiencies.tools, String::to_string)}
+				{make_proficiencies_section("Tools", &proficiencies.tools, |tool| {
+					let modifier = state.tool_modifier(tool, DEFAULT_TOOL_ABILITY);
+					format!("{tool} ({modifier:+})")
+				})}
 			</div>
 		</div>
 	}
@@ -125,3 +133,85 @@ where
 		</div>
 	}
 }
+
+/// The ability used for tool checks until another is selected in the tools section.
+const DEFAULT_TOOL_ABILITY: Ability = Ability::Dexterity;
+
+/// The tools the character is proficient with, and the bonus to a check with each tool
+/// when using the selected ability (which includes proficiency which applies to all checks of that ability).
+#[function_component]
+fn ToolsSection() -> Html {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let roller = roll::use_roller();
+	let ability = use_state_eq(|| DEFAULT_TOOL_ABILITY);
+	let select_ability = Callback::from({
+		let ability = ability.clone();
+		move |evt: web_sys::Event| {
+			let Some(value) = evt.select_value_t::<Ability>() else {
+				return;
+			};
+			ability.set(value);
+		}
+	});
+
+	let tools = state.tool_proficiencies();
+	let rows = tools.iter().map(|(tool, proficiency)| {
+		let level = tools.check_proficiency(tool, *ability);
+		let modifier = state.tool_modifier(tool, *ability);
+		let roll_check = roller.callback({
+			let tool = tool.clone();
+			let ability = *ability;
+			move |character, rand| Some(character.roll_tool_check(&tool, ability, rand))
+		});
+		html! {
+			<tr>
+				<td class="text-center">{tool}</td>
+				<td class="text-center">
+					<Tooltip tag="span" content={level.as_display_name()}>
+						<glyph::ProficiencyLevel value={level} />
+					</Tooltip>
+				</td>
+				<td class="text-center">
+					<roll::Rollable onclick={roll_check}>{format!("{modifier:+}")}</roll::Rollable>
+				</td>
+				<td>
+					{proficiency.sources().iter().map(|(path, _level)| html! {
+						<div>
+							{crate::data::as_feature_path_text(path)}
+						</div>
+					}).collect::<Vec<_>>()}
+				</td>
+			</tr>
+		}
+	});
+	let rows = rows.collect::<Vec<_>>();
+	if rows.is_empty() {
+		return make_proficiencies_section_long("Tools", &state.other_proficiencies().tools, String::to_string);
+	}
+
+	html! {
+		<div class="proficiency-section" style={"border-style: none;"}>
+			<div class="d-flex align-items-center justify-content-end my-1">
+				<span class="me-2">{"Check Ability"}</span>
+				<select class="form-select form-select-sm w-auto" onchange={select_ability}>
+					{EnumSet::<Ability>::all().into_iter().map(|option| html! {
+						<option value={option.long_name()} selected={option == *ability}>
+							{option.long_name()}
+						</option>
+					}).collect::<Vec<_>>()}
+				</select>
+			</div>
+			<table class="table table-compact table-striped m-0">
+				<thead>
+					<tr class="text-center" style="font-size: 1.1rem; color: var(--bs-heading-color);">
+						<th scope="col" style="width: 200px;">{"Tools"}</th>
+						<th scope="col">{"Proficiency"}</th>
+						<th scope="col">{"Bonus"}</th>
+						<th scope="col">{"Sources"}</th>
+					</tr>
+				</thead>
+				<tbody>{rows}</tbody>
+			</table>
+		</div>
+	}
+}
@@ -5,6 +5,7 @@ use crate::{
 	system::dnd5e::{
 		components::{glyph::Glyph, validate_uint_only},
 		data::{
+			character::{RestEffects, RestHitPoints, SpentHitDice},
 			roll::{Die, Roll, RollSet},
 			Ability, Rest,
 		},
@@ -37,103 +38,44 @@ pub fn Button(GeneralProp { value }: &GeneralProp<Rest>) -> Html {
 fn Modal(GeneralProp { value }: &GeneralProp<Rest>) -> Html {
 	let state = use_context::<CharacterHandle>().unwrap();
 	let hit_dice_to_consume = use_state_eq(|| HitDiceToConsume::default());
-	let close_modal = context_menu::use_close_fn();
+	let show_results = context_menu::use_control_action(|changes: Vec<String>, _context| {
+		context_menu::Action::open_root("Rest Complete", html!(<RestResults value={changes} />))
+	});
 
-	let constitution_mod = state.ability_modifier(Ability::Constitution, None);
-	let commit_rest = state.new_dispatch({
+	let effects = match *value {
+		Rest::Long => state.long_rest(),
+		Rest::Short => state.short_rest(&hit_dice_to_consume.spent_hit_dice(), hit_dice_to_consume.rolled_hp),
+	};
+	let commit_rest = Callback::from({
+		let state = state.clone();
+		let effects = effects.clone();
 		let rest = *value;
-		let hit_dice_to_consume = hit_dice_to_consume.clone();
-		let max_hp = state.max_hit_points().value();
-		let resets = value
-			.resets_to_apply()
-			.into_iter()
-			.map(|rest| state.rest_resets().get(rest).clone().into_iter())
-			.flatten()
-			.collect::<Vec<_>>();
-		move |_, persistent| {
-			let mut rng = rand::thread_rng();
-			let mut changes = Vec::new();
-			match rest {
-				Rest::Long => {
-					persistent.hit_points.current = max_hp;
-					changes.push(format!("Set hit points to max ({max_hp})."));
-
-					persistent.hit_points.temp = 0;
-					changes.push(format!("Cleared temporary hit points."));
-
-					persistent.hit_points.failure_saves = 0;
-					persistent.hit_points.success_saves = 0;
-					changes.push(format!("Cleared saving throws."));
-
-					let hit_die_paths = persistent
-						.classes
-						.iter()
-						.filter_map(|class| class.hit_die_selector.get_data_path())
-						.collect::<Vec<_>>();
-					for data_path in hit_die_paths {
-						persistent.set_selected(data_path, None);
-					}
-					changes.push(format!("Restored all hit dice."));
-				}
-				Rest::Short => {
-					let hp = hit_dice_to_consume.hp_to_gain(constitution_mod);
-					persistent.hit_points.current += hp;
-					changes.push(format!("Increased hit points by {hp}."));
-
-					for (amount_used, data_path) in hit_dice_to_consume.consumed_uses() {
-						let prev_value = persistent.get_first_selection_at::<u32>(data_path);
-						let prev_value = prev_value.map(Result::ok).flatten().unwrap_or(0);
-						let new_value = prev_value.saturating_add(*amount_used).to_string();
-						persistent.set_selected(data_path, Some(new_value));
-
-						let class_name = data_path.components().next().unwrap().as_os_str();
-						let class_name = class_name.to_str().unwrap();
-						changes.push(format!("Used {amount_used} hit dice from {class_name}."));
-					}
-				}
-			};
-			for entry in &resets {
-				let uses_to_remove = match &entry.restore_amount {
-					None => None,
-					Some(roll) => Some(roll.roll(&mut rng)),
-				};
-
-				let path_str = entry.source.display().to_string();
-				let path_str = path_str.replace("\\", "/");
-				match &uses_to_remove {
-					None => changes.push(format!("Restored all uses to {path_str}.")),
-					Some(gained_uses) => changes.push(format!("Restored {gained_uses} uses to {path_str}.")),
-				}
-				for data_path in &entry.data_paths {
-					let new_value = match &uses_to_remove {
-						None => None,
-						Some(gained_uses) => {
-							let prev_value = persistent.get_first_selection_at::<u32>(data_path);
-							let prev_value = prev_value.map(Result::ok).flatten().unwrap_or(0);
-							let new_value = prev_value.saturating_sub(*gained_uses);
-							(new_value > 0).then(|| new_value.to_string())
-						}
-					};
-					persistent.set_selected(data_path, new_value);
-				}
-			}
-
-			// TODO: These changes can be recorded in a commit when its time to save data.
+		move |_: MouseEvent| {
+			// Dice are rolled before the rest is applied, so that the mutation only makes fixed changes.
+			let rolls = effects.roll(&mut rand::thread_rng());
+			let changes = effects.apply_to(&mut state.persistent().clone(), &rolls);
 			log::debug!("{changes:?}");
 
-			close_modal.emit(());
-			MutatorImpact::None
+			let effects = effects.clone();
+			state.dispatch(format!("{rest} Rest"), move |persistent| {
+				effects.apply_to(persistent, &rolls);
+				match effects.requires_recompile() {
+					true => MutatorImpact::Recompile,
+					false => MutatorImpact::None,
+				}
+			});
+			show_results.emit(changes);
 		}
 	});
 
 	let can_take_rest = *value != Rest::Short || hit_dice_to_consume.has_valid_input();
 
 	html! {<>
-		<div class="text-block">{value.description()}</div>
+		<div class="text-block">{value.description(state.persistent().settings.rest_variant)}</div>
 		{(*value == Rest::Short).then(|| html!(
 			<HitDiceSection value={hit_dice_to_consume.clone()} />
 		)).unwrap_or_default()}
-		<ProjectedRestorations value={*value} />
+		<ProjectedRestorations value={effects} />
 		<div class="d-flex justify-content-center">
 			<button class="btn btn-success" disabled={!can_take_rest} onclick={commit_rest}>
 				{"Take "}{value}{" Rest"}
@@ -144,7 +86,7 @@ fn Modal(GeneralProp { value }: &GeneralProp<Rest>) -> Html {
 
 #[derive(Clone, PartialEq, Default)]
 struct HitDiceToConsume {
-	by_class: HashMap<String, (u32, PathBuf)>,
+	by_class: HashMap<String, SpentHitDice>,
 	total_rolls: RollSet,
 	rolled_hp: u32,
 }
@@ -152,14 +94,19 @@ impl HitDiceToConsume {
 	fn add(&mut self, class_name: &str, die: Die, delta: i32, data_path: &PathBuf) {
 		match self.by_class.get_mut(class_name) {
 			None if delta > 0 => {
-				self.by_class
-					.insert(class_name.to_owned(), (delta as u32, data_path.clone()));
+				let spent = SpentHitDice {
+					class_name: class_name.to_owned(),
+					die,
+					amount: delta as u32,
+					data_path: data_path.clone(),
+				};
+				self.by_class.insert(class_name.to_owned(), spent);
 			}
-			Some((die_count, _data_path)) if delta > 0 => {
-				*die_count = die_count.saturating_add(delta as u32);
+			Some(spent) if delta > 0 => {
+				spent.amount = spent.amount.saturating_add(delta as u32);
 			}
-			Some((die_count, _data_path)) if delta < 0 => {
-				*die_count = die_count.saturating_sub(-delta as u32);
+			Some(spent) if delta < 0 => {
+				spent.amount = spent.amount.saturating_sub(-delta as u32);
 			}
 			_ => {}
 		}
@@ -191,8 +138,8 @@ impl HitDiceToConsume {
 		((self.rolled_hp as i32) + roll_count * constitution_mod).max(0) as u32
 	}
 
-	fn consumed_uses(&self) -> impl Iterator<Item = &(u32, PathBuf)> + '_ {
-		self.by_class.values()
+	fn spent_hit_dice(&self) -> Vec<SpentHitDice> {
+		self.by_class.values().cloned().collect()
 	}
 }
 
@@ -282,7 +229,7 @@ fn HitDiceSection(props: &GeneralProp<UseStateHandle<HitDiceToConsume>>) -> Html
 	html!(<div class="mt-3">
 		<h4>{"Hit Dice"}</h4>
 		<span>
-			{"Hit Dice are restored on a Long Rest. \
+			{"Half of your total Hit Dice are restored on a Long Rest. \
 			Using a hit die restores the rolled amount of hit points \
 			+ your constitution modifier per hit die rolled."}
 		</span>
@@ -371,58 +318,42 @@ fn HitDiceUsageInput(
 }
 
 #[function_component]
-fn ProjectedRestorations(GeneralProp { value }: &GeneralProp<Rest>) -> Html {
-	let state = use_context::<CharacterHandle>().unwrap();
+fn ProjectedRestorations(GeneralProp { value: effects }: &GeneralProp<RestEffects>) -> Html {
 	let mut sections = Vec::new();
-	match *value {
-		Rest::Long => {
-			sections.push(
-				html!(<li style="color: var(--bs-warning);">{"WARNING: Your death saving throws will be reset."}</li>),
-			);
-			sections.push(html!(<li>{"Regain all lost hit points."}</li>));
-			sections.push(html!(<li>{"Temporary Hit Points will reset to 0."}</li>));
-
-			let mut budget = 0;
-			let mut max_hit_dice = EnumMap::<Die, usize>::default();
-			for class in &state.persistent().classes {
-				budget += class.current_level;
-				max_hit_dice[class.hit_die] += class.current_level;
-			}
-			let mut hit_die_halves = Vec::with_capacity(max_hit_dice.len());
-			for (die, total) in max_hit_dice.into_iter().rev() {
-				if budget <= 0 {
-					continue;
-				}
-				let total = total / 2;
-				let total = match total.cmp(&budget) {
-					std::cmp::Ordering::Less => {
-						budget = budget.saturating_sub(total);
-						total
-					}
-					_ => std::mem::take(&mut budget),
-				};
-				if total > 0 {
-					hit_die_halves.push(Roll::from((total as u32, die)).to_string());
-				}
-			}
-			if let Some(hit_dice) = crate::utility::list_as_english(hit_die_halves, "and") {
-				sections.push(
-					html!(<li>{format!("Regain up to {hit_dice} hit dice (half your total hit dice, minimuim of 1).")}</li>),
-				);
-			}
-		}
-		Rest::Short => {}
+	if let RestHitPoints::Restore(_) = effects.hit_points {
+		sections.push(
+			html!(<li style="color: var(--bs-warning);">{"WARNING: Your death saving throws will be reset."}</li>),
+		);
+		sections.push(html!(<li>{"Regain all lost hit points."}</li>));
+		sections.push(html!(<li>{"Temporary Hit Points will reset to 0."}</li>));
 	}
-	for rest in value.resets_to_apply() {
-		for entry in state.rest_resets().get(rest) {
-			let amt = match &entry.restore_amount {
-				None => "all".to_owned(),
-				Some(roll) => roll.to_string(),
-			};
-			let path_str = crate::data::as_feature_path_text(&entry.source).unwrap_or_default();
-			let description = format!("Restore {amt} uses of {path_str}.");
-			sections.push(html!(<li>{description}</li>));
+	if effects.reduce_exhaustion {
+		sections.push(html!(<li>{"Reduce your exhaustion level by 1."}</li>));
+	}
+	if effects.rest == Rest::Long {
+		let mut recovered_dice = Vec::with_capacity(effects.hit_dice.len());
+		for change in &effects.hit_dice {
+			let roll = Roll::from(((-change.delta).max(0) as u32, change.die));
+			recovered_dice.push(format!("{roll} ({})", change.class_name));
 		}
+		if let Some(hit_dice) = crate::utility::list_as_english(recovered_dice, "and") {
+			sections.push(html!(<li>{format!(
+				"Regain {hit_dice} hit dice (half your total hit dice, minimum of 1, largest dice first)."
+			)}</li>));
+		}
+	}
+	for entry in &effects.resets {
+		let amt = match &entry.restore_amount {
+			None => "all".to_owned(),
+			Some(roll) => roll.to_string(),
+		};
+		let path_str = crate::data::as_feature_path_text(&entry.source).unwrap_or_default();
+		let description = format!("Restore {amt} uses of {path_str}.");
+		sections.push(html!(<li>{description}</li>));
+	}
+	for item in &effects.charges {
+		let description = format!("Regain {} charges of {}.", item.reset, item.item_name);
+		sections.push(html!(<li>{description}</li>));
 	}
 	html! {
 		<div class="mt-3">
@@ -434,3 +365,16 @@ fn ProjectedRestorations(GeneralProp { value }: &GeneralProp<Rest>) -> Html {
 		</div>
 	}
 }
+
+#[function_component]
+fn RestResults(GeneralProp { value: changes }: &GeneralProp<Vec<String>>) -> Html {
+	let close_modal = context_menu::use_close_fn();
+	html! {<>
+		<ul>
+			{changes.iter().map(|change| html!(<li>{change}</li>)).collect::<Vec<_>>()}
+		</ul>
+		<div class="d-flex justify-content-center">
+			<button class="btn btn-success" onclick={close_modal}>{"Done"}</button>
+		</div>
+	</>}
+}
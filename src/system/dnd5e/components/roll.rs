@@ -1,6 +1,17 @@
 use super::GeneralProp;
-use crate::system::dnd5e::data::roll;
+use crate::{
+	page::characters::sheet::CharacterHandle,
+	system::{
+		core::SourceId,
+		dnd5e::data::{
+			character::Character,
+			roll::{self, RollKind, RollOutcome},
+		},
+	},
+};
+use std::collections::HashMap;
 use yew::prelude::*;
+use yewdux::prelude::*;
 
 #[function_component]
 pub fn ModifierIcon(props: &GeneralProp<roll::Modifier>) -> Html {
@@ -11,3 +22,135 @@ pub fn ModifierIcon(props: &GeneralProp<roll::Modifier>) -> Html {
 	});
 	html! { <span class={classes} /> }
 }
+
+static MAX_HISTORY_PER_CHARACTER: usize = 100;
+
+/// The rolls made for each character during this session, most recent last.
+#[derive(Clone, Default, PartialEq, Store)]
+pub struct RollHistory(HashMap<SourceId, Vec<RollOutcome>>);
+
+impl RollHistory {
+	pub fn get(&self, id: &SourceId) -> &[RollOutcome] {
+		match self.0.get(id) {
+			Some(rolls) => rolls.as_slice(),
+			None => &[],
+		}
+	}
+
+	pub fn push(&mut self, id: SourceId, outcome: RollOutcome) {
+		let rolls = self.0.entry(id).or_default();
+		rolls.push(outcome);
+		if rolls.len() > MAX_HISTORY_PER_CHARACTER {
+			rolls.remove(0);
+		}
+	}
+
+	pub fn clear(&mut self, id: &SourceId) {
+		self.0.remove(id);
+	}
+}
+
+#[hook]
+pub fn use_roller() -> Roller {
+	let state = use_context::<CharacterHandle>().unwrap();
+	let dispatch = use_dispatch::<RollHistory>();
+	Roller { state, dispatch }
+}
+
+/// Makes rolls for the current character, recording them in the `RollHistory`.
+#[derive(Clone)]
+pub struct Roller {
+	state: CharacterHandle,
+	dispatch: Dispatch<RollHistory>,
+}
+
+impl Roller {
+	/// Creates a click callback which makes the roll. The click event does not propagate,
+	/// so rolls can be made from elements which are inside of other clickable elements.
+	pub fn callback<F>(&self, roll: F) -> Callback<MouseEvent>
+	where
+		F: Fn(&Character, &mut rand::rngs::ThreadRng) -> Option<RollOutcome> + 'static,
+	{
+		let state = self.state.clone();
+		let dispatch = self.dispatch.clone();
+		Callback::from(move |evt: MouseEvent| {
+			evt.stop_propagation();
+			let Some(outcome) = roll(&*state, &mut rand::thread_rng()) else {
+				return;
+			};
+			let id = state.id().unversioned();
+			dispatch.reduce_mut(move |history| history.push(id, outcome));
+		})
+	}
+
+	/// Records a roll which was made as part of some other change to the character
+	/// (e.g. the healing of a spell which was cast on the character).
+	pub fn record(&self, outcome: RollOutcome) {
+		let id = self.state.id().unversioned();
+		self.dispatch.reduce_mut(move |history| history.push(id, outcome));
+	}
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct RollableProps {
+	pub onclick: Callback<MouseEvent>,
+	#[prop_or_default]
+	pub children: Children,
+}
+
+/// Wraps some displayed bonus or roll, making it a clickable roll.
+#[function_component]
+pub fn Rollable(RollableProps { onclick, children }: &RollableProps) -> Html {
+	html! {
+		<span class="rollable" {onclick}>
+			{children.clone()}
+		</span>
+	}
+}
+
+#[function_component]
+pub fn Outcome(GeneralProp { value }: &GeneralProp<RollOutcome>) -> Html {
+	let mut classes = classes!("roll-outcome");
+	if value.is_critical_success() {
+		classes.push("critical-success");
+	}
+	if value.is_critical_failure() {
+		classes.push("critical-failure");
+	}
+	let kind_suffix = match &value.kind {
+		RollKind::Check { modifier: None } => None,
+		RollKind::Check {
+			modifier: Some(modifier),
+		} => Some(modifier.display_name().to_owned()),
+		RollKind::Damage { damage_type } => damage_type.map(|damage_type| damage_type.display_name().to_owned()),
+		RollKind::Healing { .. } => None,
+	};
+	html! {
+		<div class={classes}>
+			<div class="d-flex align-items-baseline">
+				<strong>{&value.label}</strong>
+				{kind_suffix.map(|suffix| html! {
+					<span class="suffix ms-1">{"("}{suffix}{")"}</span>
+				}).unwrap_or_default()}
+				<span class="total ms-auto">{value.total()}</span>
+			</div>
+			<div class="breakdown">
+				{value.dice.iter().map(|die| html! {
+					<span class={classes!("die", die.dropped.then_some("dropped"))}>
+						{format!("{}: {}", die.die, die.value)}
+					</span>
+				}).collect::<Vec<_>>()}
+				{value.bonuses.iter().map(|bonus| html! {
+					<span class="bonus">
+						{match bonus.value >= 0 { true => "+", false => "-" }}
+						{bonus.value.abs()}
+						{format!(" ({})", bonus.source)}
+					</span>
+				}).collect::<Vec<_>>()}
+			</div>
+			{value.notes.iter().map(|note| html! {
+				<div class="note">{note}</div>
+			}).collect::<Vec<_>>()}
+		</div>
+	}
+}
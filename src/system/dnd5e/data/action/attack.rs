@@ -76,7 +76,8 @@ impl Attack {
 		return abilities;
 	}
 
-	fn best_ability_modifier(&self, primary: Ability, state: &Character) -> (Ability, i32) {
+	/// Returns the ability (of all those the attack can use) with the largest modifier, and that modifier.
+	pub fn best_ability_modifier(&self, primary: Ability, state: &Character) -> (Ability, i32) {
 		let abilities = self.all_ability_options(primary, state).into_iter();
 		let abilities = abilities.map(|ability| {
 			let modifier = state.ability_modifier(ability, None);
@@ -94,7 +95,9 @@ impl Attack {
 					.evaluate(state)
 					.then_some(state.proficiency_bonus())
 					.unwrap_or_default();
-				let atk_bonus = modifier + prof_bonus;
+				let roll_bonuses = state.attack_bonuses().get_attack_roll(self);
+				let roll_bonus = roll_bonuses.into_iter().map(|(bonus, _source)| bonus).sum::<i32>();
+				let atk_bonus = modifier + prof_bonus + roll_bonus;
 				let dmg_bonus = modifier;
 				(Some(ability), atk_bonus, dmg_bonus)
 			}
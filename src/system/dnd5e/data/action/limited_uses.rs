@@ -1,7 +1,7 @@
 use crate::{
 	kdl_ext::NodeContext,
 	system::dnd5e::{
-		data::{character::Character, Rest},
+		data::{character::Character, item::container::item::EquipableEntry, Rest},
 		Value,
 	},
 	utility::selector,
@@ -20,7 +20,8 @@ pub enum LimitedUses {
 	/// They can be referred to via `Consumer` by the path to the owner (often feature/action).
 	Usage(UseCounterData),
 	Consumer {
-		/// The path to the `LimitedUses::Usage` which this usage consumes from.
+		/// The path to the `LimitedUses::Usage` which this usage consumes from,
+		/// or the path to an inventory item whose charges are consumed.
 		resource: PathBuf,
 		/// The amount of uses of `resource` that this usage consumes.
 		cost: u32,
@@ -63,6 +64,20 @@ impl LimitedUses {
 		}
 	}
 
+	/// Returns the inventory entry whose charges are consumed by this usage, if the resource is an item with charges.
+	/// Each item tracks its own charges, so if several items share the resource path (i.e. have the same name),
+	/// the first which can pay the cost of this usage is used.
+	pub fn get_item_charges<'a>(&self, character: &'a Character) -> Option<&'a EquipableEntry> {
+		let Self::Consumer { resource, cost } = self else {
+			return None;
+		};
+		let id_paths = character.features().get_item_charges(resource).into_iter();
+		let entries = id_paths.filter_map(|id_path| character.inventory().get_entry_at_path(id_path));
+		let entries = entries.collect::<Vec<_>>();
+		let can_pay = entries.iter().find(|entry| entry.charges_remaining() >= *cost as usize);
+		can_pay.or(entries.first()).copied()
+	}
+
 	pub fn get_uses_path(&self, character: &Character) -> Option<PathBuf> {
 		let Some(data) = self.get_use_data(character) else {
 			return None;
@@ -71,6 +86,9 @@ impl LimitedUses {
 	}
 
 	pub fn get_uses_consumed(&self, character: &Character) -> u32 {
+		if let Some(entry) = self.get_item_charges(character) {
+			return entry.consumed_charges as u32;
+		}
 		let Some(data) = self.get_use_data(character) else {
 			return 0;
 		};
@@ -78,6 +96,12 @@ impl LimitedUses {
 	}
 
 	pub fn get_max_uses(&self, character: &Character) -> i32 {
+		if let Some(entry) = self.get_item_charges(character) {
+			return entry
+				.charges()
+				.map(|charges| charges.capacity as i32)
+				.unwrap_or_default();
+		}
 		let Some(data) = self.get_use_data(character) else {
 			return 0;
 		};
@@ -85,6 +109,13 @@ impl LimitedUses {
 	}
 
 	pub fn get_reset_rest(&self, character: &Character) -> Option<Rest> {
+		if let Some(entry) = self.get_item_charges(character) {
+			let charges = entry.charges();
+			return charges
+				.map(|charges| charges.reset.as_ref())
+				.flatten()
+				.map(|reset| reset.rest);
+		}
 		let Some(data) = self.get_use_data(character) else {
 			return None;
 		};
@@ -303,4 +334,41 @@ mod test {
 			Ok(())
 		}
 	}
+
+	#[test]
+	fn item_charges_with_same_name() {
+		use crate::system::dnd5e::data::{
+			character::Persistent,
+			item::{
+				equipment::{Charges, Equipment},
+				Item, Kind,
+			},
+		};
+		let wand = Item {
+			name: "Wand of Web".into(),
+			kind: Kind::Equipment(Equipment {
+				charges: Some(Charges {
+					capacity: 7,
+					reset: None,
+				}),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+		let mut persistent = Persistent::default();
+		let first = persistent.inventory.push(wand.clone());
+		let second = persistent.inventory.push(wand);
+		persistent.inventory.set_consumed_charges(&[first], 7);
+		let character = Character::from(persistent);
+		assert_eq!(character.features().get_item_charges("Inventory/Wand of Web").len(), 2);
+
+		let usage = LimitedUses::Consumer {
+			resource: "Inventory/Wand of Web".into(),
+			cost: 1,
+		};
+		let entry = usage.get_item_charges(&character).expect("a wand should have charges");
+		assert_eq!(entry.id_path, vec![second]);
+		assert_eq!(usage.get_uses_consumed(&character), 0);
+		assert_eq!(usage.get_max_uses(&character), 7);
+	}
 }
@@ -8,12 +8,15 @@ pub use formula::*;
 pub struct ArmorClass {
 	formulas: Vec<(ArmorClassFormula, PathBuf)>,
 	bonuses: Vec<(i32, Option<String>, PathBuf)>,
+	// bonuses which only apply in the character's current combat situation (e.g. cover)
+	combat_bonuses: Vec<(i32, PathBuf)>,
 }
 impl Default for ArmorClass {
 	fn default() -> Self {
 		Self {
 			formulas: vec![(ArmorClassFormula::default(), PathBuf::new())],
 			bonuses: Vec::new(),
+			combat_bonuses: Vec::new(),
 		}
 	}
 }
@@ -26,6 +29,10 @@ impl ArmorClass {
 		self.bonuses.push((bonus, context, source));
 	}
 
+	pub fn push_combat_bonus(&mut self, bonus: i32, source: PathBuf) {
+		self.combat_bonuses.push((bonus, source));
+	}
+
 	pub fn evaluate(&self, state: &Character) -> i32 {
 		let best_formula_value = self
 			.formulas
@@ -36,6 +43,12 @@ impl ArmorClass {
 		best_formula_value + self.bonuses_without_context()
 	}
 
+	/// Returns the armor class including bonuses from the character's current combat situation.
+	pub fn evaluate_situational(&self, state: &Character) -> i32 {
+		let combat_bonus = self.combat_bonuses.iter().map(|(bonus, _)| *bonus).sum::<i32>();
+		self.evaluate(state) + combat_bonus
+	}
+
 	fn bonuses_without_context(&self) -> i32 {
 		self.bonuses
 			.iter()
@@ -53,6 +66,10 @@ impl ArmorClass {
 	pub fn iter_bonuses(&self) -> impl Iterator<Item = &(i32, Option<String>, PathBuf)> {
 		self.bonuses.iter()
 	}
+
+	pub fn iter_combat_bonuses(&self) -> impl Iterator<Item = &(i32, PathBuf)> {
+		self.combat_bonuses.iter()
+	}
 }
 
 #[derive(Clone, Copy, PartialEq, Debug)]
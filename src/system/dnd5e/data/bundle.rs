@@ -5,7 +5,7 @@ use crate::{
 		core::SourceId,
 		dnd5e::{
 			data::{character::Character, description, Ability},
-			BoxedMutator, SystemComponent,
+			BoxedCriteria, BoxedMutator, SystemComponent,
 		},
 	},
 	utility::{MutatorGroup, NotInList},
@@ -23,7 +23,7 @@ pub struct Bundle {
 	/// The group this bundle is in (Race, RaceVariant, Lineage, Upbringing, Background, Feat, etc).
 	pub category: String,
 	pub description: description::Info,
-	/// The bundles required for this one to be added to a character.
+	/// The requirements which a character must meet for this bundle to be added to it.
 	pub requirements: Vec<BundleRequirement>,
 	/// The number of times this bundle can be added to a character.
 	pub limit: usize,
@@ -31,13 +31,50 @@ pub struct Bundle {
 	pub feature_config: Option<FeatureConfig>,
 }
 
-// TODO: Could bundle requirements just be a criteria/bool-evaluator?
 #[derive(Clone, PartialEq, Debug)]
 pub enum BundleRequirement {
 	/// The character must have a bundle with the specified category and name.
 	Bundle { category: String, name: String },
 	/// The character must have an ability score of at least a specific amount.
 	Ability(Ability, u32),
+	/// The character must pass some arbitrary criteria
+	/// (e.g. proficiency with medium armor, or the ability to cast a spell).
+	Criteria(BoxedCriteria),
+}
+
+impl BundleRequirement {
+	pub fn evaluate(&self, character: &Character) -> Result<(), String> {
+		match self {
+			Self::Bundle { category, name } => match character.applied_bundles().contains(category, name) {
+				true => Ok(()),
+				false => Err(format!("Missing {category} \"{name}\".")),
+			},
+			Self::Ability(ability, score) => {
+				let current = character.ability_scores().get(*ability).score().0;
+				match current >= *score {
+					true => Ok(()),
+					false => Err(format!(
+						"{} is {current}, but must be at least {score}.",
+						ability.long_name()
+					)),
+				}
+			}
+			Self::Criteria(criteria) => character.evaluate(criteria),
+		}
+	}
+}
+
+impl std::fmt::Display for BundleRequirement {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Self::Bundle { category, name } => write!(f, "{category}: {name}"),
+			Self::Ability(ability, score) => write!(f, "{} >= {score}", ability.long_name()),
+			Self::Criteria(criteria) => match criteria.description() {
+				Some(desc) => write!(f, "{desc}"),
+				None => write!(f, "custom criteria"),
+			},
+		}
+	}
 }
 
 #[derive(Clone, PartialEq, Debug, Default)]
@@ -56,7 +93,7 @@ impl MutatorGroup for Bundle {
 	}
 
 	fn apply_mutators(&self, stats: &mut Character, parent: &Path) {
-		// TODO: Check requirements before applying
+		stats.applied_bundles_mut().insert(self, &parent.join(&self.name));
 		if let Some(config) = &self.feature_config {
 			let feature = Feature {
 				name: self.name.clone(),
@@ -75,6 +112,15 @@ impl MutatorGroup for Bundle {
 	}
 }
 
+impl Bundle {
+	/// Returns the reason for each requirement of this bundle which the character does not meet.
+	pub fn unmet_requirements(&self, character: &Character) -> Vec<String> {
+		let iter = self.requirements.iter();
+		iter.filter_map(|requirement| requirement.evaluate(character).err())
+			.collect()
+	}
+}
+
 kdlize::impl_kdl_node!(Bundle, "bundle");
 
 impl SystemComponent for Bundle {
@@ -97,6 +143,8 @@ impl SystemComponent for Bundle {
 						let reqs = requirements.get_mut("Ability").unwrap();
 						reqs.insert(ability.long_name().to_owned(), serde_json::json!(score));
 					}
+					// Criteria can only be evaluated against a character, so they aren't included in the metadata
+					BundleRequirement::Criteria(_) => {}
 				}
 			}
 			requirements
@@ -144,7 +192,11 @@ impl FromKdl<NodeContext> for Bundle {
 					let score = node.next_i64_req()? as u32;
 					requirements.push(BundleRequirement::Ability(ability, score));
 				}
-				kind => return Err(NotInList(kind.into(), vec!["Bundle", "Ability"]).into()),
+				"Criteria" => {
+					let criteria = node.query_req_t::<BoxedCriteria>("scope() > criteria")?;
+					requirements.push(BundleRequirement::Criteria(criteria));
+				}
+				kind => return Err(NotInList(kind.into(), vec!["Bundle", "Ability", "Criteria"]).into()),
 			}
 		}
 
@@ -185,6 +237,11 @@ impl AsKdl for Bundle {
 					.with_entry("Ability")
 					.with_entry(ability.long_name())
 					.with_entry(*score as i64),
+				BundleRequirement::Criteria(criteria) => {
+					let mut kdl = NodeBuilder::default().with_entry("Criteria");
+					kdl.push_child_t("criteria", criteria);
+					kdl
+				}
 			};
 			node.push_child(kdl.build("requirement"));
 		}
@@ -203,3 +260,71 @@ impl AsKdl for Bundle {
 		node
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::{data::character::Persistent, evaluator::HasArmorEquipped};
+
+	fn feat(name: &str, requirements: Vec<BundleRequirement>) -> Bundle {
+		Bundle {
+			name: name.into(),
+			category: "Feat".into(),
+			requirements,
+			limit: 1,
+			..Default::default()
+		}
+	}
+
+	fn unmet_requirements(character: &Character) -> Vec<String> {
+		let iter = character.unmet_requirements().iter();
+		iter.map(|unmet| unmet.requirement.clone()).collect()
+	}
+
+	#[test]
+	fn unmet_requirements_are_reported() {
+		let mut persistent = Persistent::default();
+		persistent.ability_scores[Ability::Strength] = 10;
+		persistent.bundles.push(feat(
+			"Heavy Armor Master",
+			vec![
+				BundleRequirement::Ability(Ability::Strength, 13),
+				BundleRequirement::Bundle {
+					category: "Feat".into(),
+					name: "Heavily Armored".into(),
+				},
+				BundleRequirement::Criteria(
+					HasArmorEquipped {
+						inverted: true,
+						..Default::default()
+					}
+					.into(),
+				),
+			],
+		));
+		let character = Character::from(persistent);
+		assert_eq!(
+			unmet_requirements(&character),
+			vec!["Strength >= 13".to_owned(), "Feat: Heavily Armored".to_owned()]
+		);
+	}
+
+	#[test]
+	fn requirements_met() {
+		let mut persistent = Persistent::default();
+		persistent.ability_scores[Ability::Strength] = 14;
+		persistent.bundles.push(feat("Heavily Armored", vec![]));
+		persistent.bundles.push(feat(
+			"Heavy Armor Master",
+			vec![
+				BundleRequirement::Ability(Ability::Strength, 13),
+				BundleRequirement::Bundle {
+					category: "Feat".into(),
+					name: "Heavily Armored".into(),
+				},
+			],
+		));
+		let character = Character::from(persistent);
+		assert_eq!(unmet_requirements(&character), Vec::<String>::new());
+	}
+}
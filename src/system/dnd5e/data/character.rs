@@ -1,5 +1,9 @@
 use std::path::PathBuf;
 
+mod combat;
+pub use combat::*;
+mod damage_intake;
+pub use damage_intake::*;
 mod defaults;
 pub use defaults::*;
 mod persistent;
@@ -10,6 +14,11 @@ mod character;
 pub use character::*;
 mod hit_point;
 pub use hit_point::*;
+mod rest;
+pub use rest::*;
+mod rolls;
+mod spell_cast;
+pub use spell_cast::*;
 
 #[derive(Clone, Default, PartialEq, Debug)]
 pub struct AttributedValue<T> {
@@ -1,5 +1,5 @@
 use super::{
-	spellcasting, AttackBonuses, DefaultsBlock, Features, HitPoint, HitPoints, RestResets, Spellcasting,
+	spellcasting, AttackBonuses, CombatState, DefaultsBlock, Features, HitPoint, HitPoints, RestResets, Spellcasting,
 	StartingEquipment,
 };
 use crate::{
@@ -9,8 +9,9 @@ use crate::{
 		dnd5e::{
 			data::{
 				character::{
-					AbilityScores, Defenses, Derived, DerivedDescription, MaxHitPoints, Persistent, SavingThrows,
-					Senses, Skills, Speeds,
+					AbilityScores, AppliedBundles, Defenses, Derived, DerivedDescription, Encumbrance, Initiative,
+					MaxAttunement, MaxHitPoints, Persistent, SavingThrows, Senses, Skills, Speeds, ToolProficiencies,
+					UnmetRequirement,
 				},
 				item::container::Inventory,
 				proficiency, Ability, ArmorClass, Feature, OtherProficiencies,
@@ -41,6 +42,8 @@ pub struct Character {
 	character: Persistent,
 	derived: Derived,
 	mutators: Vec<MutatorEntry>,
+	// situational state (e.g. cover) which lasts only as long as the character sheet is open, and is never saved
+	combat: CombatState,
 }
 #[derive(Clone, PartialEq, Debug)]
 struct MutatorEntry {
@@ -57,6 +60,7 @@ impl From<Persistent> for Character {
 			character: persistent,
 			derived: Derived::default(),
 			mutators: Vec::new(),
+			combat: CombatState::default(),
 		};
 		character.recompile_minimal();
 		character
@@ -69,6 +73,7 @@ impl Character {
 			character: persistent,
 			derived: Derived::default(),
 			mutators: Vec::new(),
+			combat: CombatState::default(),
 		}
 	}
 
@@ -78,10 +83,12 @@ impl Character {
 	}
 
 	#[cfg(test)]
-	fn recompile_minimal(&mut self) {
+	pub(crate) fn recompile_minimal(&mut self) {
 		self.initiaize_recompile();
 		self.insert_mutators();
 		self.apply_cached_mutators();
+		self.apply_combat_state();
+		self.check_bundle_requirements();
 	}
 
 	fn initiaize_recompile(&mut self) {
@@ -121,6 +128,8 @@ impl Character {
 		self.derived.additional_objects = cache;
 
 		self.apply_cached_mutators();
+		self.apply_combat_state();
+		self.check_bundle_requirements();
 
 		self.inventory_mut().resolve_indirection(&provider).await?;
 		self.persistent_mut().conditions.resolve_indirection(&provider).await?;
@@ -204,6 +213,31 @@ impl Character {
 		}
 	}
 
+	/// Applies the bonuses granted by the character's current combat situation, once all other mutators
+	/// have been applied (so that bonuses like a raised shield can see the final inventory).
+	fn apply_combat_state(&mut self) {
+		let combat = self.combat.clone();
+		combat.apply(self);
+	}
+
+	/// Evaluates the requirements of every applied bundle, now that all mutators have been applied,
+	/// recording any requirements the character does not meet.
+	fn check_bundle_requirements(&mut self) {
+		let mut unmet = Vec::new();
+		for (path, requirements) in self.derived.applied_bundles.requirements() {
+			for requirement in requirements {
+				if let Err(reason) = requirement.evaluate(self) {
+					unmet.push(UnmetRequirement {
+						path: path.clone(),
+						requirement: requirement.to_string(),
+						reason,
+					});
+				}
+			}
+		}
+		self.derived.unmet_requirements = unmet;
+	}
+
 	pub fn get_selections_at(&self, path: impl AsRef<Path>) -> Option<&Vec<String>> {
 		self.character.get_selections_at(path)
 	}
@@ -277,6 +311,18 @@ impl Character {
 			.collect::<Vec<_>>()
 	}
 
+	pub fn unmet_requirements(&self) -> &Vec<UnmetRequirement> {
+		&self.derived.unmet_requirements
+	}
+
+	pub fn applied_bundles(&self) -> &AppliedBundles {
+		&self.derived.applied_bundles
+	}
+
+	pub fn applied_bundles_mut(&mut self) -> &mut AppliedBundles {
+		&mut self.derived.applied_bundles
+	}
+
 	pub fn default_blocks(&self) -> &Vec<DefaultsBlock> {
 		&self.default_blocks
 	}
@@ -293,6 +339,16 @@ impl Character {
 		&self.character.id
 	}
 
+	/// Returns the character's current combat situation (cover, raised shield, and temporary effects).
+	pub fn combat(&self) -> &CombatState {
+		&self.combat
+	}
+
+	/// Changes to the combat situation only take effect once the character is recompiled.
+	pub fn combat_mut(&mut self) -> &mut CombatState {
+		&mut self.combat
+	}
+
 	pub fn flags(&self) -> &EnumMap<Flag, bool> {
 		&self.derived.flags
 	}
@@ -301,6 +357,12 @@ impl Character {
 		&mut self.derived.flags
 	}
 
+	/// Returns true if the character has failed three death saving throws,
+	/// or some effect (like 6 levels of exhaustion) has killed them outright.
+	pub fn is_dead(&self) -> bool {
+		self.flags()[Flag::Dead] || self.hit_points().failure_saves >= 3
+	}
+
 	pub fn ability_scores(&self) -> &AbilityScores {
 		&self.derived.ability_scores
 	}
@@ -326,6 +388,18 @@ impl Character {
 		&mut self.derived.saving_throws
 	}
 
+	/// Returns the total modifier for saving throws of the ability, including proficiency and flat bonuses.
+	pub fn saving_throw_modifier(&self, ability: Ability) -> i32 {
+		let saving_throws = self.saving_throws();
+		let modifier = self.ability_modifier(ability, Some(*saving_throws.get_prof(ability).value()));
+		let bonus = saving_throws
+			.bonuses(ability)
+			.iter()
+			.map(|(bonus, _)| *bonus)
+			.sum::<i32>();
+		modifier + bonus
+	}
+
 	pub fn skills(&self) -> &Skills {
 		&self.derived.skills
 	}
@@ -366,8 +440,18 @@ impl Character {
 		proficiency::proficiency_bonus(self.character.level(None))
 	}
 
+	pub fn initiative(&self) -> &Initiative {
+		&self.derived.initiative
+	}
+
+	pub fn initiative_mut(&mut self) -> &mut Initiative {
+		&mut self.derived.initiative
+	}
+
 	pub fn initiative_bonus(&self) -> i32 {
-		self.ability_modifier(Ability::Dexterity, None)
+		let initiative = self.initiative();
+		let modifier = self.ability_modifier(Ability::Dexterity, Some(*initiative.proficiency().value()));
+		modifier + initiative.flat_bonus()
 	}
 
 	pub fn inspiration(&self) -> bool {
@@ -390,6 +474,22 @@ impl Character {
 		&mut self.derived.max_hit_points
 	}
 
+	pub fn max_attunement(&self) -> &MaxAttunement {
+		&self.derived.max_attunement
+	}
+
+	pub fn max_attunement_mut(&mut self) -> &mut MaxAttunement {
+		&mut self.derived.max_attunement
+	}
+
+	pub fn encumbrance(&self) -> &Encumbrance {
+		&self.derived.encumbrance
+	}
+
+	pub fn encumbrance_mut(&mut self) -> &mut Encumbrance {
+		&mut self.derived.encumbrance
+	}
+
 	pub fn hit_points(&self) -> &HitPoints {
 		self.character.hit_points()
 	}
@@ -414,6 +514,20 @@ impl Character {
 		&mut self.derived.defenses
 	}
 
+	pub fn tool_proficiencies(&self) -> &ToolProficiencies {
+		&self.derived.tool_proficiencies
+	}
+
+	pub fn tool_proficiencies_mut(&mut self) -> &mut ToolProficiencies {
+		&mut self.derived.tool_proficiencies
+	}
+
+	/// Returns the bonus to a check made with the tool using the provided ability.
+	pub fn tool_modifier(&self, tool: &str, ability: Ability) -> i32 {
+		let proficiency = self.tool_proficiencies().check_proficiency(tool, ability);
+		self.ability_modifier(ability, Some(proficiency))
+	}
+
 	pub fn other_proficiencies(&self) -> &OtherProficiencies {
 		&self.derived.other_proficiencies
 	}
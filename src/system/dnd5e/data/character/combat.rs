@@ -0,0 +1,266 @@
+use super::Character;
+use crate::{
+	system::dnd5e::data::{item, Ability},
+	utility::NotInList,
+};
+use std::{
+	path::{Path, PathBuf},
+	str::FromStr,
+};
+
+/// Situational state which only applies while the character is in combat,
+/// and which provides bonuses on top of the character's base armor class and saving throws.
+/// This state is transient; it is applied each time the character is compiled, but is never saved with the character.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct CombatState {
+	pub cover: Option<Cover>,
+	/// If true, the character has raised a carried (but not equipped) shield, gaining its bonus to AC.
+	pub shield_raised: bool,
+	pub effects: Vec<TemporaryEffect>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Cover {
+	Half,
+	ThreeQuarters,
+	Full,
+}
+
+/// A short-lived bonus to armor class (e.g. the Shield of Faith spell).
+#[derive(Clone, PartialEq, Debug)]
+pub struct TemporaryEffect {
+	pub name: String,
+	pub armor_class: i32,
+	/// The number of rounds remaining before the effect ends. If None, the effect lasts until it is removed.
+	pub rounds: Option<u32>,
+}
+
+impl Cover {
+	pub fn display_name(&self) -> &'static str {
+		match self {
+			Self::Half => "Half Cover",
+			Self::ThreeQuarters => "Three-Quarters Cover",
+			Self::Full => "Total Cover",
+		}
+	}
+
+	/// The bonus to AC and Dexterity saving throws granted by this cover.
+	/// Total cover grants no bonus, because the character can't be targeted directly.
+	pub fn bonus(&self) -> Option<i32> {
+		match self {
+			Self::Half => Some(2),
+			Self::ThreeQuarters => Some(5),
+			Self::Full => None,
+		}
+	}
+}
+
+impl ToString for Cover {
+	fn to_string(&self) -> String {
+		match self {
+			Self::Half => "Half",
+			Self::ThreeQuarters => "ThreeQuarters",
+			Self::Full => "Full",
+		}
+		.to_owned()
+	}
+}
+
+impl FromStr for Cover {
+	type Err = NotInList;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"Half" => Ok(Self::Half),
+			"ThreeQuarters" => Ok(Self::ThreeQuarters),
+			"Full" => Ok(Self::Full),
+			_ => Err(NotInList(s.into(), vec!["Half", "ThreeQuarters", "Full"])),
+		}
+	}
+}
+
+impl CombatState {
+	/// Returns the best AC bonus of any shield in the inventory which is not currently equipped.
+	pub fn raisable_shield(inventory: &item::container::Inventory) -> Option<(i32, &str)> {
+		let entries = inventory.entries().filter(|entry| !entry.is_equipped);
+		let shields = entries.filter_map(|entry| match &entry.item.kind {
+			item::Kind::Equipment(item::equipment::Equipment {
+				shield: Some(bonus), ..
+			}) => Some((*bonus, entry.item.name.as_str())),
+			_ => None,
+		});
+		shields.max_by_key(|(bonus, _)| *bonus)
+	}
+
+	/// Returns the bonuses to armor class granted by the combat state, and where each bonus comes from.
+	/// These bonuses are kept separate from the character's base armor class, so both totals can be displayed.
+	pub fn armor_class_bonuses(&self, inventory: &item::container::Inventory) -> Vec<(i32, PathBuf)> {
+		let parent = Path::new("Combat");
+		let mut bonuses = Vec::new();
+		if let Some(cover) = &self.cover {
+			if let Some(bonus) = cover.bonus() {
+				bonuses.push((bonus, parent.join(cover.display_name())));
+			}
+		}
+		if self.shield_raised {
+			if let Some((bonus, _name)) = Self::raisable_shield(inventory) {
+				bonuses.push((bonus, parent.join("Raised Shield")));
+			}
+		}
+		for effect in &self.effects {
+			if effect.armor_class != 0 {
+				bonuses.push((effect.armor_class, parent.join(&effect.name)));
+			}
+		}
+		bonuses
+	}
+
+	/// Returns the situational bonus to saving throws of the ability granted by cover, and where it comes from.
+	pub fn saving_throw_bonus(&self, ability: Ability) -> Option<(i32, PathBuf)> {
+		if ability != Ability::Dexterity {
+			return None;
+		}
+		let cover = self.cover.as_ref()?;
+		let bonus = cover.bonus()?;
+		Some((bonus, Path::new("Combat").join(cover.display_name())))
+	}
+
+	/// Adds the bonuses granted by the combat state to the character's armor class and saving throws,
+	/// attributed to the situation which grants them (e.g. "Combat/Half Cover").
+	pub fn apply(&self, stats: &mut Character) {
+		for (bonus, source) in self.armor_class_bonuses(stats.inventory()) {
+			stats.armor_class_mut().push_combat_bonus(bonus, source);
+		}
+		if let Some((bonus, source)) = self.saving_throw_bonus(Ability::Dexterity) {
+			stats.saving_throws_mut().add_bonus(Ability::Dexterity, bonus, source);
+		}
+	}
+
+	/// Advances the combat state by one round, ending any temporary effects whose duration has run out.
+	pub fn advance_round(&mut self) {
+		for effect in &mut self.effects {
+			if let Some(rounds) = &mut effect.rounds {
+				*rounds = rounds.saturating_sub(1);
+			}
+		}
+		self.effects.retain(|effect| effect.rounds != Some(0));
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::{
+		character::Persistent,
+		item::{container::Inventory, equipment::Equipment, Item, Kind},
+	};
+	use rand::{rngs::StdRng, SeedableRng};
+
+	fn shield_of_faith(rounds: Option<u32>) -> TemporaryEffect {
+		TemporaryEffect {
+			name: "Shield of Faith".into(),
+			armor_class: 2,
+			rounds,
+		}
+	}
+
+	#[test]
+	fn cover() {
+		let combat = CombatState {
+			cover: Some(Cover::Half),
+			..Default::default()
+		};
+		let source = PathBuf::from("Combat/Half Cover");
+		let bonuses = combat.armor_class_bonuses(&Inventory::default());
+		assert_eq!(bonuses, vec![(2, source.clone())]);
+		assert_eq!(combat.saving_throw_bonus(Ability::Dexterity), Some((2, source)));
+		assert_eq!(combat.saving_throw_bonus(Ability::Strength), None);
+	}
+
+	#[test]
+	fn total_cover_has_no_bonus() {
+		let combat = CombatState {
+			cover: Some(Cover::Full),
+			..Default::default()
+		};
+		assert_eq!(combat.armor_class_bonuses(&Inventory::default()), vec![]);
+		assert_eq!(combat.saving_throw_bonus(Ability::Dexterity), None);
+	}
+
+	#[test]
+	fn raised_shield() {
+		let mut inventory = Inventory::default();
+		inventory.insert(Item {
+			name: "Shield".into(),
+			kind: Kind::Equipment(Equipment {
+				shield: Some(2),
+				..Default::default()
+			}),
+			..Default::default()
+		});
+		let combat = CombatState {
+			shield_raised: true,
+			effects: vec![shield_of_faith(None)],
+			..Default::default()
+		};
+		let expected = vec![
+			(2, PathBuf::from("Combat/Raised Shield")),
+			(2, PathBuf::from("Combat/Shield of Faith")),
+		];
+		assert_eq!(combat.armor_class_bonuses(&inventory), expected);
+	}
+
+	fn character(combat: CombatState) -> Character {
+		let mut character = Character::from(Persistent {
+			ability_scores: enum_map::enum_map! { _ => 10 },
+			..Default::default()
+		});
+		*character.combat_mut() = combat;
+		character.recompile_minimal();
+		character
+	}
+
+	#[test]
+	fn applied_armor_class() {
+		let character = character(CombatState {
+			cover: Some(Cover::Half),
+			effects: vec![shield_of_faith(None)],
+			..Default::default()
+		});
+		let armor_class = character.armor_class();
+		assert_eq!(armor_class.evaluate(&character), 10);
+		assert_eq!(armor_class.evaluate_situational(&character), 14);
+		let sources = armor_class.iter_combat_bonuses().cloned().collect::<Vec<_>>();
+		let expected = vec![
+			(2, PathBuf::from("Combat/Half Cover")),
+			(2, PathBuf::from("Combat/Shield of Faith")),
+		];
+		assert_eq!(sources, expected);
+	}
+
+	#[test]
+	fn applied_dexterity_save() {
+		let character = character(CombatState {
+			cover: Some(Cover::ThreeQuarters),
+			..Default::default()
+		});
+		assert_eq!(character.saving_throw_modifier(Ability::Dexterity), 5);
+		assert_eq!(character.saving_throw_modifier(Ability::Strength), 0);
+		let bonuses = character.saving_throws().bonuses(Ability::Dexterity);
+		assert_eq!(bonuses, &vec![(5, PathBuf::from("Combat/Three-Quarters Cover"))]);
+
+		let outcome = character.roll_saving_throw(Ability::Dexterity, &mut StdRng::seed_from_u64(7));
+		let natural = outcome.natural_roll().unwrap() as i32;
+		assert_eq!(outcome.total(), natural + 5);
+	}
+
+	#[test]
+	fn advance_round() {
+		let mut combat = CombatState {
+			effects: vec![shield_of_faith(Some(1)), shield_of_faith(None)],
+			..Default::default()
+		};
+		combat.advance_round();
+		assert_eq!(combat.effects, vec![shield_of_faith(None)]);
+	}
+}
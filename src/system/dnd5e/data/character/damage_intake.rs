@@ -0,0 +1,246 @@
+use super::{concentration_save_dc, Character, DamageOutcome, HitPoint, HitPointDamage, HitPoints, Persistent};
+use crate::system::dnd5e::{data::DamageType, mutator::Defense};
+use std::path::PathBuf;
+
+/// A single typed portion of incoming damage (e.g. 12 fire damage from a fireball).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DamageInstance {
+	pub amount: u32,
+	pub damage_type: DamageType,
+}
+
+/// The amount of damage a character takes from a single damage instance, after applying their defenses.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DamageStep {
+	pub instance: DamageInstance,
+	/// The defenses which changed the amount of damage taken, and the feature which granted each.
+	pub defenses: Vec<(Defense, PathBuf)>,
+	/// Defenses which only apply in some circumstances (e.g. "from nonmagical attacks"),
+	/// and so were not applied automatically.
+	pub conditional: Vec<(Defense, String)>,
+	pub amount: u32,
+}
+
+/// The full breakdown of how a set of incoming damage affects a character's hit points.
+#[derive(Clone, PartialEq, Debug)]
+pub struct DamageIntake {
+	pub steps: Vec<DamageStep>,
+	pub total: u32,
+	pub critical: bool,
+	pub max_hp: u32,
+	/// The hit points the character would have after taking the damage.
+	pub hit_points: HitPoints,
+	pub damage: HitPointDamage,
+}
+
+impl Character {
+	/// Returns the amount of damage the character would take from a damage instance.
+	/// Immunity negates the damage entirely, otherwise resistance halves it (rounded down)
+	/// and then vulnerability doubles it. Multiple instances of the same defense count as one.
+	pub fn evaluate_damage_instance(&self, instance: DamageInstance) -> DamageStep {
+		let mut defenses = Vec::new();
+		let mut conditional = Vec::new();
+		for kind in [Defense::Immunity, Defense::Resistance, Defense::Vulnerability] {
+			let entries = self.defenses()[kind].iter();
+			let entries = entries.filter(|entry| match entry.damage_type {
+				None => true,
+				Some(damage_type) => damage_type == instance.damage_type,
+			});
+			let mut applied = None;
+			for entry in entries {
+				match &entry.context {
+					Some(context) => conditional.push((kind, context.clone())),
+					None if applied.is_none() => applied = Some(entry),
+					None => {}
+				}
+			}
+			if let Some(entry) = applied {
+				defenses.push((kind, entry.source.clone()));
+				if kind == Defense::Immunity {
+					break;
+				}
+			}
+		}
+
+		let mut amount = instance.amount;
+		for (kind, _source) in &defenses {
+			amount = match kind {
+				Defense::Immunity => 0,
+				Defense::Resistance => amount / 2,
+				Defense::Vulnerability => amount.saturating_mul(2),
+			};
+		}
+
+		DamageStep {
+			instance,
+			defenses,
+			conditional,
+			amount,
+		}
+	}
+
+	/// Evaluates how the character would be affected by taking all of the provided damage at once,
+	/// without changing their hit points.
+	pub fn evaluate_damage(&self, instances: &[DamageInstance], critical: bool) -> DamageIntake {
+		let steps = instances
+			.iter()
+			.map(|instance| self.evaluate_damage_instance(*instance))
+			.collect::<Vec<_>>();
+		let total = steps.iter().map(|step| step.amount).sum::<u32>();
+		let max_hp = self.get_hp(HitPoint::Max);
+		let mut hit_points = *self.hit_points();
+		let damage = hit_points.take_damage(total, max_hp, critical);
+		DamageIntake {
+			steps,
+			total,
+			critical,
+			max_hp,
+			hit_points,
+			damage,
+		}
+	}
+}
+
+impl Persistent {
+	/// Applies the total of some evaluated damage to the character's hit points.
+	/// Dropping to 0 hit points ends concentration. Otherwise, if the character took damage while concentrating,
+	/// the DCs of the saving throws needed to maintain concentration are returned (one for each source of damage).
+	pub fn apply_damage(&mut self, intake: &DamageIntake) -> Vec<u32> {
+		let dcs = intake.concentration_save_dcs(self.concentration.is_some());
+		self.hit_points
+			.take_damage(intake.total, intake.max_hp, intake.critical);
+		if self.hit_points.current == 0 {
+			self.end_concentration();
+		}
+		dcs
+	}
+}
+
+impl DamageIntake {
+	/// Returns the DCs of the saving throws a concentrating character would need to make after taking this damage.
+	/// Each damage instance is a separate source of damage, and so requires its own saving throw.
+	pub fn concentration_save_dcs(&self, concentrating: bool) -> Vec<u32> {
+		if !concentrating || self.hit_points.current == 0 {
+			return Vec::new();
+		}
+		let amounts = self.steps.iter().map(|step| step.amount).filter(|amount| *amount > 0);
+		amounts.map(concentration_save_dc).collect()
+	}
+
+	pub fn is_dead(&self) -> bool {
+		self.damage.outcome == DamageOutcome::InstantDeath || self.hit_points.failure_saves >= 3
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn character(current: u32, temp: u32) -> Character {
+		let mut character = Character::from(Persistent {
+			hit_points: HitPoints {
+				current,
+				temp,
+				..Default::default()
+			},
+			..Default::default()
+		});
+		character.max_hit_points_mut().push(20, "Test".into());
+		let defenses = character.defenses_mut();
+		defenses.push(Defense::Resistance, Some(DamageType::Fire), None, "Tiefling".into());
+		defenses.push(
+			Defense::Resistance,
+			None,
+			Some("nonmagical attacks".into()),
+			"Bear Totem".into(),
+		);
+		defenses.push(Defense::Vulnerability, Some(DamageType::Cold), None, "Curse".into());
+		defenses.push(Defense::Immunity, Some(DamageType::Poison), None, "Warforged".into());
+		defenses.push(Defense::Resistance, Some(DamageType::Poison), None, "Dwarf".into());
+		character
+	}
+
+	fn damage(amount: u32, damage_type: DamageType) -> DamageInstance {
+		DamageInstance { amount, damage_type }
+	}
+
+	#[test]
+	fn resistance() {
+		let character = character(20, 0);
+		let step = character.evaluate_damage_instance(damage(13, DamageType::Fire));
+		assert_eq!(step.amount, 6);
+		assert_eq!(step.defenses, vec![(Defense::Resistance, "Tiefling".into())]);
+		assert_eq!(
+			step.conditional,
+			vec![(Defense::Resistance, "nonmagical attacks".into())]
+		);
+	}
+
+	#[test]
+	fn vulnerability() {
+		let character = character(20, 0);
+		let step = character.evaluate_damage_instance(damage(5, DamageType::Cold));
+		assert_eq!(step.amount, 10);
+	}
+
+	#[test]
+	fn immunity() {
+		let character = character(20, 0);
+		let step = character.evaluate_damage_instance(damage(9, DamageType::Poison));
+		assert_eq!(step.amount, 0);
+		assert_eq!(step.defenses, vec![(Defense::Immunity, "Warforged".into())]);
+	}
+
+	#[test]
+	fn multiple_instances() {
+		let character = character(20, 3);
+		let instances = [damage(12, DamageType::Fire), damage(5, DamageType::Piercing)];
+		let intake = character.evaluate_damage(&instances, false);
+		assert_eq!(intake.total, 11);
+		assert_eq!(intake.damage.temp_absorbed, 3);
+		assert_eq!(intake.damage.hp_lost, 8);
+		assert_eq!(intake.hit_points.current, 12);
+		assert_eq!(intake.damage.outcome, DamageOutcome::Conscious);
+		// evaluating damage doesn't change the character
+		assert_eq!(character.hit_points().current, 20);
+	}
+
+	#[test]
+	fn concentration_save_per_source() {
+		let character = character(20, 10);
+		let instances = [
+			damage(22, DamageType::Piercing),
+			damage(9, DamageType::Poison),
+			damage(8, DamageType::Fire),
+		];
+		let intake = character.evaluate_damage(&instances, false);
+		assert_eq!(intake.total, 26);
+		assert_eq!(intake.hit_points.current, 4);
+		assert_eq!(intake.concentration_save_dcs(false), Vec::<u32>::new());
+		// immunity negates the poison damage, so only the piercing and fire damage require saves
+		assert_eq!(intake.concentration_save_dcs(true), vec![11, 10]);
+	}
+
+	#[test]
+	fn no_concentration_save_at_zero_hp() {
+		let character = character(20, 0);
+		let instances = [damage(10, DamageType::Fire), damage(15, DamageType::Cold)];
+		let intake = character.evaluate_damage(&instances, false);
+		assert_eq!(intake.hit_points.current, 0);
+		// dropping to 0 hit points ends concentration instead of requiring a save
+		assert_eq!(intake.concentration_save_dcs(true), Vec::<u32>::new());
+	}
+
+	#[test]
+	fn massive_damage() {
+		let mut character = character(4, 0);
+		let intake = character.evaluate_damage(&[damage(15, DamageType::Cold)], false);
+		assert_eq!(intake.total, 30);
+		assert_eq!(intake.damage.outcome, DamageOutcome::InstantDeath);
+		assert!(intake.is_dead());
+
+		character.persistent_mut().apply_damage(&intake);
+		assert_eq!(character.hit_points().current, 0);
+		assert_eq!(character.hit_points().failure_saves, 3);
+	}
+}
@@ -3,7 +3,7 @@ use crate::system::dnd5e::{
 	data::{
 		action::AttackQuery,
 		proficiency,
-		roll::{Modifier, Roll},
+		roll::{Modifier, Roll, RollExpression},
 		Ability, ArmorClass, DamageType, OtherProficiencies, Rest, Skill, Spell,
 	},
 	mutator::{Defense, Flag},
@@ -18,6 +18,10 @@ mod ability_score;
 pub use ability_score::*;
 mod actions;
 pub use actions::*;
+mod bundles;
+pub use bundles::*;
+mod encumbrance;
+pub use encumbrance::*;
 mod object_cache;
 pub use object_cache::*;
 mod sense;
@@ -38,14 +42,19 @@ pub use starting_equipment::*;
 #[derive(Clone, PartialEq, Debug)]
 pub struct Derived {
 	pub missing_selections: Vec<PathBuf>,
+	pub unmet_requirements: Vec<UnmetRequirement>,
 	pub ability_scores: AbilityScores,
 	pub saving_throws: SavingThrows,
 	pub skills: Skills,
+	pub initiative: Initiative,
+	pub tool_proficiencies: ToolProficiencies,
 	pub other_proficiencies: OtherProficiencies,
 	pub speeds: Speeds,
 	pub senses: Senses,
 	pub defenses: Defenses,
 	pub max_hit_points: MaxHitPoints,
+	pub max_attunement: MaxAttunement,
+	pub encumbrance: Encumbrance,
 	pub attack_bonuses: AttackBonuses,
 	pub armor_class: ArmorClass,
 	pub features: Features,
@@ -54,6 +63,7 @@ pub struct Derived {
 	pub spellcasting: Spellcasting,
 	pub starting_equipment: Vec<(Vec<StartingEquipment>, PathBuf)>,
 	pub additional_objects: AdditionalObjectCache,
+	pub applied_bundles: AppliedBundles,
 	pub rest_resets: RestResets,
 }
 
@@ -61,24 +71,31 @@ impl Default for Derived {
 	fn default() -> Self {
 		Self {
 			missing_selections: Default::default(),
+			unmet_requirements: Default::default(),
 			ability_scores: Default::default(),
 			saving_throws: Default::default(),
 			skills: Default::default(),
+			initiative: Default::default(),
+			tool_proficiencies: Default::default(),
 			other_proficiencies: Default::default(),
 			speeds: Default::default(),
 			senses: Default::default(),
 			defenses: Default::default(),
 			max_hit_points: Default::default(),
+			max_attunement: Default::default(),
+			encumbrance: Default::default(),
 			attack_bonuses: Default::default(),
 			armor_class: Default::default(),
 			features: Default::default(),
 			description: Default::default(),
 			flags: enum_map! {
 				Flag::ArmorStrengthRequirement => true,
+				Flag::Dead => false,
 			},
 			spellcasting: Default::default(),
 			starting_equipment: Default::default(),
 			additional_objects: Default::default(),
+			applied_bundles: Default::default(),
 			rest_resets: Default::default(),
 		}
 	}
@@ -88,6 +105,7 @@ impl Default for Derived {
 pub struct SavingThrows {
 	by_ability: EnumMap<Ability, ProficiencyModifiers>,
 	general_modifiers: ModifierMap,
+	bonuses: EnumMap<Ability, Vec<(i32, PathBuf)>>,
 }
 impl SavingThrows {
 	pub fn add_proficiency(&mut self, ability: Ability, source: PathBuf) {
@@ -110,6 +128,14 @@ impl SavingThrows {
 		.insert(modifier, (target, source).into());
 	}
 
+	pub fn add_bonus(&mut self, ability: Ability, bonus: i32, source: PathBuf) {
+		self.bonuses[ability].push((bonus, source));
+	}
+
+	pub fn bonuses(&self, ability: Ability) -> &Vec<(i32, PathBuf)> {
+		&self.bonuses[ability]
+	}
+
 	pub fn get_prof(&self, ability: Ability) -> &AttributedValue<proficiency::Level> {
 		&self.by_ability[ability].proficiency
 	}
@@ -184,6 +210,17 @@ impl ModifierMap {
 			.map(|(modifier, items)| items.iter().map(move |item| (modifier, item)))
 			.flatten()
 	}
+
+	/// Returns true if any entry for the modifier always applies (i.e. it has no context/target).
+	pub fn has_unconditional(&self, modifier: Modifier) -> bool {
+		self.modifiers[modifier].iter().any(|item| item.context.is_none())
+	}
+
+	/// Iterates over the entries which only apply in specific situations (i.e. they have a context/target).
+	pub fn iter_conditional(&self) -> impl Iterator<Item = (Modifier, &String)> {
+		self.iter_all()
+			.filter_map(|(modifier, item)| item.context.as_ref().map(|context| (modifier, context)))
+	}
 }
 #[derive(Clone, Default, PartialEq, Debug)]
 pub struct ModifierMapItem {
@@ -243,6 +280,78 @@ impl Skills {
 	}
 }
 
+/// Bonuses which apply to initiative checks, in addition to the Dexterity modifier
+/// and any advantage or disadvantage on all Dexterity checks.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct Initiative {
+	bonuses: Vec<(i32, PathBuf)>,
+	proficiency: AttributedValue<proficiency::Level>,
+	modifiers: ModifierMap,
+}
+impl Initiative {
+	pub fn add_bonus(&mut self, bonus: i32, source: PathBuf) {
+		self.bonuses.push((bonus, source));
+	}
+
+	pub fn add_proficiency(&mut self, level: proficiency::Level, source: PathBuf) {
+		self.proficiency.push(level, source);
+	}
+
+	pub fn add_modifier(&mut self, modifier: Modifier, context: Option<String>, source: PathBuf) {
+		self.modifiers.insert(modifier, (context, source).into());
+	}
+
+	pub fn bonuses(&self) -> &Vec<(i32, PathBuf)> {
+		&self.bonuses
+	}
+
+	pub fn proficiency(&self) -> &AttributedValue<proficiency::Level> {
+		&self.proficiency
+	}
+
+	pub fn modifiers(&self) -> &ModifierMap {
+		&self.modifiers
+	}
+
+	/// The sum of all flat bonuses to initiative (not including proficiency or ability modifiers).
+	pub fn flat_bonus(&self) -> i32 {
+		self.bonuses.iter().map(|(bonus, _)| *bonus).sum()
+	}
+}
+
+/// The proficiency level of each tool the character is proficient with.
+/// Proficiency with all checks of an ability (e.g. Jack of All Trades) also applies
+/// to checks made with any tool using that ability.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ToolProficiencies {
+	abilities: EnumMap<Ability, AttributedValue<proficiency::Level>>,
+	tools: BTreeMap<String, AttributedValue<proficiency::Level>>,
+}
+impl ToolProficiencies {
+	pub fn add_proficiency(&mut self, tool: String, level: proficiency::Level, source: PathBuf) {
+		self.tools.entry(tool).or_default().push(level, source);
+	}
+
+	pub fn add_ability_proficiency(&mut self, ability: Ability, level: proficiency::Level, source: PathBuf) {
+		self.abilities[ability].push(level, source);
+	}
+
+	pub fn proficiency(&self, tool: &str) -> Option<&AttributedValue<proficiency::Level>> {
+		self.tools.get(tool)
+	}
+
+	/// Returns the proficiency level which applies to a check made with the tool using the provided ability.
+	/// This is the higher of the proficiency with the tool and the proficiency with all checks of that ability.
+	pub fn check_proficiency(&self, tool: &str, ability: Ability) -> proficiency::Level {
+		let tool_level = self.proficiency(tool).map(|level| *level.value()).unwrap_or_default();
+		tool_level.max(*self.abilities[ability].value())
+	}
+
+	pub fn iter(&self) -> impl Iterator<Item = (&String, &AttributedValue<proficiency::Level>)> {
+		self.tools.iter()
+	}
+}
+
 #[derive(Clone, Default, PartialEq, Debug)]
 pub struct Defenses(EnumMap<Defense, Vec<DefenseEntry>>);
 #[derive(Clone, PartialEq, Debug)]
@@ -292,12 +401,39 @@ impl MaxHitPoints {
 	}
 }
 
+/// The number of magic items a character can be attuned to at the same time.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MaxAttunement(i32, BTreeMap<PathBuf, i32>);
+impl Default for MaxAttunement {
+	fn default() -> Self {
+		Self(3, BTreeMap::new())
+	}
+}
+impl MaxAttunement {
+	pub fn push(&mut self, bonus: i32, source: PathBuf) {
+		self.0 = self.0.saturating_add(bonus);
+		self.1.insert(source, bonus);
+	}
+
+	pub fn value(&self) -> usize {
+		self.0.max(0) as usize
+	}
+
+	pub fn sources(&self) -> &BTreeMap<PathBuf, i32> {
+		&self.1
+	}
+}
+
 #[derive(Clone, Default, PartialEq, Debug)]
 pub struct AttackBonuses {
 	attack_roll: Vec<AttackRollBonus>,
 	attack_damage: Vec<AttackDamageBonus>,
 	attack_ability: Vec<AttackAbility>,
 	spell_damage: Vec<SpellDamageBonus>,
+	/// Advantage or disadvantage on all attack rolls.
+	general_modifiers: ModifierMap,
+	/// Advantage or disadvantage on attack rolls which use a specific ability.
+	ability_modifiers: EnumMap<Ability, ModifierMap>,
 }
 #[derive(Clone, PartialEq, Debug)]
 struct AttackRollBonus {
@@ -307,14 +443,14 @@ struct AttackRollBonus {
 }
 #[derive(Clone, PartialEq, Debug)]
 struct AttackDamageBonus {
-	amount: Roll,
+	amount: RollExpression,
 	damage_type: Option<DamageType>,
 	queries: Vec<AttackQuery>,
 	source: PathBuf,
 }
 #[derive(Clone, PartialEq, Debug)]
 struct SpellDamageBonus {
-	amount: Roll,
+	amount: RollExpression,
 	queries: Vec<spellcasting::Filter>,
 	source: PathBuf,
 }
@@ -331,7 +467,7 @@ impl AttackBonuses {
 
 	pub fn add_to_weapon_damage(
 		&mut self,
-		amount: Roll,
+		amount: RollExpression,
 		damage_type: Option<DamageType>,
 		queries: Vec<AttackQuery>,
 		source: PathBuf,
@@ -352,7 +488,7 @@ impl AttackBonuses {
 		});
 	}
 
-	pub fn add_to_spell_damage(&mut self, amount: Roll, queries: Vec<spellcasting::Filter>, source: PathBuf) {
+	pub fn add_to_spell_damage(&mut self, amount: RollExpression, queries: Vec<spellcasting::Filter>, source: PathBuf) {
 		self.spell_damage.push(SpellDamageBonus {
 			amount,
 			queries,
@@ -360,11 +496,38 @@ impl AttackBonuses {
 		});
 	}
 
+	pub fn add_modifier(
+		&mut self,
+		ability: Option<Ability>,
+		modifier: Modifier,
+		target: Option<String>,
+		source: PathBuf,
+	) {
+		match ability {
+			Some(ability) => &mut self.ability_modifiers[ability],
+			None => &mut self.general_modifiers,
+		}
+		.insert(modifier, (target, source).into());
+	}
+
+	pub fn general_modifiers(&self) -> &ModifierMap {
+		&self.general_modifiers
+	}
+
+	pub fn ability_modifiers(&self, ability: Ability) -> &ModifierMap {
+		&self.ability_modifiers[ability]
+	}
+
 	pub fn get_weapon_attack(&self, action: &crate::system::dnd5e::data::action::Action) -> Vec<(i32, &Path)> {
+		match &action.attack {
+			Some(attack) => self.get_attack_roll(attack),
+			None => Vec::new(),
+		}
+	}
+
+	/// Returns the bonuses to the attack roll of an attack, and the source of each bonus.
+	pub fn get_attack_roll(&self, attack: &crate::system::dnd5e::data::action::Attack) -> Vec<(i32, &Path)> {
 		let mut bonuses = Vec::new();
-		let Some(attack) = &action.attack else {
-			return bonuses;
-		};
 		for bonus in &self.attack_roll {
 			// Filter out any bonuses which do not meet the restriction
 			'iter_query: for query in &bonus.queries {
@@ -380,7 +543,7 @@ impl AttackBonuses {
 	pub fn get_weapon_damage(
 		&self,
 		action: &crate::system::dnd5e::data::action::Action,
-	) -> Vec<(&Roll, &Option<DamageType>, &Path)> {
+	) -> Vec<(&RollExpression, &Option<DamageType>, &Path)> {
 		let mut bonuses = Vec::new();
 		let Some(attack) = &action.attack else {
 			return bonuses;
@@ -411,7 +574,7 @@ impl AttackBonuses {
 		abilities
 	}
 
-	pub fn get_spell_damage(&self, spell: &Spell) -> Vec<(&Roll, &Path)> {
+	pub fn get_spell_damage(&self, spell: &Spell) -> Vec<(&RollExpression, &Path)> {
 		let mut bonuses = Vec::new();
 		for bonus in &self.spell_damage {
 			// Filter out any bonuses which do not meet the restriction
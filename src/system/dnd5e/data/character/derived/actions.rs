@@ -15,6 +15,9 @@ use std::{
 pub struct Features {
 	pub path_map: PathMap<Feature>,
 	pub uses: HashMap<PathBuf, UseCounterData>,
+	/// The inventory id path and feature path (e.g. `Inventory/Wand of Web`) of each item with charges, by item id.
+	/// Limited uses can consume from an item's charges using the feature path as their resource.
+	pub item_charges: HashMap<uuid::Uuid, (Vec<uuid::Uuid>, PathBuf)>,
 	pub action_budget: ActionBudget,
 }
 
@@ -23,6 +26,24 @@ impl Features {
 		self.uses.insert(path.as_ref().to_owned(), usage_data.clone());
 	}
 
+	pub fn register_item_charges(&mut self, id_path: &Vec<uuid::Uuid>, path: impl AsRef<Path>) {
+		let Some(id) = id_path.last() else {
+			return;
+		};
+		let path = path.as_ref().to_owned();
+		self.item_charges.insert(*id, (id_path.clone(), path));
+	}
+
+	/// Returns the id paths of all items with charges whose feature path is `key`, sorted by id.
+	/// There can be more than one if the character has several items with the same name.
+	pub fn get_item_charges(&self, key: impl AsRef<Path>) -> Vec<&Vec<uuid::Uuid>> {
+		let key = key.as_ref();
+		let items = self.item_charges.values().filter(|(_id_path, path)| path == key);
+		let mut id_paths = items.map(|(id_path, _path)| id_path).collect::<Vec<_>>();
+		id_paths.sort();
+		id_paths
+	}
+
 	pub fn iter_all(&self) -> impl Iterator<Item = (PathBuf, &Feature)> + '_ {
 		self.path_map.as_vec().into_iter()
 	}
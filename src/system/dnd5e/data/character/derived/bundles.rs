@@ -0,0 +1,40 @@
+use crate::system::dnd5e::data::{bundle::BundleRequirement, Bundle};
+use std::{
+	collections::HashSet,
+	path::{Path, PathBuf},
+};
+
+/// The bundles which have been applied to a character, and the requirements of each.
+/// Requirements can depend on data provided by any mutator, so they are only checked
+/// once all mutators have been applied.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct AppliedBundles {
+	names: HashSet<(String, String)>,
+	requirements: Vec<(PathBuf, Vec<BundleRequirement>)>,
+}
+
+/// A requirement of an applied bundle which the character does not meet.
+#[derive(Clone, PartialEq, Debug)]
+pub struct UnmetRequirement {
+	/// The data path of the bundle which has the requirement.
+	pub path: PathBuf,
+	pub requirement: String,
+	pub reason: String,
+}
+
+impl AppliedBundles {
+	pub fn insert(&mut self, bundle: &Bundle, path: &Path) {
+		self.names.insert((bundle.category.clone(), bundle.name.clone()));
+		if !bundle.requirements.is_empty() {
+			self.requirements.push((path.to_owned(), bundle.requirements.clone()));
+		}
+	}
+
+	pub fn contains(&self, category: &str, name: &str) -> bool {
+		self.names.contains(&(category.to_owned(), name.to_owned()))
+	}
+
+	pub fn requirements(&self) -> &Vec<(PathBuf, Vec<BundleRequirement>)> {
+		&self.requirements
+	}
+}
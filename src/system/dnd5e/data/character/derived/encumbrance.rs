@@ -0,0 +1,120 @@
+use crate::system::dnd5e::data::Size;
+use enum_map::Enum;
+use std::path::PathBuf;
+
+/// How much weight a character can carry (based on their Strength score and size), and how much they are carrying.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Encumbrance {
+	/// Features (like Powerful Build) which make the character count as some number of sizes larger
+	/// when determining their carrying capacity.
+	size_increases: Vec<(u8, PathBuf)>,
+	strength: u32,
+	size: Size,
+	carried_weight: f64,
+}
+
+impl Default for Encumbrance {
+	fn default() -> Self {
+		Self {
+			size_increases: Vec::new(),
+			strength: 0,
+			size: Size::Medium,
+			carried_weight: 0.0,
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum EncumbranceLevel {
+	Unencumbered,
+	/// Carrying more than 5 times the character's Strength score (variant encumbrance only).
+	Encumbered,
+	/// Carrying more than 10 times the character's Strength score (variant encumbrance only).
+	HeavilyEncumbered,
+	/// Carrying more than the character's carrying capacity.
+	OverCapacity,
+}
+
+impl EncumbranceLevel {
+	pub fn display_name(&self) -> &'static str {
+		match self {
+			Self::Unencumbered => "Unencumbered",
+			Self::Encumbered => "Encumbered",
+			Self::HeavilyEncumbered => "Heavily Encumbered",
+			Self::OverCapacity => "Over Capacity",
+		}
+	}
+}
+
+impl Encumbrance {
+	pub fn add_size_increase(&mut self, steps: u8, source: PathBuf) {
+		self.size_increases.push((steps, source));
+	}
+
+	pub fn size_increases(&self) -> &Vec<(u8, PathBuf)> {
+		&self.size_increases
+	}
+
+	/// Records the character's final Strength score, size, and the total weight of their inventory,
+	/// from which the capacity and encumbrance level are determined.
+	pub fn set_carried(&mut self, strength: u32, size: Size, carried_weight: f64) {
+		self.strength = strength;
+		self.size = size;
+		self.carried_weight = carried_weight;
+	}
+
+	pub fn carried_weight(&self) -> f64 {
+		self.carried_weight
+	}
+
+	/// The size the character counts as when determining how much they can carry.
+	pub fn effective_size(&self) -> Size {
+		let steps = self
+			.size_increases
+			.iter()
+			.map(|(steps, _)| *steps as usize)
+			.sum::<usize>();
+		let index = (self.size.into_usize() + steps).min(Size::Gargantuan.into_usize());
+		Size::from_usize(index)
+	}
+
+	/// Each size category above Medium doubles the weight a creature can bear, and Tiny creatures can bear half as much.
+	pub fn size_multiplier(&self) -> f64 {
+		match self.effective_size() {
+			Size::Tiny => 0.5,
+			Size::Small | Size::Medium => 1.0,
+			Size::Large => 2.0,
+			Size::Huge => 4.0,
+			Size::Gargantuan => 8.0,
+		}
+	}
+
+	/// The weight (in pounds) the character can carry, which is 15 times their Strength score.
+	pub fn capacity(&self) -> f64 {
+		self.strength as f64 * 15.0 * self.size_multiplier()
+	}
+
+	/// The weight (in pounds) the character can push, drag, or lift, which is twice their carrying capacity.
+	pub fn push_drag_lift(&self) -> f64 {
+		self.capacity() * 2.0
+	}
+
+	/// The carried weight above which the character is encumbered and heavily encumbered, respectively,
+	/// when using the variant encumbrance rules. These scale with size in the same way as carrying capacity.
+	pub fn variant_thresholds(&self) -> (f64, f64) {
+		let strength = self.strength as f64 * self.size_multiplier();
+		(strength * 5.0, strength * 10.0)
+	}
+
+	/// Returns how hindered the character is by the weight they are carrying.
+	/// The encumbered levels are only possible when using the variant encumbrance rules.
+	pub fn level(&self, variant: bool) -> EncumbranceLevel {
+		let (encumbered, heavily_encumbered) = self.variant_thresholds();
+		match self.carried_weight {
+			weight if weight > self.capacity() => EncumbranceLevel::OverCapacity,
+			weight if variant && weight > heavily_encumbered => EncumbranceLevel::HeavilyEncumbered,
+			weight if variant && weight > encumbered => EncumbranceLevel::Encumbered,
+			_ => EncumbranceLevel::Unencumbered,
+		}
+	}
+}
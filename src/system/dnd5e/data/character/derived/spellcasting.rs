@@ -3,7 +3,10 @@ use crate::{
 		core::SourceId,
 		dnd5e::data::{
 			character::{Character, ObjectCacheProvider, Persistent},
+			proficiency,
+			roll::RollBonus,
 			spell::Spell,
+			Ability,
 		},
 	},
 	utility::AddAssignMap,
@@ -11,7 +14,7 @@ use crate::{
 use multimap::MultiMap;
 use std::{
 	collections::{BTreeMap, HashMap, HashSet},
-	path::PathBuf,
+	path::{Path, PathBuf},
 };
 
 mod caster;
@@ -44,6 +47,28 @@ pub struct Spellcasting {
 	always_prepared: HashMap<SourceId, AlwaysPreparedSpell>,
 	/// A cache of spells queried from the data provider which casters can ritual cast.
 	ritual_spells: RitualSpellCache,
+	/// Bonuses to the spell save DC of casters (e.g. Rod of the Pact Keeper).
+	save_dc_bonuses: Vec<CasterBonus>,
+	/// Bonuses to the spell attack rolls of casters (e.g. Wand of the War Mage).
+	attack_bonuses: Vec<CasterBonus>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct CasterBonus {
+	bonus: i32,
+	queries: Vec<Filter>,
+	source: PathBuf,
+}
+impl CasterBonus {
+	fn applies_to(&self, caster: Option<&Caster>) -> bool {
+		if self.queries.is_empty() {
+			return true;
+		}
+		let Some(caster) = caster else {
+			return false;
+		};
+		self.queries.iter().any(|query| query.matches_caster(caster))
+	}
 }
 
 #[derive(Clone, Default, PartialEq, Debug)]
@@ -316,6 +341,53 @@ impl Spellcasting {
 		self.ritual_spells.spells.get(spell_id)
 	}
 
+	pub fn add_save_dc_bonus(&mut self, bonus: i32, queries: Vec<Filter>, source: PathBuf) {
+		self.save_dc_bonuses.push(CasterBonus { bonus, queries, source });
+	}
+
+	pub fn add_attack_bonus(&mut self, bonus: i32, queries: Vec<Filter>, source: PathBuf) {
+		self.attack_bonuses.push(CasterBonus { bonus, queries, source });
+	}
+
+	/// Returns the bonuses to the spell save DC of spells cast by the caster.
+	/// Spells which aren't cast through a caster (`caster_id` is None) only receive unrestricted bonuses.
+	pub fn get_save_dc_bonuses(&self, caster_id: Option<&str>) -> Vec<(i32, &Path)> {
+		let caster = caster_id.map(|id| self.get_caster(id)).flatten();
+		let bonuses = self.save_dc_bonuses.iter().filter(|bonus| bonus.applies_to(caster));
+		bonuses.map(|bonus| (bonus.bonus, bonus.source.as_path())).collect()
+	}
+
+	/// Returns the bonuses to spell attack rolls of spells cast by the caster.
+	/// Spells which aren't cast through a caster (`caster_id` is None) only receive unrestricted bonuses.
+	pub fn get_attack_bonuses(&self, caster_id: Option<&str>) -> Vec<(i32, &Path)> {
+		let caster = caster_id.map(|id| self.get_caster(id)).flatten();
+		let bonuses = self.attack_bonuses.iter().filter(|bonus| bonus.applies_to(caster));
+		bonuses.map(|bonus| (bonus.bonus, bonus.source.as_path())).collect()
+	}
+
+	/// Returns the spell save DC of the caster, and each of the values which contribute to it.
+	pub fn save_dc(&self, caster_id: &str, character: &Character) -> Option<(i32, Vec<RollBonus>)> {
+		let caster = self.get_caster(caster_id)?;
+		let mut breakdown = vec![RollBonus::from((8, "Base"))];
+		breakdown.extend(caster_ability_bonuses(caster.ability, character));
+		for (bonus, source) in self.get_save_dc_bonuses(Some(caster_id)) {
+			breakdown.push(caster_bonus(bonus, source));
+		}
+		let total = breakdown.iter().map(|bonus| bonus.value).sum();
+		Some((total, breakdown))
+	}
+
+	/// Returns the spell attack bonus of the caster, and each of the values which contribute to it.
+	pub fn attack_bonus(&self, caster_id: &str, character: &Character) -> Option<(i32, Vec<RollBonus>)> {
+		let caster = self.get_caster(caster_id)?;
+		let mut breakdown = caster_ability_bonuses(caster.ability, character);
+		for (bonus, source) in self.get_attack_bonuses(Some(caster_id)) {
+			breakdown.push(caster_bonus(bonus, source));
+		}
+		let total = breakdown.iter().map(|bonus| bonus.value).sum();
+		Some((total, breakdown))
+	}
+
 	pub fn get_filter(&self, id: &str, persistent: &Persistent) -> Option<Filter> {
 		let Some(caster) = self.get_caster(id) else {
 			return None;
@@ -332,3 +404,21 @@ impl Spellcasting {
 		})
 	}
 }
+
+pub(crate) fn caster_ability_bonuses(ability: Ability, character: &Character) -> Vec<RollBonus> {
+	vec![
+		RollBonus::from((
+			character.ability_modifier(ability, None),
+			format!("{} modifier", ability.long_name()),
+		)),
+		RollBonus::from((
+			proficiency::Level::Full * character.proficiency_bonus(),
+			"Proficiency bonus",
+		)),
+	]
+}
+
+pub(crate) fn caster_bonus(bonus: i32, source: &Path) -> RollBonus {
+	let source = crate::data::as_feature_path_text(source).unwrap_or_else(|| "Bonus".into());
+	RollBonus::from((bonus, source))
+}
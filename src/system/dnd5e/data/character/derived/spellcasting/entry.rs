@@ -17,6 +17,8 @@ pub enum CastingMethod {
 		item_id: Vec<uuid::Uuid>,
 		consume_spell: bool,
 		consume_item: bool,
+		/// The number of the item's charges spent to cast the spell.
+		charges: Option<usize>,
 	},
 }
 
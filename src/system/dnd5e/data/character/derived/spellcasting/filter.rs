@@ -108,6 +108,12 @@ impl Filter {
 		true
 	}
 
+	/// Returns true if every spell of the caster's spell list has the tags required by this filter
+	/// (e.g. a filter for `Warlock` spells matches the Warlock caster).
+	pub fn matches_caster(&self, caster: &super::Caster) -> bool {
+		self.tags.iter().all(|tag| caster.restriction.tags.contains(tag))
+	}
+
 	pub fn as_criteria(&self) -> crate::database::Criteria {
 		use crate::database::Criteria;
 		let mut criteria = Vec::new();
@@ -4,7 +4,11 @@ use crate::{
 	system::{
 		core::SourceId,
 		dnd5e::{
-			data::{character::Character, item::container::Inventory, Ability, Bundle, Class, Condition, Rest, Spell},
+			data::{
+				character::Character, item::container::Inventory, Ability, Bundle, Class, Condition, Rest, RestVariant,
+				Spell,
+			},
+			mutator::{ApplyEncumbrance, Exhaustion, MAX_EXHAUSTION_LEVEL},
 			SystemComponent,
 		},
 	},
@@ -22,8 +26,14 @@ use std::{
 	sync::Arc,
 };
 
+mod concentration;
+pub use concentration::*;
 mod description;
 pub use description::*;
+mod journal;
+pub use journal::*;
+mod leveling;
+pub use leveling::*;
 
 use super::{ObjectCacheProvider, RestEntry};
 
@@ -44,7 +54,15 @@ pub struct Persistent {
 	pub conditions: Conditions,
 	pub hit_points: HitPoints,
 	pub inspiration: bool,
+	/// The character's level of exhaustion, from 0 (not exhausted) to 6.
+	pub exhaustion: u8,
+	/// The total experience points earned, used when the level progression setting is `Experience`.
+	pub experience: u32,
+	/// The spell the character is currently concentrating on, if any.
+	pub concentration: Option<Concentration>,
 	pub settings: Settings,
+	/// The log of changes made to the character, grouped into sessions when displayed.
+	pub journal: Journal,
 }
 impl MutatorGroup for Persistent {
 	type Target = Character;
@@ -82,7 +100,12 @@ impl MutatorGroup for Persistent {
 			stats.apply_from(class, parent);
 		}
 		stats.apply_from(&self.conditions, parent);
+		if self.exhaustion > 0 {
+			let mutator = Exhaustion(self.exhaustion);
+			stats.apply(&mutator.into(), &parent.join("Exhaustion"));
+		}
 		stats.apply_from(&self.inventory, parent);
+		stats.apply(&ApplyEncumbrance.into(), &parent.join("Encumbrance"));
 	}
 }
 
@@ -105,6 +128,17 @@ impl Persistent {
 		}
 	}
 
+	/// Returns the number of character levels the character can currently gain.
+	/// When using milestone leveling, levels can be gained at any time (until max level is reached).
+	pub fn available_level_ups(&self) -> usize {
+		let level = self.level(None);
+		let max_level = match self.settings.level_progression {
+			LevelProgression::Milestone => MAX_CHARACTER_LEVEL,
+			LevelProgression::Experience => self.settings.experience_table.level(self.experience),
+		};
+		max_level.saturating_sub(level)
+	}
+
 	pub fn hit_points(&self) -> &HitPoints {
 		&self.hit_points
 	}
@@ -230,6 +264,12 @@ impl FromKdl<NodeContext> for Persistent {
 		let hit_points = node.query_req_t::<HitPoints>("scope() > hit_points")?;
 
 		let inspiration = node.query_bool_opt("scope() > inspiration", 0)?.unwrap_or_default();
+		let exhaustion = node.query_i64_opt("scope() > exhaustion", 0)?.unwrap_or_default();
+		let exhaustion = exhaustion.clamp(0, MAX_EXHAUSTION_LEVEL as i64) as u8;
+		let experience = node.query_i64_opt("scope() > experience", 0)?.unwrap_or_default();
+		let experience = experience.clamp(0, u32::MAX as i64) as u32;
+		let concentration = node.query_opt_t::<Concentration>("scope() > concentration")?;
+		let journal = node.query_opt_t::<Journal>("scope() > journal")?.unwrap_or_default();
 
 		let mut conditions = Conditions::default();
 		for condition in node.query_all_t::<Condition>("scope() > condition")? {
@@ -263,6 +303,10 @@ impl FromKdl<NodeContext> for Persistent {
 			ability_scores,
 			hit_points,
 			inspiration,
+			exhaustion,
+			experience,
+			concentration,
+			journal,
 			conditions,
 			inventory,
 			selected_spells,
@@ -290,6 +334,18 @@ impl AsKdl for Persistent {
 
 		node.push_child_t("hit_points", &self.hit_points);
 		node.push_child_t("inspiration", &self.inspiration);
+		if self.exhaustion > 0 {
+			node.push_child_t("exhaustion", &self.exhaustion);
+		}
+		if self.experience != 0 {
+			node.push_child_t("experience", &self.experience);
+		}
+		if let Some(concentration) = &self.concentration {
+			node.push_child_t("concentration", concentration);
+		}
+		if !self.journal.is_empty() {
+			node.push_child_t("journal", &self.journal);
+		}
 
 		node.push_child_opt_t("inventory", &self.inventory);
 		node.push_child_opt_t("spells", &self.selected_spells);
@@ -358,6 +414,12 @@ impl HitPoints {
 		self.temp = value;
 	}
 
+	/// Temporary hit points don't stack, so gaining more of them only replaces
+	/// the current temporary hit points if the new amount is higher.
+	pub fn grant_temp_hp(&mut self, value: u32) {
+		self.temp = self.temp.max(value);
+	}
+
 	pub fn plus_hp(mut self, amount: i32, max: u32) -> Self {
 		let mut amt_abs = amount.abs() as u32;
 		let had_hp = self.current > 0;
@@ -381,6 +443,63 @@ impl HitPoints {
 		}
 		self
 	}
+
+	/// Applies damage to temporary hit points first, and then to current hit points.
+	/// Damage taken while at 0 hit points results in death saving throw failures (two if the damage is from a critical hit),
+	/// and any damage which remains after reaching 0 hit points kills the character outright if it equals or exceeds
+	/// their hit point maximum.
+	pub fn take_damage(&mut self, amount: u32, max: u32, critical: bool) -> HitPointDamage {
+		let had_hp = self.current > 0;
+		let temp_absorbed = self.temp.min(amount);
+		self.temp -= temp_absorbed;
+		let remaining = amount - temp_absorbed;
+		let hp_lost = self.current.min(remaining);
+		self.current -= hp_lost;
+		let overflow = remaining - hp_lost;
+
+		let outcome = match (had_hp, self.current > 0) {
+			(_, true) => DamageOutcome::Conscious,
+			_ if overflow > 0 && overflow >= max => DamageOutcome::InstantDeath,
+			(true, false) => DamageOutcome::Unconscious,
+			(false, false) if remaining == 0 => DamageOutcome::Unconscious,
+			(false, false) => DamageOutcome::DeathSaveFailures(if critical { 2 } else { 1 }),
+		};
+		match outcome {
+			DamageOutcome::InstantDeath => {
+				self.failure_saves = 3;
+			}
+			DamageOutcome::DeathSaveFailures(failures) => {
+				self.failure_saves = self.failure_saves.saturating_add(failures).min(3);
+			}
+			_ => {}
+		}
+
+		HitPointDamage {
+			temp_absorbed,
+			hp_lost,
+			outcome,
+		}
+	}
+}
+
+/// How much damage was absorbed by temporary and current hit points, and what happened to the character as a result.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct HitPointDamage {
+	pub temp_absorbed: u32,
+	pub hp_lost: u32,
+	pub outcome: DamageOutcome,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DamageOutcome {
+	/// The character still has hit points remaining.
+	Conscious,
+	/// The character is at 0 hit points and is unconscious.
+	Unconscious,
+	/// The character took damage while at 0 hit points, and suffered some number of death saving throw failures.
+	DeathSaveFailures(u8),
+	/// The damage remaining after dropping to 0 hit points equals or exceeds the hit point maximum.
+	InstantDeath,
 }
 impl std::ops::Add<(i32, u32)> for HitPoints {
 	type Output = Self;
@@ -481,6 +600,12 @@ impl MutatorGroup for Conditions {
 #[derive(Clone, PartialEq, Default, Debug)]
 pub struct Settings {
 	pub currency_auto_exchange: bool,
+	pub level_progression: LevelProgression,
+	pub experience_table: ExperienceTable,
+	pub rest_variant: RestVariant,
+	/// If true, carrying more than 5 or 10 times the character's Strength score reduces their speed
+	/// and gives disadvantage on some rolls (the variant encumbrance rules).
+	pub variant_encumbrance: bool,
 }
 
 impl Settings {
@@ -489,8 +614,34 @@ impl Settings {
 			"currency_auto_exchange" => {
 				self.currency_auto_exchange = node.next_bool_req()?;
 			}
+			"level_progression" => {
+				self.level_progression = node.next_str_req_t::<LevelProgression>()?;
+			}
+			"experience_table" => {
+				let mut thresholds = Vec::new();
+				while let Some(threshold) = node.next_i64_opt()? {
+					thresholds.push(threshold as u32);
+				}
+				self.experience_table = thresholds.into();
+			}
+			"rest_variant" => {
+				self.rest_variant = node.next_str_req_t::<RestVariant>()?;
+			}
+			"variant_encumbrance" => {
+				self.variant_encumbrance = node.next_bool_req()?;
+			}
 			key => {
-				return Err(NotInList(key.into(), vec!["currency_auto_exchange"]).into());
+				return Err(NotInList(
+					key.into(),
+					vec![
+						"currency_auto_exchange",
+						"level_progression",
+						"experience_table",
+						"rest_variant",
+						"variant_encumbrance",
+					],
+				)
+				.into());
 			}
 		}
 		Ok(())
@@ -503,6 +654,37 @@ impl Settings {
 				.with_entry(self.currency_auto_exchange)
 				.build("setting"),
 		);
+		if self.level_progression != LevelProgression::default() {
+			nodes.push_child(
+				NodeBuilder::default()
+					.with_entry("level_progression")
+					.with_entry(self.level_progression.to_string())
+					.build("setting"),
+			);
+		}
+		if self.experience_table != ExperienceTable::default() {
+			let mut node = NodeBuilder::default().with_entry("experience_table");
+			for threshold in self.experience_table.thresholds() {
+				node.push_entry(*threshold as i64);
+			}
+			nodes.push_child(node.build("setting"));
+		}
+		if self.rest_variant != RestVariant::default() {
+			nodes.push_child(
+				NodeBuilder::default()
+					.with_entry("rest_variant")
+					.with_entry(self.rest_variant.to_string())
+					.build("setting"),
+			);
+		}
+		if self.variant_encumbrance {
+			nodes.push_child(
+				NodeBuilder::default()
+					.with_entry("variant_encumbrance")
+					.with_entry(true)
+					.build("setting"),
+			);
+		}
 	}
 }
 
@@ -679,4 +861,70 @@ mod test_hit_points {
 		assert_eq_askdl!(&data, doc);
 		Ok(())
 	}
+
+	#[test]
+	fn grant_temp_hp() {
+		let mut hit_points = HitPoints {
+			temp: 5,
+			..Default::default()
+		};
+		hit_points.grant_temp_hp(3);
+		assert_eq!(hit_points.temp, 5);
+		hit_points.grant_temp_hp(8);
+		assert_eq!(hit_points.temp, 8);
+	}
+
+	#[test]
+	fn take_damage_temp_first() {
+		let mut hit_points = HitPoints {
+			current: 10,
+			temp: 5,
+			..Default::default()
+		};
+		let damage = hit_points.take_damage(7, 20, false);
+		assert_eq!(hit_points.temp, 0);
+		assert_eq!(hit_points.current, 8);
+		assert_eq!(
+			damage,
+			HitPointDamage {
+				temp_absorbed: 5,
+				hp_lost: 2,
+				outcome: DamageOutcome::Conscious,
+			}
+		);
+	}
+
+	#[test]
+	fn take_damage_drop_to_zero() {
+		let mut hit_points = HitPoints {
+			current: 6,
+			..Default::default()
+		};
+		let damage = hit_points.take_damage(17, 12, false);
+		assert_eq!(hit_points.current, 0);
+		assert_eq!(damage.outcome, DamageOutcome::Unconscious);
+		assert_eq!(hit_points.failure_saves, 0);
+	}
+
+	#[test]
+	fn take_damage_massive() {
+		let mut hit_points = HitPoints {
+			current: 6,
+			..Default::default()
+		};
+		let damage = hit_points.take_damage(18, 12, false);
+		assert_eq!(damage.outcome, DamageOutcome::InstantDeath);
+		assert_eq!(hit_points.failure_saves, 3);
+	}
+
+	#[test]
+	fn take_damage_at_zero() {
+		let mut hit_points = HitPoints {
+			failure_saves: 1,
+			..Default::default()
+		};
+		let damage = hit_points.take_damage(3, 12, true);
+		assert_eq!(damage.outcome, DamageOutcome::DeathSaveFailures(2));
+		assert_eq!(hit_points.failure_saves, 3);
+	}
 }
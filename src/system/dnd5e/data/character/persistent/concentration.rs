@@ -0,0 +1,160 @@
+use super::Persistent;
+use crate::{kdl_ext::NodeContext, system::core::SourceId};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+
+/// The spell a character is currently concentrating on.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Concentration {
+	pub spell_id: SourceId,
+	pub spell_name: String,
+	/// The name of the spellcasting feature (e.g. "Wizard") which cast the spell, if any.
+	pub caster: Option<String>,
+}
+
+/// Returns the DC of the Constitution saving throw required to maintain concentration after taking damage.
+pub fn concentration_save_dc(damage: u32) -> u32 {
+	(damage / 2).max(10)
+}
+
+impl Persistent {
+	/// Starts concentrating on a spell, ending any existing concentration.
+	/// Returns the concentration that was ended, if it was for a different spell.
+	pub fn begin_concentration(&mut self, concentration: Concentration) -> Option<Concentration> {
+		let previous = self.concentration.replace(concentration);
+		previous.filter(|previous| Some(previous) != self.concentration.as_ref())
+	}
+
+	pub fn end_concentration(&mut self) -> Option<Concentration> {
+		self.concentration.take()
+	}
+
+	/// Applies healing (positive) or damage (negative) to the character's hit points.
+	/// Dropping to 0 hit points ends concentration. Otherwise, if the character took damage while concentrating,
+	/// the DC of the saving throw needed to maintain concentration is returned.
+	pub fn apply_hit_point_delta(&mut self, delta: i32, max_hp: u32) -> Option<u32> {
+		let dc = self.hit_point_delta_concentration_dc(delta, max_hp);
+		self.hit_points += (delta, max_hp);
+		if self.hit_points.current == 0 {
+			self.end_concentration();
+		}
+		dc
+	}
+
+	/// Returns the DC of the saving throw needed to maintain concentration if the hit point delta were applied,
+	/// without changing the character.
+	pub fn hit_point_delta_concentration_dc(&self, delta: i32, max_hp: u32) -> Option<u32> {
+		let mut hit_points = self.hit_points;
+		hit_points += (delta, max_hp);
+		match (delta < 0 && hit_points.current > 0, &self.concentration) {
+			(true, Some(_)) => Some(concentration_save_dc(delta.unsigned_abs())),
+			_ => None,
+		}
+	}
+}
+
+impl FromKdl<NodeContext> for Concentration {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let spell_name = node.next_str_req()?.to_owned();
+		let spell_id = node.get_str_req_t::<SourceId>("id")?;
+		let caster = node.get_str_opt("caster")?.map(str::to_owned);
+		Ok(Self {
+			spell_id,
+			spell_name,
+			caster,
+		})
+	}
+}
+
+impl AsKdl for Concentration {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node.push_entry(self.spell_name.clone());
+		node.push_entry(("id", self.spell_id.to_string()));
+		if let Some(caster) = &self.caster {
+			node.push_entry(("caster", caster.clone()));
+		}
+		node
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::character::HitPoints;
+	use std::str::FromStr;
+
+	fn bless() -> Concentration {
+		Concentration {
+			spell_id: SourceId::from_str("local://basic-rules@dnd5e/spells/bless.kdl").unwrap(),
+			spell_name: "Bless".into(),
+			caster: Some("Cleric".into()),
+		}
+	}
+
+	fn concentrating(current: u32) -> Persistent {
+		Persistent {
+			hit_points: HitPoints {
+				current,
+				..Default::default()
+			},
+			concentration: Some(bless()),
+			..Default::default()
+		}
+	}
+
+	mod kdl {
+		use super::*;
+		use crate::kdl_ext::test_utils::*;
+
+		static NODE_NAME: &str = "concentration";
+
+		#[test]
+		fn with_caster() -> anyhow::Result<()> {
+			let doc = "concentration \"Bless\" id=\"local://basic-rules@dnd5e/spells/bless.kdl\" caster=\"Cleric\"";
+			let data = bless();
+			assert_eq_fromkdl!(Concentration, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn save_dc() {
+		assert_eq!(concentration_save_dc(0), 10);
+		assert_eq!(concentration_save_dc(21), 10);
+		assert_eq!(concentration_save_dc(22), 11);
+		assert_eq!(concentration_save_dc(45), 22);
+	}
+
+	#[test]
+	fn replace_concentration() {
+		let mut persistent = concentrating(10);
+		assert_eq!(persistent.begin_concentration(bless()), None);
+		let haste = Concentration {
+			spell_id: SourceId::from_str("local://basic-rules@dnd5e/spells/haste.kdl").unwrap(),
+			spell_name: "Haste".into(),
+			caster: Some("Wizard".into()),
+		};
+		assert_eq!(persistent.begin_concentration(haste.clone()), Some(bless()));
+		assert_eq!(persistent.concentration, Some(haste));
+	}
+
+	#[test]
+	fn damage_prompts_save() {
+		let mut persistent = concentrating(30);
+		assert_eq!(persistent.hit_point_delta_concentration_dc(-24, 30), Some(12));
+		assert_eq!(persistent.hit_points.current, 30);
+		assert_eq!(persistent.apply_hit_point_delta(-24, 30), Some(12));
+		assert_eq!(persistent.apply_hit_point_delta(5, 30), None);
+		assert_eq!(persistent.concentration, Some(bless()));
+	}
+
+	#[test]
+	fn zero_hp_ends_concentration() {
+		let mut persistent = concentrating(10);
+		assert_eq!(persistent.apply_hit_point_delta(-12, 30), None);
+		assert_eq!(persistent.hit_points.current, 0);
+		assert_eq!(persistent.concentration, None);
+	}
+}
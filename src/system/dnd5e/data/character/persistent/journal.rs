@@ -0,0 +1,304 @@
+use super::{Persistent, MAX_SPELL_RANK};
+use crate::kdl_ext::NodeContext;
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+use time::{Date, OffsetDateTime, UtcOffset};
+
+/// The number of entries kept in a character's journal before the oldest are forgotten,
+/// so that the journal (which is saved with the character) doesn't grow without bound.
+static JOURNAL_CAPACITY: usize = 1000;
+
+/// A timestamped record of the changes made to a character, used to recap what happened each play session.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct Journal {
+	entries: Vec<JournalEntry>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct JournalEntry {
+	/// When the change was made, in seconds since the unix epoch (UTC).
+	pub timestamp: i64,
+	pub description: String,
+	/// The specific values which were changed (e.g. "HP 12 → 5").
+	pub details: Vec<String>,
+}
+
+/// The journal entries made on a single (local) calendar day.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JournalSession<'journal> {
+	pub date: Date,
+	pub entries: Vec<&'journal JournalEntry>,
+}
+
+impl Journal {
+	pub fn is_empty(&self) -> bool {
+		self.entries.is_empty()
+	}
+
+	pub fn entries(&self) -> &Vec<JournalEntry> {
+		&self.entries
+	}
+
+	pub fn push(&mut self, entry: JournalEntry) {
+		self.entries.push(entry);
+		self.truncate(JOURNAL_CAPACITY);
+	}
+
+	/// Removes the oldest entries until there are at most `capacity` entries in the journal.
+	fn truncate(&mut self, capacity: usize) {
+		let excess = self.entries.len().saturating_sub(capacity);
+		self.entries.drain(..excess);
+	}
+
+	/// Groups the entries by the day they were made in the provided timezone, where each day counts as a session.
+	pub fn sessions(&self, offset: UtcOffset) -> Vec<JournalSession<'_>> {
+		let mut sessions = Vec::<JournalSession<'_>>::new();
+		for entry in &self.entries {
+			let date = entry.local_time(offset).date();
+			match sessions.last_mut() {
+				Some(session) if session.date == date => session.entries.push(entry),
+				_ => sessions.push(JournalSession {
+					date,
+					entries: vec![entry],
+				}),
+			}
+		}
+		sessions
+	}
+}
+
+impl JournalEntry {
+	pub fn local_time(&self, offset: UtcOffset) -> OffsetDateTime {
+		let utc = OffsetDateTime::from_unix_timestamp(self.timestamp).unwrap_or(OffsetDateTime::UNIX_EPOCH);
+		utc.to_offset(offset)
+	}
+
+	/// The local time of day the entry was made, formatted as `HH:MM`.
+	pub fn time_of_day(&self, offset: UtcOffset) -> String {
+		let time = self.local_time(offset);
+		format!("{:02}:{:02}", time.hour(), time.minute())
+	}
+}
+
+impl Persistent {
+	/// Returns a description of each notable difference between this character and a previous version of it,
+	/// such as hit points lost, spell slots spent, and items gained.
+	pub fn describe_changes_since(&self, previous: &Persistent) -> Vec<String> {
+		let mut details = Vec::new();
+		let mut compare = |name: &str, prev: i64, next: i64| {
+			if prev != next {
+				details.push(format!("{name} {prev} → {next}"));
+			}
+		};
+		compare("HP", previous.hit_points.current as i64, self.hit_points.current as i64);
+		compare("Temp HP", previous.hit_points.temp as i64, self.hit_points.temp as i64);
+		compare("Level", previous.level(None) as i64, self.level(None) as i64);
+		compare("XP", previous.experience as i64, self.experience as i64);
+		for rank in 1..=MAX_SPELL_RANK {
+			let prev = previous.consumed_spell_slots(rank) as i64;
+			let next = self.consumed_spell_slots(rank) as i64;
+			compare(&format!("Rank {rank} slots used"), prev, next);
+		}
+
+		let item_names = |persistent: &Persistent| {
+			let entries = persistent.inventory.entries();
+			entries.map(|entry| entry.item.name.clone()).collect::<Vec<_>>()
+		};
+		let (mut removed, mut added) = (item_names(previous), item_names(self));
+		removed.retain(|name| match added.iter().position(|other| other == name) {
+			Some(idx) => {
+				added.remove(idx);
+				false
+			}
+			None => true,
+		});
+		details.extend(added.into_iter().map(|name| format!("Gained {name}")));
+		details.extend(removed.into_iter().map(|name| format!("Lost {name}")));
+
+		details
+	}
+}
+
+/// Formats the sessions as a Markdown document, for sharing session recaps.
+pub fn journal_to_markdown(character_name: &str, sessions: &[JournalSession<'_>], offset: UtcOffset) -> String {
+	let mut lines = vec![format!("# {character_name} Session Log")];
+	for session in sessions {
+		lines.push(String::new());
+		lines.push(format!("## {}", session.date));
+		lines.push(String::new());
+		for entry in &session.entries {
+			let mut line = format!("- **{}** {}", entry.time_of_day(offset), entry.description);
+			if !entry.details.is_empty() {
+				line += &format!(" ({})", entry.details.join(", "));
+			}
+			lines.push(line);
+		}
+	}
+	lines.join("\n") + "\n"
+}
+
+impl FromKdl<NodeContext> for Journal {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let entries = node.query_all_t::<JournalEntry>("scope() > entry")?;
+		let mut journal = Self { entries };
+		journal.truncate(JOURNAL_CAPACITY);
+		Ok(journal)
+	}
+}
+
+impl AsKdl for Journal {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		for entry in &self.entries {
+			node.push_child_t("entry", entry);
+		}
+		node
+	}
+}
+
+impl FromKdl<NodeContext> for JournalEntry {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let timestamp = node.next_i64_req()?;
+		let description = node.next_str_req()?.to_owned();
+		let mut details = Vec::new();
+		for detail in node.query_str_all("scope() > detail", 0)? {
+			details.push(detail.to_owned());
+		}
+		Ok(Self {
+			timestamp,
+			description,
+			details,
+		})
+	}
+}
+
+impl AsKdl for JournalEntry {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node.push_entry(self.timestamp);
+		node.push_entry(self.description.clone());
+		for detail in &self.details {
+			node.push_child_t("detail", detail);
+		}
+		node
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::character::HitPoints;
+
+	// 2023-11-14 22:13:20 UTC
+	static TIMESTAMP: i64 = 1_700_000_000;
+
+	fn entry(timestamp: i64, description: &str, details: &[&str]) -> JournalEntry {
+		JournalEntry {
+			timestamp,
+			description: description.into(),
+			details: details.iter().map(|detail| (*detail).to_owned()).collect(),
+		}
+	}
+
+	fn journal() -> Journal {
+		Journal {
+			entries: vec![
+				entry(TIMESTAMP, "Take Damage", &["HP 12 → 5"]),
+				entry(TIMESTAMP + 60, "Select Spell", &[]),
+				entry(TIMESTAMP + 4 * 60 * 60, "Long Rest", &["HP 5 → 12"]),
+			],
+		}
+	}
+
+	mod kdl {
+		use super::*;
+		use crate::kdl_ext::test_utils::*;
+
+		static NODE_NAME: &str = "journal";
+
+		#[test]
+		fn entries() -> anyhow::Result<()> {
+			let doc = "
+				|journal {
+				|    entry 1700000000 \"Take Damage\" {
+				|        detail \"HP 12 → 5\"
+				|    }
+				|    entry 1700000060 \"Select Spell\"
+				|}
+			";
+			let data = Journal {
+				entries: vec![
+					entry(TIMESTAMP, "Take Damage", &["HP 12 → 5"]),
+					entry(TIMESTAMP + 60, "Select Spell", &[]),
+				],
+			};
+			assert_eq_fromkdl!(Journal, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+	}
+
+	#[test]
+	fn bounded() {
+		let mut journal = Journal::default();
+		for idx in 0..JOURNAL_CAPACITY as i64 + 5 {
+			journal.push(entry(TIMESTAMP + idx, "Take Damage", &[]));
+		}
+		assert_eq!(journal.entries().len(), JOURNAL_CAPACITY);
+		assert_eq!(journal.entries()[0].timestamp, TIMESTAMP + 5);
+	}
+
+	#[test]
+	fn sessions_by_local_date() {
+		let journal = journal();
+		let utc = journal.sessions(UtcOffset::UTC);
+		assert_eq!(utc.len(), 2);
+		assert_eq!(utc[0].entries.len(), 2);
+		assert_eq!(utc[1].date.to_string(), "2023-11-15");
+
+		let offset = UtcOffset::from_hms(-5, 0, 0).unwrap();
+		let local = journal.sessions(offset);
+		assert_eq!(local.len(), 1);
+		assert_eq!(local[0].date.to_string(), "2023-11-14");
+	}
+
+	#[test]
+	fn markdown() {
+		let journal = journal();
+		let sessions = journal.sessions(UtcOffset::UTC);
+		let expected = "\
+# Aerith Session Log
+
+## 2023-11-14
+
+- **22:13** Take Damage (HP 12 → 5)
+- **22:14** Select Spell
+
+## 2023-11-15
+
+- **02:13** Long Rest (HP 5 → 12)
+";
+		assert_eq!(journal_to_markdown("Aerith", &sessions, UtcOffset::UTC), expected);
+	}
+
+	#[test]
+	fn describe_changes() {
+		let previous = Persistent {
+			hit_points: HitPoints {
+				current: 12,
+				..Default::default()
+			},
+			..Default::default()
+		};
+		let mut next = previous.clone();
+		next.hit_points.current = 5;
+		next.hit_points.temp = 3;
+		next.experience = 300;
+		assert_eq!(
+			next.describe_changes_since(&previous),
+			vec!["HP 12 → 5".to_owned(), "Temp HP 0 → 3".into(), "XP 0 → 300".into()]
+		);
+		assert_eq!(previous.describe_changes_since(&previous), Vec::<String>::new());
+	}
+}
@@ -0,0 +1,115 @@
+use crate::utility::InvalidEnumStr;
+use enumset::EnumSetType;
+use std::str::FromStr;
+
+pub static MAX_CHARACTER_LEVEL: usize = 20;
+
+/// How a character gains levels.
+#[derive(Debug, EnumSetType, Default)]
+pub enum LevelProgression {
+	/// Levels are granted by the game master at significant points in the story.
+	#[default]
+	Milestone,
+	/// Levels are gained by accumulating experience points (see `ExperienceTable`).
+	Experience,
+}
+
+impl ToString for LevelProgression {
+	fn to_string(&self) -> String {
+		match self {
+			Self::Milestone => "Milestone",
+			Self::Experience => "Experience",
+		}
+		.into()
+	}
+}
+
+impl FromStr for LevelProgression {
+	type Err = InvalidEnumStr<Self>;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"Milestone" => Ok(Self::Milestone),
+			"Experience" => Ok(Self::Experience),
+			_ => Err(InvalidEnumStr::from(s)),
+		}
+	}
+}
+
+/// The amount of experience points required to reach each character level.
+/// The first entry is the experience required for level 1, the second for level 2, and so on.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExperienceTable(Vec<u32>);
+
+impl Default for ExperienceTable {
+	fn default() -> Self {
+		Self(vec![
+			0, 300, 900, 2700, 6500, 14000, 23000, 34000, 48000, 64000, 85000, 100000, 120000, 140000, 165000, 195000,
+			225000, 265000, 305000, 355000,
+		])
+	}
+}
+
+impl From<Vec<u32>> for ExperienceTable {
+	fn from(mut thresholds: Vec<u32>) -> Self {
+		thresholds.sort();
+		thresholds.truncate(MAX_CHARACTER_LEVEL);
+		Self(thresholds)
+	}
+}
+
+impl ExperienceTable {
+	pub fn thresholds(&self) -> &Vec<u32> {
+		&self.0
+	}
+
+	/// Returns the character level which the amount of experience points grants.
+	pub fn level(&self, experience: u32) -> usize {
+		self.0
+			.iter()
+			.filter(|threshold| **threshold <= experience)
+			.count()
+			.max(1)
+	}
+
+	/// Returns the amount of experience points required to reach a specific character level,
+	/// or None if the level cannot be reached through experience.
+	pub fn threshold(&self, level: usize) -> Option<u32> {
+		match level {
+			0 => None,
+			level => self.0.get(level - 1).cloned(),
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	#[test]
+	fn level_from_experience() {
+		let table = ExperienceTable::default();
+		assert_eq!(table.level(0), 1);
+		assert_eq!(table.level(299), 1);
+		assert_eq!(table.level(300), 2);
+		assert_eq!(table.level(6500), 5);
+		assert_eq!(table.level(1_000_000), 20);
+	}
+
+	#[test]
+	fn threshold() {
+		let table = ExperienceTable::default();
+		assert_eq!(table.threshold(0), None);
+		assert_eq!(table.threshold(1), Some(0));
+		assert_eq!(table.threshold(3), Some(900));
+		assert_eq!(table.threshold(21), None);
+	}
+
+	#[test]
+	fn custom_table() {
+		let table = ExperienceTable::from(vec![100, 0, 50]);
+		assert_eq!(table.thresholds(), &vec![0, 50, 100]);
+		assert_eq!(table.level(75), 2);
+		assert_eq!(table.threshold(4), None);
+	}
+}
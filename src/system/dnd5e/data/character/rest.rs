@@ -0,0 +1,471 @@
+use super::{Character, Persistent, RestEntry};
+use crate::system::dnd5e::data::{item::equipment::ChargesReset, roll::Die, Ability, Rest};
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Some number of hit dice from a specific class, which are spent during a short rest.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpentHitDice {
+	pub class_name: String,
+	pub die: Die,
+	pub amount: u32,
+	/// The data path of the class's hit die selector, where the number of spent hit dice is stored.
+	pub data_path: PathBuf,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RestHitPoints {
+	/// Hit points are restored to the maximum, and temporary hit points and death saving throws are cleared.
+	Restore(u32),
+	/// Hit points increase by some amount (up to the maximum).
+	Gain { amount: u32, max: u32 },
+}
+
+/// The change in the number of spent hit dice for a class.
+#[derive(Clone, PartialEq, Debug)]
+pub struct HitDiceChange {
+	pub class_name: String,
+	pub die: Die,
+	pub data_path: PathBuf,
+	/// The number of hit dice spent (if positive) or recovered (if negative).
+	pub delta: i32,
+	/// The total number of hit dice which are spent after the rest.
+	pub spent: u32,
+}
+
+/// The charges regained by an item in the inventory during a rest.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ItemChargesReset {
+	pub id_path: Vec<Uuid>,
+	pub item_name: String,
+	pub capacity: usize,
+	pub reset: ChargesReset,
+}
+
+/// All of the changes that a rest makes to a character.
+/// Created via `Character::long_rest` or `Character::short_rest`, and applied to the persistent data using `apply_to`
+/// once the amounts regained randomly have been rolled using `roll`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RestEffects {
+	pub rest: Rest,
+	pub hit_points: RestHitPoints,
+	pub hit_dice: Vec<HitDiceChange>,
+	pub resets: Vec<RestEntry>,
+	pub charges: Vec<ItemChargesReset>,
+	pub reduce_exhaustion: bool,
+}
+
+/// The amounts rolled for the uses and item charges regained randomly during a rest,
+/// in the same order as the `resets` and `charges` of the `RestEffects` they were rolled for.
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct RestRolls {
+	pub uses: Vec<Option<u32>>,
+	pub charges: Vec<usize>,
+}
+
+impl Character {
+	fn spent_hit_dice(&self, data_path: &PathBuf) -> u32 {
+		let spent = self.get_first_selection_at::<u32>(data_path);
+		spent.map(Result::ok).flatten().unwrap_or(0)
+	}
+
+	fn rest_resets_for(&self, rest: Rest) -> Vec<RestEntry> {
+		let resets = rest.resets_to_apply().into_iter();
+		resets
+			.map(|rest| self.rest_resets().get(rest).clone())
+			.flatten()
+			.collect()
+	}
+
+	fn item_charges_for(&self, rest: Rest) -> Vec<ItemChargesReset> {
+		let resets = self.persistent().settings.rest_variant.charge_resets(rest);
+		let mut charges = Vec::new();
+		for entry in self.inventory().all_entries() {
+			let Some(item_charges) = entry.charges() else {
+				continue;
+			};
+			let Some(reset) = &item_charges.reset else {
+				continue;
+			};
+			if !resets.contains(&reset.rest) || entry.consumed_charges == 0 {
+				continue;
+			}
+			charges.push(ItemChargesReset {
+				id_path: entry.id_path.clone(),
+				item_name: entry.item.name.clone(),
+				capacity: item_charges.capacity,
+				reset: reset.clone(),
+			});
+		}
+		charges
+	}
+
+	/// Determines the effects of taking a long rest.
+	/// Hit points are fully restored, and half of the character's total hit dice (minimum of 1) are recovered,
+	/// starting with the classes that have the largest hit die.
+	pub fn long_rest(&self) -> RestEffects {
+		let classes = &self.persistent().classes;
+		let total_hit_dice = classes.iter().map(|class| class.current_level as u32).sum::<u32>();
+		let mut budget = (total_hit_dice / 2).max(1);
+
+		let mut by_largest_die = classes.iter().collect::<Vec<_>>();
+		by_largest_die.sort_by(|a, b| b.hit_die.value().cmp(&a.hit_die.value()));
+
+		let mut hit_dice = Vec::new();
+		for class in by_largest_die {
+			let Some(data_path) = class.hit_die_selector.get_data_path() else {
+				continue;
+			};
+			let spent = self.spent_hit_dice(&data_path);
+			let recovered = spent.min(budget);
+			if recovered == 0 {
+				continue;
+			}
+			budget -= recovered;
+			hit_dice.push(HitDiceChange {
+				class_name: class.name.clone(),
+				die: class.hit_die,
+				data_path,
+				delta: -(recovered as i32),
+				spent: spent - recovered,
+			});
+		}
+
+		RestEffects {
+			rest: Rest::Long,
+			hit_points: RestHitPoints::Restore(self.max_hit_points().value()),
+			hit_dice,
+			resets: self.rest_resets_for(Rest::Long),
+			charges: self.item_charges_for(Rest::Long),
+			reduce_exhaustion: self.persistent().exhaustion > 0,
+		}
+	}
+
+	/// Determines the effects of taking a short rest, during which the provided hit dice are spent.
+	/// The character regains the rolled hit points, plus their constitution modifier for each hit die spent.
+	pub fn short_rest(&self, spent_hit_dice: &[SpentHitDice], rolled_hp: u32) -> RestEffects {
+		let constitution_mod = self.ability_modifier(Ability::Constitution, None);
+		let num_rolls = spent_hit_dice.iter().map(|spent| spent.amount).sum::<u32>() as i32;
+		let amount = ((rolled_hp as i32) + num_rolls * constitution_mod).max(0) as u32;
+
+		let mut hit_dice = Vec::new();
+		for spent in spent_hit_dice {
+			if spent.amount == 0 {
+				continue;
+			}
+			hit_dice.push(HitDiceChange {
+				class_name: spent.class_name.clone(),
+				die: spent.die,
+				data_path: spent.data_path.clone(),
+				delta: spent.amount as i32,
+				spent: self.spent_hit_dice(&spent.data_path).saturating_add(spent.amount),
+			});
+		}
+
+		RestEffects {
+			rest: Rest::Short,
+			hit_points: RestHitPoints::Gain {
+				amount,
+				max: self.max_hit_points().value(),
+			},
+			hit_dice,
+			resets: self.rest_resets_for(Rest::Short),
+			charges: self.item_charges_for(Rest::Short),
+			reduce_exhaustion: false,
+		}
+	}
+}
+
+impl RestEffects {
+	/// Rolls the number of uses and item charges regained randomly by the rest.
+	/// This happens before the rest is applied, so that applying it only ever makes fixed changes.
+	pub fn roll(&self, rng: &mut impl rand::Rng) -> RestRolls {
+		let mut rolls = RestRolls::default();
+		for entry in &self.resets {
+			let uses = entry.restore_amount.as_ref().map(|roll| roll.roll(rng));
+			rolls.uses.push(uses);
+		}
+		for item in &self.charges {
+			rolls.charges.push(item.reset.roll_regained(item.capacity, rng));
+		}
+		rolls
+	}
+
+	/// Applies the rest (with the amounts rolled for it) to the persistent data of a character,
+	/// returning a description of each change made.
+	pub fn apply_to(&self, persistent: &mut Persistent, rolls: &RestRolls) -> Vec<String> {
+		let mut changes = Vec::new();
+		match self.hit_points {
+			RestHitPoints::Restore(max_hp) => {
+				persistent.hit_points.current = max_hp;
+				changes.push(format!("Set hit points to max ({max_hp})."));
+
+				persistent.hit_points.temp = 0;
+				changes.push(format!("Cleared temporary hit points."));
+
+				persistent.hit_points.failure_saves = 0;
+				persistent.hit_points.success_saves = 0;
+				changes.push(format!("Cleared saving throws."));
+			}
+			RestHitPoints::Gain { amount, max } => {
+				persistent.hit_points = persistent.hit_points.plus_hp(amount as i32, max);
+				changes.push(format!("Increased hit points by {amount}."));
+			}
+		}
+
+		for change in &self.hit_dice {
+			let spent = (change.spent > 0).then(|| change.spent.to_string());
+			persistent.set_selected(&change.data_path, spent);
+			let class_name = &change.class_name;
+			match change.delta {
+				delta if delta > 0 => changes.push(format!("Used {delta} hit dice from {class_name}.")),
+				delta => changes.push(format!("Recovered {} hit dice from {class_name}.", -delta)),
+			}
+		}
+
+		for (entry, uses_to_remove) in self.resets.iter().zip(&rolls.uses) {
+			let path_str = entry.source.display().to_string();
+			let path_str = path_str.replace("\\", "/");
+			match uses_to_remove {
+				None => changes.push(format!("Restored all uses to {path_str}.")),
+				Some(gained_uses) => changes.push(format!("Restored {gained_uses} uses to {path_str}.")),
+			}
+			for data_path in &entry.data_paths {
+				let new_value = match uses_to_remove {
+					None => None,
+					Some(gained_uses) => {
+						let prev_value = persistent.get_first_selection_at::<u32>(data_path);
+						let prev_value = prev_value.map(Result::ok).flatten().unwrap_or(0);
+						let new_value = prev_value.saturating_sub(*gained_uses);
+						(new_value > 0).then(|| new_value.to_string())
+					}
+				};
+				persistent.set_selected(data_path, new_value);
+			}
+		}
+
+		for (item, amount) in self.charges.iter().zip(&rolls.charges) {
+			let regained = persistent.inventory.restore_charges(&item.id_path, *amount);
+			changes.push(format!("Regained {regained} charges to {}.", item.item_name));
+		}
+
+		if self.reduce_exhaustion && persistent.exhaustion > 0 {
+			persistent.exhaustion -= 1;
+			changes.push(format!("Reduced exhaustion level to {}.", persistent.exhaustion));
+		}
+
+		changes
+	}
+
+	/// Returns true if applying the rest changes data which mutators depend on,
+	/// and the character needs to be recompiled.
+	pub fn requires_recompile(&self) -> bool {
+		self.reduce_exhaustion
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::Class;
+
+	fn character(classes: Vec<(&str, Die, usize, u32)>) -> Character {
+		let mut persistent = Persistent::default();
+		for (name, hit_die, current_level, _spent) in &classes {
+			persistent.add_class(Class {
+				name: (*name).into(),
+				hit_die: *hit_die,
+				current_level: *current_level,
+				..Default::default()
+			});
+		}
+		let mut character = Character::from(persistent);
+		for (idx, (_, _, _, spent)) in classes.into_iter().enumerate() {
+			let data_path = hit_die_path(&character, idx);
+			character
+				.persistent_mut()
+				.set_selected(data_path, Some(spent.to_string()));
+		}
+		character
+	}
+
+	fn hit_die_path(character: &Character, idx: usize) -> PathBuf {
+		character.persistent().classes[idx]
+			.hit_die_selector
+			.get_data_path()
+			.unwrap()
+	}
+
+	fn recovered(effects: &RestEffects) -> Vec<(&str, i32, u32)> {
+		let iter = effects.hit_dice.iter();
+		iter.map(|change| (change.class_name.as_str(), change.delta, change.spent))
+			.collect()
+	}
+
+	#[test]
+	fn long_rest_recovers_half_hit_dice() {
+		let character = character(vec![("Fighter", Die::D10, 4, 4)]);
+		let effects = character.long_rest();
+		assert_eq!(recovered(&effects), vec![("Fighter", -2, 2)]);
+	}
+
+	#[test]
+	fn long_rest_recovers_minimum_one() {
+		let character = character(vec![("Fighter", Die::D10, 1, 1)]);
+		let effects = character.long_rest();
+		assert_eq!(recovered(&effects), vec![("Fighter", -1, 0)]);
+	}
+
+	#[test]
+	fn long_rest_recovers_largest_dice_first() {
+		let character = character(vec![("Wizard", Die::D6, 3, 3), ("Barbarian", Die::D12, 2, 1)]);
+		let effects = character.long_rest();
+		assert_eq!(recovered(&effects), vec![("Barbarian", -1, 0), ("Wizard", -1, 2)]);
+	}
+
+	#[test]
+	fn long_rest_nothing_spent() {
+		let character = character(vec![("Fighter", Die::D10, 4, 0)]);
+		let effects = character.long_rest();
+		assert_eq!(recovered(&effects), vec![]);
+	}
+
+	#[test]
+	fn short_rest_spends_hit_dice() {
+		let character = character(vec![("Fighter", Die::D10, 4, 1)]);
+		let spent = SpentHitDice {
+			class_name: "Fighter".into(),
+			die: Die::D10,
+			amount: 2,
+			data_path: hit_die_path(&character, 0),
+		};
+		let effects = character.short_rest(&[spent], 12);
+		assert_eq!(recovered(&effects), vec![("Fighter", 2, 3)]);
+		assert_eq!(effects.hit_points, RestHitPoints::Gain { amount: 12, max: 0 });
+	}
+
+	#[test]
+	fn apply_long_rest() {
+		let mut character = character(vec![("Fighter", Die::D10, 4, 3)]);
+		character.persistent_mut().exhaustion = 2;
+		character.persistent_mut().hit_points.temp = 5;
+		let effects = character.long_rest();
+		assert!(effects.requires_recompile());
+
+		let mut persistent = character.persistent().clone();
+		let _changes = effects.apply_to(&mut persistent, &effects.roll(&mut rand::thread_rng()));
+		assert_eq!(persistent.exhaustion, 1);
+		assert_eq!(persistent.hit_points.temp, 0);
+		let spent = persistent.get_first_selection_at::<u32>(hit_die_path(&character, 0));
+		assert_eq!(spent.map(Result::ok).flatten(), Some(1));
+	}
+
+	#[test]
+	fn long_rest_regains_item_charges() {
+		use crate::system::dnd5e::data::item::{
+			equipment::{Charges, Equipment},
+			Item, Kind,
+		};
+		let mut character = character(vec![("Wizard", Die::D6, 1, 0)]);
+		let wand = character.persistent_mut().inventory.push(Item {
+			name: "Wand of Magic Missiles".into(),
+			kind: Kind::Equipment(Equipment {
+				charges: Some(Charges {
+					capacity: 7,
+					reset: Some(ChargesReset {
+						roll: None,
+						base: 3,
+						rest: Rest::Long,
+					}),
+				}),
+				..Default::default()
+			}),
+			..Default::default()
+		});
+		character.persistent_mut().inventory.set_consumed_charges(&[wand], 5);
+		assert!(character.short_rest(&[], 0).charges.is_empty());
+
+		let effects = character.long_rest();
+		assert_eq!(effects.charges.len(), 1);
+		let mut persistent = character.persistent().clone();
+		let changes = effects.apply_to(&mut persistent, &effects.roll(&mut rand::thread_rng()));
+		assert!(changes.contains(&"Regained 3 charges to Wand of Magic Missiles.".to_owned()));
+		assert_eq!(persistent.inventory.get_entry(&wand).unwrap().consumed_charges, 2);
+	}
+
+	#[test]
+	fn rest_regains_charges_of_items_in_containers() {
+		use crate::system::dnd5e::data::item::{
+			container::Inventory,
+			equipment::{Charges, Equipment},
+			Item, Kind,
+		};
+		let mut character = character(vec![("Wizard", Die::D6, 1, 0)]);
+		let backpack = character.persistent_mut().inventory.push(Item {
+			name: "Backpack".into(),
+			items: Some(Inventory::new()),
+			..Default::default()
+		});
+		let wand = Item {
+			name: "Wand of Magic Missiles".into(),
+			kind: Kind::Equipment(Equipment {
+				charges: Some(Charges {
+					capacity: 7,
+					reset: Some(ChargesReset {
+						roll: None,
+						base: 0,
+						rest: Rest::Long,
+					}),
+				}),
+				..Default::default()
+			}),
+			..Default::default()
+		};
+		let inventory = &mut character.persistent_mut().inventory;
+		let wand_path = inventory.insert_to(wand, &Some(vec![backpack]));
+		inventory.set_consumed_charges(&wand_path, 4);
+
+		let effects = character.long_rest();
+		assert_eq!(effects.charges.len(), 1);
+		assert_eq!(effects.charges[0].id_path, wand_path);
+		let mut persistent = character.persistent().clone();
+		let _changes = effects.apply_to(&mut persistent, &effects.roll(&mut rand::thread_rng()));
+		let entry = persistent.inventory.get_entry_at_path(&wand_path);
+		assert_eq!(entry.map(|entry| entry.consumed_charges), Some(0));
+	}
+
+	#[test]
+	fn gritty_realism_short_rest_regains_daily_charges() {
+		use crate::system::dnd5e::data::{
+			item::{
+				equipment::{Charges, Equipment},
+				Item, Kind,
+			},
+			RestVariant,
+		};
+		let mut character = character(vec![("Wizard", Die::D6, 1, 0)]);
+		character.persistent_mut().settings.rest_variant = RestVariant::GrittyRealism;
+		let wand = character.persistent_mut().inventory.push(Item {
+			name: "Wand of Web".into(),
+			kind: Kind::Equipment(Equipment {
+				charges: Some(Charges {
+					capacity: 7,
+					reset: Some(ChargesReset {
+						roll: None,
+						base: 0,
+						rest: Rest::Long,
+					}),
+				}),
+				..Default::default()
+			}),
+			..Default::default()
+		});
+		character.persistent_mut().inventory.set_consumed_charges(&[wand], 5);
+
+		let effects = character.short_rest(&[], 0);
+		assert_eq!(effects.charges.len(), 1);
+		let mut persistent = character.persistent().clone();
+		let _changes = effects.apply_to(&mut persistent, &effects.roll(&mut rand::thread_rng()));
+		assert_eq!(persistent.inventory.get_entry(&wand).unwrap().consumed_charges, 0);
+	}
+}
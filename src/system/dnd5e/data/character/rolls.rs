@@ -0,0 +1,298 @@
+use super::{Character, ModifierMap};
+use crate::system::dnd5e::data::{
+	action::{Attack, AttackCheckKind},
+	proficiency,
+	roll::{Modifier, RollBonus, RollOutcome},
+	Ability, DamageRoll, Skill,
+};
+
+impl Character {
+	fn check_bonuses(&self, ability: Ability, proficiency: proficiency::Level) -> Vec<RollBonus> {
+		let mut bonuses = vec![RollBonus::from((
+			self.ability_modifier(ability, None),
+			format!("{} modifier", ability.long_name()),
+		))];
+		let prof_bonus = proficiency * self.proficiency_bonus();
+		if prof_bonus != 0 {
+			bonuses.push(RollBonus::from((prof_bonus, proficiency.as_display_name())));
+		}
+		bonuses
+	}
+
+	/// Rolls an ability check for a skill, using the character's proficiency and
+	/// any advantage or disadvantage which applies to all checks for that skill.
+	pub fn roll_skill_check(&self, skill: Skill, rand: &mut impl rand::Rng) -> RollOutcome {
+		let proficiency = *self.skills().proficiency(skill).value();
+		let maps = [
+			self.skills().ability_modifiers(skill.ability()),
+			self.skills().skill_modifiers(skill),
+		];
+		let bonuses = self.check_bonuses(skill.ability(), proficiency);
+		let label = format!("{} Check", skill.display_name());
+		RollOutcome::check(label, resolve_modifier(&maps), bonuses, rand).with_notes(conditional_notes(&maps))
+	}
+
+	/// Rolls an ability check with a tool using the provided ability, applying the character's
+	/// proficiency with the tool and any advantage or disadvantage on checks of that ability.
+	pub fn roll_tool_check(&self, tool: &str, ability: Ability, rand: &mut impl rand::Rng) -> RollOutcome {
+		let proficiency = self.tool_proficiencies().check_proficiency(tool, ability);
+		let maps = [self.skills().ability_modifiers(ability)];
+		let bonuses = self.check_bonuses(ability, proficiency);
+		let label = format!("{tool} ({})", ability.long_name());
+		RollOutcome::check(label, resolve_modifier(&maps), bonuses, rand).with_notes(conditional_notes(&maps))
+	}
+
+	/// Rolls initiative, which is a Dexterity check that also includes any bonuses,
+	/// proficiency, and advantage or disadvantage specific to initiative.
+	pub fn roll_initiative(&self, rand: &mut impl rand::Rng) -> RollOutcome {
+		let initiative = self.initiative();
+		let maps = [
+			self.skills().ability_modifiers(Ability::Dexterity),
+			initiative.modifiers(),
+		];
+		let mut bonuses = self.check_bonuses(Ability::Dexterity, *initiative.proficiency().value());
+		for (bonus, source) in initiative.bonuses() {
+			let source = crate::data::as_feature_path_text(source).unwrap_or_else(|| "Bonus".into());
+			bonuses.push(RollBonus::from((*bonus, source)));
+		}
+		RollOutcome::check("Initiative", resolve_modifier(&maps), bonuses, rand).with_notes(conditional_notes(&maps))
+	}
+
+	/// Rolls a saving throw for an ability, using the character's proficiency, any bonuses to those saves
+	/// (such as cover), and any advantage or disadvantage which applies to all saves for that ability.
+	pub fn roll_saving_throw(&self, ability: Ability, rand: &mut impl rand::Rng) -> RollOutcome {
+		let proficiency = *self.saving_throws().get_prof(ability).value();
+		let maps = [
+			self.saving_throws().general_modifiers(),
+			self.saving_throws().ability_modifiers(ability),
+		];
+		let mut bonuses = self.check_bonuses(ability, proficiency);
+		for (bonus, source) in self.saving_throws().bonuses(ability) {
+			let source = crate::data::as_feature_path_text(source).unwrap_or_else(|| "Bonus".into());
+			bonuses.push(RollBonus::from((*bonus, source)));
+		}
+		let label = format!("{} Save", ability.long_name());
+		RollOutcome::check(label, resolve_modifier(&maps), bonuses, rand).with_notes(conditional_notes(&maps))
+	}
+
+	/// Rolls the attack roll for an attack. Returns None if the attack forces a saving throw instead.
+	pub fn roll_attack(&self, name: &str, attack: &Attack, rand: &mut impl rand::Rng) -> Option<RollOutcome> {
+		let AttackCheckKind::AttackRoll { ability, proficient } = &attack.check else {
+			return None;
+		};
+		let (ability, modifier) = attack.best_ability_modifier(*ability, self);
+		let mut bonuses = vec![RollBonus::from((modifier, format!("{} modifier", ability.long_name())))];
+		if proficient.evaluate(self) {
+			bonuses.push(RollBonus::from((self.proficiency_bonus(), "Proficiency bonus")));
+		}
+		let attack_bonuses = self.attack_bonuses();
+		for (bonus, source) in attack_bonuses.get_attack_roll(attack) {
+			let source = crate::data::as_feature_path_text(source).unwrap_or_else(|| "Bonus".into());
+			bonuses.push(RollBonus::from((bonus, source)));
+		}
+		let maps = [
+			attack_bonuses.general_modifiers(),
+			attack_bonuses.ability_modifiers(ability),
+		];
+		let label = format!("{name} Attack");
+		Some(RollOutcome::check(label, resolve_modifier(&maps), bonuses, rand).with_notes(conditional_notes(&maps)))
+	}
+
+	/// Rolls the damage dealt by an attack. Returns None if the attack has no damage.
+	pub fn roll_damage(&self, name: &str, attack: &Attack, rand: &mut impl rand::Rng) -> Option<RollOutcome> {
+		let DamageRoll {
+			roll,
+			base_bonus,
+			damage_type,
+		} = attack.damage.as_ref()?;
+		let (ability, _atk_bonus, dmg_bonus) = attack.evaluate_bonuses(self);
+		let expression = roll.as_ref().map(|roll| roll.evaluate(self)).unwrap_or_default();
+		let mut bonuses = Vec::new();
+		if *base_bonus != 0 {
+			bonuses.push(RollBonus::from((*base_bonus, "Base")));
+		}
+		if let (Some(ability), true) = (ability, dmg_bonus != 0) {
+			bonuses.push(RollBonus::from((
+				dmg_bonus,
+				format!("{} modifier", ability.long_name()),
+			)));
+		}
+		let label = format!("{name} Damage");
+		Some(RollOutcome::damage(
+			label,
+			&expression,
+			bonuses,
+			Some(*damage_type),
+			rand,
+		))
+	}
+}
+
+pub(super) fn resolve_modifier(maps: &[&ModifierMap]) -> Option<Modifier> {
+	let has_any = |modifier| maps.iter().any(|map| map.has_unconditional(modifier));
+	Modifier::resolve(has_any(Modifier::Advantage), has_any(Modifier::Disadvantage))
+}
+
+pub(super) fn conditional_notes(maps: &[&ModifierMap]) -> Vec<String> {
+	let conditional = maps.iter().map(|map| map.iter_conditional()).flatten();
+	conditional
+		.map(|(modifier, context)| format!("{} ({context})", modifier.display_name()))
+		.collect()
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::{character::Persistent, roll::RollKind};
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::path::PathBuf;
+
+	fn rng() -> StdRng {
+		StdRng::seed_from_u64(7)
+	}
+
+	/// A character with average ability scores, so every ability modifier is 0.
+	fn character() -> Character {
+		Character::from(Persistent {
+			ability_scores: enum_map::enum_map! { _ => 10 },
+			..Default::default()
+		})
+	}
+
+	fn modifier(outcome: &RollOutcome) -> Option<Modifier> {
+		match outcome.kind {
+			RollKind::Check { modifier } => modifier,
+			RollKind::Damage { .. } | RollKind::Healing { .. } => None,
+		}
+	}
+
+	#[test]
+	fn tool_check_proficiency() {
+		let mut character = character();
+		let tools = character.tool_proficiencies_mut();
+		tools.add_proficiency("Thieves' Tools".into(), proficiency::Level::Full, PathBuf::from("Test"));
+		let outcome = character.roll_tool_check("Thieves' Tools", Ability::Dexterity, &mut rng());
+		let bonus = character.proficiency_bonus();
+		assert_eq!(outcome.label, "Thieves' Tools (Dexterity)");
+		assert_eq!(
+			outcome.bonuses,
+			vec![
+				RollBonus::from((0, "Dexterity modifier")),
+				RollBonus::from((bonus, "Proficient"))
+			]
+		);
+	}
+
+	#[test]
+	fn skill_check_proficiency() {
+		let mut character = character();
+		let skills = character.skills_mut();
+		skills.add_proficiency(Skill::Athletics, proficiency::Level::Full, PathBuf::from("Test"));
+		let outcome = character.roll_skill_check(Skill::Athletics, &mut rng());
+		let bonus = character.proficiency_bonus();
+		assert_eq!(outcome.label, "Athletics Check");
+		assert_eq!(modifier(&outcome), None);
+		assert_eq!(
+			outcome.bonuses,
+			vec![
+				RollBonus::from((0, "Strength modifier")),
+				RollBonus::from((bonus, "Proficient"))
+			]
+		);
+		let natural = outcome.natural_roll().unwrap() as i32;
+		assert_eq!(outcome.total(), natural + bonus);
+	}
+
+	#[test]
+	fn skill_check_modifiers() {
+		let mut character = character();
+		let skills = character.skills_mut();
+		skills.add_ability_modifier(Ability::Dexterity, Modifier::Disadvantage, None, "A".into());
+		skills.add_skill_modifier(
+			Skill::Stealth,
+			Modifier::Advantage,
+			Some("in darkness".into()),
+			"B".into(),
+		);
+		let outcome = character.roll_skill_check(Skill::Stealth, &mut rng());
+		assert_eq!(modifier(&outcome), Some(Modifier::Disadvantage));
+		assert_eq!(outcome.notes, vec!["Advantage (in darkness)".to_owned()]);
+	}
+
+	#[test]
+	fn initiative_bonuses() {
+		let mut character = character();
+		let initiative = character.initiative_mut();
+		initiative.add_bonus(5, PathBuf::from("Alert"));
+		initiative.add_proficiency(proficiency::Level::HalfDown, PathBuf::from("Jack of All Trades"));
+		initiative.add_modifier(Modifier::Advantage, None, PathBuf::from("Feral Instinct"));
+		let half_prof = proficiency::Level::HalfDown * character.proficiency_bonus();
+		assert_eq!(character.initiative_bonus(), 5 + half_prof);
+
+		let outcome = character.roll_initiative(&mut rng());
+		assert_eq!(outcome.label, "Initiative");
+		assert_eq!(modifier(&outcome), Some(Modifier::Advantage));
+		assert_eq!(
+			outcome.bonuses,
+			vec![
+				RollBonus::from((0, "Dexterity modifier")),
+				RollBonus::from((half_prof, "Half Proficient (rounded down)")),
+				RollBonus::from((5, "Alert")),
+			]
+		);
+	}
+
+	#[test]
+	fn attack_bonus_sources() {
+		use crate::{system::dnd5e::data::action::AttackQuery, utility::Value};
+		let mut character = character();
+		let query = vec![AttackQuery::default()];
+		let attack_bonuses = character.attack_bonuses_mut();
+		attack_bonuses.add_to_weapon_attacks(1, query.clone(), PathBuf::from("Magic Sword"));
+		attack_bonuses.add_to_weapon_attacks(2, query, PathBuf::from("Archery"));
+		attack_bonuses.add_modifier(Some(Ability::Strength), Modifier::Disadvantage, None, "A".into());
+		let attack = Attack {
+			kind: None,
+			check: AttackCheckKind::AttackRoll {
+				ability: Ability::Strength,
+				proficient: Value::Fixed(true),
+			},
+			area_of_effect: None,
+			damage: None,
+			weapon_kind: None,
+			classification: None,
+			properties: Vec::new(),
+		};
+		let outcome = character.roll_attack("Longsword", &attack, &mut rng()).unwrap();
+		let prof_bonus = character.proficiency_bonus();
+		assert_eq!(outcome.label, "Longsword Attack");
+		assert_eq!(modifier(&outcome), Some(Modifier::Disadvantage));
+		assert_eq!(
+			outcome.bonuses,
+			vec![
+				RollBonus::from((0, "Strength modifier")),
+				RollBonus::from((prof_bonus, "Proficiency bonus")),
+				RollBonus::from((1, "Magic Sword")),
+				RollBonus::from((2, "Archery")),
+			]
+		);
+		let (_ability, atk_bonus, _dmg_bonus) = attack.evaluate_bonuses(&character);
+		assert_eq!(atk_bonus, prof_bonus + 3);
+	}
+
+	#[test]
+	fn saving_throw_modifiers_cancel() {
+		let mut character = character();
+		let saves = character.saving_throws_mut();
+		saves.add_modifier(None, Modifier::Disadvantage, None, "A".into());
+		saves.add_modifier(Some(Ability::Wisdom), Modifier::Advantage, None, "B".into());
+		let outcome = character.roll_saving_throw(Ability::Wisdom, &mut rng());
+		assert_eq!(outcome.label, "Wisdom Save");
+		assert_eq!(modifier(&outcome), None);
+		assert_eq!(outcome.dice.len(), 1);
+
+		let outcome = character.roll_saving_throw(Ability::Strength, &mut rng());
+		assert_eq!(modifier(&outcome), Some(Modifier::Disadvantage));
+		assert_eq!(outcome.dice.len(), 2);
+	}
+}
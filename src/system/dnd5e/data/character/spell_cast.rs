@@ -0,0 +1,484 @@
+use super::{
+	rolls::{conditional_notes, resolve_modifier},
+	spellcasting::{caster_ability_bonuses, caster_bonus, AbilityOrStat, SpellEntry},
+	Character, Persistent,
+};
+use crate::system::dnd5e::data::{
+	proficiency,
+	roll::{RollBonus, RollExpression, RollOutcome},
+	spell::{self, MaterialCost},
+	Ability, DamageType, Spell,
+};
+
+/// The spell slots of a single rank, and how many of them have been used.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct SlotRank {
+	pub rank: u8,
+	pub consumed: usize,
+	pub total: usize,
+}
+
+impl SlotRank {
+	pub fn remaining(&self) -> usize {
+		self.total.saturating_sub(self.consumed)
+	}
+}
+
+/// The attack, save, damage, and healing of a spell when it is cast at a specific rank.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SpellCast {
+	pub rank: u8,
+	pub attack_bonus: Option<i32>,
+	/// Each of the values which contribute to the attack bonus, and where they come from.
+	pub attack_breakdown: Vec<RollBonus>,
+	/// The spellcasting ability used for the attack roll, if the attack bonus isn't a fixed value.
+	pub attack_ability: Option<Ability>,
+	pub save_dc: Option<(Ability, i32)>,
+	pub damage: Option<(RollExpression, DamageType)>,
+	pub healing: Option<RollExpression>,
+	pub temp_hit_points: Option<RollExpression>,
+}
+
+impl Persistent {
+	pub fn consumed_spell_slots(&self, rank: u8) -> usize {
+		let data_path = self.selected_spells.consumed_slots_path(rank);
+		let consumed = self.get_first_selection_at::<usize>(&data_path);
+		consumed.map(Result::ok).flatten().unwrap_or_default()
+	}
+
+	/// Uses one of the spell slots of the provided rank.
+	/// Returns false if all `total` slots of that rank have already been used.
+	pub fn spend_spell_slot(&mut self, rank: u8, total: usize) -> bool {
+		let consumed = self.consumed_spell_slots(rank);
+		if consumed >= total {
+			return false;
+		}
+		let data_path = self.selected_spells.consumed_slots_path(rank);
+		self.set_selected_value(&data_path, (consumed + 1).to_string());
+		true
+	}
+
+	/// Returns a user-displayable reason for each costly material component of the spell
+	/// which can't be provided by the character's inventory or wallet.
+	/// The components with a currency cost are checked together, against their combined worth.
+	pub fn missing_materials(&self, spell: &Spell) -> Vec<String> {
+		let mut missing = Vec::new();
+		let mut worth_descriptions = Vec::new();
+		for material in &spell.components.materials {
+			match &material.cost {
+				None => {}
+				Some(MaterialCost::Item(id)) => {
+					if self.inventory.find_by_source(id).is_none() {
+						missing.push(format!("Requires {} in your inventory.", material.description));
+					}
+				}
+				Some(MaterialCost::Worth(_)) => {
+					worth_descriptions.push(material.description.as_str());
+				}
+			}
+		}
+		let worth = spell.components.materials_worth();
+		if !self.inventory.wallet().contains(&worth, true) {
+			let descriptions = worth_descriptions.join(" and ");
+			missing.push(format!("Requires {worth} to provide {descriptions}."));
+		}
+		missing
+	}
+
+	/// Removes the consumed costly material components of the spell from the inventory and wallet.
+	/// Returns false, without removing anything, if any of the spell's costly components are missing.
+	pub fn consume_materials(&mut self, spell: &Spell) -> bool {
+		if !self.missing_materials(spell).is_empty() {
+			return false;
+		}
+		for material in &spell.components.materials {
+			if !material.consumed {
+				continue;
+			}
+			match &material.cost {
+				None => {}
+				Some(MaterialCost::Item(id)) => {
+					if let Some(id_path) = self.inventory.find_by_source(id) {
+						self.inventory.remove_one_at_path(&id_path);
+					}
+				}
+				Some(MaterialCost::Worth(worth)) => {
+					self.inventory.wallet_mut().remove(*worth, true);
+				}
+			}
+		}
+		true
+	}
+}
+
+impl Character {
+	/// Returns the spell slots (pooled across all casters, including bonus slots)
+	/// which a spell of the provided rank can be cast with, lowest rank first.
+	pub fn slots_for_rank(&self, spell_rank: u8) -> Vec<SlotRank> {
+		let Some(slots) = self.spellcasting().spell_slots(self) else {
+			return Vec::new();
+		};
+		let slots = slots.into_iter().filter(|(rank, _)| *rank >= spell_rank.max(1));
+		let slots = slots.map(|(rank, total)| SlotRank {
+			rank,
+			consumed: self.persistent().consumed_spell_slots(rank),
+			total,
+		});
+		slots.collect()
+	}
+
+	/// Returns the attack bonus of spells cast through the entry.
+	/// Bonuses to the caster's spell attacks only apply if the entry uses a spellcasting ability.
+	pub fn spell_attack_bonus(&self, entry: &SpellEntry) -> i32 {
+		self.spell_attack_breakdown(entry).iter().map(|bonus| bonus.value).sum()
+	}
+
+	/// Returns each of the values which contribute to the attack bonus of spells cast through the entry.
+	pub fn spell_attack_breakdown(&self, entry: &SpellEntry) -> Vec<RollBonus> {
+		match entry.attack_bonus {
+			AbilityOrStat::Stat(modifier) => vec![RollBonus::from((modifier, "Spell attack bonus"))],
+			AbilityOrStat::Ability(ability) => {
+				let mut breakdown = caster_ability_bonuses(ability, self);
+				let bonuses = self.spellcasting().get_attack_bonuses(entry.classified_as.as_deref());
+				breakdown.extend(bonuses.into_iter().map(|(bonus, source)| caster_bonus(bonus, source)));
+				breakdown
+			}
+		}
+	}
+
+	/// Returns the save DC of spells cast through the entry.
+	/// Bonuses to the caster's save DC only apply if the entry uses a spellcasting ability.
+	pub fn spell_save_dc(&self, entry: &SpellEntry) -> i32 {
+		match entry.save_dc {
+			AbilityOrStat::Stat(dc) => dc as i32,
+			AbilityOrStat::Ability(ability) => {
+				let bonuses = self.spellcasting().get_save_dc_bonuses(entry.classified_as.as_deref());
+				let bonus = bonuses.into_iter().map(|(bonus, _source)| bonus).sum::<i32>();
+				8 + self.ability_modifier(ability, Some(proficiency::Level::Full)) + bonus
+			}
+		}
+	}
+
+	/// Evaluates the attack bonus, save DC, damage, and healing of a spell cast at the provided rank.
+	/// Damage and healing are scaled for any ranks above the spell's own rank,
+	/// damage includes bonuses to spell damage, and the attack bonus and save DC include bonuses for the caster.
+	pub fn evaluate_spell_cast(&self, spell: &Spell, entry: Option<&SpellEntry>, rank: u8) -> SpellCast {
+		let (attack_bonus, attack_breakdown) = match &spell.check {
+			Some(spell::Check::AttackRoll(_)) => {
+				let breakdown = entry.map(|entry| self.spell_attack_breakdown(entry));
+				let breakdown = breakdown.unwrap_or_default();
+				(Some(breakdown.iter().map(|bonus| bonus.value).sum()), breakdown)
+			}
+			_ => (None, Vec::new()),
+		};
+		let attack_ability = match entry.map(|entry| entry.attack_bonus) {
+			Some(AbilityOrStat::Ability(ability)) if attack_bonus.is_some() => Some(ability),
+			_ => None,
+		};
+		let save_dc = match &spell.check {
+			Some(spell::Check::SavingThrow(ability, fixed_dc)) => {
+				let dc = match (entry, fixed_dc) {
+					(None, _) => 0,
+					(
+						Some(SpellEntry {
+							save_dc: AbilityOrStat::Ability(_),
+							..
+						}),
+						Some(dc),
+					) => *dc as i32,
+					(Some(entry), _) => self.spell_save_dc(entry),
+				};
+				Some((*ability, dc))
+			}
+			_ => None,
+		};
+		let modifier = entry.map(|entry| entry.damage_ability).flatten();
+		let modifier = modifier.map(|ability| self.ability_modifier(ability, None));
+		let modifier = modifier.unwrap_or_default();
+		let upcast_amount = rank.saturating_sub(spell.rank) as u32;
+		let damage = spell.damage.as_ref().map(|damage| {
+			let (mut rolls, bonus) = damage.evaluate(self, modifier, upcast_amount);
+			for (bonus_roll, _source) in self.attack_bonuses().get_spell_damage(spell) {
+				rolls.extend(bonus_roll);
+			}
+			rolls.add_modifier(bonus);
+			(rolls, damage.damage_type())
+		});
+		let healing = spell.healing.as_ref();
+		let healing = healing.map(|healing| healing.evaluate(self, modifier, upcast_amount));
+		let temp_hit_points = spell.temp_hit_points.as_ref();
+		let temp_hit_points = temp_hit_points.map(|temp| temp.evaluate(self, modifier, upcast_amount));
+		SpellCast {
+			rank,
+			attack_bonus,
+			attack_breakdown,
+			attack_ability,
+			save_dc,
+			damage,
+			healing,
+			temp_hit_points,
+		}
+	}
+
+	/// Rolls the attack roll of a spell, with advantage or disadvantage from any effects
+	/// which apply to all attack rolls or to attack rolls using the spellcasting ability.
+	pub fn roll_spell_attack(&self, spell: &Spell, cast: &SpellCast, rand: &mut impl rand::Rng) -> Option<RollOutcome> {
+		cast.attack_bonus?;
+		let attack_bonuses = self.attack_bonuses();
+		let mut maps = vec![attack_bonuses.general_modifiers()];
+		if let Some(ability) = cast.attack_ability {
+			maps.push(attack_bonuses.ability_modifiers(ability));
+		}
+		let label = format!("{} Attack", spell.name);
+		let bonuses = cast.attack_breakdown.clone();
+		Some(RollOutcome::check(label, resolve_modifier(&maps), bonuses, rand).with_notes(conditional_notes(&maps)))
+	}
+
+	pub fn roll_spell_damage(&self, spell: &Spell, cast: &SpellCast, rand: &mut impl rand::Rng) -> Option<RollOutcome> {
+		let (rolls, damage_type) = cast.damage.as_ref()?;
+		let label = format!("{} Damage", spell.name);
+		Some(RollOutcome::damage(label, rolls, Vec::new(), Some(*damage_type), rand))
+	}
+
+	pub fn roll_spell_healing(
+		&self,
+		spell: &Spell,
+		cast: &SpellCast,
+		rand: &mut impl rand::Rng,
+	) -> Option<RollOutcome> {
+		let rolls = cast.healing.as_ref()?;
+		let label = format!("{} Healing", spell.name);
+		Some(RollOutcome::healing(label, rolls, Vec::new(), false, rand))
+	}
+
+	pub fn roll_spell_temp_hit_points(
+		&self,
+		spell: &Spell,
+		cast: &SpellCast,
+		rand: &mut impl rand::Rng,
+	) -> Option<RollOutcome> {
+		let rolls = cast.temp_hit_points.as_ref()?;
+		let label = format!("{} Temporary HP", spell.name);
+		Some(RollOutcome::healing(label, rolls, Vec::new(), true, rand))
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::{
+		core::SourceId,
+		dnd5e::data::{
+			currency::{Kind, Wallet},
+			item::Item,
+		},
+	};
+
+	fn spell_with_material(consumed: bool, cost: MaterialCost) -> Spell {
+		Spell {
+			components: spell::Components {
+				materials: vec![spell::Material {
+					description: "a costly material".into(),
+					consumed,
+					cost: Some(cost),
+				}],
+				..Default::default()
+			},
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn caster_bonuses() {
+		use crate::system::dnd5e::data::{
+			action::AttackKind,
+			character::spellcasting::{CastingMethod, Filter},
+		};
+		let mut character = Character::from(Persistent {
+			ability_scores: enum_map::enum_map! { _ => 10 },
+			..Default::default()
+		});
+		let spellcasting = character.spellcasting_mut();
+		spellcasting.add_attack_bonus(2, vec![], "Wand".into());
+		spellcasting.add_save_dc_bonus(1, vec![], "Rod".into());
+		let warlock_only = Filter {
+			tags: ["Warlock".into()].into(),
+			..Default::default()
+		};
+		spellcasting.add_attack_bonus(1, vec![warlock_only], "Pact Rod".into());
+		let entry = SpellEntry {
+			source: "Feat".into(),
+			classified_as: None,
+			method: CastingMethod::AtWill,
+			attack_bonus: AbilityOrStat::Ability(Ability::Intelligence),
+			save_dc: AbilityOrStat::Ability(Ability::Intelligence),
+			damage_ability: None,
+			casting_duration: None,
+			rank: None,
+			range: None,
+		};
+		let prof = character.proficiency_bonus();
+
+		let spell = Spell {
+			check: Some(spell::Check::AttackRoll(AttackKind::Ranged)),
+			..Default::default()
+		};
+		let cast = character.evaluate_spell_cast(&spell, Some(&entry), 0);
+		assert_eq!(cast.attack_bonus, Some(prof + 2));
+
+		let spell = Spell {
+			check: Some(spell::Check::SavingThrow(Ability::Dexterity, None)),
+			..Default::default()
+		};
+		let cast = character.evaluate_spell_cast(&spell, Some(&entry), 0);
+		assert_eq!(cast.save_dc, Some((Ability::Dexterity, 8 + prof + 1)));
+
+		let spell = Spell {
+			check: Some(spell::Check::SavingThrow(Ability::Dexterity, Some(13))),
+			..Default::default()
+		};
+		let cast = character.evaluate_spell_cast(&spell, Some(&entry), 0);
+		assert_eq!(cast.save_dc, Some((Ability::Dexterity, 13)));
+	}
+
+	#[test]
+	fn spell_attack_roll() {
+		use crate::system::dnd5e::data::{
+			action::AttackKind,
+			character::spellcasting::CastingMethod,
+			roll::{Modifier, RollKind},
+		};
+		use rand::{rngs::StdRng, SeedableRng};
+		let mut character = Character::from(Persistent {
+			ability_scores: enum_map::enum_map! { _ => 10 },
+			..Default::default()
+		});
+		character.spellcasting_mut().add_attack_bonus(2, vec![], "Wand".into());
+		let attack_bonuses = character.attack_bonuses_mut();
+		attack_bonuses.add_modifier(None, Modifier::Disadvantage, None, "Exhaustion".into());
+		let entry = SpellEntry {
+			source: "Feat".into(),
+			classified_as: None,
+			method: CastingMethod::AtWill,
+			attack_bonus: AbilityOrStat::Ability(Ability::Intelligence),
+			save_dc: AbilityOrStat::Ability(Ability::Intelligence),
+			damage_ability: None,
+			casting_duration: None,
+			rank: None,
+			range: None,
+		};
+		let spell = Spell {
+			name: "Fire Bolt".into(),
+			check: Some(spell::Check::AttackRoll(AttackKind::Ranged)),
+			..Default::default()
+		};
+		let cast = character.evaluate_spell_cast(&spell, Some(&entry), 0);
+		let outcome = character.roll_spell_attack(&spell, &cast, &mut StdRng::seed_from_u64(7));
+		let outcome = outcome.expect("spell has an attack roll");
+		assert_eq!(outcome.label, "Fire Bolt Attack");
+		let RollKind::Check { modifier } = outcome.kind else {
+			panic!("spell attack is not a check");
+		};
+		assert_eq!(modifier, Some(Modifier::Disadvantage));
+		let prof = character.proficiency_bonus();
+		assert_eq!(
+			outcome.bonuses,
+			vec![
+				RollBonus::from((0, "Intelligence modifier")),
+				RollBonus::from((prof, "Proficiency bonus")),
+				RollBonus::from((2, "Wand")),
+			]
+		);
+	}
+
+	#[test]
+	fn spend_spell_slot() {
+		let mut persistent = Persistent::default();
+		assert_eq!(persistent.consumed_spell_slots(3), 0);
+		assert!(persistent.spend_spell_slot(3, 2));
+		assert!(persistent.spend_spell_slot(3, 2));
+		assert!(!persistent.spend_spell_slot(3, 2));
+		assert_eq!(persistent.consumed_spell_slots(3), 2);
+		assert_eq!(persistent.consumed_spell_slots(2), 0);
+	}
+
+	#[test]
+	fn slot_rank_remaining() {
+		let slots = SlotRank {
+			rank: 1,
+			consumed: 3,
+			total: 2,
+		};
+		assert_eq!(slots.remaining(), 0);
+	}
+
+	#[test]
+	fn consume_material_worth() {
+		let spell = spell_with_material(true, MaterialCost::Worth(Wallet::from([(300, Kind::Gold)])));
+		let mut persistent = Persistent::default();
+		*persistent.inventory.wallet_mut() = Wallet::from([(200, Kind::Gold)]);
+		assert_eq!(
+			persistent.missing_materials(&spell),
+			vec!["Requires 300 gp to provide a costly material.".to_owned()]
+		);
+		assert!(!persistent.consume_materials(&spell));
+		*persistent.inventory.wallet_mut() += Wallet::from([(150, Kind::Gold)]);
+		assert!(persistent.missing_materials(&spell).is_empty());
+		assert!(persistent.consume_materials(&spell));
+		assert_eq!(*persistent.inventory.wallet(), Wallet::from([(50, Kind::Gold)]));
+	}
+
+	#[test]
+	fn consume_materials_total_worth() {
+		let material = |description: &str, gold: u64| spell::Material {
+			description: description.into(),
+			consumed: true,
+			cost: Some(MaterialCost::Worth(Wallet::from([(gold, Kind::Gold)]))),
+		};
+		let spell = Spell {
+			components: spell::Components {
+				materials: vec![material("a diamond", 300), material("a ruby", 100)],
+				..Default::default()
+			},
+			..Default::default()
+		};
+		let mut persistent = Persistent::default();
+		*persistent.inventory.wallet_mut() = Wallet::from([(350, Kind::Gold)]);
+		assert_eq!(
+			persistent.missing_materials(&spell),
+			vec!["Requires 400 gp to provide a diamond and a ruby.".to_owned()]
+		);
+		assert!(!persistent.consume_materials(&spell));
+		*persistent.inventory.wallet_mut() += Wallet::from([(50, Kind::Gold)]);
+		assert!(persistent.missing_materials(&spell).is_empty());
+		assert!(persistent.consume_materials(&spell));
+		assert!(persistent.inventory.wallet().is_empty());
+	}
+
+	#[test]
+	fn material_item() {
+		let id = SourceId {
+			path: "items/pearl.kdl".into(),
+			..Default::default()
+		};
+		let mut persistent = Persistent::default();
+		let kept = spell_with_material(false, MaterialCost::Item(id.clone()));
+		let consumed = spell_with_material(true, MaterialCost::Item(id.clone()));
+		assert!(!persistent.consume_materials(&kept));
+		persistent.inventory.insert(Item {
+			id: id.clone(),
+			name: "Pearl".into(),
+			..Default::default()
+		});
+		assert!(persistent.consume_materials(&kept));
+		assert!(persistent.inventory.find_by_source(&id).is_some());
+		assert!(persistent.consume_materials(&consumed));
+		assert!(persistent.inventory.find_by_source(&id).is_none());
+	}
+
+	#[test]
+	fn no_slots_without_casters() {
+		let character = Character::from(Persistent::default());
+		assert_eq!(character.slots_for_rank(1), Vec::new());
+	}
+}
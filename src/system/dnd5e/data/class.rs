@@ -3,9 +3,9 @@ use crate::kdl_ext::NodeContext;
 use crate::{
 	system::{
 		core::SourceId,
-		dnd5e::{mutator::AddMaxHitPoints, BoxedMutator, SystemComponent, Value},
+		dnd5e::{evaluator::HasAbilityScore, mutator::AddMaxHitPoints, BoxedMutator, SystemComponent, Value},
 	},
-	utility::{selector, MutatorGroup},
+	utility::{selector, Evaluator, MutatorGroup},
 };
 use kdlize::{ext::DocumentExt, AsKdl, FromKdl, NodeBuilder};
 use std::{path::Path, str::FromStr};
@@ -20,8 +20,12 @@ pub struct Class {
 	pub current_level: usize,
 	/// Mutators that are applied only when this class is the primary class (not multiclassing).
 	pub mutators: Vec<BoxedMutator>,
+	/// Mutators that are applied only when this class is taken as a secondary class (multiclassing),
+	/// typically granting a subset of the proficiencies in `mutators`.
+	pub multiclass_mutators: Vec<BoxedMutator>,
+	/// The ability scores a character must have to multiclass into or out of this class.
+	pub multiclass_requirement: Option<HasAbilityScore>,
 	pub levels: Vec<Level>,
-	// TODO: `multiclass-req` data node (already in data, just not in structures yet)
 }
 
 impl Default for Class {
@@ -37,12 +41,33 @@ impl Default for Class {
 			}),
 			current_level: Default::default(),
 			mutators: Default::default(),
+			multiclass_mutators: Default::default(),
+			multiclass_requirement: Default::default(),
 			levels: Default::default(),
 		}
 	}
 }
 
 impl Class {
+	/// Returns true if this class is the first class the character took.
+	/// Characters without any classes treat every class as primary.
+	pub fn is_primary(&self, character: &Character) -> bool {
+		match character.persistent().classes.first() {
+			None => true,
+			Some(primary) => primary.name == self.name,
+		}
+	}
+
+	/// Checks if the character meets the ability score prerequisites for multiclassing with this class.
+	pub fn evaluate_multiclass_requirement(&self, character: &Character) -> Result<(), String> {
+		let Some(requirement) = &self.multiclass_requirement else {
+			return Ok(());
+		};
+		requirement
+			.evaluate(character)
+			.map_err(|reason| format!("{}: {reason}", self.name))
+	}
+
 	pub fn iter_levels<'a>(&'a self, all: bool) -> impl Iterator<Item = LevelWithIndex<'a>> + 'a {
 		self.levels
 			.iter()
@@ -61,6 +86,9 @@ impl MutatorGroup for Class {
 		for mutator in &self.mutators {
 			mutator.set_data_path(&path_to_self);
 		}
+		for mutator in &self.multiclass_mutators {
+			mutator.set_data_path(&path_to_self);
+		}
 		for level in self.iter_levels(true) {
 			level.set_data_path(&path_to_self);
 		}
@@ -68,7 +96,11 @@ impl MutatorGroup for Class {
 
 	fn apply_mutators(&self, stats: &mut Character, parent: &Path) {
 		let path_to_self = parent.join(&self.name);
-		for mutator in &self.mutators {
+		let mutators = match self.is_primary(stats) {
+			true => &self.mutators,
+			false => &self.multiclass_mutators,
+		};
+		for mutator in mutators {
 			stats.apply(mutator, &path_to_self);
 		}
 		for level in self.iter_levels(false) {
@@ -101,6 +133,8 @@ impl FromKdl<NodeContext> for Class {
 		let current_level = node.get_i64_opt("level")?.unwrap_or_default() as usize;
 
 		let mutators = node.query_all_t("scope() > mutator")?;
+		let multiclass_mutators = node.query_all_t("scope() > multiclass > mutator")?;
+		let multiclass_requirement = node.query_opt_t::<HasAbilityScore>("scope() > multiclass-req")?;
 
 		let mut levels = Vec::with_capacity(20);
 		levels.resize_with(20, Default::default);
@@ -117,6 +151,8 @@ impl FromKdl<NodeContext> for Class {
 			hit_die,
 			current_level,
 			mutators,
+			multiclass_mutators,
+			multiclass_requirement,
 			levels,
 			..Default::default()
 		})
@@ -135,10 +171,18 @@ impl AsKdl for Class {
 		node.push_child_opt_t("source", &self.id);
 		node.push_child_opt_t("description", &self.description);
 		node.push_child_entry("hit-die", self.hit_die.to_string());
+		node.push_child_opt_t("multiclass-req", &self.multiclass_requirement);
 
 		for mutator in &self.mutators {
 			node.push_child_t("mutator", mutator);
 		}
+		if !self.multiclass_mutators.is_empty() {
+			let mut multiclass = NodeBuilder::default();
+			for mutator in &self.multiclass_mutators {
+				multiclass.push_child_t("mutator", mutator);
+			}
+			node.push_child(multiclass.build("multiclass"));
+		}
 
 		for (idx, level) in self.levels.iter().enumerate() {
 			let level_node = level.as_kdl();
@@ -357,3 +401,55 @@ impl AsKdl for Subclass {
 		node
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::{
+		data::{character::Persistent, Ability},
+		mutator::AddMaxAttunement,
+	};
+
+	fn class(name: &str, primary_bonus: i32, multiclass_bonus: i32) -> Class {
+		Class {
+			name: name.into(),
+			current_level: 1,
+			mutators: vec![AddMaxAttunement {
+				value: Value::Fixed(primary_bonus),
+			}
+			.into()],
+			multiclass_mutators: vec![AddMaxAttunement {
+				value: Value::Fixed(multiclass_bonus),
+			}
+			.into()],
+			multiclass_requirement: Some(HasAbilityScore {
+				any: false,
+				minimums: vec![(Ability::Intelligence, 13)],
+			}),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn multiclass_mutators() {
+		let mut persistent = Persistent::default();
+		persistent.classes.push(class("Fighter", 1, 2));
+		persistent.classes.push(class("Wizard", 4, 8));
+		let character = Character::from(persistent);
+		// base of 3, plus the primary mutators of Fighter and the multiclass mutators of Wizard
+		assert_eq!(character.max_attunement().value(), 12);
+	}
+
+	#[test]
+	fn multiclass_requirement() {
+		let mut persistent = Persistent::default();
+		persistent.ability_scores[Ability::Intelligence] = 12;
+		let character = Character::from(persistent);
+		let wizard = class("Wizard", 0, 0);
+		assert_eq!(
+			wizard.evaluate_multiclass_requirement(&character),
+			Err("Wizard: Ability scores too low: Intelligence is 12 (needs 13).".into())
+		);
+		assert_eq!(class("Fighter", 0, 0).is_primary(&character), true);
+	}
+}
@@ -87,6 +87,16 @@ impl Wallet {
 		*self == Self::default()
 	}
 
+	/// The total number of coins in the wallet, regardless of their kind.
+	pub fn coin_count(&self) -> u64 {
+		self.0.values().sum::<u64>()
+	}
+
+	/// The weight of all the coins in the wallet in pounds, where 50 coins of any kind weigh 1 pound.
+	pub fn weight(&self) -> f64 {
+		self.coin_count() as f64 / 50.0
+	}
+
 	pub fn contains(&self, other: &Wallet, auto_exchange: bool) -> bool {
 		// if we can exchange, then we will always be able to cover other
 		// as long as we have a greater total value
@@ -180,6 +190,22 @@ impl Wallet {
 	}
 }
 
+impl std::fmt::Display for Wallet {
+	/// Writes the non-zero amounts in the wallet, largest currency first (e.g. "5 gp, 3 sp").
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let amounts = self
+			.0
+			.iter()
+			.filter(|(_, amt)| **amt > 0)
+			.sorted_by(|(a, _), (b, _)| b.cmp(a));
+		let amounts = amounts.map(|(kind, amt)| format!("{amt} {}", kind.abbreviation()));
+		match amounts.collect::<Vec<_>>() {
+			amounts if amounts.is_empty() => write!(f, "0 {}", Kind::Copper.abbreviation()),
+			amounts => write!(f, "{}", amounts.join(", ")),
+		}
+	}
+}
+
 impl FromKdl<NodeContext> for Wallet {
 	type Error = anyhow::Error;
 	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
@@ -298,6 +324,21 @@ mod test {
 		assert_eq!(wallet[Kind::Platinum], 0);
 	}
 
+	#[test]
+	fn weight() {
+		let wallet = Wallet::from([(30, Kind::Copper), (45, Kind::Gold)]);
+		assert_eq!(wallet.coin_count(), 75);
+		assert_eq!(wallet.weight(), 1.5);
+	}
+
+	#[test]
+	fn display() {
+		assert_eq!(Wallet::default().to_string(), "0 cp");
+		assert_eq!(Wallet::from([(300, Kind::Gold)]).to_string(), "300 gp");
+		let wallet = Wallet::from([(3, Kind::Silver), (5, Kind::Gold)]);
+		assert_eq!(wallet.to_string(), "5 gp, 3 sp");
+	}
+
 	#[test]
 	fn from_u64() {
 		let wallet = Wallet::from(15753);
@@ -34,7 +34,7 @@ pub struct Item {
 	pub tags: Vec<String>,
 	// TODO: Tests for item containers
 	// Items which are contained within this item instance.
-	pub items: Option<container::ItemContainer<Item>>,
+	pub items: Option<container::Inventory>,
 	// Items (specific by SourceId or custom defn) which should be converted into the item container when added to a character sheet.
 	// The owning item must have a container (typically empty) if using this property.
 	pub item_refs: Vec<IndirectItem>,
@@ -52,6 +52,14 @@ impl Item {
 		}
 	}
 
+	/// Returns true if the item is a piece of equipment which must be attuned to in order to gain its attunement benefits.
+	pub fn requires_attunement(&self) -> bool {
+		match &self.kind {
+			Kind::Equipment(equipment) => equipment.attunement.is_some(),
+			_ => false,
+		}
+	}
+
 	/// Returs Ok if the item can currently be equipped, otherwise returns a user-displayable reason why it cannot be equipped.
 	pub fn can_be_equipped(&self, state: &Character) -> Result<(), String> {
 		match &self.kind {
@@ -67,6 +75,13 @@ impl Item {
 		}
 	}
 
+	/// The weight of the whole stack of this item, plus the weight of anything it contains.
+	pub fn total_weight(&self) -> f64 {
+		let stack_weight = self.weight as f64 * self.quantity() as f64;
+		let contents = self.items.as_ref().map(|items| items.total_weight());
+		stack_weight + contents.unwrap_or_default()
+	}
+
 	pub fn can_stack(&self) -> bool {
 		matches!(&self.kind, Kind::Simple { .. }) && self.items.is_none()
 	}
@@ -164,7 +179,7 @@ impl FromKdl<NodeContext> for Item {
 			tags
 		};
 		let kind = node.query_opt_t::<Kind>("scope() > kind")?.unwrap_or_default();
-		let items = node.query_opt_t::<container::ItemContainer<Item>>("scope() > items")?;
+		let items = node.query_opt_t::<container::Inventory>("scope() > items")?;
 		let item_refs = match node.query_opt("scope() > items > templates")? {
 			None => Vec::new(),
 			Some(node) => node.query_all_t::<IndirectItem>("scope() > item")?,
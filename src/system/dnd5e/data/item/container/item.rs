@@ -5,7 +5,7 @@ use crate::{
 		dnd5e::data::{
 			character::{Character, ObjectCacheProvider},
 			currency::Wallet,
-			item::{Item, Restriction},
+			item::{Item, Kind, Restriction},
 			Indirect, Spell,
 		},
 	},
@@ -82,6 +82,15 @@ impl<T> ItemContainer<T> {
 	}
 }
 
+impl<T: AsItem> ItemContainer<T> {
+	/// The weight (in pounds) of every item in the container, including the contents of nested containers
+	/// and the coins in each wallet.
+	pub fn total_weight(&self) -> f64 {
+		let items = self.items_by_id.values().map(|entry| entry.as_item().total_weight());
+		items.sum::<f64>() + self.wallet.weight()
+	}
+}
+
 impl<T: AsItem> ItemContainer<T> {
 	pub fn get_item(&self, id: &Uuid) -> Option<&Item> {
 		self.items_by_id.get(id).map(|entry| entry.as_item())
@@ -111,6 +120,16 @@ impl<T: AsItem> ItemContainer<T> {
 		Some(item)
 	}
 
+	/// Sets the id path of the item which owns this container, updating the id path of each entry to match.
+	fn set_parent_item_id(&mut self, id: Vec<Uuid>) {
+		for (item_id, entry) in &mut self.items_by_id {
+			let mut path = id.clone();
+			path.push(*item_id);
+			entry.set_id_path(path);
+		}
+		self.parent_item_id = id;
+	}
+
 	fn push_entry(&mut self, mut entry: T) -> Uuid {
 		let id = Uuid::new_v4();
 		entry.set_id_path({
@@ -169,10 +188,14 @@ impl<T: AsItem> ItemContainer<T> {
 	}
 
 	pub fn remove(&mut self, id: &Uuid) -> Option<Item> {
+		self.remove_entry(id).map(|entry| entry.into_item())
+	}
+
+	fn remove_entry(&mut self, id: &Uuid) -> Option<T> {
 		if let Ok(idx) = self.itemids_by_name.binary_search(id) {
 			self.itemids_by_name.remove(idx);
 		}
-		self.items_by_id.remove(id).map(|entry| entry.into_item())
+		self.items_by_id.remove(id)
 	}
 
 	pub fn remove_at_path(&mut self, id_path: &Vec<Uuid>) -> Option<Item> {
@@ -202,6 +225,40 @@ impl<T: AsItem> ItemContainer<T> {
 		None
 	}
 
+	/// Returns the id path of the first item whose id matches the provided source id (regardless of version),
+	/// including items inside of other items.
+	pub fn find_by_source(&self, id: &SourceId) -> Option<Vec<Uuid>> {
+		let id = id.unversioned();
+		for (item_id, entry) in self.iter_by_name() {
+			let item = entry.as_item();
+			if item.id.unversioned() == id {
+				return Some(vec![*item_id]);
+			}
+			let Some(container) = &item.items else {
+				continue;
+			};
+			if let Some(mut path) = container.find_by_source(&id) {
+				path.insert(0, *item_id);
+				return Some(path);
+			}
+		}
+		None
+	}
+
+	/// Removes a single item at the path. If the item is a stack of items, the size of the stack is reduced instead.
+	pub fn remove_one_at_path(&mut self, id_path: &Vec<Uuid>) -> bool {
+		let Some(item) = self.get_mut_at_path(id_path) else {
+			return false;
+		};
+		if let Kind::Simple { count } = &mut item.kind {
+			if *count > 1 {
+				*count -= 1;
+				return true;
+			}
+		}
+		self.remove_at_path(id_path).is_some()
+	}
+
 	// Expands all Indirect items and spells contained within the container,
 	// recursively visiting all items which contain other items or spells.
 	#[async_recursion(?Send)]
@@ -370,6 +427,141 @@ impl Inventory {
 		};
 		entry.is_equipped = equipped;
 	}
+
+	pub fn get_entry(&self, id: &Uuid) -> Option<&EquipableEntry> {
+		self.items_by_id.get(id)
+	}
+
+	/// Returns the entry at the id path, which may be an item inside of other items.
+	pub fn get_entry_at_path(&self, id_path: &[Uuid]) -> Option<&EquipableEntry> {
+		let Some((id, parent_path)) = id_path.split_last() else {
+			return None;
+		};
+		let mut container = self;
+		for parent_id in parent_path {
+			let Some(items) = container.get_item(parent_id).and_then(|item| item.items.as_ref()) else {
+				return None;
+			};
+			container = items;
+		}
+		container.get_entry(id)
+	}
+
+	fn get_entry_mut_at_path(&mut self, id_path: &[Uuid]) -> Option<&mut EquipableEntry> {
+		let Some((id, parent_path)) = id_path.split_last() else {
+			return None;
+		};
+		let Some(container) = self.container_at_path_mut(parent_path) else {
+			return None;
+		};
+		container.items_by_id.get_mut(id)
+	}
+
+	/// Returns every entry in the inventory, including the entries for items inside of other items.
+	pub fn all_entries(&self) -> Vec<&EquipableEntry> {
+		let mut entries = Vec::new();
+		for (_id, entry) in self.iter_by_name() {
+			entries.push(entry);
+			if let Some(container) = &entry.item.items {
+				entries.extend(container.all_entries());
+			}
+		}
+		entries
+	}
+
+	/// Sets the number of charges which have been spent for an item, up to the item's charge capacity.
+	pub fn set_consumed_charges(&mut self, id_path: &[Uuid], consumed: usize) {
+		let Some(entry) = self.get_entry_mut_at_path(id_path) else {
+			return;
+		};
+		let capacity = entry.charges().map(|charges| charges.capacity).unwrap_or_default();
+		entry.consumed_charges = consumed.min(capacity);
+	}
+
+	/// Spends some number of charges from an item.
+	/// Returns false if the item does not have enough charges remaining, in which case no charges are spent.
+	pub fn consume_charges(&mut self, id_path: &[Uuid], amount: usize) -> bool {
+		let Some(entry) = self.get_entry_mut_at_path(id_path) else {
+			return false;
+		};
+		if entry.charges_remaining() < amount {
+			return false;
+		}
+		entry.consumed_charges += amount;
+		true
+	}
+
+	/// Regains up to `amount` spent charges for an item, returning the number of charges actually regained.
+	pub fn restore_charges(&mut self, id_path: &[Uuid], amount: usize) -> usize {
+		let Some(entry) = self.get_entry_mut_at_path(id_path) else {
+			return 0;
+		};
+		let regained = entry.consumed_charges.min(amount);
+		entry.consumed_charges -= regained;
+		regained
+	}
+
+	pub fn is_attuned(&self, id: &Uuid) -> bool {
+		self.items_by_id.get(id).map(|entry| entry.is_attuned).unwrap_or(false)
+	}
+
+	/// Returns the number of items in the inventory which the character is currently attuned to,
+	/// including items stored inside of other items.
+	pub fn attuned_count(&self) -> usize {
+		self.all_entries().into_iter().filter(|entry| entry.is_attuned).count()
+	}
+
+	/// Attunes to, or ends attunement with, the item.
+	/// Returns false if attunement was not changed, either because the item does not require attunement
+	/// or because the character is already attuned to `max_attuned` items.
+	pub fn set_attuned(&mut self, id: &Uuid, attuned: bool, max_attuned: usize) -> bool {
+		if attuned && !self.is_attuned(id) && self.attuned_count() >= max_attuned {
+			return false;
+		}
+		let Some(entry) = self.items_by_id.get_mut(&id) else {
+			return false;
+		};
+		if attuned && !entry.item.requires_attunement() {
+			return false;
+		}
+		entry.is_attuned = attuned;
+		true
+	}
+
+	fn container_at_path_mut(&mut self, id_path: &[Uuid]) -> Option<&mut Inventory> {
+		if id_path.is_empty() {
+			return Some(self);
+		}
+		let Some(item) = self.get_mut_at_path(&id_path.to_vec()) else {
+			return None;
+		};
+		item.items.as_mut()
+	}
+
+	/// Moves the item at the path into the specified item container (see `insert_to`), returning its new path.
+	/// The character stays attuned to the item and its spent charges are kept,
+	/// but moving an item between containers always unequips it.
+	pub fn move_to(&mut self, id_path: &Vec<Uuid>, container_id: &Option<Vec<Uuid>>) -> Option<Vec<Uuid>> {
+		let Some((id, parent_path)) = id_path.split_last() else {
+			return None;
+		};
+		let Some(parent) = self.container_at_path_mut(parent_path) else {
+			return None;
+		};
+		let Some(entry) = parent.remove_entry(id) else {
+			return None;
+		};
+		let (is_attuned, consumed_charges) = (entry.is_attuned, entry.consumed_charges);
+		let new_path = self.insert_to(entry.item, container_id);
+		if let Some((id, parent_path)) = new_path.split_last() {
+			let parent = self.container_at_path_mut(parent_path);
+			if let Some(entry) = parent.and_then(|parent| parent.items_by_id.get_mut(id)) {
+				entry.is_attuned = is_attuned;
+				entry.consumed_charges = consumed_charges;
+			}
+		}
+		Some(new_path)
+	}
 }
 
 impl MutatorGroup for Inventory {
@@ -388,3 +580,154 @@ impl MutatorGroup for Inventory {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::item::equipment::{Attunement, Charges, Equipment};
+
+	fn magic_item(name: &str) -> Item {
+		Item {
+			name: name.into(),
+			kind: Kind::Equipment(Equipment {
+				attunement: Some(Attunement::default()),
+				..Default::default()
+			}),
+			..Default::default()
+		}
+	}
+
+	#[test]
+	fn remove_one_from_stack() {
+		let id = SourceId {
+			path: "items/diamond.kdl".into(),
+			..Default::default()
+		};
+		let mut inventory = Inventory::new();
+		inventory.push(Item {
+			id: id.clone(),
+			name: "Diamond".into(),
+			kind: Kind::Simple { count: 2 },
+			..Default::default()
+		});
+		let path = inventory.find_by_source(&id).expect("diamond should be in inventory");
+		assert!(inventory.remove_one_at_path(&path));
+		assert_eq!(inventory.get_item(&path[0]).map(Item::quantity), Some(1));
+		assert!(inventory.remove_one_at_path(&path));
+		assert_eq!(inventory.find_by_source(&id), None);
+	}
+
+	#[test]
+	fn attunement_limit() {
+		let mut inventory = Inventory::new();
+		let ring = inventory.push(magic_item("Ring of Protection"));
+		let cloak = inventory.push(magic_item("Cloak of Protection"));
+		assert!(inventory.set_attuned(&ring, true, 1));
+		assert!(!inventory.set_attuned(&cloak, true, 1));
+		assert_eq!(inventory.attuned_count(), 1);
+		assert!(inventory.set_attuned(&ring, false, 1));
+		assert!(inventory.set_attuned(&cloak, true, 1));
+		assert!(inventory.is_attuned(&cloak));
+		assert!(!inventory.is_attuned(&ring));
+	}
+
+	#[test]
+	fn item_charges() {
+		let mut inventory = Inventory::new();
+		let wand = inventory.push(Item {
+			name: "Wand of Magic Missiles".into(),
+			kind: Kind::Equipment(Equipment {
+				charges: Some(Charges {
+					capacity: 7,
+					reset: None,
+				}),
+				..Default::default()
+			}),
+			..Default::default()
+		});
+		assert!(inventory.consume_charges(&[wand], 5));
+		assert!(!inventory.consume_charges(&[wand], 3));
+		assert_eq!(inventory.get_entry(&wand).unwrap().charges_remaining(), 2);
+		assert_eq!(inventory.restore_charges(&[wand], 10), 5);
+		assert_eq!(inventory.get_entry(&wand).unwrap().charges_remaining(), 7);
+		inventory.set_consumed_charges(&[wand], 20);
+		assert_eq!(inventory.get_entry(&wand).unwrap().consumed_charges, 7);
+	}
+
+	#[test]
+	fn attunement_kept_in_containers() {
+		let mut inventory = Inventory::new();
+		let ring = inventory.push(magic_item("Ring of Protection"));
+		let backpack = inventory.push(Item {
+			name: "Backpack".into(),
+			items: Some(Inventory::new()),
+			..Default::default()
+		});
+		assert!(inventory.set_attuned(&ring, true, 3));
+		inventory.set_equipped(&ring, true);
+		let path = inventory.move_to(&vec![ring], &Some(vec![backpack]));
+		let path = path.expect("ring should be moved into the backpack");
+		assert_eq!(path.len(), 2);
+		assert_eq!(inventory.get_item(&ring), None);
+		assert_eq!(inventory.attuned_count(), 1);
+
+		let cloak = inventory.push(magic_item("Cloak of Protection"));
+		assert!(!inventory.set_attuned(&cloak, true, 1));
+
+		let path = inventory.move_to(&path, &None).expect("ring should be moved");
+		assert!(inventory.is_attuned(&path[0]));
+		assert!(!inventory.is_equipped(&path[0]));
+		assert_eq!(inventory.attuned_count(), 1);
+	}
+
+	#[test]
+	fn attunement_benefits() {
+		use crate::system::dnd5e::{
+			data::{bounded::BoundValue, character::Persistent},
+			mutator::Speed,
+		};
+		let flying_speed = |inventory: &Inventory| {
+			let character = Character::from(Persistent {
+				inventory: inventory.clone(),
+				..Default::default()
+			});
+			character.speeds().get("Flying").map(|speed| speed.value())
+		};
+		let mut inventory = Inventory::new();
+		let ring = inventory.push(Item {
+			name: "Ring of Flying".into(),
+			kind: Kind::Equipment(Equipment {
+				attunement: Some(Attunement {
+					mutators: vec![Speed {
+						name: "Flying".into(),
+						argument: BoundValue::Base(30),
+					}
+					.into()],
+				}),
+				..Default::default()
+			}),
+			..Default::default()
+		});
+		let backpack = inventory.push(Item {
+			name: "Backpack".into(),
+			items: Some(Inventory::new()),
+			..Default::default()
+		});
+		assert_eq!(flying_speed(&inventory), None);
+		assert!(inventory.set_attuned(&ring, true, 3));
+		assert_eq!(flying_speed(&inventory), Some(30));
+		assert!(inventory.move_to(&vec![ring], &Some(vec![backpack])).is_some());
+		assert_eq!(flying_speed(&inventory), Some(30));
+	}
+
+	#[test]
+	fn attunement_required() {
+		let mut inventory = Inventory::new();
+		let rope = inventory.push(Item {
+			name: "Rope".into(),
+			..Default::default()
+		});
+		assert!(!inventory.set_attuned(&rope, true, 3));
+		assert_eq!(inventory.attuned_count(), 0);
+	}
+}
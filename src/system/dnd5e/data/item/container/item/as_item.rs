@@ -18,7 +18,7 @@ impl AsItem for Item {
 
 	fn set_id_path(&mut self, id: Vec<uuid::Uuid>) {
 		if let Some(container) = &mut self.items {
-			container.parent_item_id = id;
+			container.set_parent_item_id(id);
 		}
 	}
 
@@ -2,9 +2,10 @@ use crate::kdl_ext::NodeContext;
 use crate::{
 	system::dnd5e::data::{
 		character::Character,
-		item::{container::item::AsItem, Item, Kind},
+		item::{container::item::AsItem, equipment::Charges, Item, Kind},
 	},
 	utility::MutatorGroup,
+	GeneralError,
 };
 use kdlize::{AsKdl, FromKdl, NodeBuilder};
 use std::path::Path;
@@ -14,6 +15,24 @@ pub struct EquipableEntry {
 	pub id_path: Vec<uuid::Uuid>,
 	pub item: Item,
 	pub is_equipped: bool,
+	/// If the character is attuned to this item. Only items which require attunement can be attuned.
+	pub is_attuned: bool,
+	/// The number of charges which have been spent, if the item has charges.
+	pub consumed_charges: usize,
+}
+
+impl EquipableEntry {
+	pub fn charges(&self) -> Option<&Charges> {
+		match &self.item.kind {
+			Kind::Equipment(equipment) => equipment.charges.as_ref(),
+			_ => None,
+		}
+	}
+
+	pub fn charges_remaining(&self) -> usize {
+		let capacity = self.charges().map(|charges| charges.capacity).unwrap_or_default();
+		capacity.saturating_sub(self.consumed_charges)
+	}
 }
 
 impl AsItem for EquipableEntry {
@@ -22,6 +41,8 @@ impl AsItem for EquipableEntry {
 			id_path: Vec::new(),
 			item,
 			is_equipped: false,
+			is_attuned: false,
+			consumed_charges: 0,
 		}
 	}
 
@@ -55,17 +76,32 @@ impl MutatorGroup for EquipableEntry {
 		if let Kind::Equipment(equipment) = &self.item.kind {
 			equipment.set_data_path(&path_to_item);
 		}
+		if let Some(items) = &self.item.items {
+			items.set_data_path(&path_to_item);
+		}
 	}
 
 	fn apply_mutators(&self, stats: &mut Character, parent: &Path) {
+		let path_to_item = parent.join(&self.item.name);
+		// Items stored inside of this one are never equipped, but the character can still be attuned to them.
+		if let Some(items) = &self.item.items {
+			stats.apply_from(items, &path_to_item);
+		}
+
 		let Kind::Equipment(equipment) = &self.item.kind else {
 			return;
 		};
+		if equipment.charges.is_some() {
+			stats.features_mut().register_item_charges(&self.id_path, &path_to_item);
+		}
+		// Attunement benefits do not depend on the item being equipped.
+		if let (true, Some(attunement)) = (self.is_attuned, &equipment.attunement) {
+			stats.apply_from(attunement, &path_to_item);
+		}
 		if !self.is_equipped {
 			return;
 		}
 
-		let path_to_item = parent.join(&self.item.name);
 		stats.apply_from(equipment, &path_to_item);
 		if let Some(weapon) = &equipment.weapon {
 			stats.add_feature(weapon.attack_action(self), &path_to_item);
@@ -81,9 +117,16 @@ impl FromKdl<NodeContext> for EquipableEntry {
 	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
 		let item = Item::from_kdl(node)?;
 		let is_equipped = node.get_bool_opt("equipped")?.unwrap_or_default();
+		let is_attuned = node.get_bool_opt("attuned")?.unwrap_or_default();
+		let consumed_charges = node.get_i64_opt("charges_consumed")?.unwrap_or_default();
+		let Ok(consumed_charges) = usize::try_from(consumed_charges) else {
+			return Err(GeneralError(format!("Negative charges_consumed {consumed_charges}")).into());
+		};
 		Ok(Self {
 			id_path: Vec::new(),
 			is_equipped,
+			is_attuned,
+			consumed_charges,
 			item,
 		})
 	}
@@ -95,6 +138,12 @@ impl AsKdl for EquipableEntry {
 		if self.is_equipped {
 			node.push_entry(("equipped", true));
 		}
+		if self.is_attuned {
+			node.push_entry(("attuned", true));
+		}
+		if self.consumed_charges > 0 {
+			node.push_entry(("charges_consumed", self.consumed_charges as i64));
+		}
 		node
 	}
 }
@@ -63,6 +63,8 @@ pub struct ContainerSpell {
 	pub save_dc: Option<u8>,
 	// The spell attack bonus that must be used for this spell.
 	pub attack_bonus: Option<i32>,
+	// The number of charges of the item which are spent to cast this spell.
+	pub charges: Option<usize>,
 }
 impl ContainerSpell {
 	pub fn spell_id(&self) -> &SourceId {
@@ -106,6 +108,7 @@ impl SpellContainer {
 				item_id: Vec::new(),
 				consume_spell: casting.consume_spell,
 				consume_item: casting.consume_item,
+				charges: contained.charges,
 			},
 			attack_bonus: AbilityOrStat::Stat(atk_bonus),
 			save_dc: AbilityOrStat::Stat(save_dc),
@@ -273,6 +276,7 @@ impl From<SourceId> for ContainerSpell {
 			rank: None,
 			save_dc: None,
 			attack_bonus: None,
+			charges: None,
 		}
 	}
 }
@@ -284,11 +288,13 @@ impl FromKdl<NodeContext> for ContainerSpell {
 		let rank = node.get_i64_opt("rank")?.map(|v| v as u8);
 		let save_dc = node.get_i64_opt("save_dc")?.map(|v| v as u8);
 		let attack_bonus = node.get_i64_opt("atk_bonus")?.map(|v| v as i32);
+		let charges = node.get_i64_opt("charges")?.map(|v| v as usize);
 		Ok(Self {
 			spell,
 			rank,
 			save_dc,
 			attack_bonus,
+			charges,
 		})
 	}
 }
@@ -306,6 +312,9 @@ impl AsKdl for ContainerSpell {
 		if let Some(num) = &self.attack_bonus {
 			node.push_entry(("atk_bonus", *num as i64));
 		}
+		if let Some(num) = &self.charges {
+			node.push_entry(("charges", *num as i64));
+		}
 
 		node
 	}
@@ -385,6 +394,7 @@ mod test {
 				rank: Some(2),
 				save_dc: Some(15),
 				attack_bonus: Some(2),
+				charges: None,
 			}],
 			..Default::default()
 		};
@@ -427,6 +437,7 @@ mod test {
 				rank: None,
 				save_dc: None,
 				attack_bonus: None,
+				charges: None,
 			}],
 			..Default::default()
 		};
@@ -467,4 +478,34 @@ mod test {
 		assert_eq_askdl!(&data, doc);
 		Ok(())
 	}
+
+	#[test]
+	fn charged_spells() -> anyhow::Result<()> {
+		let doc = "
+			|spells {
+			|    casting {
+			|        save_dc 15
+			|        atk_bonus 7
+			|    }
+			|    spell \"local://basic-rules@dnd5e/spells/magicMissile.kdl\" charges=1
+			|}
+		";
+		let data = SpellContainer {
+			casting: Some(Casting {
+				duration: None,
+				consume_item: false,
+				consume_spell: false,
+				save_dc: Some(15),
+				attack_bonus: Some(7),
+			}),
+			spells: vec![ContainerSpell {
+				charges: Some(1),
+				..ContainerSpell::from(SourceId::from_str("local://basic-rules@dnd5e/spells/magicMissile.kdl")?)
+			}],
+			..Default::default()
+		};
+		assert_eq_fromkdl!(SpellContainer, doc, data);
+		assert_eq_askdl!(&data, doc);
+		Ok(())
+	}
 }
@@ -7,7 +7,7 @@ use crate::{
 	utility::MutatorGroup,
 };
 use kdlize::{AsKdl, FromKdl, NodeBuilder};
-use std::{collections::HashMap, path::Path};
+use std::{collections::HashMap, path::Path, str::FromStr};
 
 #[derive(Clone, PartialEq, Default, Debug)]
 pub struct Equipment {
@@ -21,8 +21,10 @@ pub struct Equipment {
 	pub shield: Option<i32>,
 	/// If this item is a weapon, tthis is the weapon data.
 	pub weapon: Option<Weapon>,
-	/// If this weapon can be attuned, this is the attunement data.
+	/// If this item can be attuned, this is the attunement data.
 	pub attunement: Option<Attunement>,
+	/// If this item has charges, this is the number of charges it can hold and how they are regained.
+	/// The number of charges which have been spent is tracked by each item instance in the inventory.
 	pub charges: Option<Charges>,
 }
 
@@ -33,11 +35,39 @@ pub struct Charges {
 }
 #[derive(Clone, PartialEq, Debug)]
 pub struct ChargesReset {
-	pub roll: Roll,
+	pub roll: Option<Roll>,
 	pub base: usize,
 	pub rest: Rest,
 }
 
+impl ChargesReset {
+	/// Returns true if all charges are regained on rest (there is no base amount or roll).
+	pub fn restores_all(&self) -> bool {
+		self.roll.is_none() && self.base == 0
+	}
+
+	/// Returns the number of charges regained on rest (`base + roll`).
+	/// If the reset restores all charges, this is the capacity of the item.
+	pub fn roll_regained(&self, capacity: usize, rand: &mut impl rand::Rng) -> usize {
+		if self.restores_all() {
+			return capacity;
+		}
+		let rolled = self.roll.as_ref().map(|roll| roll.roll(rand)).unwrap_or_default();
+		self.base + rolled as usize
+	}
+}
+
+impl std::fmt::Display for ChargesReset {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match (&self.roll, self.base) {
+			(None, 0) => write!(f, "all"),
+			(None, base) => write!(f, "{base}"),
+			(Some(roll), 0) => write!(f, "{roll}"),
+			(Some(roll), base) => write!(f, "{roll} + {base}"),
+		}
+	}
+}
+
 impl MutatorGroup for Equipment {
 	type Target = Character;
 
@@ -48,6 +78,9 @@ impl MutatorGroup for Equipment {
 		if let Some(armor) = &self.armor {
 			armor.set_data_path(path_to_item);
 		}
+		if let Some(attunement) = &self.attunement {
+			attunement.set_data_path(path_to_item);
+		}
 	}
 
 	fn apply_mutators(&self, stats: &mut Character, path_to_item: &Path) {
@@ -95,12 +128,8 @@ impl FromKdl<NodeContext> for Equipment {
 			Some(node) => Some(node.get_i64_req("bonus")? as i32),
 		};
 		let weapon = node.query_opt_t::<Weapon>("scope() > weapon")?;
-		let attunement = match node.query_opt("scope() > attunement")? {
-			None => None,
-			Some(mut _node) => {
-				None // TODO: Some(Attunement::from_kdl(node, &mut ctx.next_node())?)
-			}
-		};
+		let attunement = node.query_opt_t::<Attunement>("scope() > attunement")?;
+		let charges = node.query_opt_t::<Charges>("scope() > charges")?;
 
 		Ok(Self {
 			criteria,
@@ -109,7 +138,7 @@ impl FromKdl<NodeContext> for Equipment {
 			shield,
 			weapon,
 			attunement,
-			charges: None,
+			charges,
 		})
 	}
 }
@@ -131,8 +160,11 @@ impl AsKdl for Equipment {
 		if let Some(weapon) = &self.weapon {
 			node.push_child_t("weapon", weapon);
 		}
-		if let Some(_attunement) = &self.attunement {
-			// TODO: Attunement node.push_child_t("attunement", attunement);
+		if let Some(attunement) = &self.attunement {
+			node.push_child_t("attunement", attunement);
+		}
+		if let Some(charges) = &self.charges {
+			node.push_child_t("charges", charges);
 		}
 
 		if let Some(criteria) = &self.criteria {
@@ -146,9 +178,91 @@ impl AsKdl for Equipment {
 	}
 }
 
+impl FromKdl<NodeContext> for Charges {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let capacity = node.next_i64_req()? as usize;
+		let reset = node.query_opt_t::<ChargesReset>("scope() > reset")?;
+		Ok(Self { capacity, reset })
+	}
+}
+
+impl AsKdl for Charges {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node.push_entry(self.capacity as i64);
+		if let Some(reset) = &self.reset {
+			node.push_child_t("reset", reset);
+		}
+		node
+	}
+}
+
+impl FromKdl<NodeContext> for ChargesReset {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let rest = node.next_str_req_t::<Rest>()?;
+		let base = node.get_i64_opt("base")?.unwrap_or_default() as usize;
+		let roll = match node.get_str_opt("roll")? {
+			None => None,
+			Some(roll) => Some(Roll::from_str(roll)?),
+		};
+		Ok(Self { roll, base, rest })
+	}
+}
+
+impl AsKdl for ChargesReset {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node.push_entry(self.rest.to_string());
+		if self.base > 0 {
+			node.push_entry(("base", self.base as i64));
+		}
+		if let Some(roll) = &self.roll {
+			node.push_entry(("roll", roll.to_string()));
+		}
+		node
+	}
+}
+
 #[derive(Clone, PartialEq, Default, Debug)]
 pub struct Attunement {
-	pub modifiers: Vec<BoxedMutator>,
+	/// Passive mutators applied while the character is attuned to the item, whether or not it is equipped.
+	pub mutators: Vec<BoxedMutator>,
+}
+
+impl MutatorGroup for Attunement {
+	type Target = Character;
+
+	fn set_data_path(&self, path_to_item: &std::path::Path) {
+		for mutator in &self.mutators {
+			mutator.set_data_path(path_to_item);
+		}
+	}
+
+	fn apply_mutators(&self, stats: &mut Character, path_to_item: &Path) {
+		for mutator in &self.mutators {
+			stats.apply(mutator, path_to_item);
+		}
+	}
+}
+
+impl FromKdl<NodeContext> for Attunement {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let mutators = node.query_all_t("scope() > mutator")?;
+		Ok(Self { mutators })
+	}
+}
+
+impl AsKdl for Attunement {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		for mutator in &self.mutators {
+			node.push_child_t("mutator", mutator);
+		}
+		node
+	}
 }
 
 #[cfg(test)]
@@ -243,6 +357,56 @@ mod test {
 			Ok(())
 		}
 
+		#[test]
+		fn attunement() -> anyhow::Result<()> {
+			let doc = "
+				|equipment {
+				|    attunement {
+				|        mutator \"add_modifier\" \"Advantage\" (Skill)\"Specific\" \"Perception\"
+				|    }
+				|}
+			";
+			let data = Equipment {
+				attunement: Some(Attunement {
+					mutators: vec![AddModifier {
+						modifier: Modifier::Advantage,
+						context: None,
+						kind: ModifierKind::Skill(selector::Value::Specific(Skill::Perception)),
+					}
+					.into()],
+				}),
+				..Default::default()
+			};
+			assert_eq_fromkdl!(Equipment, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+
+		#[test]
+		fn charges() -> anyhow::Result<()> {
+			let doc = "
+				|equipment {
+				|    charges 10 {
+				|        reset \"Long\" base=4 roll=\"1d6\"
+				|    }
+				|}
+			";
+			let data = Equipment {
+				charges: Some(Charges {
+					capacity: 10,
+					reset: Some(ChargesReset {
+						roll: Some(Roll::from((1, Die::D6))),
+						base: 4,
+						rest: Rest::Long,
+					}),
+				}),
+				..Default::default()
+			};
+			assert_eq_fromkdl!(Equipment, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+
 		#[test]
 		fn shield() -> anyhow::Result<()> {
 			let doc = "
@@ -259,4 +423,23 @@ mod test {
 			Ok(())
 		}
 	}
+
+	#[test]
+	fn charges_regained() {
+		let mut rand = rand::thread_rng();
+		let all = ChargesReset {
+			roll: None,
+			base: 0,
+			rest: Rest::Long,
+		};
+		assert!(all.restores_all());
+		assert_eq!(all.roll_regained(3, &mut rand), 3);
+		let fixed = ChargesReset {
+			roll: Some(Roll::from(2)),
+			base: 1,
+			rest: Rest::Long,
+		};
+		assert_eq!(fixed.roll_regained(7, &mut rand), 3);
+		assert_eq!(fixed.to_string(), "2 + 1");
+	}
 }
@@ -110,7 +110,7 @@ impl Weapon {
 					check: AttackCheckKind::AttackRoll {
 						ability: self.attack_ability(),
 						proficient: Value::Evaluated(
-							evaluator::Any(vec![
+							evaluator::AnyBool(vec![
 								IsProficientWith::Weapon(WeaponProficiency::Kind(self.kind)).into(),
 								IsProficientWith::Weapon(WeaponProficiency::Classification(
 									self.classification.clone(),
@@ -31,7 +31,7 @@ impl FromStr for Rest {
 }
 
 static DESC_SHORT: &str = "\
-A short rest is a period of downtime, at least 1 hour long, \
+A short rest is a period of downtime, at least {duration} long, \
 during which a character does nothing more strenuous than \
 eating, drinking, reading, and tending to wounds.";
 static DESC_LONG: &str = "\
@@ -42,12 +42,22 @@ no more than 2 hours of light activity, such as reading, talking, eating, or sta
 If the rest is interrupted by a period of strenuous activity — at least 1 hour of \
 walking, fighting, casting spells, or similar adventuring activity — \
 the characters must begin the rest again to gain any benefit from it.";
+static DESC_LONG_VARIANT: &str = "\
+A long rest is a period of extended downtime, at least {duration} long, \
+during which a character sleeps and performs no more than light activity, \
+such as reading, talking, eating, or standing watch.
+
+If the rest is interrupted by a period of strenuous activity — \
+walking, fighting, casting spells, or similar adventuring activity — \
+the characters must begin the rest again to gain any benefit from it.";
 
 impl Rest {
-	pub fn description(&self) -> &'static str {
-		match self {
-			Self::Short => DESC_SHORT,
-			Self::Long => DESC_LONG,
+	pub fn description(&self, variant: RestVariant) -> String {
+		let duration = variant.duration(*self);
+		match (self, variant) {
+			(Self::Short, _) => DESC_SHORT.replace("{duration}", duration),
+			(Self::Long, RestVariant::Standard) => DESC_LONG.to_owned(),
+			(Self::Long, _) => DESC_LONG_VARIANT.replace("{duration}", duration),
 		}
 	}
 
@@ -58,3 +68,70 @@ impl Rest {
 		}
 	}
 }
+
+/// Optional rules which change how long rests take (from the Dungeon Master's Guide).
+#[derive(Debug, EnumSetType, Default)]
+pub enum RestVariant {
+	#[default]
+	Standard,
+	/// Short rests take 8 hours and long rests take 7 days, making rests a strategic choice.
+	GrittyRealism,
+	/// Short rests take 5 minutes and long rests take 1 hour, allowing for a faster paced campaign.
+	EpicHeroism,
+}
+
+impl RestVariant {
+	/// Returns the minimum amount of time it takes to complete a rest using this variant.
+	pub fn duration(&self, rest: Rest) -> &'static str {
+		match (self, rest) {
+			(Self::Standard, Rest::Short) => "1 hour",
+			(Self::Standard, Rest::Long) => "8 hours",
+			(Self::GrittyRealism, Rest::Short) => "8 hours",
+			(Self::GrittyRealism, Rest::Long) => "7 days",
+			(Self::EpicHeroism, Rest::Short) => "5 minutes",
+			(Self::EpicHeroism, Rest::Long) => "1 hour",
+		}
+	}
+
+	/// Returns the kinds of rest whose item charges are regained when taking a rest with this variant.
+	/// Items which regain charges daily (at dawn) reset on a long rest. With gritty realism,
+	/// a short rest is a full night's sleep, so dawn passes and those items also regain charges on a short rest.
+	pub fn charge_resets(&self, rest: Rest) -> Vec<Rest> {
+		match (self, rest) {
+			(Self::GrittyRealism, Rest::Short) => vec![Rest::Short, Rest::Long],
+			(_, rest) => rest.resets_to_apply(),
+		}
+	}
+
+	pub fn display_name(&self) -> &'static str {
+		match self {
+			Self::Standard => "Standard",
+			Self::GrittyRealism => "Gritty Realism",
+			Self::EpicHeroism => "Epic Heroism",
+		}
+	}
+}
+
+impl ToString for RestVariant {
+	fn to_string(&self) -> String {
+		match self {
+			Self::Standard => "Standard",
+			Self::GrittyRealism => "GrittyRealism",
+			Self::EpicHeroism => "EpicHeroism",
+		}
+		.into()
+	}
+}
+
+impl FromStr for RestVariant {
+	type Err = InvalidEnumStr<Self>;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"Standard" => Ok(Self::Standard),
+			"GrittyRealism" => Ok(Self::GrittyRealism),
+			"EpicHeroism" => Ok(Self::EpicHeroism),
+			_ => Err(InvalidEnumStr::from(s)),
+		}
+	}
+}
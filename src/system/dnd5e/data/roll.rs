@@ -7,8 +7,12 @@ mod die;
 pub use die::*;
 mod evaluated;
 pub use evaluated::*;
+mod expression;
+pub use expression::*;
 mod modifier;
 pub use modifier::*;
+mod outcome;
+pub use outcome::*;
 mod set;
 pub use set::*;
 
@@ -12,6 +12,7 @@ pub enum Die {
 	D10,
 	D12,
 	D20,
+	D100,
 }
 impl Die {
 	pub fn value(self) -> u32 {
@@ -22,6 +23,7 @@ impl Die {
 			Self::D10 => 10,
 			Self::D12 => 12,
 			Self::D20 => 20,
+			Self::D100 => 100,
 		}
 	}
 
@@ -44,6 +46,7 @@ impl TryFrom<u32> for Die {
 			10 => Ok(Self::D10),
 			12 => Ok(Self::D12),
 			20 => Ok(Self::D20),
+			100 => Ok(Self::D100),
 			_ => Err(GeneralError(format!("Invalid die number: {value}"))),
 		}
 	}
@@ -59,8 +62,9 @@ impl FromStr for Die {
 			"d10" => Ok(Self::D10),
 			"d12" => Ok(Self::D12),
 			"d20" => Ok(Self::D20),
+			"d100" => Ok(Self::D100),
 			_ => Err(GeneralError(format!(
-				"Invalid die type {s:?}, expected d4, d6, d8, d10, d12, or d20"
+				"Invalid die type {s:?}, expected d4, d6, d8, d10, d12, d20, or d100"
 			))),
 		}
 	}
@@ -74,6 +78,7 @@ impl std::fmt::Display for Die {
 			Self::D10 => write!(f, "d10"),
 			Self::D12 => write!(f, "d12"),
 			Self::D20 => write!(f, "d20"),
+			Self::D100 => write!(f, "d100"),
 		}
 	}
 }
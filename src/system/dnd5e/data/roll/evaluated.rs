@@ -2,17 +2,28 @@ use crate::kdl_ext::NodeContext;
 use crate::{
 	system::dnd5e::{
 		data::character::Character,
-		data::roll::{Die, Roll},
+		data::roll::{Die, Roll, RollExpression},
 		Value,
 	},
 	utility::Dependencies,
 };
 use kdlize::{AsKdl, FromKdl, NodeBuilder};
 
-#[derive(Clone, PartialEq, Default, Debug)]
-pub struct EvaluatedRoll {
-	pub amount: Value<i32>,
-	pub die: Option<Value<i32>>,
+/// A roll which may depend on the character's stats (e.g. a die size which scales with level).
+/// Fixed rolls can be any dice expression, while evaluated rolls are a single term of some amount and die.
+#[derive(Clone, PartialEq, Debug)]
+pub enum EvaluatedRoll {
+	Fixed(RollExpression),
+	Evaluated {
+		amount: Value<i32>,
+		die: Option<Value<i32>>,
+	},
+}
+
+impl Default for EvaluatedRoll {
+	fn default() -> Self {
+		Self::Fixed(RollExpression::default())
+	}
 }
 
 impl<T> From<T> for EvaluatedRoll
@@ -20,33 +31,47 @@ where
 	Roll: From<T>,
 {
 	fn from(value: T) -> Self {
-		let roll = Roll::from(value);
-		Self {
-			amount: Value::Fixed(roll.amount as i32),
-			die: roll.die.map(|die| Value::Fixed(die.value() as i32)),
-		}
+		Self::Fixed(RollExpression::from(Roll::from(value)))
+	}
+}
+
+impl From<RollExpression> for EvaluatedRoll {
+	fn from(expression: RollExpression) -> Self {
+		Self::Fixed(expression)
 	}
 }
 
 impl EvaluatedRoll {
 	pub fn dependencies(&self) -> Dependencies {
-		let mut deps = self.amount.dependencies();
-		if let Some(die_value) = &self.die {
-			deps = deps.join(die_value.dependencies());
+		match self {
+			Self::Fixed(_) => Dependencies::default(),
+			Self::Evaluated { amount, die } => {
+				let mut deps = amount.dependencies();
+				if let Some(die_value) = die {
+					deps = deps.join(die_value.dependencies());
+				}
+				deps
+			}
 		}
-		deps
 	}
 
-	pub fn evaluate(&self, character: &Character) -> Roll {
-		let amount = self.amount.evaluate(character) as u32;
-		let die = match &self.die {
+	pub fn evaluate(&self, character: &Character) -> RollExpression {
+		let (amount, die) = match self {
+			Self::Fixed(expression) => return expression.clone(),
+			Self::Evaluated { amount, die } => (amount, die),
+		};
+		let amount = amount.evaluate(character) as u32;
+		let die = match die {
 			None => None,
 			Some(value) => {
 				let die_value = value.evaluate(character) as u32;
 				Die::try_from(die_value).ok()
 			}
 		};
-		Roll { amount, die }
+		match die {
+			None => RollExpression::from(amount),
+			Some(die) => RollExpression::from((amount, die)),
+		}
 	}
 }
 
@@ -54,11 +79,11 @@ impl FromKdl<NodeContext> for EvaluatedRoll {
 	type Error = anyhow::Error;
 	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
 		if let Some(entry) = node.next_opt() {
-			return Ok(Self::from(Roll::from_kdl_value(entry.value())?));
+			return Ok(Self::Fixed(RollExpression::from_kdl_value(entry.value())?));
 		}
 		let amount = node.query_req_t::<Value<i32>>("scope() > amount")?;
 		let die = node.query_opt_t::<Value<i32>>("scope() > die")?;
-		Ok(Self { amount, die })
+		Ok(Self::Evaluated { amount, die })
 	}
 }
 
@@ -66,17 +91,11 @@ impl AsKdl for EvaluatedRoll {
 	fn as_kdl(&self) -> NodeBuilder {
 		let mut node = NodeBuilder::default();
 		match self {
-			// These first two are when the EvaluatedRoll is a fixed Roll, and thus can be serialized as such
-			Self {
-				amount: Value::Fixed(amt),
-				die: None,
-			} => node.with_entry(*amt as i64),
-			Self {
-				amount: Value::Fixed(amt),
-				die: Some(Value::Fixed(die)),
-			} => node.with_entry_typed(format!("{amt}d{die}"), "Roll"),
-			// While this one puts the amount and die into child nodes for evaluator serialization
-			Self { amount, die } => {
+			// Fixed rolls are serialized as a flat number or a dice expression string
+			Self::Fixed(expression) if expression.dice().is_empty() => node.with_entry(expression.modifier() as i64),
+			Self::Fixed(expression) => node.with_entry_typed(expression.to_string(), "Roll"),
+			// While evaluated rolls put the amount and die into child nodes for evaluator serialization
+			Self::Evaluated { amount, die } => {
 				node.push_child_t("amount", amount);
 				if let Some(die) = die {
 					node.push_child_t("die", die);
@@ -97,6 +116,7 @@ mod test {
 			kdl_ext::{test_utils::*, NodeContext},
 			system::{core::NodeRegistry, dnd5e::evaluator::GetProficiencyBonus},
 		};
+		use std::str::FromStr;
 
 		static NODE_NAME: &str = "roll";
 
@@ -107,10 +127,7 @@ mod test {
 		#[test]
 		fn basic_fixed() -> anyhow::Result<()> {
 			let doc = "roll 1";
-			let data = EvaluatedRoll {
-				amount: Value::Fixed(1),
-				die: None,
-			};
+			let data = EvaluatedRoll::from(1);
 			assert_eq_fromkdl!(EvaluatedRoll, doc, data);
 			assert_eq_askdl!(&data, doc);
 			Ok(())
@@ -119,10 +136,16 @@ mod test {
 		#[test]
 		fn basic_die() -> anyhow::Result<()> {
 			let doc = "roll (Roll)\"3d4\"";
-			let data = EvaluatedRoll {
-				amount: Value::Fixed(3),
-				die: Some(Value::Fixed(4)),
-			};
+			let data = EvaluatedRoll::from((3, Die::D4));
+			assert_eq_fromkdl!(EvaluatedRoll, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+
+		#[test]
+		fn expression() -> anyhow::Result<()> {
+			let doc = "roll (Roll)\"2d6r2+1d4+1\"";
+			let data = EvaluatedRoll::from(RollExpression::from_str("2d6r2+1d4+1")?);
 			assert_eq_fromkdl!(EvaluatedRoll, doc, data);
 			assert_eq_askdl!(&data, doc);
 			Ok(())
@@ -135,7 +158,7 @@ mod test {
 				|    amount (Evaluator)\"get_proficiency_bonus\"
 				|}
 			";
-			let data = EvaluatedRoll {
+			let data = EvaluatedRoll::Evaluated {
 				amount: Value::Evaluated(GetProficiencyBonus.into()),
 				die: None,
 			};
@@ -152,7 +175,7 @@ mod test {
 				|    die (Evaluator)\"get_proficiency_bonus\"
 				|}
 			";
-			let data = EvaluatedRoll {
+			let data = EvaluatedRoll::Evaluated {
 				amount: Value::Fixed(5),
 				die: Some(Value::Evaluated(GetProficiencyBonus.into())),
 			};
@@ -0,0 +1,598 @@
+use super::{Die, ParseRollError, Roll, RolledDie};
+use crate::{kdl_ext::NodeContext, GeneralError};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+use std::str::FromStr;
+
+/// Which of the dice in a `DiceTerm` count towards the total.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Keep {
+	Highest(u32),
+	Lowest(u32),
+}
+
+/// Some amount of the same die, rolled together (e.g. `4d6kh3` or `2d6r2`).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DiceTerm {
+	pub amount: u32,
+	pub die: Die,
+	pub keep: Option<Keep>,
+	/// Any die which rolls this value or lower is rerolled once, and the new roll must be used.
+	pub reroll: Option<u32>,
+}
+
+impl From<(u32, Die)> for DiceTerm {
+	fn from((amount, die): (u32, Die)) -> Self {
+		Self {
+			amount,
+			die,
+			keep: None,
+			reroll: None,
+		}
+	}
+}
+
+impl DiceTerm {
+	/// Returns true if every die in the term counts towards the total and is never rerolled.
+	pub fn is_plain(&self) -> bool {
+		self.keep.is_none() && self.reroll.is_none()
+	}
+
+	/// The indices of the dice (sorted lowest to highest) which count towards the total.
+	fn kept_range(&self) -> std::ops::Range<usize> {
+		let amount = self.amount as usize;
+		match self.keep {
+			None => 0..amount,
+			Some(Keep::Highest(count)) => amount - (count as usize).min(amount)..amount,
+			Some(Keep::Lowest(count)) => 0..(count as usize).min(amount),
+		}
+	}
+
+	pub fn min(&self) -> u32 {
+		self.kept_range().len() as u32
+	}
+
+	pub fn max(&self) -> u32 {
+		self.kept_range().len() as u32 * self.die.value()
+	}
+
+	/// The probability of a single die landing on each face (index 0 being a roll of 1), accounting for rerolls.
+	fn face_probabilities(&self) -> Vec<f64> {
+		let sides = self.die.value() as usize;
+		let uniform = 1.0 / sides as f64;
+		let reroll = (self.reroll.unwrap_or(0) as usize).min(sides);
+		let rerolled = reroll as f64 * uniform;
+		let faces = 1..=sides;
+		let faces = faces.map(|face| match face <= reroll {
+			true => rerolled * uniform,
+			false => uniform + rerolled * uniform,
+		});
+		faces.collect()
+	}
+
+	pub fn average(&self) -> f64 {
+		let probabilities = self.face_probabilities();
+		let amount = self.amount as usize;
+		if self.keep.is_none() {
+			let faces = probabilities.iter().enumerate();
+			let single = faces.map(|(idx, chance)| (idx + 1) as f64 * chance).sum::<f64>();
+			return single * amount as f64;
+		}
+		// The expected value of the j-th lowest die is the sum (over each face v) of the chance that die is at least v.
+		// The j-th lowest die is at least v when at least (amount - j) dice roll v or higher.
+		let mut total = 0.0;
+		for j in self.kept_range() {
+			for face in 0..probabilities.len() {
+				let at_least = probabilities[face..].iter().sum::<f64>();
+				let successes = (amount - j)..=amount;
+				total += successes
+					.map(|count| {
+						let misses = (amount - count) as i32;
+						binomial(amount, count) * at_least.powi(count as i32) * (1.0 - at_least).powi(misses)
+					})
+					.sum::<f64>();
+			}
+		}
+		total
+	}
+
+	/// Rolls each die in the term. Dice which were rerolled or not kept are marked as dropped.
+	pub fn roll(&self, rand: &mut impl rand::Rng) -> Vec<RolledDie> {
+		let mut rolled = Vec::with_capacity(self.amount as usize);
+		for _ in 0..self.amount {
+			let mut value = self.die.roll(rand, 1);
+			if let Some(reroll) = self.reroll {
+				if value <= reroll {
+					rolled.push(RolledDie {
+						die: self.die,
+						value,
+						dropped: true,
+					});
+					value = self.die.roll(rand, 1);
+				}
+			}
+			rolled.push(RolledDie {
+				die: self.die,
+				value,
+				dropped: false,
+			});
+		}
+		if self.keep.is_some() {
+			let by_value = rolled.iter().enumerate();
+			let mut by_value = by_value
+				.filter(|(_, die)| !die.dropped)
+				.map(|(idx, die)| (idx, die.value))
+				.collect::<Vec<_>>();
+			by_value.sort_by_key(|(_, value)| *value);
+			let kept = self.kept_range();
+			for (order, (idx, _)) in by_value.into_iter().enumerate() {
+				if !kept.contains(&order) {
+					rolled[idx].dropped = true;
+				}
+			}
+		}
+		rolled
+	}
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+	(0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+impl std::fmt::Display for DiceTerm {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}{}", self.amount, self.die)?;
+		match self.keep {
+			None => {}
+			Some(Keep::Highest(count)) => write!(f, "kh{count}")?,
+			Some(Keep::Lowest(count)) => write!(f, "kl{count}")?,
+		}
+		if let Some(reroll) = self.reroll {
+			write!(f, "r{reroll}")?;
+		}
+		Ok(())
+	}
+}
+
+impl FromStr for DiceTerm {
+	type Err = ParseRollError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		static EXPECTED: &'static str = "{int}d{int} with optional kh{int}, kl{int}, or r{int}";
+		let Some((amount_str, remaining)) = s.split_once('d') else {
+			return Err(GeneralError(format!("Dice term {s:?} missing die, expected format {EXPECTED:?}.")).into());
+		};
+		let amount = match amount_str.is_empty() {
+			true => 1,
+			false => amount_str.parse::<u32>()?,
+		};
+		let sides_len = remaining.find(|c: char| !c.is_ascii_digit() && c != '%');
+		let (sides_str, mut suffix) = remaining.split_at(sides_len.unwrap_or(remaining.len()));
+		let die = match sides_str {
+			"%" => Die::D100,
+			sides => Die::try_from(sides.parse::<u32>()?)?,
+		};
+
+		let mut term = Self::from((amount, die));
+		while !suffix.is_empty() {
+			let (kind, remaining) = if let Some(remaining) = suffix.strip_prefix("kh") {
+				("kh", remaining)
+			} else if let Some(remaining) = suffix.strip_prefix("kl") {
+				("kl", remaining)
+			} else if let Some(remaining) = suffix.strip_prefix("k") {
+				("kh", remaining)
+			} else if let Some(remaining) = suffix.strip_prefix("r") {
+				("r", remaining)
+			} else {
+				return Err(GeneralError(format!("Invalid suffix {suffix:?} in {s:?}, expected {EXPECTED:?}.")).into());
+			};
+			let digits_len = remaining.find(|c: char| !c.is_ascii_digit()).unwrap_or(remaining.len());
+			let (value_str, next) = remaining.split_at(digits_len);
+			let value = match value_str.is_empty() {
+				true => 1,
+				false => value_str.parse::<u32>()?,
+			};
+			match kind {
+				"kh" => term.keep = Some(Keep::Highest(value)),
+				"kl" => term.keep = Some(Keep::Lowest(value)),
+				_ => term.reroll = Some(value),
+			}
+			suffix = next;
+		}
+
+		if let Some(Keep::Highest(count) | Keep::Lowest(count)) = term.keep {
+			if count == 0 || count > amount {
+				return Err(GeneralError(format!("Cannot keep {count} of {amount} dice in {s:?}.")).into());
+			}
+		}
+		if let Some(reroll) = term.reroll {
+			if reroll == 0 || reroll >= die.value() {
+				return Err(
+					GeneralError(format!("Cannot reroll values of {reroll} or less for {die} in {s:?}.")).into(),
+				);
+			}
+		}
+		Ok(term)
+	}
+}
+
+/// A full dice expression, made up of any number of dice terms and a flat modifier
+/// (e.g. `2d6+1d4+3`, `4d6kh3`, or `2d6r2`).
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct RollExpression {
+	dice: Vec<DiceTerm>,
+	modifier: i32,
+}
+
+impl<T> From<T> for RollExpression
+where
+	Roll: From<T>,
+{
+	fn from(value: T) -> Self {
+		let roll = Roll::from(value);
+		let mut expression = Self::default();
+		match roll.die {
+			None => expression.modifier = roll.amount as i32,
+			Some(die) => expression.push(DiceTerm::from((roll.amount, die))),
+		}
+		expression
+	}
+}
+
+impl From<DiceTerm> for RollExpression {
+	fn from(term: DiceTerm) -> Self {
+		Self {
+			dice: vec![term],
+			modifier: 0,
+		}
+	}
+}
+
+impl RollExpression {
+	pub fn dice(&self) -> &Vec<DiceTerm> {
+		&self.dice
+	}
+
+	pub fn modifier(&self) -> i32 {
+		self.modifier
+	}
+
+	pub fn add_modifier(&mut self, amount: i32) {
+		self.modifier += amount;
+	}
+
+	/// Adds a dice term to the expression. Plain terms are merged with any existing plain term of the same die.
+	pub fn push(&mut self, term: DiceTerm) {
+		if term.amount == 0 {
+			return;
+		}
+		if term.is_plain() {
+			let existing = self.dice.iter_mut();
+			let mut existing = existing.filter(|existing| existing.is_plain() && existing.die == term.die);
+			if let Some(existing) = existing.next() {
+				existing.amount += term.amount;
+				return;
+			}
+		}
+		self.dice.push(term);
+	}
+
+	pub fn extend(&mut self, other: &RollExpression) {
+		for term in &other.dice {
+			self.push(*term);
+		}
+		self.modifier += other.modifier;
+	}
+
+	/// Returns an expression which rolls this expression some number of times.
+	pub fn times(&self, count: u32) -> Self {
+		let mut expression = Self::default();
+		for _ in 0..count {
+			expression.extend(self);
+		}
+		expression
+	}
+
+	pub fn min(&self) -> i32 {
+		let dice = self.dice.iter().map(DiceTerm::min).sum::<u32>();
+		dice as i32 + self.modifier
+	}
+
+	pub fn max(&self) -> i32 {
+		let dice = self.dice.iter().map(DiceTerm::max).sum::<u32>();
+		dice as i32 + self.modifier
+	}
+
+	pub fn average(&self) -> f64 {
+		let dice = self.dice.iter().map(DiceTerm::average).sum::<f64>();
+		dice + self.modifier as f64
+	}
+
+	/// Rolls every die in the expression, without the flat modifier.
+	pub fn roll_dice(&self, rand: &mut impl rand::Rng) -> Vec<RolledDie> {
+		let mut rolled = Vec::new();
+		for term in &self.dice {
+			rolled.extend(term.roll(rand));
+		}
+		rolled
+	}
+
+	pub fn roll(&self, rand: &mut impl rand::Rng) -> i32 {
+		let dice = self.roll_dice(rand).into_iter().filter(|die| !die.dropped);
+		dice.map(|die| die.value as i32).sum::<i32>() + self.modifier
+	}
+
+	pub fn from_kdl_value(kdl: &kdl::KdlValue) -> anyhow::Result<Self> {
+		if let Some(amt) = kdl.as_i64() {
+			let mut expression = Self::default();
+			expression.add_modifier(amt as i32);
+			return Ok(expression);
+		}
+		if let Some(str) = kdl.as_string() {
+			return Ok(Self::from_str(str)?);
+		}
+		Err(crate::kdl_ext::InvalidValueType(kdl.clone(), "i64 or string").into())
+	}
+}
+
+impl std::fmt::Display for RollExpression {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		if self.dice.is_empty() {
+			return write!(f, "{}", self.modifier);
+		}
+		for (idx, term) in self.dice.iter().enumerate() {
+			if idx > 0 {
+				write!(f, "+")?;
+			}
+			write!(f, "{term}")?;
+		}
+		match self.modifier {
+			0 => Ok(()),
+			modifier => write!(f, "{modifier:+}"),
+		}
+	}
+}
+
+impl FromStr for RollExpression {
+	type Err = ParseRollError;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let compact = s.chars().filter(|c| !c.is_whitespace()).collect::<String>();
+		let mut terms = Vec::new();
+		let mut current = String::new();
+		let mut negative = false;
+		for (idx, c) in compact.char_indices() {
+			match c {
+				'+' | '-' => {
+					if idx > 0 {
+						terms.push((negative, std::mem::take(&mut current)));
+					}
+					negative = c == '-';
+				}
+				c => current.push(c),
+			}
+		}
+		terms.push((negative, current));
+
+		let mut expression = Self::default();
+		for (negative, term) in terms {
+			if term.is_empty() {
+				return Err(GeneralError(format!("Roll expression {s:?} is missing a term.")).into());
+			}
+			if !term.contains('d') {
+				let value = term.parse::<i32>()?;
+				expression.modifier += if negative { -value } else { value };
+				continue;
+			}
+			if negative {
+				return Err(GeneralError(format!("Dice cannot be subtracted in roll expression {s:?}.")).into());
+			}
+			// terms are not merged when parsing, so the expression is written back out the same way
+			expression.dice.push(DiceTerm::from_str(&term)?);
+		}
+		Ok(expression)
+	}
+}
+
+impl FromKdl<NodeContext> for RollExpression {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		Self::from_kdl_value(node.next_req()?.value())
+	}
+}
+
+impl AsKdl for RollExpression {
+	fn as_kdl(&self) -> NodeBuilder {
+		let node = NodeBuilder::default();
+		if self.dice.is_empty() {
+			node.with_entry(self.to_string())
+		} else {
+			node.with_entry_typed(self.to_string(), "Roll")
+		}
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	fn expr(s: &str) -> RollExpression {
+		RollExpression::from_str(s).unwrap()
+	}
+
+	mod parse {
+		use super::*;
+
+		#[test]
+		fn flat() {
+			assert_eq!(expr("4"), RollExpression::from(4));
+			assert_eq!(expr("-2").modifier(), -2);
+		}
+
+		#[test]
+		fn multiple_terms() {
+			let expression = expr("2d6 + 1d4 + 3");
+			assert_eq!(
+				expression.dice(),
+				&vec![DiceTerm::from((2, Die::D6)), DiceTerm::from((1, Die::D4))]
+			);
+			assert_eq!(expression.modifier(), 3);
+			assert_eq!(expression.to_string(), "2d6+1d4+3");
+		}
+
+		#[test]
+		fn subtracted_modifier() {
+			let expression = expr("1d8-1+2");
+			assert_eq!(expression.modifier(), 1);
+			assert_eq!(expr("1d8-1").to_string(), "1d8-1");
+		}
+
+		#[test]
+		fn keep() {
+			let term = expr("4d6kh3").dice()[0];
+			assert_eq!(term.keep, Some(Keep::Highest(3)));
+			let term = expr("2d20kl1").dice()[0];
+			assert_eq!(term.keep, Some(Keep::Lowest(1)));
+			assert_eq!(expr("2d20k").to_string(), "2d20kh1");
+		}
+
+		#[test]
+		fn reroll() {
+			let term = expr("2d6r2").dice()[0];
+			assert_eq!(term.reroll, Some(2));
+			assert_eq!(expr("1d10r").to_string(), "1d10r1");
+		}
+
+		#[test]
+		fn percentile() {
+			assert_eq!(expr("1d100"), RollExpression::from((1, Die::D100)));
+			assert_eq!(expr("d%").to_string(), "1d100");
+		}
+
+		#[test]
+		fn invalid() {
+			assert!(RollExpression::from_str("").is_err());
+			assert!(RollExpression::from_str("2d7").is_err());
+			assert!(RollExpression::from_str("2d6+").is_err());
+			assert!(RollExpression::from_str("1d20-1d4").is_err());
+			assert!(RollExpression::from_str("2d6kh3").is_err());
+			assert!(RollExpression::from_str("1d6r6").is_err());
+			assert!(RollExpression::from_str("1d6x").is_err());
+		}
+	}
+
+	mod kdl {
+		use super::*;
+		use crate::kdl_ext::test_utils::*;
+
+		static NODE_NAME: &str = "roll";
+
+		#[test]
+		fn fixed() -> anyhow::Result<()> {
+			let doc = "roll \"4\"";
+			let data = RollExpression::from(4);
+			assert_eq_fromkdl!(RollExpression, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+
+		#[test]
+		fn multi_term() -> anyhow::Result<()> {
+			let doc = "roll (Roll)\"2d6+1d4+3\"";
+			let data = expr("2d6+1d4+3");
+			assert_eq_fromkdl!(RollExpression, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+
+		#[test]
+		fn keep_reroll() -> anyhow::Result<()> {
+			let doc = "roll (Roll)\"4d6kh3r1\"";
+			let data = RollExpression::from(DiceTerm {
+				amount: 4,
+				die: Die::D6,
+				keep: Some(Keep::Highest(3)),
+				reroll: Some(1),
+			});
+			assert_eq_fromkdl!(RollExpression, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+	}
+
+	mod stats {
+		use super::*;
+
+		fn assert_approx(value: f64, expected: f64) {
+			assert!((value - expected).abs() < 0.001, "{value} != {expected}");
+		}
+
+		#[test]
+		fn min_max() {
+			let expression = expr("2d6+1d4+3");
+			assert_eq!(expression.min(), 6);
+			assert_eq!(expression.max(), 19);
+			let expression = expr("4d6kh3");
+			assert_eq!(expression.min(), 3);
+			assert_eq!(expression.max(), 18);
+		}
+
+		#[test]
+		fn average_plain() {
+			assert_approx(expr("2d6+3").average(), 10.0);
+			assert_approx(expr("1d100").average(), 50.5);
+		}
+
+		#[test]
+		fn average_keep() {
+			assert_approx(expr("2d20kh1").average(), 13.825);
+			assert_approx(expr("2d20kl1").average(), 7.175);
+			assert_approx(expr("4d6kh3").average(), 12.244598765);
+		}
+
+		#[test]
+		fn average_reroll() {
+			// great weapon fighting: (3+4+5+6)/6 + (2/6 * 3.5) per die
+			assert_approx(expr("2d6r2").average(), 8.333333);
+		}
+	}
+
+	mod roll {
+		use super::*;
+		use rand::{rngs::StdRng, SeedableRng};
+
+		#[test]
+		fn within_bounds() {
+			let expression = expr("2d6+1d4kh1+3");
+			for seed in 0..50 {
+				let value = expression.roll(&mut StdRng::seed_from_u64(seed));
+				assert!((expression.min()..=expression.max()).contains(&value));
+			}
+		}
+
+		#[test]
+		fn keep_highest() {
+			let term = expr("4d6kh3").dice()[0];
+			for seed in 0..50 {
+				let rolled = term.roll(&mut StdRng::seed_from_u64(seed));
+				assert_eq!(rolled.len(), 4);
+				let dropped = rolled.iter().filter(|die| die.dropped).collect::<Vec<_>>();
+				assert_eq!(dropped.len(), 1);
+				let lowest = rolled.iter().map(|die| die.value).min().unwrap();
+				assert_eq!(dropped[0].value, lowest);
+			}
+		}
+
+		#[test]
+		fn reroll() {
+			let term = expr("8d6r2").dice()[0];
+			for seed in 0..50 {
+				let rolled = term.roll(&mut StdRng::seed_from_u64(seed));
+				let kept = rolled.iter().filter(|die| !die.dropped).collect::<Vec<_>>();
+				assert_eq!(kept.len(), 8);
+				for die in rolled.iter().filter(|die| die.dropped) {
+					assert!(die.value <= 2);
+				}
+			}
+		}
+	}
+}
@@ -15,6 +15,16 @@ impl Modifier {
 			Modifier::Disadvantage => "Disadvantage",
 		}
 	}
+
+	/// Returns the modifier that applies to a roll which may have advantage and/or disadvantage.
+	/// If a roll has both, they cancel out and the roll is made normally.
+	pub fn resolve(advantage: bool, disadvantage: bool) -> Option<Self> {
+		match (advantage, disadvantage) {
+			(true, false) => Some(Self::Advantage),
+			(false, true) => Some(Self::Disadvantage),
+			_ => None,
+		}
+	}
 }
 impl ToString for Modifier {
 	fn to_string(&self) -> String {
@@ -0,0 +1,234 @@
+use super::{DiceTerm, Die, Keep, Modifier, RollExpression};
+use crate::system::dnd5e::data::DamageType;
+
+/// A single die which was rolled as part of a `RollOutcome`.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RolledDie {
+	pub die: Die,
+	pub value: u32,
+	/// True if the die was rolled but does not count towards the total
+	/// (i.e. the discarded d20 when rolling with advantage or disadvantage, or a die which was rerolled).
+	pub dropped: bool,
+}
+
+/// A flat amount added to a roll, and where that amount came from.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RollBonus {
+	pub value: i32,
+	pub source: String,
+}
+
+impl<S: Into<String>> From<(i32, S)> for RollBonus {
+	fn from((value, source): (i32, S)) -> Self {
+		Self {
+			value,
+			source: source.into(),
+		}
+	}
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum RollKind {
+	/// A d20 roll, like an ability check, saving throw, or attack roll.
+	Check {
+		modifier: Option<Modifier>,
+	},
+	Damage {
+		damage_type: Option<DamageType>,
+	},
+	/// Hit points restored (or, if `temporary`, temporary hit points granted) to a creature.
+	Healing {
+		temporary: bool,
+	},
+}
+
+/// The result of rolling dice, with each die rolled and every bonus applied,
+/// so the total can be broken down for the user.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RollOutcome {
+	pub label: String,
+	pub kind: RollKind,
+	pub dice: Vec<RolledDie>,
+	pub bonuses: Vec<RollBonus>,
+	/// Situational information which may affect the roll, but was not applied automatically
+	/// (e.g. advantage against a specific kind of effect).
+	pub notes: Vec<String>,
+}
+
+impl RollOutcome {
+	/// Rolls a d20 check. If the check has advantage or disadvantage, two d20s are rolled
+	/// and the lower or higher die (respectively) is dropped.
+	pub fn check(
+		label: impl Into<String>,
+		modifier: Option<Modifier>,
+		bonuses: Vec<RollBonus>,
+		rand: &mut impl rand::Rng,
+	) -> Self {
+		let d20 = match modifier {
+			None => DiceTerm::from((1, Die::D20)),
+			Some(modifier) => DiceTerm {
+				keep: Some(match modifier {
+					Modifier::Advantage => Keep::Highest(1),
+					Modifier::Disadvantage => Keep::Lowest(1),
+				}),
+				..DiceTerm::from((2, Die::D20))
+			},
+		};
+		Self {
+			label: label.into(),
+			kind: RollKind::Check { modifier },
+			dice: d20.roll(rand),
+			bonuses,
+			notes: Vec::new(),
+		}
+	}
+
+	/// Rolls each of the dice in the expression. The flat modifier of the expression is treated as a base bonus.
+	pub fn damage(
+		label: impl Into<String>,
+		expression: &RollExpression,
+		mut bonuses: Vec<RollBonus>,
+		damage_type: Option<DamageType>,
+		rand: &mut impl rand::Rng,
+	) -> Self {
+		if expression.modifier() != 0 {
+			bonuses.insert(0, RollBonus::from((expression.modifier(), "Base")));
+		}
+		Self {
+			label: label.into(),
+			kind: RollKind::Damage { damage_type },
+			dice: expression.roll_dice(rand),
+			bonuses,
+			notes: Vec::new(),
+		}
+	}
+
+	/// Rolls each of the dice in the expression, like `damage`, for an amount of healing or temporary hit points.
+	pub fn healing(
+		label: impl Into<String>,
+		expression: &RollExpression,
+		bonuses: Vec<RollBonus>,
+		temporary: bool,
+		rand: &mut impl rand::Rng,
+	) -> Self {
+		Self {
+			kind: RollKind::Healing { temporary },
+			..Self::damage(label, expression, bonuses, None, rand)
+		}
+	}
+
+	pub fn with_notes(mut self, notes: Vec<String>) -> Self {
+		self.notes = notes;
+		self
+	}
+
+	pub fn total(&self) -> i32 {
+		let dice = self.dice.iter().filter(|die| !die.dropped);
+		let dice_total = dice.map(|die| die.value as i32).sum::<i32>();
+		let bonus_total = self.bonuses.iter().map(|bonus| bonus.value).sum::<i32>();
+		let total = dice_total + bonus_total;
+		match self.kind {
+			// damage and healing can never be less than 0
+			RollKind::Damage { .. } | RollKind::Healing { .. } => total.max(0),
+			RollKind::Check { .. } => total,
+		}
+	}
+
+	/// Returns the value of the d20 which counts towards a check, if this is a check.
+	pub fn natural_roll(&self) -> Option<u32> {
+		let RollKind::Check { .. } = &self.kind else {
+			return None;
+		};
+		let kept = self.dice.iter().find(|die| !die.dropped);
+		kept.map(|die| die.value)
+	}
+
+	pub fn is_critical_success(&self) -> bool {
+		self.natural_roll() == Some(20)
+	}
+
+	pub fn is_critical_failure(&self) -> bool {
+		self.natural_roll() == Some(1)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use rand::{rngs::StdRng, SeedableRng};
+	use std::str::FromStr;
+
+	fn rng() -> StdRng {
+		StdRng::seed_from_u64(42)
+	}
+
+	#[test]
+	fn check_no_modifier() {
+		let outcome = RollOutcome::check("Test", None, vec![(3, "Bonus").into()], &mut rng());
+		assert_eq!(outcome.dice.len(), 1);
+		let natural = outcome.natural_roll().unwrap();
+		assert!((1..=20).contains(&natural));
+		assert_eq!(outcome.total(), natural as i32 + 3);
+	}
+
+	#[test]
+	fn check_advantage() {
+		for seed in 0..20 {
+			let mut rand = StdRng::seed_from_u64(seed);
+			let outcome = RollOutcome::check("Test", Some(Modifier::Advantage), vec![], &mut rand);
+			assert_eq!(outcome.dice.len(), 2);
+			assert_eq!(outcome.dice.iter().filter(|die| die.dropped).count(), 1);
+			let highest = outcome.dice.iter().map(|die| die.value).max().unwrap();
+			assert_eq!(outcome.natural_roll(), Some(highest));
+		}
+	}
+
+	#[test]
+	fn check_disadvantage() {
+		for seed in 0..20 {
+			let mut rand = StdRng::seed_from_u64(seed);
+			let outcome = RollOutcome::check("Test", Some(Modifier::Disadvantage), vec![], &mut rand);
+			assert_eq!(outcome.dice.len(), 2);
+			let lowest = outcome.dice.iter().map(|die| die.value).min().unwrap();
+			assert_eq!(outcome.natural_roll(), Some(lowest));
+			assert_eq!(outcome.total(), lowest as i32);
+		}
+	}
+
+	#[test]
+	fn damage() {
+		let expression = RollExpression::from_str("2d6+1").unwrap();
+		let outcome = RollOutcome::damage(
+			"Test",
+			&expression,
+			vec![(2, "Strength modifier").into()],
+			None,
+			&mut rng(),
+		);
+		assert_eq!(outcome.dice.len(), 2);
+		assert!(outcome
+			.dice
+			.iter()
+			.all(|die| die.die == Die::D6 && (1..=6).contains(&die.value)));
+		assert_eq!(outcome.bonuses.len(), 2);
+		let dice_total = outcome.dice.iter().map(|die| die.value as i32).sum::<i32>();
+		assert_eq!(outcome.total(), dice_total + 3);
+		assert_eq!(outcome.natural_roll(), None);
+	}
+
+	#[test]
+	fn healing() {
+		let expression = RollExpression::from_str("1d8+3").unwrap();
+		let outcome = RollOutcome::healing("Test", &expression, vec![], false, &mut rng());
+		assert_eq!(outcome.kind, RollKind::Healing { temporary: false });
+		assert_eq!(outcome.dice.len(), 1);
+		assert_eq!(outcome.total(), outcome.dice[0].value as i32 + 3);
+	}
+
+	#[test]
+	fn damage_minimum_zero() {
+		let expression = RollExpression::from((1, Die::D4));
+		let outcome = RollOutcome::damage("Test", &expression, vec![(-10, "Penalty").into()], None, &mut rng());
+		assert_eq!(outcome.total(), 0);
+	}
+}
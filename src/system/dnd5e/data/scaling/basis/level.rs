@@ -1,4 +1,4 @@
-use crate::system::dnd5e::data::roll::Roll;
+use crate::system::dnd5e::data::roll::{Roll, RollExpression};
 
 pub trait DefaultLevelMap {
 	fn default_for_level(level: usize) -> Option<Self>
@@ -17,3 +17,9 @@ impl DefaultLevelMap for Roll {
 		None
 	}
 }
+
+impl DefaultLevelMap for RollExpression {
+	fn default_for_level(_level: usize) -> Option<Self> {
+		None
+	}
+}
@@ -1,8 +1,10 @@
-use super::{description, AreaOfEffect};
+use super::{description, AreaOfEffect, IndirectCondition};
 use crate::kdl_ext::NodeContext;
 use crate::system::{core::SourceId, dnd5e::SystemComponent};
 use kdlize::{ext::DocumentExt, AsKdl, FromKdl, NodeBuilder};
 
+mod amount;
+pub use amount::*;
 mod casting_time;
 pub use casting_time::*;
 mod check;
@@ -13,6 +15,8 @@ mod damage;
 pub use damage::*;
 mod duration;
 pub use duration::*;
+mod healing;
+pub use healing::*;
 mod range;
 pub use range::*;
 
@@ -28,6 +32,12 @@ pub struct Spell {
 	pub range: Range,
 	pub check: Option<Check>,
 	pub damage: Option<Damage>,
+	/// Hit points restored to the target of the spell.
+	pub healing: Option<Healing>,
+	/// Temporary hit points granted to the target of the spell.
+	pub temp_hit_points: Option<Healing>,
+	/// Conditions which the spell inflicts on its targets.
+	pub conditions: Vec<IndirectCondition>,
 	pub area_of_effect: Option<AreaOfEffect>,
 	pub duration: Duration,
 	pub tags: Vec<String>,
@@ -78,6 +88,9 @@ impl FromKdl<NodeContext> for Spell {
 		let duration = node.query_req_t::<Duration>("scope() > duration")?;
 		let check = node.query_opt_t::<Check>("scope() > check")?;
 		let damage = node.query_opt_t::<Damage>("scope() > damage")?;
+		let healing = node.query_opt_t::<Healing>("scope() > healing")?;
+		let temp_hit_points = node.query_opt_t::<Healing>("scope() > temp-hp")?;
+		let conditions = node.query_all_t::<IndirectCondition>("scope() > condition")?;
 
 		let mut tags = node.query_str_all("scope() > tag", 0)?;
 		tags.sort();
@@ -94,6 +107,9 @@ impl FromKdl<NodeContext> for Spell {
 			range,
 			check,
 			damage,
+			healing,
+			temp_hit_points,
+			conditions,
 			area_of_effect,
 			duration,
 			tags,
@@ -131,6 +147,15 @@ impl AsKdl for Spell {
 		if let Some(damage) = &self.damage {
 			node.push_child_t("damage", damage);
 		}
+		if let Some(healing) = &self.healing {
+			node.push_child_t("healing", healing);
+		}
+		if let Some(temp_hit_points) = &self.temp_hit_points {
+			node.push_child_t("temp-hp", temp_hit_points);
+		}
+		for condition in &self.conditions {
+			node.push_child_t("condition", condition);
+		}
 
 		node.push_child_opt_t("description", &self.description);
 
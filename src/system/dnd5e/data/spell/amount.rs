@@ -0,0 +1,74 @@
+use crate::kdl_ext::NodeContext;
+use crate::system::dnd5e::data::{character::Character, roll::RollExpression, scaling};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+use std::str::FromStr;
+
+/// An amount rolled for a spell's effect (e.g. its damage or healing),
+/// which can scale with the character and increase when the spell is cast at a higher rank.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Amount {
+	pub(super) roll: scaling::Value<RollExpression>,
+	// amount to add to the rolled value
+	pub(super) base: i32,
+	// if true, add the spellcasting ability modifier to the total
+	pub(super) include_ability_modifier: bool,
+	pub(super) upcast: Option<RollExpression>,
+}
+
+impl Amount {
+	/// Returns the rolls for this amount when cast `upcast_amount` ranks above the spell's rank,
+	/// and the flat bonus (including the provided ability modifier, if applicable) added to those rolls.
+	pub fn evaluate(&self, character: &Character, modifier: i32, upcast_amount: u32) -> (RollExpression, i32) {
+		let mut rolls = self.roll.evaluate(character).unwrap_or_default();
+		if let Some(upcast_roll) = &self.upcast {
+			if upcast_amount > 0 {
+				rolls.extend(&upcast_roll.times(upcast_amount));
+			}
+		}
+		let mut bonus = self.base;
+		if self.include_ability_modifier {
+			bonus += modifier;
+		}
+		(rolls, bonus)
+	}
+
+	/// Adds the optional properties of the amount to a node which already contains the amount's roll.
+	pub(super) fn push_properties(&self, node: &mut NodeBuilder) {
+		if self.base != 0 {
+			node.push_entry(("base", self.base as i64));
+		}
+		if self.include_ability_modifier {
+			node.push_entry(("ability", true));
+		}
+		if let Some(upcast) = &self.upcast {
+			node.push_entry(("upcast", upcast.to_string()));
+		}
+	}
+}
+
+impl FromKdl<NodeContext> for Amount {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let roll = scaling::Value::from_kdl(node)?;
+		let base = node.get_i64_opt("base")?.unwrap_or_default() as i32;
+		let ability = node.get_bool_opt("ability")?.unwrap_or_default();
+		let upcast = match node.get_str_opt("upcast")? {
+			None => None,
+			Some(str) => Some(RollExpression::from_str(str)?),
+		};
+		Ok(Self {
+			roll,
+			base,
+			include_ability_modifier: ability,
+			upcast,
+		})
+	}
+}
+
+impl AsKdl for Amount {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = self.roll.as_kdl();
+		self.push_properties(&mut node);
+		node
+	}
+}
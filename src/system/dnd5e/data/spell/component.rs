@@ -1,17 +1,47 @@
 use crate::kdl_ext::NodeContext;
+use crate::system::{core::SourceId, dnd5e::data::currency::Wallet};
 use crate::utility::NotInList;
 use kdlize::{AsKdl, FromKdl, NodeBuilder};
 
 // Components:
 // Verbal
 // Somatic
-// Material (string + consumes=bool)
+// Material (string + consumes=bool + optional cost)
 // can have multiple material component entries, which are collected into a vec
 #[derive(Default, Clone, PartialEq, Debug)]
 pub struct Components {
 	pub verbal: bool,
 	pub somatic: bool,
-	pub materials: Vec<(String, /*consumes on cast*/ bool)>,
+	pub materials: Vec<Material>,
+}
+
+impl Components {
+	/// The combined worth of all materials with a currency cost, which the caster must have all at once.
+	pub fn materials_worth(&self) -> Wallet {
+		let costs = self.materials.iter().filter_map(|material| match &material.cost {
+			Some(MaterialCost::Worth(worth)) => Some(*worth),
+			_ => None,
+		});
+		costs.fold(Wallet::default(), |total, worth| total + worth)
+	}
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Material {
+	pub description: String,
+	/// If true, the material is used up when the spell is cast.
+	pub consumed: bool,
+	/// What the caster must have in order to provide the material.
+	/// Materials without a cost can be substituted by a component pouch or spellcasting focus.
+	pub cost: Option<MaterialCost>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum MaterialCost {
+	/// A specific item which must be in the caster's inventory.
+	Item(SourceId),
+	/// Some amount of currency, which must be in the caster's wallet (e.g. a diamond worth at least 300 gp).
+	Worth(Wallet),
 }
 
 impl FromKdl<NodeContext> for Components {
@@ -25,9 +55,23 @@ impl FromKdl<NodeContext> for Components {
 				"Verbal" => components.verbal = true,
 				"Somatic" => components.somatic = true,
 				"Material" => {
-					let material = node.next_str_req()?.to_owned();
-					let consume_on_cast = node.get_bool_opt("consumes")?.unwrap_or_default();
-					components.materials.push((material, consume_on_cast));
+					let description = node.next_str_req()?.to_owned();
+					let consumed = node.get_bool_opt("consumes")?.unwrap_or_default();
+					let cost = match node.query_opt("scope() > item")? {
+						Some(mut node) => {
+							let id = node.next_str_req_t::<SourceId>()?;
+							Some(MaterialCost::Item(id.with_relative_basis(node.context().id(), false)))
+						}
+						None => match node.query_opt("scope() > worth")? {
+							Some(mut node) => Some(MaterialCost::Worth(Wallet::from_kdl(&mut node)?)),
+							None => None,
+						},
+					};
+					components.materials.push(Material {
+						description,
+						consumed,
+						cost,
+					});
 				}
 				name => return Err(NotInList(name.into(), vec!["Verbal", "Somatic", "Material"]).into()),
 			}
@@ -45,14 +89,19 @@ impl AsKdl for Components {
 		if self.somatic {
 			node.push_child_entry("component", "Somatic");
 		}
-		for (material, consumed) in &self.materials {
+		for material in &self.materials {
 			node.push_child({
 				let mut node = NodeBuilder::default()
 					.with_entry("Material")
-					.with_entry(material.clone());
-				if *consumed {
+					.with_entry(material.description.clone());
+				if material.consumed {
 					node.push_entry(("consumes", true));
 				}
+				match &material.cost {
+					None => {}
+					Some(MaterialCost::Item(id)) => node.push_child_t("item", id),
+					Some(MaterialCost::Worth(wallet)) => node.push_child_t("worth", wallet),
+				}
 				node.build("component")
 			});
 		}
@@ -66,7 +115,7 @@ mod test {
 
 	mod kdl {
 		use super::*;
-		use crate::kdl_ext::test_utils::*;
+		use crate::{kdl_ext::test_utils::*, system::dnd5e::data::currency::Kind};
 
 		static NODE_NAME: &str = "list";
 
@@ -114,7 +163,11 @@ mod test {
 			let data = Components {
 				verbal: false,
 				somatic: false,
-				materials: vec![("a swatch of wool".into(), false)],
+				materials: vec![Material {
+					description: "a swatch of wool".into(),
+					consumed: false,
+					cost: None,
+				}],
 			};
 			assert_eq_fromkdl!(Components, doc, data);
 			assert_eq_askdl!(&data, doc);
@@ -131,7 +184,60 @@ mod test {
 			let data = Components {
 				verbal: false,
 				somatic: false,
-				materials: vec![("a swatch of wool".into(), true)],
+				materials: vec![Material {
+					description: "a swatch of wool".into(),
+					consumed: true,
+					cost: None,
+				}],
+			};
+			assert_eq_fromkdl!(Components, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+
+		#[test]
+		fn material_worth() -> anyhow::Result<()> {
+			let doc = "
+				|list {
+				|    component \"Material\" \"a diamond worth at least 300 gp\" consumes=true {
+				|        worth 300 (Currency)\"Gold\"
+				|    }
+				|}
+			";
+			let data = Components {
+				verbal: false,
+				somatic: false,
+				materials: vec![Material {
+					description: "a diamond worth at least 300 gp".into(),
+					consumed: true,
+					cost: Some(MaterialCost::Worth(Wallet::from([(300, Kind::Gold)]))),
+				}],
+			};
+			assert_eq_fromkdl!(Components, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+
+		#[test]
+		fn material_item() -> anyhow::Result<()> {
+			let doc = "
+				|list {
+				|    component \"Material\" \"a pearl worth at least 100 gp\" {
+				|        item \"items/pearl.kdl\"
+				|    }
+				|}
+			";
+			let data = Components {
+				verbal: false,
+				somatic: false,
+				materials: vec![Material {
+					description: "a pearl worth at least 100 gp".into(),
+					consumed: false,
+					cost: Some(MaterialCost::Item(SourceId {
+						path: "items/pearl.kdl".into(),
+						..Default::default()
+					})),
+				}],
 			};
 			assert_eq_fromkdl!(Components, doc, data);
 			assert_eq_askdl!(&data, doc);
@@ -0,0 +1,117 @@
+use super::Amount;
+use crate::kdl_ext::NodeContext;
+use crate::system::dnd5e::data::{character::Character, roll::RollExpression};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+
+/// An amount of hit points restored by a spell (e.g. Cure Wounds),
+/// or granted as temporary hit points (e.g. False Life).
+#[derive(Clone, PartialEq, Debug)]
+pub struct Healing {
+	amount: Amount,
+}
+
+impl Healing {
+	/// Returns the roll for this amount when cast `upcast_amount` ranks above the spell's rank,
+	/// including the flat bonus and (if applicable) the provided ability modifier.
+	pub fn evaluate(&self, character: &Character, modifier: i32, upcast_amount: u32) -> RollExpression {
+		let (mut rolls, bonus) = self.amount.evaluate(character, modifier, upcast_amount);
+		rolls.add_modifier(bonus);
+		rolls
+	}
+}
+
+impl FromKdl<NodeContext> for Healing {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let amount = Amount::from_kdl(node)?;
+		Ok(Self { amount })
+	}
+}
+
+impl AsKdl for Healing {
+	fn as_kdl(&self) -> NodeBuilder {
+		self.amount.as_kdl()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::{roll::Die, scaling};
+	use std::str::FromStr;
+
+	mod kdl {
+		use super::*;
+		use crate::kdl_ext::test_utils::*;
+
+		static NODE_NAME: &str = "healing";
+
+		#[test]
+		fn fixed_roll() -> anyhow::Result<()> {
+			let doc = "healing \"1d8\" ability=true upcast=\"1d8\"";
+			let data = Healing {
+				amount: Amount {
+					roll: scaling::Value::Fixed(RollExpression::from((1, Die::D8))),
+					base: 0,
+					include_ability_modifier: true,
+					upcast: Some(RollExpression::from((1, Die::D8))),
+				},
+			};
+			assert_eq_fromkdl!(Healing, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+
+		#[test]
+		fn with_base() -> anyhow::Result<()> {
+			let doc = "healing \"1d4\" base=4 upcast=\"5\"";
+			let data = Healing {
+				amount: Amount {
+					roll: scaling::Value::Fixed(RollExpression::from((1, Die::D4))),
+					base: 4,
+					include_ability_modifier: false,
+					upcast: Some(RollExpression::from_str("5")?),
+				},
+			};
+			assert_eq_fromkdl!(Healing, doc, data);
+			assert_eq_askdl!(&data, doc);
+			Ok(())
+		}
+	}
+
+	mod evaluate {
+		use super::*;
+		use crate::system::dnd5e::data::character::Persistent;
+
+		#[test]
+		fn upcast() -> anyhow::Result<()> {
+			let character = Character::from(Persistent::default());
+			let healing = Healing {
+				amount: Amount {
+					roll: scaling::Value::Fixed(RollExpression::from((1, Die::D8))),
+					base: 0,
+					include_ability_modifier: true,
+					upcast: Some(RollExpression::from((1, Die::D8))),
+				},
+			};
+			assert_eq!(healing.evaluate(&character, 3, 0), RollExpression::from_str("1d8+3")?);
+			assert_eq!(healing.evaluate(&character, 3, 2), RollExpression::from_str("3d8+3")?);
+			Ok(())
+		}
+
+		#[test]
+		fn flat_upcast() -> anyhow::Result<()> {
+			let character = Character::from(Persistent::default());
+			let healing = Healing {
+				amount: Amount {
+					roll: scaling::Value::Fixed(RollExpression::from((1, Die::D4))),
+					base: 4,
+					include_ability_modifier: false,
+					upcast: Some(RollExpression::from_str("5")?),
+				},
+			};
+			assert_eq!(healing.evaluate(&character, 3, 1), RollExpression::from_str("1d4+9")?);
+			Ok(())
+		}
+	}
+}
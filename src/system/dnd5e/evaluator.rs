@@ -2,11 +2,15 @@ mod get_ability;
 pub use get_ability::*;
 mod get_prof_bonus;
 pub use get_prof_bonus::*;
+mod get_exhaustion;
+pub use get_exhaustion::*;
 mod get_hit_points;
 pub use get_hit_points::*;
 mod get_level;
 pub use get_level::*;
 
+mod has_ability_score;
+pub use has_ability_score::*;
 mod has_armor;
 pub use has_armor::*;
 mod has_attack;
@@ -16,6 +20,8 @@ pub use has_condition::*;
 mod has_proficiency;
 pub use has_proficiency::*;
 
+mod compare;
+pub use compare::*;
 mod logic;
 pub use logic::*;
 
@@ -0,0 +1,232 @@
+use crate::kdl_ext::NodeContext;
+use crate::{
+	system::dnd5e::{data::character::Character, Value},
+	utility::{Dependencies, Evaluator, NotInList},
+};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+use std::str::FromStr;
+
+/// Criteria which passes if a numerical comparison between two values holds true.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Compare {
+	pub operation: Comparison,
+	pub lhs: Value<i32>,
+	pub rhs: Value<i32>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Comparison {
+	Equal,
+	NotEqual,
+	LessThan,
+	LessThanOrEqual,
+	GreaterThan,
+	GreaterThanOrEqual,
+}
+
+impl Comparison {
+	pub fn symbol(&self) -> &'static str {
+		match self {
+			Self::Equal => "==",
+			Self::NotEqual => "!=",
+			Self::LessThan => "<",
+			Self::LessThanOrEqual => "<=",
+			Self::GreaterThan => ">",
+			Self::GreaterThanOrEqual => ">=",
+		}
+	}
+
+	fn phrase(&self) -> &'static str {
+		match self {
+			Self::Equal => "is",
+			Self::NotEqual => "is not",
+			Self::LessThan => "is less than",
+			Self::LessThanOrEqual => "is at most",
+			Self::GreaterThan => "is greater than",
+			Self::GreaterThanOrEqual => "is at least",
+		}
+	}
+
+	pub fn compare(&self, lhs: i32, rhs: i32) -> bool {
+		match self {
+			Self::Equal => lhs == rhs,
+			Self::NotEqual => lhs != rhs,
+			Self::LessThan => lhs < rhs,
+			Self::LessThanOrEqual => lhs <= rhs,
+			Self::GreaterThan => lhs > rhs,
+			Self::GreaterThanOrEqual => lhs >= rhs,
+		}
+	}
+}
+
+impl FromStr for Comparison {
+	type Err = NotInList;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		match s {
+			"==" => Ok(Self::Equal),
+			"!=" => Ok(Self::NotEqual),
+			"<" => Ok(Self::LessThan),
+			"<=" => Ok(Self::LessThanOrEqual),
+			">" => Ok(Self::GreaterThan),
+			">=" => Ok(Self::GreaterThanOrEqual),
+			_ => Err(NotInList(s.into(), vec!["==", "!=", "<", "<=", ">", ">="])),
+		}
+	}
+}
+
+crate::impl_trait_eq!(Compare);
+kdlize::impl_kdl_node!(Compare, "compare");
+
+impl Evaluator for Compare {
+	type Context = Character;
+	type Item = Result<(), String>;
+
+	fn description(&self) -> Option<String> {
+		let lhs = self.lhs.description()?;
+		let rhs = self.rhs.description()?;
+		Some(format!("{lhs} {} {rhs}", self.operation.phrase()))
+	}
+
+	fn dependencies(&self) -> Dependencies {
+		self.lhs.dependencies().join(self.rhs.dependencies())
+	}
+
+	fn evaluate(&self, state: &Self::Context) -> Self::Item {
+		let lhs = self.lhs.evaluate(state);
+		let rhs = self.rhs.evaluate(state);
+		match self.operation.compare(lhs, rhs) {
+			true => Ok(()),
+			false => Err(format!("Expected {lhs} {} {rhs}.", self.operation.symbol())),
+		}
+	}
+}
+
+impl FromKdl<NodeContext> for Compare {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let operation = Comparison::from_str(node.next_str_req()?)?;
+		let lhs = node.query_req_t::<Value<i32>>("scope() > lhs")?;
+		let rhs = node.query_req_t::<Value<i32>>("scope() > rhs")?;
+		Ok(Self { operation, lhs, rhs })
+	}
+}
+
+impl AsKdl for Compare {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node.push_entry(self.operation.symbol());
+		node.push_child_t("lhs", &self.lhs);
+		node.push_child_t("rhs", &self.rhs);
+		node
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	mod kdl {
+		use super::*;
+		use crate::{
+			kdl_ext::test_utils::*,
+			system::{
+				core::NodeRegistry,
+				dnd5e::evaluator::{test::test_utils, GetLevelInt},
+			},
+		};
+
+		test_utils!(Compare, node_reg());
+
+		fn node_reg() -> NodeRegistry {
+			let mut node_reg = NodeRegistry::default();
+			node_reg.register_evaluator::<Compare>();
+			node_reg.register_evaluator::<GetLevelInt>();
+			node_reg
+		}
+
+		#[test]
+		fn at_least() -> anyhow::Result<()> {
+			let doc = "
+				|evaluator \"compare\" \">=\" {
+				|    lhs (Evaluator)\"get_level\" class=\"Fighter\"
+				|    rhs 5
+				|}
+			";
+			let data = Compare {
+				operation: Comparison::GreaterThanOrEqual,
+				lhs: Value::Evaluated(GetLevelInt::from(Some("Fighter")).into()),
+				rhs: Value::Fixed(5),
+			};
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+
+		#[test]
+		fn equal() -> anyhow::Result<()> {
+			let doc = "
+				|evaluator \"compare\" \"==\" {
+				|    lhs (Evaluator)\"get_level\"
+				|    rhs 1
+				|}
+			";
+			let data = Compare {
+				operation: Comparison::Equal,
+				lhs: Value::Evaluated(GetLevelInt::default().into()),
+				rhs: Value::Fixed(1),
+			};
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+	}
+
+	mod evaluate {
+		use super::*;
+		use crate::system::dnd5e::{
+			data::{character::Persistent, Class, Level},
+			evaluator::GetLevelInt,
+		};
+
+		fn character(level: usize) -> Character {
+			let mut persistent = Persistent::default();
+			persistent.classes.push(Class {
+				name: "Fighter".into(),
+				current_level: level,
+				levels: {
+					let mut vec = Vec::with_capacity(level);
+					vec.resize_with(level, || Level::default());
+					vec
+				},
+				..Default::default()
+			});
+			Character::from(persistent)
+		}
+
+		fn level_at_least(level: i32) -> Compare {
+			Compare {
+				operation: Comparison::GreaterThanOrEqual,
+				lhs: Value::Evaluated(GetLevelInt::default().into()),
+				rhs: Value::Fixed(level),
+			}
+		}
+
+		#[test]
+		fn greater_or_equal() {
+			assert_eq!(level_at_least(5).evaluate(&character(5)), Ok(()));
+			assert_eq!(
+				level_at_least(5).evaluate(&character(4)),
+				Err("Expected 4 >= 5.".into())
+			);
+		}
+
+		#[test]
+		fn description() {
+			assert_eq!(
+				level_at_least(5).description(),
+				Some("your character level is at least 5".into())
+			);
+		}
+	}
+}
@@ -0,0 +1,77 @@
+use crate::kdl_ext::NodeContext;
+use crate::{system::dnd5e::data::character::Character, utility::Evaluator};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct GetExhaustion;
+
+crate::impl_trait_eq!(GetExhaustion);
+kdlize::impl_kdl_node!(GetExhaustion, "get_exhaustion");
+
+impl FromKdl<NodeContext> for GetExhaustion {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(_node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		Ok(Self)
+	}
+}
+
+impl AsKdl for GetExhaustion {
+	fn as_kdl(&self) -> NodeBuilder {
+		NodeBuilder::default()
+	}
+}
+
+impl Evaluator for GetExhaustion {
+	type Context = Character;
+	type Item = i32;
+
+	fn description(&self) -> Option<String> {
+		Some(format!("your exhaustion level"))
+	}
+
+	fn evaluate(&self, state: &Self::Context) -> Self::Item {
+		state.persistent().exhaustion as i32
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	mod kdl {
+		use super::*;
+		use crate::{kdl_ext::test_utils::*, system::dnd5e::evaluator::test::test_utils};
+
+		test_utils!(GetExhaustion);
+
+		#[test]
+		fn basic() -> anyhow::Result<()> {
+			let doc = "evaluator \"get_exhaustion\"";
+			let data = GetExhaustion;
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+	}
+
+	mod evaluate {
+		use super::*;
+		use crate::system::dnd5e::data::character::Persistent;
+
+		fn character(exhaustion: u8) -> Character {
+			let mut persistent = Persistent::default();
+			persistent.exhaustion = exhaustion;
+			Character::from(persistent)
+		}
+
+		#[test]
+		fn none() {
+			assert_eq!(GetExhaustion.evaluate(&character(0)), 0);
+		}
+
+		#[test]
+		fn exhausted() {
+			assert_eq!(GetExhaustion.evaluate(&character(3)), 3);
+		}
+	}
+}
@@ -0,0 +1,176 @@
+use crate::kdl_ext::NodeContext;
+use crate::{
+	system::dnd5e::data::{character::Character, Ability},
+	utility::{Dependencies, Evaluator},
+};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+
+/// Checks if the character's ability scores are at least some minimum values
+/// (e.g. the ability score prerequisites for multiclassing).
+#[derive(Clone, PartialEq, Default, Debug)]
+pub struct HasAbilityScore {
+	/// By default, every minimum must be met. If true, only one of the minimums must be met.
+	pub any: bool,
+	pub minimums: Vec<(Ability, u32)>,
+}
+
+crate::impl_trait_eq!(HasAbilityScore);
+kdlize::impl_kdl_node!(HasAbilityScore, "has_ability_score");
+
+impl Evaluator for HasAbilityScore {
+	type Context = Character;
+	type Item = Result<(), String>;
+
+	fn description(&self) -> Option<String> {
+		let iter = self.minimums.iter();
+		let minimums = iter.map(|(ability, score)| format!("{} {score}", ability.long_name()));
+		let joiner = match self.any {
+			true => "or",
+			false => "and",
+		};
+		let minimums = crate::utility::list_as_english(minimums.collect(), joiner)?;
+		Some(format!("your ability scores are at least {minimums}"))
+	}
+
+	fn dependencies(&self) -> Dependencies {
+		["ability_score_finalize"].into()
+	}
+
+	fn evaluate(&self, character: &Self::Context) -> Self::Item {
+		let mut failures = Vec::with_capacity(self.minimums.len());
+		for (ability, minimum) in &self.minimums {
+			let score = character.ability_scores().get(*ability).score().0;
+			match (score >= *minimum, self.any) {
+				(true, true) => return Ok(()),
+				(true, false) => {}
+				(false, _) => failures.push(format!("{} is {score} (needs {minimum})", ability.long_name())),
+			}
+		}
+		match (failures.is_empty(), self.any) {
+			(true, _) => Ok(()),
+			(false, true) => Err(format!(
+				"None of the ability scores are high enough: {}.",
+				failures.join(", ")
+			)),
+			(false, false) => Err(format!("Ability scores too low: {}.", failures.join(", "))),
+		}
+	}
+}
+
+impl FromKdl<NodeContext> for HasAbilityScore {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let any = node.get_bool_opt("any")?.unwrap_or_default();
+		let mut minimums = Vec::new();
+		for node in &mut node.query_all("scope() > score")? {
+			let ability = node.next_str_req_t::<Ability>()?;
+			let score = node.next_i64_req()? as u32;
+			minimums.push((ability, score));
+		}
+		Ok(Self { any, minimums })
+	}
+}
+
+impl AsKdl for HasAbilityScore {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		if self.any {
+			node.push_entry(("any", true));
+		}
+		for (ability, score) in &self.minimums {
+			node.push_child(
+				NodeBuilder::default()
+					.with_entry(ability.long_name())
+					.with_entry(*score as i64)
+					.build("score"),
+			);
+		}
+		node
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	mod kdl {
+		use super::*;
+		use crate::{kdl_ext::test_utils::*, system::dnd5e::evaluator::test::test_utils};
+
+		test_utils!(HasAbilityScore);
+
+		#[test]
+		fn all() -> anyhow::Result<()> {
+			let doc = "
+				|evaluator \"has_ability_score\" {
+				|    score \"Strength\" 13
+				|    score \"Charisma\" 13
+				|}
+			";
+			let data = HasAbilityScore {
+				any: false,
+				minimums: vec![(Ability::Strength, 13), (Ability::Charisma, 13)],
+			};
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+
+		#[test]
+		fn any() -> anyhow::Result<()> {
+			let doc = "
+				|evaluator \"has_ability_score\" any=true {
+				|    score \"Strength\" 13
+				|    score \"Dexterity\" 13
+				|}
+			";
+			let data = HasAbilityScore {
+				any: true,
+				minimums: vec![(Ability::Strength, 13), (Ability::Dexterity, 13)],
+			};
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+	}
+
+	mod evaluate {
+		use super::*;
+		use crate::system::dnd5e::data::character::Persistent;
+
+		fn character(scores: &[(Ability, u32)]) -> Character {
+			let mut persistent = Persistent::default();
+			for (ability, score) in scores {
+				persistent.ability_scores[*ability] = *score;
+			}
+			Character::from(persistent)
+		}
+
+		#[test]
+		fn all() {
+			let eval = HasAbilityScore {
+				any: false,
+				minimums: vec![(Ability::Strength, 13), (Ability::Charisma, 13)],
+			};
+			let ctx = character(&[(Ability::Strength, 14), (Ability::Charisma, 13)]);
+			assert_eq!(eval.evaluate(&ctx), Ok(()));
+			let ctx = character(&[(Ability::Strength, 14), (Ability::Charisma, 10)]);
+			assert_eq!(
+				eval.evaluate(&ctx),
+				Err("Ability scores too low: Charisma is 10 (needs 13).".into())
+			);
+		}
+
+		#[test]
+		fn any() {
+			let eval = HasAbilityScore {
+				any: true,
+				minimums: vec![(Ability::Strength, 13), (Ability::Dexterity, 13)],
+			};
+			let ctx = character(&[(Ability::Strength, 8), (Ability::Dexterity, 15)]);
+			assert_eq!(eval.evaluate(&ctx), Ok(()));
+			let ctx = character(&[(Ability::Strength, 8), (Ability::Dexterity, 12)]);
+			assert!(eval.evaluate(&ctx).is_err());
+		}
+	}
+}
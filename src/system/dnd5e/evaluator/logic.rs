@@ -1,40 +1,387 @@
 use crate::{
-	kdl_ext::{AsKdl, NodeBuilder},
-	system::dnd5e::{data::character::Character, BoxedEvaluator},
-	utility::{Dependencies, Evaluator},
+	kdl_ext::NodeContext,
+	system::dnd5e::data::character::Character,
+	utility::{Dependencies, Evaluator, GenericEvaluator},
 };
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+use std::fmt::Debug;
 
+pub type AllCriteria = All<Result<(), String>>;
+pub type AllBool = All<bool>;
+pub type AnyCriteria = Any<Result<(), String>>;
+pub type AnyBool = Any<bool>;
+pub type NotCriteria = Not<Result<(), String>>;
+pub type NotBool = Not<bool>;
+
+/// The output of an evaluator which can be combined by boolean logic evaluators.
+/// Criteria (`Result<(), String>`) carry a reason for failing, whereas plain booleans do not.
+pub trait LogicItem: Sized {
+	fn passed(&self) -> bool;
+	fn pass() -> Self;
+	fn fail(reason: String) -> Self;
+	fn failure_reason(self) -> Option<String>;
+}
+impl LogicItem for bool {
+	fn passed(&self) -> bool {
+		*self
+	}
+
+	fn pass() -> Self {
+		true
+	}
+
+	fn fail(_reason: String) -> Self {
+		false
+	}
+
+	fn failure_reason(self) -> Option<String> {
+		(!self).then(String::new)
+	}
+}
+impl LogicItem for Result<(), String> {
+	fn passed(&self) -> bool {
+		self.is_ok()
+	}
+
+	fn pass() -> Self {
+		Ok(())
+	}
+
+	fn fail(reason: String) -> Self {
+		Err(reason)
+	}
+
+	fn failure_reason(self) -> Option<String> {
+		self.err()
+	}
+}
+
+fn join_dependencies<T>(evaluators: &[GenericEvaluator<Character, T>]) -> Dependencies {
+	let iter = evaluators.iter();
+	iter.fold(Dependencies::default(), |deps, eval| deps.join(eval.dependencies()))
+}
+
+/// Describes the list of evaluators joined by the provided conjunction,
+/// if and only if every evaluator in the list can be described.
+fn join_descriptions<T>(evaluators: &[GenericEvaluator<Character, T>], joiner: &str) -> Option<String> {
+	let descriptions = evaluators.iter().map(|eval| eval.description());
+	let descriptions = descriptions.collect::<Option<Vec<_>>>()?;
+	crate::utility::list_as_english(descriptions, joiner)
+}
+
+fn criteria_from_kdl<T: 'static>(
+	node: &mut crate::kdl_ext::NodeReader<'_>,
+) -> anyhow::Result<Vec<GenericEvaluator<Character, T>>> {
+	node.query_all_t::<GenericEvaluator<Character, T>>("scope() > criteria")
+}
+
+/// Passes if every one of its criteria passes (logical AND).
+/// An empty list of criteria always passes.
 #[derive(Clone, PartialEq, Debug)]
-pub struct Any(pub Vec<BoxedEvaluator<bool>>);
+pub struct All<T>(pub Vec<GenericEvaluator<Character, T>>);
 
-crate::impl_trait_eq!(Any);
-kdlize::impl_kdl_node!(Any, "any");
-impl Evaluator for Any {
+crate::impl_trait_eq!(AllCriteria);
+crate::impl_trait_eq!(AllBool);
+kdlize::impl_kdl_node!(AllCriteria, "all");
+kdlize::impl_kdl_node!(AllBool, "all_bool");
+
+impl<T> Evaluator for All<T>
+where
+	Self: crate::utility::TraitEq + kdlize::NodeId,
+	T: 'static + Debug + Send + Sync + LogicItem,
+{
 	type Context = Character;
-	type Item = bool;
+	type Item = T;
 
 	fn description(&self) -> Option<String> {
-		None
+		join_descriptions(&self.0, "and")
 	}
 
 	fn dependencies(&self) -> Dependencies {
-		self.0
-			.iter()
-			.fold(Dependencies::default(), |deps, eval| deps.join(eval.dependencies()))
+		join_dependencies(&self.0)
 	}
 
 	fn evaluate(&self, state: &Self::Context) -> Self::Item {
 		for evaluator in &self.0 {
-			if evaluator.evaluate(state) {
-				return true;
+			let output = evaluator.evaluate(state);
+			if !output.passed() {
+				return output;
 			}
 		}
-		false
+		T::pass()
+	}
+}
+
+impl<T: 'static> FromKdl<NodeContext> for All<T> {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		Ok(Self(criteria_from_kdl(node)?))
+	}
+}
+
+impl<T> AsKdl for All<T> {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		for evaluator in &self.0 {
+			node.push_child_t("criteria", evaluator);
+		}
+		node
+	}
+}
+
+/// Passes if at least one of its criteria passes (logical OR).
+/// An empty list of criteria never passes.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Any<T>(pub Vec<GenericEvaluator<Character, T>>);
+
+crate::impl_trait_eq!(AnyCriteria);
+crate::impl_trait_eq!(AnyBool);
+kdlize::impl_kdl_node!(AnyCriteria, "any");
+kdlize::impl_kdl_node!(AnyBool, "any_bool");
+
+impl<T> Evaluator for Any<T>
+where
+	Self: crate::utility::TraitEq + kdlize::NodeId,
+	T: 'static + Debug + Send + Sync + LogicItem,
+{
+	type Context = Character;
+	type Item = T;
+
+	fn description(&self) -> Option<String> {
+		join_descriptions(&self.0, "or")
+	}
+
+	fn dependencies(&self) -> Dependencies {
+		join_dependencies(&self.0)
+	}
+
+	fn evaluate(&self, state: &Self::Context) -> Self::Item {
+		let mut reasons = Vec::with_capacity(self.0.len());
+		for evaluator in &self.0 {
+			let output = evaluator.evaluate(state);
+			if output.passed() {
+				return output;
+			}
+			reasons.extend(output.failure_reason().filter(|reason| !reason.is_empty()));
+		}
+		match reasons.is_empty() {
+			true => T::fail("None of the criteria were met.".into()),
+			false => T::fail(reasons.join(" ")),
+		}
+	}
+}
+
+impl<T: 'static> FromKdl<NodeContext> for Any<T> {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		Ok(Self(criteria_from_kdl(node)?))
 	}
 }
-impl AsKdl for Any {
+
+impl<T> AsKdl for Any<T> {
 	fn as_kdl(&self) -> NodeBuilder {
-		// STUB: Not registered for documents
-		NodeBuilder::default()
+		let mut node = NodeBuilder::default();
+		for evaluator in &self.0 {
+			node.push_child_t("criteria", evaluator);
+		}
+		node
+	}
+}
+
+/// Inverts the result of a single criteria (logical NOT).
+#[derive(Clone, PartialEq, Debug)]
+pub struct Not<T>(pub GenericEvaluator<Character, T>);
+
+crate::impl_trait_eq!(NotCriteria);
+crate::impl_trait_eq!(NotBool);
+kdlize::impl_kdl_node!(NotCriteria, "not");
+kdlize::impl_kdl_node!(NotBool, "not_bool");
+
+impl<T> Evaluator for Not<T>
+where
+	Self: crate::utility::TraitEq + kdlize::NodeId,
+	T: 'static + Debug + Send + Sync + LogicItem,
+{
+	type Context = Character;
+	type Item = T;
+
+	fn description(&self) -> Option<String> {
+		Some(format!("it is not the case that {}", self.0.description()?))
+	}
+
+	fn dependencies(&self) -> Dependencies {
+		self.0.dependencies()
+	}
+
+	fn evaluate(&self, state: &Self::Context) -> Self::Item {
+		match self.0.evaluate(state).passed() {
+			true => T::fail(match self.0.description() {
+				Some(desc) => format!("Criteria must not be met: {desc}."),
+				None => "Inverted criteria was met.".into(),
+			}),
+			false => T::pass(),
+		}
+	}
+}
+
+impl<T: 'static> FromKdl<NodeContext> for Not<T> {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		Ok(Self(node.query_req_t("scope() > criteria")?))
+	}
+}
+
+impl<T> AsKdl for Not<T> {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node.push_child_t("criteria", &self.0);
+		node
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	mod kdl {
+		use super::*;
+		use crate::{
+			kdl_ext::test_utils::*,
+			system::{
+				core::NodeRegistry,
+				dnd5e::{
+					data::ArmorExtended,
+					evaluator::{test::test_utils, HasArmorEquipped, HasCondition},
+				},
+			},
+		};
+
+		test_utils!(AnyCriteria, node_reg());
+
+		fn node_reg() -> NodeRegistry {
+			let mut node_reg = NodeRegistry::default();
+			node_reg.register_evaluator::<AllCriteria>();
+			node_reg.register_evaluator::<AnyCriteria>();
+			node_reg.register_evaluator::<NotCriteria>();
+			node_reg.register_evaluator::<HasArmorEquipped>();
+			node_reg.register_evaluator::<HasCondition>();
+			node_reg
+		}
+
+		#[test]
+		fn empty() -> anyhow::Result<()> {
+			let doc = "evaluator \"any\"";
+			let data = AnyCriteria(vec![]);
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+
+		#[test]
+		fn nested() -> anyhow::Result<()> {
+			let doc = "
+				|evaluator \"any\" {
+				|    criteria \"all\" {
+				|        criteria \"has_armor_equipped\" inverted=true
+				|        criteria \"not\" {
+				|            criteria \"has_armor_equipped\" {
+				|                kind \"Shield\"
+				|            }
+				|        }
+				|    }
+				|    criteria \"has_condition\"
+				|}
+			";
+			let data = AnyCriteria(vec![
+				AllCriteria(vec![
+					HasArmorEquipped {
+						inverted: true,
+						..Default::default()
+					}
+					.into(),
+					NotCriteria(
+						HasArmorEquipped {
+							kinds: [ArmorExtended::Shield].into(),
+							..Default::default()
+						}
+						.into(),
+					)
+					.into(),
+				])
+				.into(),
+				HasCondition::default().into(),
+			]);
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+	}
+
+	mod evaluate {
+		use super::*;
+		use crate::system::dnd5e::{
+			data::{character::Persistent, Condition},
+			evaluator::HasCondition,
+		};
+
+		fn character(conditions: Vec<Condition>) -> Character {
+			let mut persistent = Persistent::default();
+			for condition in conditions {
+				persistent.conditions.insert(condition);
+			}
+			Character::from(persistent)
+		}
+
+		fn has_any_condition() -> GenericEvaluator<Character, Result<(), String>> {
+			HasCondition::default().into()
+		}
+
+		fn has_no_conditions() -> GenericEvaluator<Character, Result<(), String>> {
+			HasCondition {
+				inverted: true,
+				..Default::default()
+			}
+			.into()
+		}
+
+		#[test]
+		fn all() {
+			let ctx = character(vec![]);
+			assert_eq!(AllCriteria(vec![]).evaluate(&ctx), Ok(()));
+			assert_eq!(AllCriteria(vec![has_no_conditions()]).evaluate(&ctx), Ok(()));
+			let all = AllCriteria(vec![has_no_conditions(), has_any_condition()]);
+			assert_eq!(all.evaluate(&ctx), Err("No conditions found.".into()));
+		}
+
+		#[test]
+		fn any() {
+			let ctx = character(vec![]);
+			assert!(AnyCriteria(vec![]).evaluate(&ctx).is_err());
+			assert_eq!(
+				AnyCriteria(vec![has_any_condition()]).evaluate(&ctx),
+				Err("No conditions found.".into())
+			);
+			let any = AnyCriteria(vec![has_any_condition(), has_no_conditions()]);
+			assert_eq!(any.evaluate(&ctx), Ok(()));
+		}
+
+		#[test]
+		fn not() {
+			let ctx = character(vec![]);
+			assert_eq!(NotCriteria(has_any_condition()).evaluate(&ctx), Ok(()));
+			assert_eq!(
+				NotCriteria(has_no_conditions()).evaluate(&ctx),
+				Err("Criteria must not be met: you don't have any conditions.".into())
+			);
+		}
+
+		#[test]
+		fn description() {
+			let any = AnyCriteria(vec![has_any_condition(), NotCriteria(has_no_conditions()).into()]);
+			assert_eq!(
+				any.description(),
+				Some("you have any condition or it is not the case that you don't have any conditions".into())
+			);
+		}
 	}
 }
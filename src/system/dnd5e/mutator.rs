@@ -13,12 +13,24 @@ pub use apply_if::*;
 mod armor_class;
 pub use armor_class::*;
 
+mod attunement;
+pub use attunement::*;
+
 mod bonus;
 pub use bonus::*;
 
+mod carrying_size;
+pub use carrying_size::*;
+
 mod description;
 pub use description::*;
 
+mod encumbrance;
+pub use encumbrance::*;
+
+mod exhaustion;
+pub use exhaustion::*;
+
 mod defense;
 pub use defense::*;
 
@@ -0,0 +1,107 @@
+use crate::kdl_ext::NodeContext;
+use crate::{
+	system::dnd5e::{
+		data::{character::Character, description},
+		Value,
+	},
+	utility::{Dependencies, Mutator},
+};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+
+/// Increases the number of magic items the character can be attuned to at once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddMaxAttunement {
+	pub value: Value<i32>,
+}
+
+crate::impl_trait_eq!(AddMaxAttunement);
+kdlize::impl_kdl_node!(AddMaxAttunement, "add_max_attunement");
+
+impl Mutator for AddMaxAttunement {
+	type Target = Character;
+
+	fn dependencies(&self) -> Dependencies {
+		self.value.dependencies()
+	}
+
+	fn description(&self, _state: Option<&Character>) -> description::Section {
+		static PREFIX: &'static str = "The number of magic items you can attune to at the same time increases by";
+		description::Section {
+			content: match &self.value {
+				Value::Fixed(amount) => format!("{PREFIX} {amount}."),
+				Value::Evaluated(evaluator) => format!(
+					"{PREFIX} {}.",
+					match evaluator.description() {
+						Some(desc) => desc,
+						None => "some amount".into(),
+					}
+				),
+			}
+			.into(),
+			..Default::default()
+		}
+	}
+
+	fn apply(&self, stats: &mut Character, parent: &std::path::Path) {
+		let value = self.value.evaluate(stats);
+		stats.max_attunement_mut().push(value, parent.to_owned());
+	}
+}
+
+impl FromKdl<NodeContext> for AddMaxAttunement {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let value = Value::from_kdl(node)?;
+		Ok(Self { value })
+	}
+}
+
+impl AsKdl for AddMaxAttunement {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node += self.value.as_kdl();
+		node
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	mod kdl {
+		use super::*;
+		use crate::{kdl_ext::test_utils::*, system::dnd5e::mutator::test::test_utils};
+
+		test_utils!(AddMaxAttunement);
+
+		#[test]
+		fn value() -> anyhow::Result<()> {
+			let doc = "mutator \"add_max_attunement\" 1";
+			let data = AddMaxAttunement { value: Value::Fixed(1) };
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+	}
+
+	mod mutate {
+		use super::*;
+		use crate::system::dnd5e::data::{character::Persistent, Bundle};
+
+		#[test]
+		fn increases_default() {
+			let mut persistent = Persistent::default();
+			persistent.bundles.push(Bundle {
+				name: "Magic Item Adept".into(),
+				mutators: vec![AddMaxAttunement { value: Value::Fixed(1) }.into()],
+				..Default::default()
+			});
+			let character = Character::from(persistent);
+			assert_eq!(character.max_attunement().value(), 4);
+			assert_eq!(
+				character.max_attunement().sources(),
+				&[("Magic Item Adept".into(), 1)].into()
+			);
+		}
+	}
+}
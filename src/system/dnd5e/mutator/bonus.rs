@@ -3,7 +3,7 @@ use crate::{
 	system::dnd5e::data::{
 		action::AttackQuery,
 		character::{spellcasting, Character},
-		description,
+		description, proficiency,
 		roll::EvaluatedRoll,
 		Ability, DamageType,
 	},
@@ -33,10 +33,24 @@ pub enum Bonus {
 		damage: EvaluatedRoll,
 		query: Vec<spellcasting::Filter>,
 	},
+	SpellSaveDC {
+		bonus: i32,
+		query: Vec<spellcasting::Filter>,
+	},
+	SpellAttack {
+		bonus: i32,
+		query: Vec<spellcasting::Filter>,
+	},
 	ArmorClass {
 		bonus: i32,
 		context: Option<String>,
 	},
+	Initiative {
+		bonus: i32,
+	},
+	InitiativeProficiency {
+		level: proficiency::Level,
+	},
 }
 
 crate::impl_trait_eq!(Bonus);
@@ -66,7 +80,11 @@ impl Mutator for Bonus {
 			Self::SpellDamage { damage, .. } => {
 				deps += damage.dependencies();
 			}
+			Self::SpellSaveDC { .. } => {}
+			Self::SpellAttack { .. } => {}
 			Self::ArmorClass { .. } => {}
+			Self::Initiative { .. } => {}
+			Self::InitiativeProficiency { .. } => {}
 		}
 		deps
 	}
@@ -102,11 +120,27 @@ impl Mutator for Bonus {
 					.attack_bonuses_mut()
 					.add_to_spell_damage(bonus, query.clone(), parent.to_owned());
 			}
+			Self::SpellSaveDC { bonus, query } => {
+				stats
+					.spellcasting_mut()
+					.add_save_dc_bonus(*bonus, query.clone(), parent.to_owned());
+			}
+			Self::SpellAttack { bonus, query } => {
+				stats
+					.spellcasting_mut()
+					.add_attack_bonus(*bonus, query.clone(), parent.to_owned());
+			}
 			Self::ArmorClass { bonus, context } => {
 				stats
 					.armor_class_mut()
 					.push_bonus(*bonus, context.clone(), parent.to_owned());
 			}
+			Self::Initiative { bonus } => {
+				stats.initiative_mut().add_bonus(*bonus, parent.to_owned());
+			}
+			Self::InitiativeProficiency { level } => {
+				stats.initiative_mut().add_proficiency(*level, parent.to_owned());
+			}
 		}
 	}
 }
@@ -141,11 +175,34 @@ impl FromKdl<NodeContext> for Bonus {
 				let query = node.query_all_t::<spellcasting::Filter>("scope() > query")?;
 				Ok(Self::SpellDamage { damage, query })
 			}
+			(Some("Spell"), "SaveDC") => {
+				let bonus = node.query_i64_req("scope() > bonus", 0)? as i32;
+				let query = node.query_all_t::<spellcasting::Filter>("scope() > query")?;
+				Ok(Self::SpellSaveDC { bonus, query })
+			}
+			(Some("Spell"), "Attack") => {
+				let bonus = node.query_i64_req("scope() > bonus", 0)? as i32;
+				let query = node.query_all_t::<spellcasting::Filter>("scope() > query")?;
+				Ok(Self::SpellAttack { bonus, query })
+			}
 			(None, "ArmorClass") => {
 				let bonus = node.next_i64_req()? as i32;
 				let context = node.get_str_opt("context")?.map(str::to_owned);
 				Ok(Self::ArmorClass { bonus, context })
 			}
+			(None, "Initiative") => {
+				let entry = node.next_req()?;
+				match entry.type_opt() {
+					Some("Proficiency") => {
+						let level = entry.as_str_req()?.parse::<proficiency::Level>()?;
+						Ok(Self::InitiativeProficiency { level })
+					}
+					_ => {
+						let bonus = entry.as_i64_req()? as i32;
+						Ok(Self::Initiative { bonus })
+					}
+				}
+			}
 			(type_id, name) => Err(NotInList(
 				format!("{}{name}", type_id.map(|id| format!("({id})")).unwrap_or_default()),
 				vec![
@@ -153,7 +210,10 @@ impl FromKdl<NodeContext> for Bonus {
 					"(Attack)Roll",
 					"(Attack)AbilityModifier",
 					"(Spell)Damage",
+					"(Spell)SaveDC",
+					"(Spell)Attack",
 					"ArmorClass",
+					"Initiative",
 				],
 			)
 			.into()),
@@ -204,6 +264,16 @@ impl crate::kdl_ext::AsKdl for Bonus {
 				}
 				node
 			}
+			Self::Initiative { bonus } => {
+				node.push_entry("Initiative");
+				node.push_entry(*bonus as i64);
+				node
+			}
+			Self::InitiativeProficiency { level } => {
+				node.push_entry("Initiative");
+				node.push_entry_typed(level.to_string(), "Proficiency");
+				node
+			}
 			Self::SpellDamage { damage, query } => {
 				node.push_entry_typed("Damage", "Spell");
 				node.push_child_t("damage", damage);
@@ -212,6 +282,22 @@ impl crate::kdl_ext::AsKdl for Bonus {
 				}
 				node
 			}
+			Self::SpellSaveDC { bonus, query } => {
+				node.push_entry_typed("SaveDC", "Spell");
+				node.push_child_entry("bonus", *bonus as i64);
+				for query in query {
+					node.push_child_t("query", query);
+				}
+				node
+			}
+			Self::SpellAttack { bonus, query } => {
+				node.push_entry_typed("Attack", "Spell");
+				node.push_child_entry("bonus", *bonus as i64);
+				for query in query {
+					node.push_child_t("query", query);
+				}
+				node
+			}
 		}
 	}
 }
@@ -322,7 +408,7 @@ mod test {
 					|}
 				";
 				let data = Bonus::AttackDamage {
-					damage: EvaluatedRoll {
+					damage: EvaluatedRoll::Evaluated {
 						amount: Value::Evaluated(GetLevelInt::default().into()),
 						die: None,
 					},
@@ -405,6 +491,72 @@ mod test {
 			}
 		}
 
+		mod spell_check {
+			use super::*;
+
+			#[test]
+			fn save_dc() -> anyhow::Result<()> {
+				let doc = "
+					|mutator \"bonus\" (Spell)\"SaveDC\" {
+					|    bonus 1
+					|    query {
+					|        tag \"Warlock\"
+					|    }
+					|}
+				";
+				let data = Bonus::SpellSaveDC {
+					bonus: 1,
+					query: vec![spellcasting::Filter {
+						tags: ["Warlock".into()].into(),
+						..Default::default()
+					}],
+				};
+				assert_eq_askdl!(&data, doc);
+				assert_eq_fromkdl!(Target, doc, data.into());
+				Ok(())
+			}
+
+			#[test]
+			fn attack() -> anyhow::Result<()> {
+				let doc = "
+					|mutator \"bonus\" (Spell)\"Attack\" {
+					|    bonus 2
+					|}
+				";
+				let data = Bonus::SpellAttack {
+					bonus: 2,
+					query: vec![],
+				};
+				assert_eq_askdl!(&data, doc);
+				assert_eq_fromkdl!(Target, doc, data.into());
+				Ok(())
+			}
+		}
+
+		mod initiative {
+			use super::*;
+
+			#[test]
+			fn flat() -> anyhow::Result<()> {
+				let doc = "mutator \"bonus\" \"Initiative\" 5";
+				let data = Bonus::Initiative { bonus: 5 };
+				assert_eq_askdl!(&data, doc);
+				assert_eq_fromkdl!(Target, doc, data.into());
+				Ok(())
+			}
+
+			#[test]
+			fn proficiency() -> anyhow::Result<()> {
+				let doc = "mutator \"bonus\" \"Initiative\" (Proficiency)\"Full\"";
+				let data = Bonus::InitiativeProficiency {
+					level: proficiency::Level::Full,
+				};
+				assert_eq_askdl!(&data, doc);
+				assert_eq_fromkdl!(Target, doc, data.into());
+				Ok(())
+			}
+		}
+
 		#[test]
 		fn attack_ability_modifier() -> anyhow::Result<()> {
 			let doc = "
@@ -0,0 +1,86 @@
+use crate::kdl_ext::NodeContext;
+use crate::{
+	system::dnd5e::data::{character::Character, description},
+	utility::Mutator,
+	GeneralError,
+};
+use kdlize::{AsKdl, FromKdl, NodeBuilder};
+
+/// The character counts as some number of sizes larger when determining their carrying capacity
+/// and the weight they can push, drag, or lift (e.g. Powerful Build).
+#[derive(Clone, Debug, PartialEq)]
+pub struct AddCarryingSize(pub u8);
+
+crate::impl_trait_eq!(AddCarryingSize);
+kdlize::impl_kdl_node!(AddCarryingSize, "add_carrying_size");
+
+impl Mutator for AddCarryingSize {
+	type Target = Character;
+
+	fn description(&self, _state: Option<&Character>) -> description::Section {
+		let sizes = match self.0 {
+			1 => "one size".to_owned(),
+			count => format!("{count} sizes"),
+		};
+		description::Section {
+			content: format!(
+				"You count as {sizes} larger when determining your carrying capacity \
+				and the weight you can push, drag, or lift."
+			)
+			.into(),
+			..Default::default()
+		}
+	}
+
+	fn apply(&self, stats: &mut Character, parent: &std::path::Path) {
+		stats.encumbrance_mut().add_size_increase(self.0, parent.to_owned());
+	}
+}
+
+impl FromKdl<NodeContext> for AddCarryingSize {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let steps = node.next_i64_opt()?.unwrap_or(1);
+		let Ok(steps) = u8::try_from(steps) else {
+			return Err(GeneralError(format!("Invalid number of carrying sizes {steps}, expected 0 to 255")).into());
+		};
+		Ok(Self(steps))
+	}
+}
+
+impl AsKdl for AddCarryingSize {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node.push_entry(self.0 as i64);
+		node
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	mod kdl {
+		use super::*;
+		use crate::{kdl_ext::test_utils::*, system::dnd5e::mutator::test::test_utils};
+
+		test_utils!(AddCarryingSize);
+
+		#[test]
+		fn one_size() -> anyhow::Result<()> {
+			let doc = "mutator \"add_carrying_size\" 1";
+			let data = AddCarryingSize(1);
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+
+		#[test]
+		fn out_of_range() {
+			for doc in ["mutator \"add_carrying_size\" -1", "mutator \"add_carrying_size\" 256"] {
+				let parsed = from_doc::<Target, _>(NODE_NAME, doc, node_ctx(), from_kdl);
+				assert!(parsed.is_err());
+			}
+		}
+	}
+}
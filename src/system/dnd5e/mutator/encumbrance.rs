@@ -0,0 +1,294 @@
+use crate::{
+	system::dnd5e::{
+		data::{
+			bounded::BoundValue,
+			character::{Character, EncumbranceLevel},
+			description,
+			roll::Modifier,
+			Ability,
+		},
+		mutator::AddCarryingSize,
+	},
+	utility::{Dependencies, Mutator},
+};
+use kdlize::{AsKdl, NodeBuilder, NodeId};
+
+/// Determines the character's carrying capacity and the weight of their inventory.
+/// If the character uses the variant encumbrance rules, this also applies the penalties for carrying too much.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ApplyEncumbrance;
+
+crate::impl_trait_eq!(ApplyEncumbrance);
+kdlize::impl_kdl_node!(ApplyEncumbrance, "encumbrance");
+
+impl ApplyEncumbrance {
+	/// The penalties for carrying the weight, which only apply when using the variant encumbrance rules.
+	fn level_effects(level: EncumbranceLevel, variant: bool) -> Vec<&'static str> {
+		if !variant {
+			return vec![];
+		}
+		match level {
+			EncumbranceLevel::Unencumbered => vec![],
+			EncumbranceLevel::Encumbered => vec!["Speed reduced by 10 feet"],
+			EncumbranceLevel::HeavilyEncumbered => vec![
+				"Speed reduced by 20 feet",
+				"Disadvantage on ability checks, attack rolls, and saving throws which use Strength, Dexterity, or Constitution",
+			],
+			EncumbranceLevel::OverCapacity => vec![
+				"Speed reduced to 5 feet",
+				"Disadvantage on ability checks, attack rolls, and saving throws which use Strength, Dexterity, or Constitution",
+			],
+		}
+	}
+}
+
+impl Mutator for ApplyEncumbrance {
+	type Target = Character;
+
+	fn dependencies(&self) -> Dependencies {
+		// Speed penalties apply to the character's full speed, and capacity depends on their final Strength score.
+		["ability_score_finalize", AddCarryingSize::id(), "speed"].into()
+	}
+
+	fn description(&self, state: Option<&Character>) -> description::Section {
+		let Some(state) = state else {
+			return description::Section::default();
+		};
+		let encumbrance = state.encumbrance();
+		let variant = state.persistent().settings.variant_encumbrance;
+		let level = encumbrance.level(variant);
+		description::Section {
+			content: format!(
+				"You are carrying {:.1} lb. of your {:.0} lb. carrying capacity ({}).",
+				encumbrance.carried_weight(),
+				encumbrance.capacity(),
+				level.display_name()
+			)
+			.into(),
+			children: Self::level_effects(level, variant)
+				.into_iter()
+				.map(|effect| description::Section {
+					content: effect.into(),
+					..Default::default()
+				})
+				.collect(),
+			..Default::default()
+		}
+	}
+
+	fn apply(&self, stats: &mut Character, parent: &std::path::Path) {
+		let strength = *stats.ability_scores().get(Ability::Strength).score();
+		let size = stats.persistent().description.size();
+		let carried_weight = stats.persistent().inventory.total_weight();
+		stats.encumbrance_mut().set_carried(strength, size, carried_weight);
+
+		// Carrying capacity is always tracked, but the penalties for carrying a lot only apply with the variant rules.
+		if !stats.persistent().settings.variant_encumbrance {
+			return;
+		}
+		let level = stats.encumbrance().level(true);
+		let speeds = stats.speeds().iter();
+		let speeds = speeds
+			.map(|(name, bounded)| (name.clone(), bounded.value()))
+			.collect::<Vec<_>>();
+		for (name, value) in speeds {
+			let penalty = match level {
+				EncumbranceLevel::Unencumbered => 0,
+				EncumbranceLevel::Encumbered => 10,
+				EncumbranceLevel::HeavilyEncumbered => 20,
+				EncumbranceLevel::OverCapacity => value - 5,
+			};
+			let amount = penalty.min(value);
+			if amount > 0 {
+				stats
+					.speeds_mut()
+					.insert(name, BoundValue::Subtract(amount), parent.to_owned());
+			}
+		}
+		if level >= EncumbranceLevel::HeavilyEncumbered {
+			for ability in [Ability::Strength, Ability::Dexterity, Ability::Constitution] {
+				stats
+					.skills_mut()
+					.add_ability_modifier(ability, Modifier::Disadvantage, None, parent.to_owned());
+				stats
+					.saving_throws_mut()
+					.add_modifier(Some(ability), Modifier::Disadvantage, None, parent.to_owned());
+				stats
+					.attack_bonuses_mut()
+					.add_modifier(Some(ability), Modifier::Disadvantage, None, parent.to_owned());
+			}
+		}
+	}
+}
+
+impl AsKdl for ApplyEncumbrance {
+	fn as_kdl(&self) -> NodeBuilder {
+		// STUB: not available to documents, encumbrance is always applied from the persistent inventory
+		NodeBuilder::default()
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::system::dnd5e::data::{
+		character::Persistent,
+		currency::{self, Wallet},
+		item::{container::Inventory, Item, Kind},
+		Bundle,
+	};
+
+	fn character(
+		strength: u32,
+		weight: f32,
+		variant: bool,
+		mutators: Vec<crate::system::dnd5e::BoxedMutator>,
+	) -> Character {
+		let mut persistent = Persistent::default();
+		persistent.ability_scores[Ability::Strength] = strength;
+		persistent.description.height = 60;
+		persistent.settings.variant_encumbrance = variant;
+		let mut inventory = Inventory::default();
+		inventory.insert(Item {
+			name: "Gear".into(),
+			weight,
+			..Default::default()
+		});
+		persistent.inventory = inventory;
+		let mut mutators = mutators;
+		mutators.push(
+			crate::system::dnd5e::mutator::Speed {
+				name: "Walking".into(),
+				argument: BoundValue::Base(30),
+			}
+			.into(),
+		);
+		persistent.bundles.push(Bundle {
+			name: "Base".into(),
+			mutators,
+			..Default::default()
+		});
+		Character::from(persistent)
+	}
+
+	fn walking_speed(character: &Character) -> i32 {
+		let speed = character.speeds().get("Walking");
+		speed.map(|speed| speed.value()).unwrap_or_default()
+	}
+
+	#[test]
+	fn capacity() {
+		let character = character(10, 60.0, false, vec![]);
+		assert_eq!(character.encumbrance().capacity(), 150.0);
+		assert_eq!(character.encumbrance().push_drag_lift(), 300.0);
+		assert_eq!(character.encumbrance().carried_weight(), 60.0);
+		assert_eq!(character.encumbrance().level(false), EncumbranceLevel::Unencumbered);
+		// Penalties only apply when using the variant rules.
+		assert_eq!(walking_speed(&character), 30);
+	}
+
+	#[test]
+	fn carried_weight_includes_containers_and_coins() {
+		let mut persistent = Persistent::default();
+		let mut backpack = Item {
+			name: "Backpack".into(),
+			weight: 5.0,
+			items: Some(Default::default()),
+			..Default::default()
+		};
+		if let Some(items) = &mut backpack.items {
+			items.insert(Item {
+				name: "Torch".into(),
+				weight: 1.0,
+				kind: Kind::Simple { count: 10 },
+				..Default::default()
+			});
+			*items.wallet_mut() = Wallet::from([(50, currency::Kind::Silver)]);
+		}
+		persistent.inventory.insert(backpack);
+		*persistent.inventory.wallet_mut() = Wallet::from([(100, currency::Kind::Gold)]);
+		assert_eq!(persistent.inventory.total_weight(), 5.0 + 10.0 + 1.0 + 2.0);
+	}
+
+	#[test]
+	fn size_increase() {
+		let powerful_build = AddCarryingSize(1).into();
+		let character = character(10, 200.0, false, vec![powerful_build]);
+		assert_eq!(character.encumbrance().capacity(), 300.0);
+		assert_eq!(character.encumbrance().level(false), EncumbranceLevel::Unencumbered);
+	}
+
+	#[test]
+	fn over_capacity() {
+		let character = character(10, 151.0, false, vec![]);
+		assert_eq!(character.encumbrance().level(false), EncumbranceLevel::OverCapacity);
+	}
+
+	#[test]
+	fn variant_encumbered() {
+		let character = character(10, 60.0, true, vec![]);
+		assert_eq!(character.encumbrance().level(true), EncumbranceLevel::Encumbered);
+		assert_eq!(walking_speed(&character), 20);
+		let modifiers = character.skills().ability_modifiers(Ability::Strength);
+		assert!(modifiers.get(Modifier::Disadvantage).is_empty());
+	}
+
+	#[test]
+	fn variant_thresholds() {
+		let medium = character(10, 0.0, true, vec![]);
+		assert_eq!(medium.encumbrance().variant_thresholds(), (50.0, 100.0));
+		let powerful_build = AddCarryingSize(1).into();
+		let large = character(10, 0.0, true, vec![powerful_build]);
+		assert_eq!(large.encumbrance().variant_thresholds(), (100.0, 200.0));
+
+		let level = |weight: f32| character(10, weight, true, vec![]).encumbrance().level(true);
+		assert_eq!(level(50.0), EncumbranceLevel::Unencumbered);
+		assert_eq!(level(50.5), EncumbranceLevel::Encumbered);
+		assert_eq!(level(100.0), EncumbranceLevel::Encumbered);
+		assert_eq!(level(100.5), EncumbranceLevel::HeavilyEncumbered);
+		assert_eq!(level(150.0), EncumbranceLevel::HeavilyEncumbered);
+		assert_eq!(level(150.5), EncumbranceLevel::OverCapacity);
+	}
+
+	#[test]
+	fn variant_heavily_encumbered() {
+		let character = character(10, 120.0, true, vec![]);
+		assert_eq!(character.encumbrance().level(true), EncumbranceLevel::HeavilyEncumbered);
+		assert_eq!(walking_speed(&character), 10);
+		let modifiers = character.skills().ability_modifiers(Ability::Dexterity);
+		assert_eq!(modifiers.get(Modifier::Disadvantage).len(), 1);
+		let modifiers = character.skills().ability_modifiers(Ability::Wisdom);
+		assert!(modifiers.get(Modifier::Disadvantage).is_empty());
+	}
+
+	#[test]
+	fn heavily_encumbered_disadvantage() {
+		let character = character(10, 120.0, true, vec![]);
+		for ability in [Ability::Strength, Ability::Dexterity, Ability::Constitution] {
+			let attacks = character.attack_bonuses().ability_modifiers(ability);
+			assert_eq!(attacks.get(Modifier::Disadvantage).len(), 1);
+			let saves = character.saving_throws().ability_modifiers(ability);
+			assert_eq!(saves.get(Modifier::Disadvantage).len(), 1);
+		}
+		let attacks = character.attack_bonuses().ability_modifiers(Ability::Charisma);
+		assert!(attacks.get(Modifier::Disadvantage).is_empty());
+	}
+
+	#[test]
+	fn variant_disabled() {
+		let character = character(10, 120.0, false, vec![]);
+		assert_eq!(walking_speed(&character), 30);
+		let attacks = character.attack_bonuses().ability_modifiers(Ability::Strength);
+		assert!(attacks.get(Modifier::Disadvantage).is_empty());
+		let saves = character.saving_throws().ability_modifiers(Ability::Strength);
+		assert!(saves.get(Modifier::Disadvantage).is_empty());
+		let description = ApplyEncumbrance.description(Some(&character));
+		assert!(description.children.is_empty());
+	}
+
+	#[test]
+	fn variant_over_capacity() {
+		let character = character(10, 200.0, true, vec![]);
+		assert_eq!(walking_speed(&character), 5);
+	}
+}
@@ -0,0 +1,251 @@
+use crate::kdl_ext::NodeContext;
+use crate::{
+	system::dnd5e::{
+		data::{bounded::BoundValue, character::Character, description, roll::Modifier, Ability},
+		mutator::{AddMaxHitPoints, ApplyEncumbrance, ArmorStrengthRequirement, Flag, Speed},
+	},
+	utility::{Dependencies, Mutator},
+};
+use enumset::EnumSet;
+use kdlize::{AsKdl, FromKdl, NodeBuilder, NodeId};
+
+pub static MAX_EXHAUSTION_LEVEL: u8 = 6;
+
+static LEVEL_EFFECTS: [&str; 6] = [
+	"Disadvantage on ability checks",
+	"Speed halved",
+	"Disadvantage on attack rolls and saving throws",
+	"Hit point maximum halved",
+	"Speed reduced to 0",
+	"Death",
+];
+
+/// The effects of the character's exhaustion level. Each level includes the effects of all lower levels.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Exhaustion(pub u8);
+
+crate::impl_trait_eq!(Exhaustion);
+kdlize::impl_kdl_node!(Exhaustion, "exhaustion");
+
+impl Exhaustion {
+	pub fn level(&self) -> u8 {
+		self.0.min(MAX_EXHAUSTION_LEVEL)
+	}
+
+	/// Returns the effects of each level up to and including the provided level.
+	pub fn level_effects(level: u8) -> impl Iterator<Item = &'static str> {
+		LEVEL_EFFECTS.iter().take(level as usize).cloned()
+	}
+}
+
+impl Mutator for Exhaustion {
+	type Target = Character;
+
+	fn dependencies(&self) -> Dependencies {
+		// Speed and max hit points are halved, so all other changes to them must be applied first.
+		[
+			Speed::id(),
+			ArmorStrengthRequirement::id(),
+			ApplyEncumbrance::id(),
+			AddMaxHitPoints::id(),
+		]
+		.into()
+	}
+
+	fn description(&self, _state: Option<&Character>) -> description::Section {
+		description::Section {
+			content: format!("You have {} levels of exhaustion.", self.level()).into(),
+			children: vec![description::Section {
+				content: description::SectionContent::Table {
+					column_count: 2,
+					headers: Some(vec!["Level".into(), "Effect".into()]),
+					rows: Self::level_effects(self.level())
+						.enumerate()
+						.map(|(idx, effect)| vec![(idx + 1).to_string(), effect.to_owned()])
+						.collect(),
+				},
+				..Default::default()
+			}],
+			..Default::default()
+		}
+	}
+
+	fn apply(&self, stats: &mut Character, parent: &std::path::Path) {
+		let level = self.level();
+		if level >= 1 {
+			for ability in EnumSet::<Ability>::all() {
+				stats
+					.skills_mut()
+					.add_ability_modifier(ability, Modifier::Disadvantage, None, parent.to_owned());
+			}
+		}
+		if level >= 2 {
+			let speeds = stats.speeds().iter();
+			let speeds = speeds
+				.map(|(name, bounded)| (name.clone(), bounded.value()))
+				.collect::<Vec<_>>();
+			for (name, value) in speeds {
+				// level 5 reduces speed to 0, otherwise speed is halved (rounded down)
+				let amount = match level >= 5 {
+					true => value,
+					false => value - value / 2,
+				};
+				if amount > 0 {
+					stats
+						.speeds_mut()
+						.insert(name, BoundValue::Subtract(amount), parent.to_owned());
+				}
+			}
+		}
+		if level >= 3 {
+			stats
+				.attack_bonuses_mut()
+				.add_modifier(None, Modifier::Disadvantage, None, parent.to_owned());
+			stats
+				.saving_throws_mut()
+				.add_modifier(None, Modifier::Disadvantage, None, parent.to_owned());
+		}
+		if level >= 4 {
+			let max_hp = stats.max_hit_points().value() as i32;
+			stats
+				.max_hit_points_mut()
+				.push(-(max_hp - max_hp / 2), parent.to_owned());
+		}
+		if level >= 6 {
+			stats.flags_mut()[Flag::Dead] = true;
+		}
+	}
+}
+
+impl FromKdl<NodeContext> for Exhaustion {
+	type Error = anyhow::Error;
+	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
+		let level = node.next_i64_req()?;
+		Ok(Self(level.clamp(0, MAX_EXHAUSTION_LEVEL as i64) as u8))
+	}
+}
+
+impl AsKdl for Exhaustion {
+	fn as_kdl(&self) -> NodeBuilder {
+		let mut node = NodeBuilder::default();
+		node.push_entry(self.level() as i64);
+		node
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use crate::{
+		system::dnd5e::data::{character::Persistent, Bundle},
+		utility::Value,
+	};
+
+	fn character(exhaustion: u8) -> Character {
+		let mut persistent = Persistent::default();
+		persistent.exhaustion = exhaustion;
+		persistent.bundles.push(Bundle {
+			name: "Base".into(),
+			mutators: vec![
+				Speed {
+					name: "Walking".into(),
+					argument: BoundValue::Base(30),
+				}
+				.into(),
+				AddMaxHitPoints {
+					id: None,
+					value: Value::Fixed(25),
+				}
+				.into(),
+			],
+			..Default::default()
+		});
+		Character::from(persistent)
+	}
+
+	fn walking_speed(character: &Character) -> i32 {
+		character
+			.speeds()
+			.get("Walking")
+			.map(|speed| speed.value())
+			.unwrap_or_default()
+	}
+
+	#[test]
+	fn none() {
+		let character = character(0);
+		assert_eq!(walking_speed(&character), 30);
+		assert_eq!(character.max_hit_points().value(), 25);
+		assert!(character
+			.saving_throws()
+			.general_modifiers()
+			.get(Modifier::Disadvantage)
+			.is_empty());
+	}
+
+	#[test]
+	fn level_1() {
+		let character = character(1);
+		let modifiers = character.skills().ability_modifiers(Ability::Strength);
+		assert_eq!(modifiers.get(Modifier::Disadvantage).len(), 1);
+		assert_eq!(walking_speed(&character), 30);
+	}
+
+	#[test]
+	fn level_2() {
+		let character = character(2);
+		assert_eq!(walking_speed(&character), 15);
+		assert!(character
+			.saving_throws()
+			.general_modifiers()
+			.get(Modifier::Disadvantage)
+			.is_empty());
+	}
+
+	#[test]
+	fn level_3() {
+		let character = character(3);
+		let modifiers = character.saving_throws().general_modifiers();
+		assert_eq!(modifiers.get(Modifier::Disadvantage).len(), 1);
+		let modifiers = character.attack_bonuses().general_modifiers();
+		assert_eq!(modifiers.get(Modifier::Disadvantage).len(), 1);
+		assert_eq!(character.max_hit_points().value(), 25);
+		assert!(!character.is_dead());
+	}
+
+	#[test]
+	fn level_4() {
+		let character = character(4);
+		assert_eq!(character.max_hit_points().value(), 12);
+		assert_eq!(walking_speed(&character), 15);
+	}
+
+	#[test]
+	fn level_5() {
+		let character = character(5);
+		assert_eq!(walking_speed(&character), 0);
+		assert!(!character.is_dead());
+	}
+
+	#[test]
+	fn level_6() {
+		let character = character(6);
+		assert!(character.is_dead());
+	}
+
+	mod kdl {
+		use super::*;
+		use crate::{kdl_ext::test_utils::*, system::dnd5e::mutator::test::test_utils};
+
+		test_utils!(Exhaustion);
+
+		#[test]
+		fn level() -> anyhow::Result<()> {
+			let doc = "mutator \"exhaustion\" 3";
+			let data = Exhaustion(3);
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+	}
+}
@@ -1,7 +1,7 @@
 use crate::kdl_ext::NodeContext;
 use crate::{
 	system::dnd5e::data::{bounded::BoundValue, character::Character, description, Ability},
-	utility::{InvalidEnumStr, Mutator},
+	utility::{InvalidEnumStr, Mutator, NotInList},
 };
 use enum_map::Enum;
 use enumset::EnumSetType;
@@ -12,12 +12,16 @@ use std::str::FromStr;
 pub enum Flag {
 	// TODO: Test the usage of ArmorStrengthRequirement, w/ & w/o armor that has a req
 	ArmorStrengthRequirement,
+	/// The character has died as a result of some effect (e.g. 6 levels of exhaustion),
+	/// regardless of their hit points or death saving throws.
+	Dead,
 }
 
 impl ToString for Flag {
 	fn to_string(&self) -> String {
 		match self {
 			Self::ArmorStrengthRequirement => "ArmorStrengthRequirement",
+			Self::Dead => "Dead",
 		}
 		.into()
 	}
@@ -29,12 +33,13 @@ impl FromStr for Flag {
 	fn from_str(s: &str) -> Result<Self, Self::Err> {
 		match s {
 			"ArmorStrengthRequirement" => Ok(Self::ArmorStrengthRequirement),
+			"Dead" => Ok(Self::Dead),
 			_ => Err(InvalidEnumStr::from(s)),
 		}
 	}
 }
 
-// TODO: Test logic and from_kdl for SetFlag
+// TODO: Test logic for SetFlag
 #[derive(Clone, Debug, PartialEq)]
 pub struct SetFlag {
 	pub flag: Flag,
@@ -61,6 +66,10 @@ impl FromKdl<NodeContext> for SetFlag {
 	type Error = anyhow::Error;
 	fn from_kdl<'doc>(node: &mut crate::kdl_ext::NodeReader<'doc>) -> anyhow::Result<Self> {
 		let flag = node.next_str_req_t::<Flag>()?;
+		// Death is only ever set by the rules which cause it (like exhaustion), not by content.
+		if flag == Flag::Dead {
+			return Err(NotInList(flag.to_string(), vec!["ArmorStrengthRequirement"]).into());
+		}
 		let value = node.next_bool_req()?;
 		Ok(Self { flag, value })
 	}
@@ -141,3 +150,34 @@ mod test {
 		}
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+
+	mod kdl {
+		use super::*;
+		use crate::{kdl_ext::test_utils::*, system::dnd5e::mutator::test::test_utils};
+
+		test_utils!(SetFlag);
+
+		#[test]
+		fn armor_strength_requirement() -> anyhow::Result<()> {
+			let doc = "mutator \"flag\" \"ArmorStrengthRequirement\" false";
+			let data = SetFlag {
+				flag: Flag::ArmorStrengthRequirement,
+				value: false,
+			};
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
+
+		#[test]
+		fn dead() {
+			let doc = "mutator \"flag\" \"Dead\" true";
+			let parsed = from_doc::<Target, _>(NODE_NAME, doc, node_ctx(), from_kdl);
+			assert!(parsed.is_err());
+		}
+	}
+}
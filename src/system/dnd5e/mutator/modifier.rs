@@ -142,7 +142,9 @@ impl Mutator for AddModifier {
 					.add_skill_modifier(skill, self.modifier, self.context.clone(), parent.to_owned());
 			}
 			ModifierKind::Initiative => {
-				// TODO: apply advantage or disadvantage to initiative
+				stats
+					.initiative_mut()
+					.add_modifier(self.modifier, self.context.clone(), parent.to_owned());
 			}
 		}
 	}
@@ -169,9 +171,12 @@ impl FromKdl<NodeContext> for AddModifier {
 				let skill = selector::Value::from_kdl(node)?;
 				ModifierKind::Skill(skill)
 			}
-			"Initiative" => ModifierKind::Initiative,
+			"Initiative" => {
+				node.next_req()?;
+				ModifierKind::Initiative
+			}
 			name => {
-				return Err(NotInList(name.into(), vec!["Ability", "SavingThrow", "Skill"]).into());
+				return Err(NotInList(name.into(), vec!["Ability", "SavingThrow", "Skill", "Initiative"]).into());
 			}
 		};
 		Ok(Self {
@@ -295,6 +300,19 @@ mod test {
 			assert_eq_fromkdl!(Target, doc, data.into());
 			Ok(())
 		}
+
+		#[test]
+		fn initiative() -> anyhow::Result<()> {
+			let doc = "mutator \"add_modifier\" \"Advantage\" (Initiative)\"\"";
+			let data = AddModifier {
+				modifier: roll::Modifier::Advantage,
+				context: None,
+				kind: ModifierKind::Initiative,
+			};
+			assert_eq_askdl!(&data, doc);
+			assert_eq_fromkdl!(Target, doc, data.into());
+			Ok(())
+		}
 	}
 
 	mod mutate {
@@ -375,5 +393,16 @@ mod test {
 				vec![(Some("Poison".into()), PathBuf::from("TestMutator")).into()]
 			);
 		}
+
+		#[test]
+		fn initiative() {
+			let character = character(AddModifier {
+				modifier: roll::Modifier::Advantage,
+				context: None,
+				kind: ModifierKind::Initiative,
+			});
+			let modifiers = character.initiative().modifiers().get(roll::Modifier::Advantage);
+			assert_eq!(*modifiers, vec![(None, PathBuf::from("TestMutator")).into()]);
+		}
 	}
 }
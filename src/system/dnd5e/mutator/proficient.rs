@@ -162,13 +162,17 @@ impl Mutator for AddProficiency {
 		match &self {
 			Self::Ability(ability, level) => {
 				if let Some(ability) = stats.resolve_selector(ability) {
-					// TODO: Grant proficiency for an ability (in addition to the skills which use that ability)
 					let derived_skills = stats.skills_mut();
 					for skill in EnumSet::<Skill>::all() {
 						if skill.ability() == ability {
 							derived_skills.add_proficiency(skill, *level, parent.to_owned());
 						}
 					}
+					let tools = stats.tool_proficiencies_mut();
+					tools.add_ability_proficiency(ability, *level, parent.to_owned());
+					if ability == Ability::Dexterity {
+						stats.initiative_mut().add_proficiency(*level, parent.to_owned());
+					}
 				}
 			}
 			Self::SavingThrow(ability) => {
@@ -204,9 +208,10 @@ impl Mutator for AddProficiency {
 					.weapons
 					.insert(value.clone(), parent.to_owned());
 			}
-			Self::Tool { tool, level: _ } => {
-				// TODO: Actually grant the tool's proficiency level (there is no place to put that data yet)
+			Self::Tool { tool, level } => {
 				if let Some(value) = stats.resolve_selector(tool) {
+					let tools = stats.tool_proficiencies_mut();
+					tools.add_proficiency(value.clone(), *level, parent.to_owned());
 					stats.other_proficiencies_mut().tools.insert(value, parent.to_owned());
 				}
 			}
@@ -836,6 +841,59 @@ mod test {
 				*character.other_proficiencies().tools,
 				[("Thieves' Tools".into(), ["AddProficiency".into()].into())].into()
 			);
+			assert_eq!(
+				character.tool_proficiencies().proficiency("Thieves' Tools"),
+				Some(&AttributedValue::from((
+					proficiency::Level::Full,
+					vec![("AddProficiency".into(), proficiency::Level::Full)]
+				)))
+			);
+		}
+
+		#[test]
+		fn tool_expertise() {
+			let character = character(
+				AddProficiency::Tool {
+					tool: selector::Value::Specific("Thieves' Tools".into()),
+					level: proficiency::Level::Double,
+				},
+				None,
+			);
+			let bonus = character.proficiency_bonus();
+			let dexterity = character.ability_modifier(Ability::Dexterity, None);
+			assert_eq!(
+				character
+					.tool_proficiencies()
+					.check_proficiency("Thieves' Tools", Ability::Dexterity),
+				proficiency::Level::Double
+			);
+			assert_eq!(
+				character.tool_modifier("Thieves' Tools", Ability::Dexterity),
+				dexterity + bonus * 2
+			);
+		}
+
+		#[test]
+		fn ability_applies_to_tools() {
+			let character = character(
+				AddProficiency::Ability(
+					selector::Value::Specific(Ability::Intelligence),
+					proficiency::Level::HalfDown,
+				),
+				None,
+			);
+			assert_eq!(
+				character
+					.tool_proficiencies()
+					.check_proficiency("Alchemist's Supplies", Ability::Intelligence),
+				proficiency::Level::HalfDown
+			);
+			assert_eq!(
+				character
+					.tool_proficiencies()
+					.check_proficiency("Alchemist's Supplies", Ability::Wisdom),
+				proficiency::Level::None
+			);
 		}
 	}
 }